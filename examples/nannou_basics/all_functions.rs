@@ -17,6 +17,7 @@ fn model(app: &App) -> Model {
         .raw_event(raw_window_event)
         .key_pressed(key_pressed)
         .key_released(key_released)
+        .received_character(received_character)
         .mouse_moved(mouse_moved)
         .mouse_pressed(mouse_pressed)
         .mouse_released(mouse_released)
@@ -62,6 +63,7 @@ fn window_event(_app: &App, _model: &mut Model, event: WindowEvent) {
     match event {
         KeyPressed(_key) => {}
         KeyReleased(_key) => {}
+        ReceivedCharacter(_character) => {}
         MouseMoved(_pos) => {}
         MousePressed(_button) => {}
         MouseReleased(_button) => {}
@@ -87,6 +89,8 @@ fn key_pressed(_app: &App, _model: &mut Model, _key: Key) {}
 
 fn key_released(_app: &App, _model: &mut Model, _key: Key) {}
 
+fn received_character(_app: &App, _model: &mut Model, _character: char) {}
+
 fn mouse_moved(_app: &App, _model: &mut Model, _pos: Point2) {}
 
 fn mouse_pressed(_app: &App, _model: &mut Model, _button: MouseButton) {}
@@ -70,8 +70,9 @@ fn model(app: &App) -> Model {
         oscillator_buffer_size,
         &uniform_buffer,
     );
-    let pipeline_layout = create_pipeline_layout(device, &bind_group_layout);
-    let pipeline = create_compute_pipeline(device, &pipeline_layout, &cs_mod);
+    let pipeline =
+        wgpu::ComputePipelineBuilder::from_layout_descriptor(&[&bind_group_layout][..], &cs_mod)
+            .build(device);
 
     let compute = Compute {
         oscillator_buffer,
@@ -128,12 +129,12 @@ fn update(app: &App, model: &mut Model, _update: Update) {
         0,
         uniforms_size,
     );
-    {
-        let mut cpass = encoder.begin_compute_pass();
-        cpass.set_pipeline(&compute.pipeline);
-        cpass.set_bind_group(0, &compute.bind_group, &[]);
-        cpass.dispatch(OSCILLATOR_COUNT as u32, 1, 1);
-    }
+    wgpu::dispatch_compute(
+        &mut encoder,
+        &compute.pipeline,
+        &[&compute.bind_group],
+        [OSCILLATOR_COUNT, 1, 1],
+    );
     encoder.copy_buffer_to_buffer(
         &compute.oscillator_buffer,
         0,
@@ -238,31 +239,6 @@ fn create_bind_group(
         .build(device, layout)
 }
 
-fn create_pipeline_layout(
-    device: &wgpu::Device,
-    bind_group_layout: &wgpu::BindGroupLayout,
-) -> wgpu::PipelineLayout {
-    device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-        bind_group_layouts: &[&bind_group_layout],
-    })
-}
-
-fn create_compute_pipeline(
-    device: &wgpu::Device,
-    layout: &wgpu::PipelineLayout,
-    cs_mod: &wgpu::ShaderModule,
-) -> wgpu::ComputePipeline {
-    let compute_stage = wgpu::ProgrammableStageDescriptor {
-        module: &cs_mod,
-        entry_point: "main",
-    };
-    let desc = wgpu::ComputePipelineDescriptor {
-        layout,
-        compute_stage,
-    };
-    device.create_compute_pipeline(&desc)
-}
-
 // See `nannou::wgpu::bytes` docs for why these are necessary.
 
 fn uniforms_as_bytes(uniforms: &Uniforms) -> &[u8] {
@@ -0,0 +1,127 @@
+//! Benchmarks for each stage of the `Draw` pipeline.
+//!
+//! The renderer benchmark requires a GPU adapter and is skipped if none is available.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nannou::draw::primitive::Primitive;
+use nannou::draw::renderer::{RenderContext, RenderPrimitive};
+use nannou::draw::{self, Draw, DrawCommand};
+use nannou::lyon::tessellation::{FillTessellator, StrokeTessellator};
+use nannou::math::{Matrix4, SquareMatrix};
+use nannou::prelude::*;
+use nannou::wgpu;
+
+const PRIMITIVES: usize = 10_000;
+
+// Draw a grid of stroked ellipses and filled rects.
+fn draw_scene(draw: &Draw) {
+    for i in 0..PRIMITIVES {
+        let x = (i % 100) as f32 * 10.0 - 500.0;
+        let y = (i / 100) as f32 * 10.0 - 500.0;
+        if i % 2 == 0 {
+            draw.ellipse()
+                .x_y(x, y)
+                .w_h(8.0, 8.0)
+                .stroke(BLACK)
+                .stroke_weight(1.0);
+        } else {
+            draw.rect().x_y(x, y).w_h(8.0, 8.0).color(PLUM);
+        }
+    }
+}
+
+fn recording(c: &mut Criterion) {
+    let draw = Draw::new();
+    c.bench_function("draw_recording", |b| {
+        b.iter(|| {
+            draw_scene(&draw);
+            black_box(draw.drain_commands().count());
+            draw.reset();
+        })
+    });
+}
+
+fn tessellation(c: &mut Criterion) {
+    let draw = Draw::new();
+    draw_scene(&draw);
+    let prims: Vec<Primitive> = draw
+        .drain_commands()
+        .filter_map(|cmd| match cmd {
+            DrawCommand::Primitive(prim) => Some(prim),
+            _ => None,
+        })
+        .collect();
+
+    let intermediary_mesh = draw::Mesh::default();
+    let theme = draw::Theme::default();
+    let transform = Matrix4::identity();
+    let mut fill_tessellator = FillTessellator::new();
+    let mut stroke_tessellator = StrokeTessellator::new();
+    let mut mesh = draw::Mesh::default();
+    c.bench_function("draw_tessellation", |b| {
+        b.iter(|| {
+            mesh.clear();
+            for prim in prims.iter().cloned() {
+                let ctxt = RenderContext {
+                    transform: &transform,
+                    intermediary_mesh: &intermediary_mesh,
+                    path_event_buffer: &[],
+                    path_points_colored_buffer: &[],
+                    path_points_textured_buffer: &[],
+                    path_points_weighted_buffer: &[],
+                    text_buffer: "",
+                    theme: &theme,
+                    glyph_cache: None,
+                    fill_tessellator: &mut fill_tessellator,
+                    stroke_tessellator: &mut stroke_tessellator,
+                    output_attachment_size: vec2(1024.0, 1024.0),
+                    output_attachment_scale_factor: 1.0,
+                };
+                black_box(prim.render_primitive(ctxt, &mut mesh));
+            }
+        })
+    });
+}
+
+fn renderer(c: &mut Criterion) {
+    let adapters = wgpu::AdapterMap::default();
+    let options = wgpu::RequestAdapterOptions {
+        power_preference: wgpu::DEFAULT_POWER_PREFERENCE,
+        compatible_surface: None,
+    };
+    let adapter = match adapters.get_or_request(options, wgpu::DEFAULT_BACKENDS) {
+        Some(adapter) => adapter,
+        None => {
+            eprintln!("no GPU adapter available, skipping `draw_renderer` benchmark");
+            return;
+        }
+    };
+    let device_queue = adapter.get_or_request_device(wgpu::default_device_descriptor());
+    let device = device_queue.device();
+
+    let texture = wgpu::TextureBuilder::new()
+        .size([1024, 1024])
+        .format(Frame::TEXTURE_FORMAT)
+        .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+        .build(device);
+    let descriptor = texture.descriptor_cloned();
+    let mut renderer =
+        draw::RendererBuilder::new().build_from_texture_descriptor(device, &descriptor);
+
+    let draw = Draw::new();
+    c.bench_function("draw_renderer_encode", |b| {
+        b.iter(|| {
+            draw_scene(&draw);
+            let desc = wgpu::CommandEncoderDescriptor {
+                label: Some("draw_renderer_encode"),
+            };
+            let mut encoder = device.create_command_encoder(&desc);
+            renderer.render_to_texture(device, &mut encoder, &draw, &texture);
+            black_box(encoder.finish());
+            draw.reset();
+        })
+    });
+}
+
+criterion_group!(benches, recording, tessellation, renderer);
+criterion_main!(benches);
@@ -8,10 +8,12 @@
 //!   thread.
 //! - [**LoopMode**](./enum.LoopMode.html) - describes the behaviour of the application event loop.
 
+use crate::color::ColorVisionDeficiency;
 use crate::draw;
 use crate::event::{self, Event, Key, LoopEvent, Update};
 use crate::frame::{Frame, RawFrame};
 use crate::geom;
+use crate::headless::Headless;
 use crate::state;
 use crate::time::DurationF64;
 use crate::ui;
@@ -65,6 +67,7 @@ pub struct Builder<M = (), E = Event> {
     default_window_size: Option<DefaultWindowSize>,
     capture_frame_timeout: Option<Option<Duration>>,
     max_capture_frame_jobs: Option<u32>,
+    headless: Option<[u32; 2]>,
 }
 
 /// A nannou `Sketch` builder.
@@ -106,6 +109,8 @@ pub struct App {
     pub(crate) event_loop_window_target: Option<EventLoopWindowTarget>,
     pub(crate) event_loop_proxy: Proxy,
     pub(crate) windows: RefCell<HashMap<window::Id, Window>>,
+    // The render target used in place of a window when running headlessly.
+    pub(crate) headless: Option<Headless>,
     /// A map of active wgpu physial device adapters.
     adapters: wgpu::AdapterMap,
     draw_state: DrawState,
@@ -125,11 +130,21 @@ pub struct App {
     /// change this to query the OS somehow, but I don't think `winit` provides a way to do this
     /// yet.
     pub keys: state::Keys,
+    /// The state of files dragged over and dropped onto the app's windows.
+    ///
+    /// `hovered` is the list of files currently being dragged over a window.
+    ///
+    /// `dropped` is the list of files dropped since the previous update, allowing for handling
+    /// several dropped files at once within `update` rather than one at a time via `dropped_file`.
+    pub files: state::Files,
     /// Key time measurements tracked by the App.
     ///
     /// `duration.since_start` specifies the duration since the app started running.
     ///
     /// `duration.since_prev_update` specifies the duration since the previous update event.
+    ///
+    /// `duration.update_duration` and `duration.view_duration` specify the time taken by the most
+    /// recent update and view respectively.
     pub duration: state::Time,
     /// The time in seconds since the `App` started running.
     ///
@@ -148,6 +163,10 @@ pub struct App {
     /// the number becomes higher. Instead, we recommend using `app.duration.since_start` or
     /// `app.duration.since_prev_update` to access a more precise form of app time.
     pub time: DrawScalar,
+    // The progress between the most recent fixed update and the next in `FixedTimestep` mode.
+    fixed_timestep_alpha: f64,
+    // The exponentially smoothed number of frames per second.
+    smoothed_fps: f32,
 }
 
 /// Miscellaneous app configuration parameters.
@@ -156,12 +175,21 @@ struct Config {
     loop_mode: LoopMode,
     exit_on_escape: bool,
     fullscreen_on_shortcut: bool,
+    color_vision_deficiency: Option<ColorVisionDeficiency>,
+    picking: bool,
+    tessellation_cache: bool,
+    offline_timestep: Option<Duration>,
+    fps_limit: Option<f32>,
 }
 
 // Draw state managed by the **App**.
 #[derive(Debug)]
 struct DrawState {
     draw: RefCell<draw::Draw<DrawScalar>>,
+    // The **Draw** instances associated with specific windows via `draw_for`.
+    window_draws: RefCell<HashMap<window::Id, draw::Draw<DrawScalar>>>,
+    // The **Draw**s queued via `Draw::to_window` to be rendered to each window's next frame.
+    window_queues: RefCell<HashMap<window::Id, Vec<draw::Draw<DrawScalar>>>>,
     renderers: RefCell<HashMap<window::Id, RefCell<draw::Renderer>>>,
 }
 
@@ -175,7 +203,8 @@ pub type DrawScalar = geom::scalar::Default;
 /// **App**'s inner event loop.
 #[derive(Clone)]
 pub struct Proxy {
-    event_loop_proxy: winit::event_loop::EventLoopProxy<()>,
+    // `None` when running headlessly, in which case there is no event loop to wake.
+    event_loop_proxy: Option<winit::event_loop::EventLoopProxy<()>>,
     // Indicates whether or not the events loop is currently asleep.
     //
     // This is set to `true` each time the events loop is ready to return and the `LoopMode` is
@@ -191,6 +220,12 @@ struct LoopState {
     loop_start: Instant,
     last_update: Instant,
     total_updates: u64,
+    // The moment at which `MainEventsCleared` was last handled.
+    last_frame: Instant,
+    // Real time that has not yet been consumed by fixed updates in `FixedTimestep` mode.
+    fixed_timestep_accumulator: Duration,
+    // Whether the most recent `MainEventsCleared` was skipped due to the fps limit.
+    frame_skipped: bool,
 }
 
 /// The mode in which the **App** is currently running the event loop and emitting `Update` events.
@@ -231,6 +266,24 @@ pub enum LoopMode {
         /// The number of updates that must be emited regardless of non-update events
         number_of_updates: usize,
     },
+
+    /// Emits `Update`s at a fixed simulation rate, decoupled from the rate at which frames are
+    /// rendered.
+    ///
+    /// Real time is accumulated between frames and as many updates are emitted as fit within the
+    /// accumulated time. Each update advances `app.time` and `app.duration` by exactly `timestep`,
+    /// so simulations behave identically regardless of the frame rate of the machine.
+    ///
+    /// The remaining fraction of a timestep is available within `view` via
+    /// `app.fixed_timestep_alpha()`, allowing for interpolating between the previous and current
+    /// simulation states.
+    FixedTimestep {
+        /// The simulated duration of each update.
+        timestep: Duration,
+        /// The maximum number of updates emitted per frame. Any remaining accumulated time is
+        /// discarded, avoiding a "spiral of death" when updates take longer than `timestep`.
+        max_updates_per_frame: usize,
+    },
 }
 
 impl<M> Builder<M, Event>
@@ -259,6 +312,7 @@ where
             default_window_size: None,
             max_capture_frame_jobs: None,
             capture_frame_timeout: None,
+            headless: None,
         }
     }
 
@@ -283,6 +337,7 @@ where
             default_window_size,
             max_capture_frame_jobs,
             capture_frame_timeout,
+            headless,
             ..
         } = self;
         Builder {
@@ -295,6 +350,7 @@ where
             default_window_size,
             max_capture_frame_jobs,
             capture_frame_timeout,
+            headless,
         }
     }
 }
@@ -378,6 +434,36 @@ where
         self
     }
 
+    /// Run the `App` headlessly, rendering each frame to an offscreen texture of the given size in
+    /// pixels rather than to a window.
+    ///
+    /// No winit event loop or windows are created, allowing sketches to be rendered on machines
+    /// without a display, e.g. for CI, render farms and servers. The `update` and `view` functions
+    /// are called in turn without delay until the number of updates specified via
+    /// `LoopMode::NTimes` is reached, after which the `App` exits. The loop mode defaults to
+    /// `LoopMode::loop_once()` when running headlessly, and `LoopMode`s other than `NTimes` run
+    /// until the process is terminated.
+    ///
+    /// Frames may be captured via `app.headless().unwrap().capture_frame(path)`. Windows cannot be
+    /// created, and methods that require a window such as `app.main_window()` will panic. Input
+    /// events are never emitted.
+    ///
+    /// ```no_run
+    /// use nannou::prelude::*;
+    ///
+    /// fn main() {
+    ///     nannou::app(model).headless(1920, 1080).run();
+    /// }
+    ///
+    /// fn model(app: &App) {
+    ///     app.set_loop_mode(LoopMode::loop_ntimes(60));
+    /// }
+    /// ```
+    pub fn headless(mut self, width: u32, height: u32) -> Self {
+        self.headless = Some([width, height]);
+        self
+    }
+
     /// The maximum number of simultaneous capture frame jobs that can be run per window before we
     /// block and wait for the existing jobs to complete.
     ///
@@ -422,6 +508,10 @@ where
     /// thread as some platforms require that their application event loop and windows are
     /// initialised on the main thread.
     pub fn run(self) {
+        if let Some(size) = self.headless {
+            return self.run_headless(size);
+        }
+
         // Start the winit window event loop.
         let event_loop = winit::event_loop::EventLoop::new();
 
@@ -429,7 +519,7 @@ where
         let event_loop_proxy = event_loop.create_proxy();
         let event_loop_is_asleep = Arc::new(AtomicBool::new(false));
         let event_loop_proxy = Proxy {
-            event_loop_proxy,
+            event_loop_proxy: Some(event_loop_proxy),
             event_loop_is_asleep,
         };
 
@@ -477,6 +567,49 @@ where
             self.exit,
         );
     }
+
+    // Build and run the `App` without a winit event loop, rendering to an offscreen texture.
+    fn run_headless(self, size: [u32; 2]) {
+        let event_loop_proxy = Proxy {
+            event_loop_proxy: None,
+            event_loop_is_asleep: Arc::new(AtomicBool::new(false)),
+        };
+        let max_capture_frame_jobs = self
+            .max_capture_frame_jobs
+            .unwrap_or(num_cpus::get() as u32);
+        let capture_frame_timeout = self
+            .capture_frame_timeout
+            .unwrap_or(Some(Self::DEFAULT_CAPTURE_FRAME_TIMEOUT));
+        let mut app = App::new(
+            event_loop_proxy,
+            None,
+            self.default_window_size,
+            max_capture_frame_jobs,
+            capture_frame_timeout,
+        );
+        app.set_loop_mode(LoopMode::loop_once());
+        let headless = Headless::new(
+            app.wgpu_adapters(),
+            size,
+            max_capture_frame_jobs,
+            capture_frame_timeout,
+        )
+        .expect("no available wgpu adapter detected");
+        *app.focused_window.borrow_mut() = Some(headless.id());
+        app.headless = Some(headless);
+
+        // Call the user's model function.
+        let model = (self.model)(&app);
+
+        run_headless_loop(
+            app,
+            model,
+            self.event,
+            self.update,
+            self.default_view,
+            self.exit,
+        );
+    }
 }
 
 impl<E> SketchBuilder<E>
@@ -489,6 +622,14 @@ where
         self
     }
 
+    /// Run the sketch headlessly, rendering to an offscreen texture of the given size in pixels.
+    ///
+    /// See `app::Builder::headless` for details.
+    pub fn headless(mut self, width: u32, height: u32) -> Self {
+        self.builder = self.builder.headless(width, height);
+        self
+    }
+
     /// Build and run a `Sketch` with the specified parameters.
     ///
     /// This calls `App::run` internally. See that method for details!
@@ -511,6 +652,12 @@ impl Builder<(), Event> {
     }
 }
 
+// The moment at which the next frame is due according to the app's fps limit, if any.
+fn next_frame_instant(app: &App, loop_state: &LoopState) -> Option<Instant> {
+    app.fps_limit()
+        .map(|fps| loop_state.last_frame + update_interval(fps as f64))
+}
+
 /// Given some "frames per second", return the interval between frames as a `Duration`.
 fn update_interval(fps: f64) -> Duration {
     assert!(fps > 0.0);
@@ -527,6 +674,9 @@ impl LoopMode {
     /// mode.
     pub const UPDATES_PER_WAIT_EVENT: u32 = 3;
 
+    /// The default maximum number of updates emitted per frame in **FixedTimestep** mode.
+    pub const DEFAULT_MAX_UPDATES_PER_FRAME: usize = 8;
+
     /// A simplified constructor for the default `RefreshSync` loop mode.
     ///
     /// Assumes a display refresh rate of ~60hz and in turn specifies a `minimum_update_latency` of
@@ -557,6 +707,18 @@ impl LoopMode {
     pub fn loop_once() -> Self {
         Self::loop_ntimes(1)
     }
+
+    /// Specify the **FixedTimestep** mode with the given number of updates per second.
+    ///
+    /// At most `Self::DEFAULT_MAX_UPDATES_PER_FRAME` updates are emitted per frame.
+    pub fn fixed_timestep(updates_per_second: f64) -> Self {
+        let timestep = update_interval(updates_per_second);
+        let max_updates_per_frame = Self::DEFAULT_MAX_UPDATES_PER_FRAME;
+        LoopMode::FixedTimestep {
+            timestep,
+            max_updates_per_frame,
+        }
+    }
 }
 
 impl Default for LoopMode {
@@ -570,10 +732,20 @@ impl Default for Config {
         let loop_mode = Default::default();
         let exit_on_escape = App::DEFAULT_EXIT_ON_ESCAPE;
         let fullscreen_on_shortcut = App::DEFAULT_FULLSCREEN_ON_SHORTCUT;
+        let color_vision_deficiency = None;
+        let picking = false;
+        let tessellation_cache = false;
+        let offline_timestep = None;
+        let fps_limit = None;
         Config {
             loop_mode,
             exit_on_escape,
             fullscreen_on_shortcut,
+            color_vision_deficiency,
+            picking,
+            tessellation_cache,
+            offline_timestep,
+            fps_limit,
         }
     }
 }
@@ -595,14 +767,24 @@ impl App {
         let windows = RefCell::new(HashMap::new());
         let draw = RefCell::new(draw::Draw::default());
         let config = RefCell::new(Default::default());
+        let window_draws = RefCell::new(Default::default());
+        let window_queues = RefCell::new(Default::default());
         let renderers = RefCell::new(Default::default());
-        let draw_state = DrawState { draw, renderers };
+        let draw_state = DrawState {
+            draw,
+            window_draws,
+            window_queues,
+            renderers,
+        };
         let focused_window = RefCell::new(None);
         let ui = ui::Arrangement::new();
         let mouse = state::Mouse::new();
         let keys = state::Keys::default();
+        let files = state::Files::default();
         let duration = state::Time::default();
         let time = duration.since_start.secs() as _;
+        let fixed_timestep_alpha = 0.0;
+        let smoothed_fps = 0.0;
         let app = App {
             event_loop_proxy,
             event_loop_window_target,
@@ -612,18 +794,26 @@ impl App {
             focused_window,
             adapters,
             windows,
+            headless: None,
             config,
             draw_state,
             ui,
             mouse,
             keys,
+            files,
             duration,
             time,
+            fixed_timestep_alpha,
+            smoothed_fps,
         };
         app
     }
 
     /// Returns the list of all the monitors available on the system.
+    ///
+    /// The order of the list is determined by the platform, but is consistent for as long as the
+    /// set of connected monitors remains the same. Indices into this list may be passed to
+    /// `Window::set_fullscreen_borderless`.
     pub fn available_monitors(&self) -> Vec<winit::monitor::MonitorHandle> {
         match self.event_loop_window_target {
             Some(EventLoopWindowTarget::Owned(ref event_loop)) => {
@@ -639,6 +829,21 @@ impl App {
         }
     }
 
+    /// The files dropped onto the app's windows since the previous update.
+    ///
+    /// Short-hand for `app.files.dropped`. See `state::Files::dropped_with_extensions` for
+    /// accepting only certain kinds of files.
+    pub fn dropped_files(&self) -> &[PathBuf] {
+        &self.files.dropped
+    }
+
+    /// Returns the monitor at the given index within the list produced by `available_monitors`.
+    ///
+    /// Returns `None` if there is no monitor at the given index.
+    pub fn monitor(&self, index: usize) -> Option<winit::monitor::MonitorHandle> {
+        self.available_monitors().into_iter().nth(index)
+    }
+
     /// Returns the primary monitor of the system.
     pub fn primary_monitor(&self) -> winit::monitor::MonitorHandle {
         match self.event_loop_window_target {
@@ -728,6 +933,9 @@ impl App {
     ///
     /// **Panics** if there are no windows or if no window is in focus.
     pub fn window_rect(&self) -> geom::Rect<DrawScalar> {
+        if let Some(headless) = self.headless.as_ref() {
+            return headless.rect();
+        }
         self.main_window().rect()
     }
 
@@ -758,6 +966,12 @@ impl App {
         &self.adapters
     }
 
+    /// The offscreen render target used in place of a window, if the **App** is running
+    /// headlessly via `app::Builder::headless`.
+    pub fn headless(&self) -> Option<&Headless> {
+        self.headless.as_ref()
+    }
+
     /// Return whether or not the `App` is currently set to exit when the `Escape` key is pressed.
     pub fn exit_on_escape(&self) -> bool {
         self.config.borrow().exit_on_escape
@@ -806,6 +1020,150 @@ impl App {
         self.config.borrow_mut().loop_mode = mode;
     }
 
+    /// The fraction of a timestep that has elapsed since the most recent update in
+    /// `LoopMode::FixedTimestep`, in the range `0.0..1.0`.
+    ///
+    /// This is useful for interpolating between the previous and current simulation states
+    /// within `view`. Always `0.0` in other loop modes.
+    pub fn fixed_timestep_alpha(&self) -> f64 {
+        self.fixed_timestep_alpha
+    }
+
+    /// The virtual duration by which time advances for each update, if offline rendering is
+    /// enabled.
+    pub fn offline_timestep(&self) -> Option<Duration> {
+        self.config.borrow().offline_timestep
+    }
+
+    /// Advance `app.time`, `app.duration` and the `Update` durations by a fixed virtual timestep
+    /// per update, regardless of how much real time has passed.
+    ///
+    /// This is useful for exporting frames of a sketch that is written against wall-clock time
+    /// at a higher resolution or frame rate than it can run in real time. For example, a timestep
+    /// of `Duration::from_secs(1) / 120` produces frames for a 120fps video, no matter how long
+    /// each frame takes to render and capture.
+    ///
+    /// Time continues from its current value when the timestep is enabled. Specify `None` to
+    /// return to real time. By default this is `None`.
+    pub fn set_offline_timestep(&self, timestep: Option<Duration>) {
+        self.config.borrow_mut().offline_timestep = timestep;
+    }
+
+    /// The maximum number of frames per second, if any.
+    pub fn fps_limit(&self) -> Option<f32> {
+        self.config.borrow().fps_limit
+    }
+
+    /// Limit the rate at which updates and frames occur to the given number of frames per second.
+    ///
+    /// Rather than spinning while waiting for the next frame, the event loop sleeps until the
+    /// frame is due. This is useful for capping GPU and CPU usage, e.g. for long running
+    /// installations. The limit applies on top of the loop mode, and the frame rate may still be
+    /// lower due to vsync, see `Window::set_present_mode`. Specify `None` to remove the limit.
+    ///
+    /// By default, there is no limit.
+    ///
+    /// **Panics** if the given limit is not greater than `0.0`.
+    pub fn set_fps_limit(&self, fps: Option<f32>) {
+        if let Some(fps) = fps {
+            assert!(fps > 0.0, "the fps limit must be greater than 0.0");
+        }
+        self.config.borrow_mut().fps_limit = fps;
+    }
+
+    /// The color vision deficiency currently simulated when rendering via **Draw**, if any.
+    pub fn color_vision_deficiency(&self) -> Option<ColorVisionDeficiency> {
+        self.config.borrow().color_vision_deficiency
+    }
+
+    /// Simulate the given color vision deficiency when rendering via **Draw**.
+    ///
+    /// This can be toggled at any time while sketching in order to check the accessibility of
+    /// the work. Specify `None` to disable the simulation. By default this is `None`.
+    pub fn set_color_vision_deficiency(&self, cvd: Option<ColorVisionDeficiency>) {
+        self.config.borrow_mut().color_vision_deficiency = cvd;
+    }
+
+    /// Whether or not primitive IDs are rendered for picking via `App::pick`.
+    pub fn picking_enabled(&self) -> bool {
+        self.config.borrow().picking
+    }
+
+    /// Enable or disable the rendering of primitive IDs for picking via `App::pick`.
+    ///
+    /// While enabled, each **Draw** render is followed by a second pass that draws the ID of
+    /// every primitive to an offscreen texture. By default this is `false`.
+    pub fn set_picking_enabled(&self, enabled: bool) {
+        self.config.borrow_mut().picking = enabled;
+    }
+
+    /// The ID of the topmost primitive at the given point within the most recent **Draw** render
+    /// to the window with the given ID, e.g. the primitive beneath the mouse.
+    ///
+    /// The point is in the same coordinates as the **Draw** API. Returns `None` if picking is
+    /// disabled or if there is no primitive at the given point.
+    ///
+    /// This blocks until the ID has been read back from the GPU.
+    pub fn pick(
+        &self,
+        window_id: window::Id,
+        point: geom::Point2<DrawScalar>,
+    ) -> Option<draw::renderer::PrimitiveId> {
+        let window = self.window(window_id)?;
+        let renderers = self.draw_state.renderers.borrow();
+        let renderer = renderers.get(&window_id)?;
+        let id = renderer.borrow().pick(window.swap_chain_device(), point);
+        id
+    }
+
+    /// Whether or not **Draw** renderers cache tessellated primitives between frames.
+    pub fn tessellation_cache_enabled(&self) -> bool {
+        self.config.borrow().tessellation_cache
+    }
+
+    /// Enable or disable the caching of tessellated paths and polygons between frames.
+    ///
+    /// This can reduce the CPU time spent rendering scenes containing many complex paths that are
+    /// unchanged between frames, but costs a copy of each path's data per frame. By default this
+    /// is `false`.
+    pub fn set_tessellation_cache_enabled(&self, enabled: bool) {
+        self.config.borrow_mut().tessellation_cache = enabled;
+    }
+
+    /// Statistics describing the use of the tessellation cache while rendering the most recent
+    /// **Draw** to the window with the given ID.
+    ///
+    /// Returns `None` if nothing has been drawn to the window via **Draw** yet.
+    pub fn tessellation_cache_stats(
+        &self,
+        window_id: window::Id,
+    ) -> Option<draw::renderer::cache::Stats> {
+        let renderers = self.draw_state.renderers.borrow();
+        let renderer = renderers.get(&window_id)?;
+        let stats = renderer.borrow().tessellation_cache().stats();
+        Some(stats)
+    }
+
+    /// The CPU time spent within each stage of rendering the most recent **Draw** to the window
+    /// with the given ID.
+    ///
+    /// Returns `None` if nothing has been drawn to the window via **Draw** yet.
+    #[cfg(feature = "profile")]
+    pub fn draw_timings(&self, window_id: window::Id) -> Option<draw::renderer::Timings> {
+        let renderers = self.draw_state.renderers.borrow();
+        let renderer = renderers.get(&window_id)?;
+        let timings = renderer.borrow().timings();
+        Some(timings)
+    }
+
+    /// Clear the tessellation caches of all **Draw** renderers, forcing all primitives to be
+    /// re-tessellated on the following frame.
+    pub fn clear_tessellation_caches(&self) {
+        for renderer in self.draw_state.renderers.borrow().values() {
+            renderer.borrow_mut().tessellation_cache_mut().clear();
+        }
+    }
+
     /// A handle to the **App** that can be shared across threads.
     ///
     /// This can be used to "wake up" the **App**'s inner event loop.
@@ -832,15 +1190,76 @@ impl App {
         draw.clone()
     }
 
+    /// Produce the **Draw** associated with the window with the given ID.
+    ///
+    /// Unlike `app.draw()` which shares a single **Draw** between all windows, each window has its
+    /// own **Draw** with its own theme and default context. This is useful for applications with
+    /// multiple windows that each require different defaults. Like `app.draw()`, the state is
+    /// reset on each call, while the theme and the default context persist.
+    ///
+    /// Use `set_draw_for` to associate a pre-configured **Draw** with a window.
+    pub fn draw_for(&self, window_id: window::Id) -> draw::Draw {
+        let mut window_draws = self.draw_state.window_draws.borrow_mut();
+        let draw = window_draws
+            .entry(window_id)
+            .or_insert_with(draw::Draw::new);
+        draw.reset();
+        draw.clone()
+    }
+
+    /// Associate the given **Draw** with the window with the given ID.
+    ///
+    /// The theme of the given **Draw** along with its context (e.g. its default blend mode,
+    /// sampler or transform) are used by all **Draw**s subsequently produced via `draw_for`.
+    pub fn set_draw_for(&self, window_id: window::Id, draw: draw::Draw) {
+        self.draw_state
+            .window_draws
+            .borrow_mut()
+            .insert(window_id, draw);
+    }
+
     /// The number of times the focused window's **view** function has been called since the start
     /// of the program.
     pub fn elapsed_frames(&self) -> u64 {
+        if let Some(headless) = self.headless.as_ref() {
+            return headless.elapsed_frames();
+        }
         self.main_window().frame_count
     }
 
-    /// The number of frames that can currently be displayed a second
+    /// The number of frames currently being produced per second.
+    ///
+    /// The rate is smoothed over recent frames so that it remains readable when displayed, e.g.
+    /// within a sketch's window title. For the rate based solely on the most recent update, see
+    /// `app.duration.updates_per_second()`.
     pub fn fps(&self) -> f32 {
-        self.duration.updates_per_second()
+        self.smoothed_fps
+    }
+
+    /// The time in seconds since the previous update.
+    ///
+    /// This is a convenience for `app.duration.since_prev_update.secs()`, useful for time-based
+    /// animation that progresses at the same rate regardless of the frame rate, e.g.
+    /// `model.x += speed * app.delta_time()`. When rendering offline or in
+    /// `LoopMode::FixedTimestep`, this is the timestep.
+    pub fn delta_time(&self) -> DrawScalar {
+        self.duration.since_prev_update.secs() as _
+    }
+
+    // Update the smoothed frame rate with the duration of the most recent frame.
+    fn update_fps(&mut self, frame_duration: Duration) {
+        // The weight given to the previous rate with each new frame.
+        const SMOOTHING: f32 = 0.9;
+        let secs = frame_duration.secs() as f32;
+        if secs <= 0.0 {
+            return;
+        }
+        let fps = 1.0 / secs;
+        self.smoothed_fps = if self.smoothed_fps == 0.0 {
+            fps
+        } else {
+            self.smoothed_fps * SMOOTHING + fps * (1.0 - SMOOTHING)
+        };
     }
 
     /// The name of the nannou executable that is currently running.
@@ -867,8 +1286,12 @@ impl Proxy {
     /// method as frequently as necessary across methods without causing any underlying OS methods
     /// to be called more than necessary.
     pub fn wakeup(&self) -> Result<(), winit::event_loop::EventLoopClosed<()>> {
+        let event_loop_proxy = match self.event_loop_proxy {
+            Some(ref proxy) => proxy,
+            None => return Ok(()),
+        };
         if self.event_loop_is_asleep.load(atomic::Ordering::Relaxed) {
-            self.event_loop_proxy.send_event(())?;
+            event_loop_proxy.send_event(())?;
             self.event_loop_is_asleep
                 .store(false, atomic::Ordering::Relaxed);
         }
@@ -880,20 +1303,50 @@ impl draw::Draw {
     /// Render the **Draw**'s inner list of commands to the texture associated with the **Frame**.
     ///
     /// The **App** stores a unique render.
-    pub fn to_frame(&self, app: &App, frame: &Frame) -> Result<(), draw::renderer::DrawError> {
+    ///
+    /// On success, returns statistics describing the render including vertex and index counts,
+    /// the number of pipeline switches and the CPU time spent.
+    pub fn to_frame(
+        &self,
+        app: &App,
+        frame: &Frame,
+    ) -> Result<draw::renderer::RenderResult, draw::renderer::DrawError> {
+        // When running headlessly, all frames are rendered to the headless target.
+        if let Some(headless) = app.headless.as_ref() {
+            let device = headless.device();
+            let frame_dims = headless.size_pixels();
+            let msaa_samples = headless.msaa_samples();
+            return self.to_frame_inner(app, frame, device, frame_dims, 1.0, msaa_samples);
+        }
+
         let window_id = frame.window_id();
         let window = app
             .window(window_id)
             .expect("no window to draw to for `Draw`'s window_id");
+        let device = window.swap_chain_device();
+        let frame_dims: [u32; 2] = window.tracked_state.physical_size.into();
+        let scale_factor = window.tracked_state.scale_factor as f32;
+        let msaa_samples = window.msaa_samples();
+        self.to_frame_inner(app, frame, device, frame_dims, scale_factor, msaa_samples)
+    }
+
+    // Render to the frame via the renderer associated with its window, creating the renderer with
+    // the given parameters if necessary.
+    fn to_frame_inner(
+        &self,
+        app: &App,
+        frame: &Frame,
+        device: &wgpu::Device,
+        frame_dims: [u32; 2],
+        scale_factor: f32,
+        msaa_samples: u32,
+    ) -> Result<draw::renderer::RenderResult, draw::renderer::DrawError> {
+        let window_id = frame.window_id();
 
         // Retrieve a renderer for this window.
         let renderers = app.draw_state.renderers.borrow_mut();
         let renderer = RefMut::map(renderers, |renderers| {
             renderers.entry(window_id).or_insert_with(|| {
-                let device = window.swap_chain_device();
-                let frame_dims: [u32; 2] = window.tracked_state.physical_size.into();
-                let scale_factor = window.tracked_state.scale_factor as f32;
-                let msaa_samples = window.msaa_samples();
                 let target_format = crate::frame::Frame::TEXTURE_FORMAT;
                 let renderer = draw::RendererBuilder::new().build(
                     device,
@@ -906,10 +1359,110 @@ impl draw::Draw {
             })
         });
 
-        let scale_factor = window.tracked_state.scale_factor as _;
         let mut renderer = renderer.borrow_mut();
-        renderer.render_to_frame(window.swap_chain_device(), self, scale_factor, frame);
-        Ok(())
+        renderer.set_color_vision_deficiency(app.color_vision_deficiency());
+        renderer.set_picking_enabled(app.picking_enabled());
+        renderer
+            .tessellation_cache_mut()
+            .set_enabled(app.tessellation_cache_enabled());
+        let result = renderer.render_to_frame(device, self, scale_factor, frame);
+        if let Some(dir) = frame.debug_dump_dir() {
+            if let Err(e) = renderer.dump_debug(&dir) {
+                // TODO: Log errors, don't print to stderr.
+                eprintln!("failed to dump renderer debug output: {}", e);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Queue a copy of the **Draw**'s inner list of commands to be rendered to the next frame of the
+    /// window with the given ID.
+    ///
+    /// Unlike `to_frame`, the **Draw**'s commands are left untouched, allowing for the same drawing
+    /// to be rendered to several windows within the same frame, e.g. a control window along with a
+    /// fullscreen output:
+    ///
+    /// ```no_run
+    /// # use nannou::prelude::*;
+    /// # struct Model { control: WindowId, output: WindowId }
+    /// fn view(app: &App, model: &Model, frame: Frame) {
+    ///     // The output window shows the drawing queued by the control window. Clearing its frame
+    ///     // here would hide the queued drawing, so nothing else is drawn to it.
+    ///     if frame.window_id() != model.control {
+    ///         return;
+    ///     }
+    ///     let draw = app.draw();
+    ///     draw.background().color(BLACK);
+    ///     draw.ellipse().color(PLUM);
+    ///     draw.to_window(app, model.output);
+    ///     draw.to_frame(app, &frame).unwrap();
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// The queued drawing is rendered to the window's frame the next time the window is redrawn,
+    /// before the window's `view` function is called. Anything drawn within that `view` will appear
+    /// on top, including backgrounds, so a `view` that clears the frame, e.g. via
+    /// `draw.background()`, hides the queued drawing. The `view` of the target window should
+    /// leave the frame uncleared as in the example above.
+    ///
+    /// If the target window has already been redrawn during the current update, e.g. because it
+    /// was redrawn before the window whose `view` queued the drawing, the drawing appears in the
+    /// target window's following frame instead, one frame late.
+    ///
+    /// Queued drawings are discarded for windows with a `raw_view` function or without any `view`
+    /// function.
+    pub fn to_window(&self, app: &App, window_id: window::Id) {
+        app.draw_state
+            .window_queues
+            .borrow_mut()
+            .entry(window_id)
+            .or_insert_with(Vec::new)
+            .push(self.duplicate());
+    }
+
+    /// Render the **Draw**'s inner list of commands to the given **Texture** via the GPU device
+    /// and queue of the given **Window**.
+    ///
+    /// The render is encoded and submitted immediately, so the texture may be drawn from again
+    /// (e.g. via `draw.texture`) later within the same frame. This is useful for feedback loops
+    /// and compositions of multiple passes. The texture must have been created with the window's
+    /// device and with the `OUTPUT_ATTACHMENT` usage. Drawing coordinates map one point to one
+    /// pixel of the texture.
+    ///
+    /// The window stores a unique renderer for each texture format, created on first use.
+    pub fn to_texture(
+        &self,
+        window: &Window,
+        texture: &wgpu::Texture,
+    ) -> Result<draw::renderer::RenderResult, draw::renderer::DrawError> {
+        let device = window.swap_chain_device();
+        let mut renderers = window.texture_renderers.borrow_mut();
+        let renderer = renderers.entry(texture.format()).or_insert_with(|| {
+            draw::RendererBuilder::new().build_from_texture_descriptor(device, texture.descriptor())
+        });
+        let cmd_encoder_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("nannou_draw_to_texture"),
+        };
+        let mut encoder = device.create_command_encoder(&cmd_encoder_desc);
+        let result = renderer.render_to_texture(device, &mut encoder, self, texture);
+        window.swap_chain_queue().submit(&[encoder.finish()]);
+        Ok(result)
+    }
+}
+
+// Render any **Draw**s queued for the frame's window via `Draw::to_window`.
+fn render_queued_draws(app: &App, frame: &Frame) {
+    let queued = app
+        .draw_state
+        .window_queues
+        .borrow_mut()
+        .remove(&frame.window_id());
+    for draw in queued.into_iter().flatten() {
+        if draw.to_frame(app, frame).is_err() {
+            // TODO: Log errors, don't print to stderr.
+            eprintln!("failed to render queued `Draw` to window");
+        }
     }
 }
 
@@ -1014,6 +1567,9 @@ fn run_loop<M, E>(
         loop_start,
         last_update: loop_start,
         total_updates: 0,
+        last_frame: loop_start,
+        fixed_timestep_accumulator: Duration::from_secs(0),
+        frame_skipped: false,
     };
 
     // Run the event loop.
@@ -1028,17 +1584,57 @@ fn run_loop<M, E>(
         match event {
             // Check to see if we need to emit an update and request a redraw.
             winit::event::Event::MainEventsCleared => {
-                if let Some(model) = model.as_mut() {
+                // Skip this iteration if the previous frame began too recently for the fps limit.
+                let frame_due = next_frame_instant(&app, &loop_state)
+                    .map(|next| Instant::now() >= next)
+                    .unwrap_or(true);
+                loop_state.frame_skipped = !frame_due;
+                if let Some(model) = model.as_mut().filter(|_| frame_due) {
                     let loop_mode = app.loop_mode();
                     let now = Instant::now();
-                    let mut do_update = |loop_state: &mut LoopState| {
-                        apply_update(&mut app, model, event_fn, update_fn, loop_state, now);
+                    let mut do_update = |app: &mut App, loop_state: &mut LoopState| {
+                        apply_update(app, model, event_fn, update_fn, loop_state, now);
                     };
                     match loop_mode {
                         LoopMode::NTimes { number_of_updates }
                             if loop_state.total_updates >= number_of_updates as u64 => {}
-                        _ => do_update(&mut loop_state),
+                        LoopMode::FixedTimestep {
+                            timestep,
+                            max_updates_per_frame,
+                        } => {
+                            let elapsed = now.duration_since(loop_state.last_frame);
+                            loop_state.fixed_timestep_accumulator += elapsed;
+                            let mut updates = 0;
+                            while loop_state.fixed_timestep_accumulator >= timestep
+                                && updates < max_updates_per_frame
+                            {
+                                do_update(&mut app, &mut loop_state);
+                                loop_state.fixed_timestep_accumulator -= timestep;
+                                updates += 1;
+                            }
+                            // Discard any backlog that could not be caught up on.
+                            if loop_state.fixed_timestep_accumulator >= timestep {
+                                let acc = loop_state.fixed_timestep_accumulator.as_nanos();
+                                let remainder = acc % timestep.as_nanos().max(1);
+                                loop_state.fixed_timestep_accumulator =
+                                    Duration::from_nanos(remainder as u64);
+                            }
+                            app.fixed_timestep_alpha =
+                                loop_state.fixed_timestep_accumulator.secs() / timestep.secs();
+                            // Frames are rendered regardless of whether or not an update occurred.
+                            if updates == 0 {
+                                for window in app.windows.borrow().values() {
+                                    window.window.request_redraw();
+                                }
+                            }
+                        }
+                        _ => {
+                            app.fixed_timestep_alpha = 0.0;
+                            do_update(&mut app, &mut loop_state);
+                        }
                     }
+                    app.update_fps(now.duration_since(loop_state.last_frame));
+                    loop_state.last_frame = now;
                 }
             }
 
@@ -1055,6 +1651,7 @@ fn run_loop<M, E>(
                     let window = windows
                         .get_mut(&window_id)
                         .expect("no window for redraw request ID");
+                    window.apply_pending_present_mode();
                     let swap_chain = window
                         .swap_chain
                         .swap_chain
@@ -1100,15 +1697,18 @@ fn run_loop<M, E>(
                     // Otherwise, use the fallback, default view passed to the app if there was one.
                     let window_view = window.user_functions.view.clone();
 
+                    let view_start = Instant::now();
                     match window_view {
                         Some(window::View::Sketch(view)) => {
                             let data = frame_data.as_ref().expect("missing `frame_data`");
                             let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+                            render_queued_draws(&app, &frame);
                             view(&app, frame);
                         }
                         Some(window::View::WithModel(view)) => {
                             let data = frame_data.as_ref().expect("missing `frame_data`");
                             let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+                            render_queued_draws(&app, &frame);
                             let view = view
                                 .to_fn_ptr::<M>()
                                 .expect("unexpected model argument given to window view function");
@@ -1118,6 +1718,7 @@ fn run_loop<M, E>(
                             let raw_view = raw_view.to_fn_ptr::<M>().expect(
                                 "unexpected model argument given to window raw_view function",
                             );
+                            app.draw_state.window_queues.borrow_mut().remove(&window_id);
                             (*raw_view)(&app, &model, raw_frame);
                         }
                         None => match default_view {
@@ -1125,17 +1726,23 @@ fn run_loop<M, E>(
                                 let data = frame_data.as_ref().expect("missing `frame_data`");
                                 let frame =
                                     Frame::new_empty(raw_frame, &data.render, &data.capture);
+                                render_queued_draws(&app, &frame);
                                 view(&app, frame);
                             }
                             Some(View::WithModel(view)) => {
                                 let data = frame_data.as_ref().expect("missing `frame_data`");
                                 let frame =
                                     Frame::new_empty(raw_frame, &data.render, &data.capture);
+                                render_queued_draws(&app, &frame);
                                 view(&app, &model, frame);
                             }
-                            None => raw_frame.submit(),
+                            None => {
+                                app.draw_state.window_queues.borrow_mut().remove(&window_id);
+                                raw_frame.submit();
+                            }
                         },
                     }
+                    app.duration.view_duration = view_start.elapsed();
                 }
 
                 // Replace the render data and swap chain.
@@ -1224,6 +1831,14 @@ fn run_loop<M, E>(
             _ => ControlFlow::Poll,
         };
 
+        // Sleep until the next frame is due rather than polling when the frame rate is limited. A
+        // skipped frame is always revisited, even in `Wait` mode, so that no update is lost.
+        if let Some(next) = next_frame_instant(&app, &loop_state) {
+            if *control_flow == ControlFlow::Poll || loop_state.frame_skipped {
+                *control_flow = ControlFlow::WaitUntil(next);
+            }
+        }
+
         // If we need to exit, call the user's function and update control flow.
         if exit {
             if let Some(model) = model.take() {
@@ -1246,6 +1861,102 @@ fn run_loop<M, E>(
     }
 }
 
+// The application loop used when running headlessly.
+//
+// Without windows there are no input events to process or swap chain images to wait on, so the
+// user's update and view functions are simply called in turn until the number of updates specified
+// via `LoopMode::NTimes` is reached.
+fn run_headless_loop<M, E>(
+    mut app: App,
+    mut model: M,
+    event_fn: Option<EventFn<M, E>>,
+    update_fn: Option<UpdateFn<M>>,
+    default_view: Option<View<M>>,
+    exit_fn: Option<ExitFn<M>>,
+) where
+    M: 'static,
+    E: LoopEvent,
+{
+    let loop_start = Instant::now();
+    let mut loop_state = LoopState {
+        updates_since_event: 0,
+        loop_start,
+        last_update: loop_start,
+        total_updates: 0,
+        last_frame: loop_start,
+        fixed_timestep_accumulator: Duration::from_secs(0),
+        frame_skipped: false,
+    };
+
+    loop {
+        if let LoopMode::NTimes { number_of_updates } = app.loop_mode() {
+            if loop_state.total_updates >= number_of_updates as u64 {
+                break;
+            }
+        }
+        let now = Instant::now();
+        apply_update(
+            &mut app,
+            &mut model,
+            event_fn,
+            update_fn,
+            &mut loop_state,
+            now,
+        );
+        app.update_fps(now.duration_since(loop_state.last_frame));
+        loop_state.last_frame = now;
+        let view_start = Instant::now();
+        view_headless(&mut app, &model, default_view.as_ref());
+        app.duration.view_duration = view_start.elapsed();
+    }
+
+    if let Some(exit_fn) = exit_fn {
+        exit_fn(&app, model);
+    }
+}
+
+// Request a frame from the user for the headless render target.
+fn view_headless<M>(app: &mut App, model: &M, default_view: Option<&View<M>>)
+where
+    M: 'static,
+{
+    let nth_frame = {
+        let headless = app
+            .headless
+            .as_mut()
+            .expect("missing headless render target");
+        let nth_frame = headless.frame_count;
+        headless.frame_count += 1;
+        nth_frame
+    };
+
+    let app: &App = app;
+    let headless = app
+        .headless
+        .as_ref()
+        .expect("missing headless render target");
+    let raw_frame = RawFrame::new_empty(
+        headless.device_queue_pair().clone(),
+        headless.id(),
+        nth_frame,
+        &headless.texture_view,
+        Headless::TEXTURE_FORMAT,
+        headless.rect(),
+    );
+    let data = &headless.frame_data;
+    match default_view {
+        Some(View::Sketch(view)) => {
+            let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+            view(app, frame);
+        }
+        Some(View::WithModel(view)) => {
+            let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+            view(app, model, frame);
+        }
+        None => raw_frame.submit(),
+    }
+}
+
 // Apply an update to the model via the user's function and update the app and loop state
 // accordingly.
 fn apply_update<M, E>(
@@ -1259,9 +1970,21 @@ fn apply_update<M, E>(
     M: 'static,
     E: LoopEvent,
 {
-    // Update the app's durations.
-    let since_last = now.duration_since(loop_state.last_update);
-    let since_start = now.duration_since(loop_state.loop_start);
+    // Update the app's durations, advancing by the virtual or fixed timestep if there is one.
+    let timestep = match app.offline_timestep() {
+        Some(timestep) => Some(timestep),
+        None => match app.loop_mode() {
+            LoopMode::FixedTimestep { timestep, .. } => Some(timestep),
+            _ => None,
+        },
+    };
+    let (since_last, since_start) = match timestep {
+        Some(timestep) => (timestep, app.duration.since_start + timestep),
+        None => (
+            now.duration_since(loop_state.last_update),
+            now.duration_since(loop_state.loop_start),
+        ),
+    };
     app.duration.since_prev_update = since_last;
     app.duration.since_start = since_start;
     app.time = since_start.secs() as _;
@@ -1269,6 +1992,7 @@ fn apply_update<M, E>(
         since_start,
         since_last,
     };
+    let update_start = Instant::now();
     // User event function.
     if let Some(event_fn) = event_fn {
         let event = E::from(update.clone());
@@ -1278,6 +2002,9 @@ fn apply_update<M, E>(
     if let Some(update_fn) = update_fn {
         update_fn(app, model, update);
     }
+    app.duration.update_duration = update_start.elapsed();
+    // Dropped files are only presented to a single update.
+    app.files.dropped.clear();
     loop_state.last_update = now;
     loop_state.total_updates += 1;
     loop_state.updates_since_event += 1;
@@ -1367,6 +2094,8 @@ where
         // Returns the `Window` that was removed.
         fn remove_related_window_state(app: &App, window_id: &window::Id) -> Option<Window> {
             app.draw_state.renderers.borrow_mut().remove(window_id);
+            app.draw_state.window_draws.borrow_mut().remove(window_id);
+            app.draw_state.window_queues.borrow_mut().remove(window_id);
             app.windows.borrow_mut().remove(window_id)
         }
 
@@ -1447,6 +2176,21 @@ where
                     }
                 }
 
+                winit::event::WindowEvent::HoveredFile(ref path) => {
+                    app.files.hovered.push(path.clone());
+                    app.files.window = Some(window_id);
+                }
+
+                winit::event::WindowEvent::HoveredFileCancelled => {
+                    app.files.hovered.clear();
+                }
+
+                winit::event::WindowEvent::DroppedFile(ref path) => {
+                    app.files.hovered.retain(|p| p != path);
+                    app.files.dropped.push(path.clone());
+                    app.files.window = Some(window_id);
+                }
+
                 _ => (),
             }
 
@@ -1572,6 +2316,9 @@ where
             match simple {
                 event::WindowEvent::KeyPressed(key) => call_user_function!(key_pressed, key),
                 event::WindowEvent::KeyReleased(key) => call_user_function!(key_released, key),
+                event::WindowEvent::ReceivedCharacter(c) => {
+                    call_user_function!(received_character, c)
+                }
                 event::WindowEvent::MouseMoved(pos) => call_user_function!(mouse_moved, pos),
                 event::WindowEvent::MousePressed(button) => {
                     call_user_function!(mouse_pressed, button)
@@ -4,9 +4,13 @@
 //! See the [**named**](./named/index.html) module for a set of provided color constants.
 
 pub mod conv;
+pub mod ramp;
+pub mod vision;
 
 pub use self::conv::IntoLinSrgba;
 pub use self::named::*;
+pub use self::ramp::{ColorRamp, CosinePalette, Palette};
+pub use self::vision::ColorVisionDeficiency;
 #[doc(inline)]
 pub use palette::*;
 
@@ -0,0 +1,320 @@
+//! Items for working with collections of colors, including the **Palette**, **ColorRamp** and
+//! **CosinePalette** types.
+
+use crate::color::{IntoLinSrgba, LinSrgba, Rgba8, Srgb, Srgba};
+use crate::rand::rngs::SmallRng;
+use crate::rand::seq::SliceRandom;
+use crate::rand::SeedableRng;
+use crate::serde_derive::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+
+/// An ordered set of colors, e.g. the swatches used throughout a sketch.
+///
+/// Colors are stored in the non-linear sRGBA space, matching the **draw::Theme**.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    colors: Vec<Srgba>,
+}
+
+/// A continuous range of colors described by a list of stops, sampled via `t` between `0.0` and
+/// `1.0`.
+///
+/// Interpolation between stops occurs in linear sRGB space.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColorRamp {
+    stops: Vec<(f32, LinSrgba)>,
+}
+
+/// A procedural palette, producing colors via `a + b * cos(2π * (c * t + d))` per channel.
+///
+/// See Inigo Quilez's [article on the
+/// technique](https://iquilezles.org/www/articles/palettes/palettes.htm) for details and
+/// examples of parameters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CosinePalette {
+    pub a: [f32; 3],
+    pub b: [f32; 3],
+    pub c: [f32; 3],
+    pub d: [f32; 3],
+}
+
+/// Errors that may occur while parsing a hexadecimal color string.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseHexError {
+    /// The string did not contain 3, 4, 6 or 8 hexadecimal digits.
+    InvalidLength(String),
+    /// The string contained a character that is not a hexadecimal digit.
+    InvalidDigit(String),
+}
+
+/// Parse a color from a hexadecimal string.
+///
+/// The leading `#` is optional. Supported forms are `RGB`, `RGBA`, `RRGGBB` and `RRGGBBAA`.
+pub fn parse_hex(s: &str) -> Result<Rgba8, ParseHexError> {
+    let hex = s.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParseHexError::InvalidDigit(s.to_string()));
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).unwrap();
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+    let (r, g, b, a) = match hex.len() {
+        3 => (digit(0) * 17, digit(1) * 17, digit(2) * 17, 255),
+        4 => (digit(0) * 17, digit(1) * 17, digit(2) * 17, digit(3) * 17),
+        6 => (byte(0), byte(2), byte(4), 255),
+        8 => (byte(0), byte(2), byte(4), byte(6)),
+        _ => return Err(ParseHexError::InvalidLength(s.to_string())),
+    };
+    Ok(Rgba8::new(r, g, b, a))
+}
+
+impl Palette {
+    /// Create a palette from the given colors.
+    pub fn new<I, C>(colors: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: IntoLinSrgba<f32>,
+    {
+        let colors = colors
+            .into_iter()
+            .map(|c| Srgba::from_linear(c.into_lin_srgba()))
+            .collect();
+        Palette { colors }
+    }
+
+    /// Create a palette from a list of hexadecimal color strings, e.g. `["#264653", "#2a9d8f"]`.
+    ///
+    /// See the `parse_hex` function for the supported formats.
+    pub fn from_hex<I, S>(hex: I) -> Result<Self, ParseHexError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let colors = hex
+            .into_iter()
+            .map(|s| parse_hex(s.as_ref()).map(|c| c.into_format()))
+            .collect::<Result<_, _>>()?;
+        Ok(Palette { colors })
+    }
+
+    /// Create a palette from a list of hexadecimal integer literals, e.g. `[0x264653, 0x2a9d8f]`.
+    pub fn from_u32s<I>(hex: I) -> Self
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        let colors = hex
+            .into_iter()
+            .map(|c| {
+                let (r, g, b) = super::rgb_u32(c).into_format::<f32>().into_components();
+                Srgba::new(r, g, b, 1.0)
+            })
+            .collect();
+        Palette { colors }
+    }
+
+    /// The number of colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Whether or not the palette contains any colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The colors within the palette.
+    pub fn colors(&self) -> &[Srgba] {
+        &self.colors
+    }
+
+    /// Produce an iterator yielding each color in the palette.
+    pub fn iter(&self) -> std::slice::Iter<Srgba> {
+        self.colors.iter()
+    }
+
+    /// Retrieve the color at the given index, wrapping around to the start of the palette if the
+    /// index exceeds its length.
+    ///
+    /// **Panics** if the palette is empty.
+    pub fn wrapped(&self, index: usize) -> Srgba {
+        self.colors[index % self.colors.len()]
+    }
+
+    /// Append a color to the end of the palette.
+    pub fn push<C>(&mut self, color: C)
+    where
+        C: IntoLinSrgba<f32>,
+    {
+        self.colors.push(Srgba::from_linear(color.into_lin_srgba()));
+    }
+
+    /// Shuffle the order of the colors using a random number generator seeded with `seed`.
+    ///
+    /// The same seed always produces the same order.
+    pub fn shuffle(mut self, seed: u64) -> Self {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        self.colors.shuffle(&mut rng);
+        self
+    }
+
+    /// Produce a **ColorRamp** with the palette's colors spaced evenly between `0.0` and `1.0`.
+    pub fn ramp(&self) -> ColorRamp {
+        ColorRamp::from_colors(self.colors.iter().cloned())
+    }
+
+    /// Sample the palette as a continuous ramp at `t`, where `t` is between `0.0` and `1.0`.
+    ///
+    /// This is a shorthand for `palette.ramp().sample(t)`.
+    pub fn sample(&self, t: f32) -> LinSrgba {
+        self.ramp().sample(t)
+    }
+}
+
+impl ColorRamp {
+    /// Create a ramp from a list of `(position, color)` stops.
+    ///
+    /// Stops are sorted by position.
+    pub fn new<I, C>(stops: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, C)>,
+        C: IntoLinSrgba<f32>,
+    {
+        let mut stops: Vec<_> = stops
+            .into_iter()
+            .map(|(t, c)| (t, c.into_lin_srgba()))
+            .collect();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        ColorRamp { stops }
+    }
+
+    /// Create a ramp with the given colors spaced evenly between `0.0` and `1.0`.
+    pub fn from_colors<I, C>(colors: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: IntoLinSrgba<f32>,
+    {
+        let colors: Vec<_> = colors.into_iter().map(|c| c.into_lin_srgba()).collect();
+        let step = 1.0 / (colors.len().max(2) - 1) as f32;
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (i as f32 * step, c))
+            .collect();
+        ColorRamp { stops }
+    }
+
+    /// The stops describing the ramp, sorted by position.
+    pub fn stops(&self) -> &[(f32, LinSrgba)] {
+        &self.stops
+    }
+
+    /// Sample the ramp at `t`.
+    ///
+    /// Values of `t` outside of the range of stops are clamped to the first or last stop. An empty
+    /// ramp produces transparent black.
+    pub fn sample(&self, t: f32) -> LinSrgba {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return LinSrgba::new(0.0, 0.0, 0.0, 0.0),
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.0 {
+                let span = b.0 - a.0;
+                let amt = if span > 0.0 { (t - a.0) / span } else { 1.0 };
+                return lerp(a.1, b.1, amt);
+            }
+        }
+        last.1
+    }
+
+    /// Produce a **Palette** containing `n` colors sampled at even intervals along the ramp.
+    pub fn palette(&self, n: usize) -> Palette {
+        let step = 1.0 / (n.max(2) - 1) as f32;
+        Palette::new((0..n).map(|i| self.sample(i as f32 * step)))
+    }
+}
+
+impl CosinePalette {
+    /// Create a new cosine palette from its four parameters.
+    pub fn new(a: [f32; 3], b: [f32; 3], c: [f32; 3], d: [f32; 3]) -> Self {
+        CosinePalette { a, b, c, d }
+    }
+
+    /// Sample the palette at `t`.
+    ///
+    /// The resulting channels are clamped to the range `0.0` to `1.0`.
+    pub fn sample(&self, t: f32) -> Srgb {
+        let CosinePalette { a, b, c, d } = *self;
+        let channel = |i: usize| {
+            let v = a[i] + b[i] * (std::f32::consts::PI * 2.0 * (c[i] * t + d[i])).cos();
+            v.max(0.0).min(1.0)
+        };
+        Srgb::new(channel(0), channel(1), channel(2))
+    }
+
+    /// Produce a **Palette** containing `n` colors sampled at even intervals between `0.0` and
+    /// `1.0`.
+    pub fn palette(&self, n: usize) -> Palette {
+        let step = 1.0 / (n.max(2) - 1) as f32;
+        Palette::new((0..n).map(|i| self.sample(i as f32 * step)))
+    }
+}
+
+impl<C> std::iter::FromIterator<C> for Palette
+where
+    C: IntoLinSrgba<f32>,
+{
+    fn from_iter<I>(colors: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+    {
+        Palette::new(colors)
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type Item = &'a Srgba;
+    type IntoIter = std::slice::Iter<'a, Srgba>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.colors.iter()
+    }
+}
+
+impl StdError for ParseHexError {}
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseHexError::InvalidLength(ref s) => {
+                write!(f, "`{}` does not contain 3, 4, 6 or 8 hex digits", s)
+            }
+            ParseHexError::InvalidDigit(ref s) => {
+                write!(f, "`{}` contains a non-hexadecimal digit", s)
+            }
+        }
+    }
+}
+
+// Linearly interpolate between two colors.
+fn lerp(a: LinSrgba, b: LinSrgba, t: f32) -> LinSrgba {
+    let (ar, ag, ab, aa) = a.into();
+    let (br, bg, bb, ba) = b.into();
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    LinSrgba::new(lerp(ar, br), lerp(ag, bg), lerp(ab, bb), lerp(aa, ba))
+}
+
+#[test]
+fn test_parse_hex() {
+    assert_eq!(parse_hex("#ff8000"), Ok(Rgba8::new(255, 128, 0, 255)));
+    assert_eq!(parse_hex("f80c"), Ok(Rgba8::new(255, 136, 0, 204)));
+    assert!(parse_hex("#ff80").is_ok());
+    assert!(parse_hex("#ff800").is_err());
+    assert!(parse_hex("#gg8000").is_err());
+}
@@ -0,0 +1,53 @@
+//! Simulation of color vision deficiencies, useful for checking the accessibility of a work.
+//!
+//! The simulation matrices are those described by Machado, Oliveira and Fernandes in "A
+//! Physiologically-based Model for Simulation of Color Vision Deficiency" (2009) at full severity.
+
+use crate::color::LinSrgba;
+
+/// The kinds of color vision deficiency that may be simulated.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ColorVisionDeficiency {
+    /// The absence of red-sensitive cones.
+    Protanopia,
+    /// The absence of green-sensitive cones.
+    Deuteranopia,
+    /// The absence of blue-sensitive cones.
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    /// The 3x3 row-major matrix that is applied to linear RGB values to simulate the deficiency.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        match *self {
+            ColorVisionDeficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            ColorVisionDeficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            ColorVisionDeficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// Produce the given color as it would be perceived with this deficiency.
+    ///
+    /// The alpha channel is left unchanged.
+    pub fn simulate(&self, color: LinSrgba) -> LinSrgba {
+        let (r, g, b, a) = color.into();
+        let m = self.matrix();
+        let channel = |row: [f32; 3]| {
+            let v = row[0] * r + row[1] * g + row[2] * b;
+            v.max(0.0).min(1.0)
+        };
+        LinSrgba::new(channel(m[0]), channel(m[1]), channel(m[2]), a)
+    }
+}
@@ -1,8 +1,10 @@
 use crate::color::{self, IntoLinSrgba, Srgb, Srgba};
-use crate::draw::properties::ColorScalar;
-use crate::draw::Draw;
-use crate::geom;
+use crate::draw::mesh::vertex;
+use crate::draw::properties::{ColorScalar, LinSrgba};
+use crate::draw::{self, Draw};
+use crate::geom::{self, pt2, Vector2};
 use crate::math::BaseFloat;
+use crate::wgpu;
 
 /// A type used to update the background colour.
 pub struct Background<'a, S = geom::scalar::Default>
@@ -12,6 +14,51 @@ where
     draw: &'a Draw<S>,
 }
 
+/// The way in which the frame should be filled before all other drawing commands.
+#[derive(Clone, Debug)]
+pub enum Fill {
+    /// Fill the frame with a gradient.
+    Gradient(Gradient),
+    /// Fill the frame with a texture.
+    Texture {
+        view: wgpu::TextureView,
+        mode: TextureMode,
+    },
+}
+
+/// Describes how a background texture should be laid out within the frame.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TextureMode {
+    /// Scale the texture to fit entirely within the frame, preserving its aspect ratio.
+    ///
+    /// Areas of the frame not covered by the texture are left with the background color.
+    Fit,
+    /// Scale the texture to cover the entire frame, preserving its aspect ratio.
+    ///
+    /// Parts of the texture that fall outside of the frame are cropped.
+    Cover,
+    /// Repeat the texture at its original size, starting from the top left corner of the frame.
+    Tile,
+}
+
+/// A gradient with which the background may be filled.
+///
+/// Gradients are interpolated in linear sRGB space.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gradient {
+    /// Interpolate from `start` to `end` across the frame along the given `angle` in radians.
+    ///
+    /// An angle of `0.0` interpolates from left to right, while an angle of `PI / 2.0`
+    /// interpolates from bottom to top.
+    Linear {
+        start: LinSrgba,
+        end: LinSrgba,
+        angle: f32,
+    },
+    /// Interpolate from `inner` at the center of the frame to `outer` at its corners.
+    Radial { inner: LinSrgba, outer: LinSrgba },
+}
+
 /// Begin coloring the background.
 pub fn new<'a, S>(draw: &'a Draw<S>) -> Background<'a, S>
 where
@@ -35,6 +82,73 @@ where
     {
         if let Ok(mut state) = self.draw.state.try_borrow_mut() {
             state.background_color = Some(color.into_lin_srgba());
+            state.background_fill = None;
+        }
+        self
+    }
+
+    /// Clear the background with a vertical gradient from `top` to `bottom`.
+    pub fn gradient<A, B>(self, top: A, bottom: B) -> Self
+    where
+        A: IntoLinSrgba<ColorScalar>,
+        B: IntoLinSrgba<ColorScalar>,
+    {
+        let angle = std::f32::consts::PI * 0.5;
+        self.gradient_angle(bottom, top, angle)
+    }
+
+    /// Clear the background with a linear gradient from `start` to `end`.
+    ///
+    /// The `angle` describes the direction of the gradient in radians, where `0.0` interpolates
+    /// from the left edge to the right edge of the frame.
+    pub fn gradient_angle<A, B>(self, start: A, end: B, angle: f32) -> Self
+    where
+        A: IntoLinSrgba<ColorScalar>,
+        B: IntoLinSrgba<ColorScalar>,
+    {
+        let start = start.into_lin_srgba();
+        let end = end.into_lin_srgba();
+        self.set_gradient(Gradient::Linear { start, end, angle })
+    }
+
+    /// Clear the background with a radial gradient from `inner` at the center of the frame to
+    /// `outer` at the corners.
+    pub fn gradient_radial<A, B>(self, inner: A, outer: B) -> Self
+    where
+        A: IntoLinSrgba<ColorScalar>,
+        B: IntoLinSrgba<ColorScalar>,
+    {
+        let inner = inner.into_lin_srgba();
+        let outer = outer.into_lin_srgba();
+        self.set_gradient(Gradient::Radial { inner, outer })
+    }
+
+    /// Fill the background with the given texture, scaled to cover the entire frame.
+    ///
+    /// See the `texture_mode` method for alternative layouts.
+    pub fn texture(self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.texture_mode(view, TextureMode::Cover)
+    }
+
+    /// Fill the background with the given texture, laid out according to the given `mode`.
+    pub fn texture_mode(self, view: &dyn wgpu::ToTextureView, mode: TextureMode) -> Self {
+        let view = view.to_texture_view();
+        self.fill(Fill::Texture { view, mode })
+    }
+
+    fn set_gradient(self, gradient: Gradient) -> Self {
+        self.fill(Fill::Gradient(gradient))
+    }
+
+    // Fill the frame before all other drawing commands.
+    //
+    // If no background color has been specified, the frame is first cleared with transparency.
+    fn fill(self, fill: Fill) -> Self {
+        if let Ok(mut state) = self.draw.state.try_borrow_mut() {
+            if state.background_color.is_none() {
+                state.background_color = Some(LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+            }
+            state.background_fill = Some(fill);
         }
         self
     }
@@ -108,3 +222,209 @@ where
         self.color(color::Hsva::new(hue, s, v, a))
     }
 }
+
+impl draw::renderer::RenderPrimitive for Fill {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        match self {
+            Fill::Gradient(gradient) => gradient.render_primitive(ctxt, mesh),
+            Fill::Texture { view, mode } => {
+                // The texture is laid out across the full output, of which only the region
+                // covered by the output attachment is rendered.
+                let frame = ctxt.full_output_rect;
+                let [tw, th] = view.size();
+                let (tw, th) = (tw as f32, th as f32);
+                let full_area = geom::Rect::from_corners(pt2(0.0, 0.0), pt2(1.0, 1.0));
+                let frame_aspect = frame.w() / frame.h();
+                let texture_aspect = tw / th;
+                match mode {
+                    TextureMode::Fit => {
+                        let (w, h) = if texture_aspect > frame_aspect {
+                            (frame.w(), frame.w() / texture_aspect)
+                        } else {
+                            (frame.h() * texture_aspect, frame.h())
+                        };
+                        let rect = geom::Rect::from_x_y_w_h(frame.x(), frame.y(), w, h);
+                        push_textured_quad(mesh, rect, full_area);
+                    }
+                    TextureMode::Cover => {
+                        let area = if texture_aspect > frame_aspect {
+                            let w = frame_aspect / texture_aspect;
+                            geom::Rect::from_x_y_w_h(0.5, 0.5, w, 1.0)
+                        } else {
+                            let h = texture_aspect / frame_aspect;
+                            geom::Rect::from_x_y_w_h(0.5, 0.5, 1.0, h)
+                        };
+                        push_textured_quad(mesh, frame, area);
+                    }
+                    TextureMode::Tile => {
+                        let cols = (frame.w() / tw).ceil() as usize;
+                        let rows = (frame.h() / th).ceil() as usize;
+                        let tile = geom::Rect::from_w_h(tw, th).top_left_of(frame);
+                        for row in 0..rows {
+                            for col in 0..cols {
+                                let x = col as f32 * tw;
+                                let y = -(row as f32 * th);
+                                let rect = tile.shift(Vector2::new(x, y));
+                                push_textured_quad(mesh, rect, full_area);
+                            }
+                        }
+                    }
+                }
+                draw::renderer::PrimitiveRender::texture(view)
+            }
+        }
+    }
+}
+
+impl Gradient {
+    /// The number of segments used to approximate the circle of a radial gradient.
+    pub const RADIAL_RESOLUTION: usize = 64;
+}
+
+impl draw::renderer::RenderPrimitive for Gradient {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        // The gradient always covers the full output, regardless of the current transform, so
+        // that each tile of a tiled render continues the same gradient.
+        let rect = ctxt.full_output_rect;
+        let tex_coords = vertex::default_tex_coords();
+        let start_index = mesh.raw_vertex_count() as u32;
+        match self {
+            Gradient::Linear { start, end, angle } => {
+                let direction = Vector2::new(angle.cos(), angle.sin());
+                let corners = rect.corners();
+                let dists: Vec<f32> = corners.vertices().map(|p| p.dot(direction)).collect();
+                let min = dists.iter().cloned().fold(std::f32::MAX, f32::min);
+                let max = dists.iter().cloned().fold(std::f32::MIN, f32::max);
+                let vertices = corners.vertices().zip(dists).map(|(p, d)| {
+                    let t = if max > min {
+                        (d - min) / (max - min)
+                    } else {
+                        0.0
+                    };
+                    let color = lerp_lin_srgba(start, end, t);
+                    vertex::new(p.extend(0.0), color, tex_coords)
+                });
+                let indices = geom::quad::TRIANGLE_INDICES
+                    .iter()
+                    .map(|&i| start_index + i as u32);
+                mesh.extend(vertices, indices);
+            }
+            Gradient::Radial { inner, outer } => {
+                let radius = (rect.top_right() - rect.xy()).magnitude();
+                let center = vertex::new(rect.xy().extend(0.0), inner, tex_coords);
+                let res = Self::RADIAL_RESOLUTION;
+                let ring = (0..res).map(|i| {
+                    let rad = i as f32 / res as f32 * std::f32::consts::PI * 2.0;
+                    let p = rect.xy() + pt2(rad.cos() * radius, rad.sin() * radius);
+                    vertex::new(p.extend(0.0), outer, tex_coords)
+                });
+                let vertices = std::iter::once(center).chain(ring);
+                let indices = (0..res as u32).flat_map(|i| {
+                    let a = start_index + 1 + i;
+                    let b = start_index + 1 + (i + 1) % res as u32;
+                    vec![start_index, a, b]
+                });
+                mesh.extend(vertices, indices);
+            }
+        }
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+// Push a quad covering `rect` that samples the given `area` of the bound texture.
+//
+// The `area` is described in texture coordinates with the origin in the bottom left.
+fn push_textured_quad(mesh: &mut draw::Mesh, rect: geom::Rect, area: geom::Rect) {
+    let start_index = mesh.raw_vertex_count() as u32;
+    let vertices = rect
+        .corners()
+        .vertices()
+        .zip(area.invert_y().corners().vertices())
+        .map(|(p, t)| vertex::new(p.extend(0.0), vertex::DEFAULT_VERTEX_COLOR, t));
+    let indices = geom::quad::TRIANGLE_INDICES
+        .iter()
+        .map(|&i| start_index + i as u32);
+    mesh.extend(vertices, indices);
+}
+
+// Linearly interpolate between two colors.
+fn lerp_lin_srgba(a: LinSrgba, b: LinSrgba, t: f32) -> LinSrgba {
+    let (ar, ag, ab, aa) = a.into();
+    let (br, bg, bb, ba) = b.into();
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    LinSrgba::new(lerp(ar, br), lerp(ag, bg), lerp(ab, bb), lerp(aa, ba))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw::renderer::RenderPrimitive;
+    use crate::math::{Matrix4, SquareMatrix};
+    use lyon::tessellation::{FillTessellator, StrokeTessellator};
+
+    // Render the given gradient for the top right tile of a 4x4 output split into 2x2 tiles.
+    fn render_corner_tile(gradient: Gradient) -> draw::Mesh {
+        let transform = Matrix4::identity();
+        let intermediary_mesh = draw::Mesh::default();
+        let theme = draw::Theme::default();
+        let mut fill_tessellator = FillTessellator::new();
+        let mut stroke_tessellator = StrokeTessellator::new();
+        let ctxt = draw::renderer::RenderContext {
+            transform: &transform,
+            intermediary_mesh: &intermediary_mesh,
+            path_event_buffer: &[],
+            path_points_colored_buffer: &[],
+            path_points_textured_buffer: &[],
+            path_points_weighted_buffer: &[],
+            text_buffer: "",
+            theme: &theme,
+            glyph_cache: None,
+            fill_tessellator: &mut fill_tessellator,
+            stroke_tessellator: &mut stroke_tessellator,
+            output_attachment_size: Vector2::new(2.0, 2.0),
+            output_attachment_scale_factor: 1.0,
+            full_output_rect: geom::Rect::from_w_h(4.0, 4.0),
+            projection_offset: Vector2::new(1.0, 1.0),
+        };
+        let mut mesh = draw::Mesh::default();
+        gradient.render_primitive(ctxt, &mut mesh);
+        mesh
+    }
+
+    #[test]
+    fn test_linear_gradient_corner_tile() {
+        let start = LinSrgba::new(0.0, 0.0, 0.0, 1.0);
+        let end = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        let angle = 0.0;
+        let mesh = render_corner_tile(Gradient::Linear { start, end, angle });
+        // The gradient spans the full output rather than the tile.
+        for (p, c) in mesh.points().iter().zip(mesh.colors()) {
+            assert!(p.x.abs() == 2.0 && p.y.abs() == 2.0);
+            assert!((c.red - (p.x + 2.0) / 4.0).abs() < 1e-6);
+        }
+        assert_eq!(mesh.points().len(), 4);
+    }
+
+    #[test]
+    fn test_radial_gradient_corner_tile() {
+        let inner = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        let outer = LinSrgba::new(0.0, 0.0, 0.0, 1.0);
+        let mesh = render_corner_tile(Gradient::Radial { inner, outer });
+        // The gradient is centered on the full output rather than the tile, reaching the
+        // output's corners.
+        let center = mesh.points()[0];
+        assert_eq!((center.x, center.y), (0.0, 0.0));
+        let radius = geom::vec2(2.0, 2.0).magnitude();
+        for p in &mesh.points()[1..] {
+            assert!((geom::vec2(p.x, p.y).magnitude() - radius).abs() < 1e-5);
+        }
+    }
+}
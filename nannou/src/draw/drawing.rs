@@ -1,15 +1,17 @@
 use crate::color::IntoLinSrgba;
 use crate::draw::mesh::vertex::Color;
-use crate::draw::primitive::Primitive;
+use crate::draw::primitive::{FragmentShader, Primitive};
 use crate::draw::properties::{
-    ColorScalar, SetColor, SetDimensions, SetFill, SetOrientation, SetPosition, SetStroke,
+    ColorScalar, SetColor, SetDash, SetDimensions, SetFill, SetOrientation, SetPosition, SetStroke,
 };
 use crate::draw::{self, Draw};
 use crate::geom::{self, Point2, Point3, Vector2, Vector3};
 use crate::math::{Angle, BaseFloat, Euler, Quaternion, Rad};
+use crate::wgpu;
 use lyon::path::PathEvent;
 use lyon::tessellation::{FillOptions, LineCap, LineJoin, StrokeOptions};
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// A **Drawing** in progress.
 ///
@@ -48,6 +50,8 @@ pub struct DrawingContext<'a, S> {
     pub path_points_colored_buffer: &'a mut Vec<(Point2<S>, Color)>,
     /// A re-usable buffer for collecting textured polyline points.
     pub path_points_textured_buffer: &'a mut Vec<(Point2<S>, Point2<S>)>,
+    /// A re-usable buffer for collecting polyline points with a stroke weight each.
+    pub path_points_weighted_buffer: &'a mut Vec<(Point2<S>, f32)>,
     /// A re-usable buffer for collecting text.
     pub text_buffer: &'a mut String,
 }
@@ -86,6 +90,7 @@ impl<'a, S> DrawingContext<'a, S> {
             ref mut path_event_buffer,
             ref mut path_points_colored_buffer,
             ref mut path_points_textured_buffer,
+            ref mut path_points_weighted_buffer,
             ref mut text_buffer,
         } = *state;
         DrawingContext {
@@ -93,6 +98,7 @@ impl<'a, S> DrawingContext<'a, S> {
             path_event_buffer: path_event_buffer,
             path_points_colored_buffer: path_points_colored_buffer,
             path_points_textured_buffer: path_points_textured_buffer,
+            path_points_weighted_buffer: path_points_weighted_buffer,
             text_buffer: text_buffer,
         }
     }
@@ -120,6 +126,58 @@ where
         self.finish_inner()
     }
 
+    /// Specify the order in which the drawing is rendered relative to others in the same **Draw**.
+    ///
+    /// Drawings with a greater z index are rendered on top of those with a lesser one, regardless
+    /// of the order in which they were submitted. Drawings with an equal z index retain their
+    /// submission order. By default, all drawings have a z index of `0`.
+    ///
+    /// This allows for separate parts of a program that contribute to the same **Draw** to
+    /// interleave their drawings without coordinating the order in which they are called.
+    pub fn z_index(self, z: i32) -> Self {
+        if let Ok(mut state) = self.draw.state.try_borrow_mut() {
+            state.z_indices.insert(self.index, z);
+        }
+        self
+    }
+
+    /// Render the drawing via the given fragment shader in place of the default.
+    ///
+    /// The drawing's geometry, positioning and batching are otherwise unaffected. The shader
+    /// receives the same inputs as the default fragment shader:
+    ///
+    /// - `layout(location = 0) in vec4 v_color;`
+    /// - `layout(location = 1) in vec2 v_tex_coords;`
+    /// - `layout(location = 2) flat in uint v_mode;`
+    ///
+    /// along with the glyph cache at set 1 and the drawing's texture (if any) at set 2. Uniform
+    /// data given via `fragment_shader_uniforms` is bound as a uniform buffer at set 3, binding 0.
+    /// The shader should write its result to `layout(location = 0) out vec4 f_color;`.
+    ///
+    /// The shader must be created with the same device as the renderer.
+    pub fn fragment_shader(self, module: &Arc<wgpu::ShaderModule>) -> Self {
+        if let Ok(mut state) = self.draw.state.try_borrow_mut() {
+            let shader = FragmentShader {
+                module: module.clone(),
+                uniforms: None,
+            };
+            state.fragment_shaders.insert(self.index, shader);
+        }
+        self
+    }
+
+    /// Specify the bytes of the uniform buffer made available to the drawing's fragment shader.
+    ///
+    /// This has no effect unless a `fragment_shader` was specified first.
+    pub fn fragment_shader_uniforms(self, bytes: &[u8]) -> Self {
+        if let Ok(mut state) = self.draw.state.try_borrow_mut() {
+            if let Some(shader) = state.fragment_shaders.get_mut(&self.index) {
+                shader.uniforms = Some(bytes.to_vec());
+            }
+        }
+        self
+    }
+
     // Map the given function onto the primitive stored within **Draw** at `index`.
     //
     // The functionn is only applied if the node has not yet been **Drawn**.
@@ -588,7 +646,10 @@ where
 
     /// Specify the rule used to determine what is inside and what is outside of the shape.
     ///
-    /// Currently, only the `EvenOdd` rule is implemented.
+    /// Self-intersecting shapes are split at their intersections before the rule is applied. Use
+    /// `NonZero` for shapes like stars and scribbles to fill all enclosed regions.
+    ///
+    /// Default value: `EvenOdd`.
     pub fn fill_rule(self, rule: lyon::tessellation::FillRule) -> Self {
         self.map_ty(|ty| ty.fill_rule(rule))
     }
@@ -755,3 +816,29 @@ where
         self.map_ty(|ty| ty.stroke_opts(opts))
     }
 }
+
+// SetDash methods
+
+impl<'a, T, S> Drawing<'a, T, S>
+where
+    T: SetDash + Into<Primitive<S>>,
+    Primitive<S>: Into<Option<T>>,
+    S: BaseFloat,
+{
+    /// Stroke with dashes of length `on` separated by gaps of length `off`.
+    pub fn dash(self, on: f32, off: f32) -> Self {
+        self.map_ty(|ty| ty.dash(on, off))
+    }
+
+    /// Stroke with the given alternating lengths of dashes and gaps, beginning with a dash.
+    ///
+    /// An empty pattern, or one whose lengths sum to zero, produces a solid stroke.
+    pub fn dash_pattern(self, pattern: &[f32]) -> Self {
+        self.map_ty(|ty| ty.dash_pattern(pattern))
+    }
+
+    /// The distance into the dash pattern at which the stroke begins.
+    pub fn dash_offset(self, offset: f32) -> Self {
+        self.map_ty(|ty| ty.dash_offset(offset))
+    }
+}
@@ -29,6 +29,7 @@ pub struct MeshBuilder<'a, A> {
 pub struct SingleColor(draw::mesh::vertex::Color);
 pub struct ColorPerPoint;
 pub struct TexCoordsPerPoint;
+pub struct TexCoordsFromBounds(geom::Rect);
 
 impl<'a, A> MeshBuilder<'a, A> {
     /// Begin extending the mesh.
@@ -68,6 +69,30 @@ impl<'a> MeshBuilder<'a, TexCoordsPerPoint> {
     }
 }
 
+impl<'a> MeshBuilder<'a, TexCoordsFromBounds> {
+    /// Begin extending a mesh where texture coordinates are produced by mapping the given bounds
+    /// (in the same space as the path) to the full extent of the texture.
+    pub fn tex_coords_from_bounds(
+        mesh: &'a mut draw::Mesh,
+        transform: Matrix4<f32>,
+        bounds: geom::Rect,
+    ) -> Self {
+        Self::new(mesh, transform, TexCoordsFromBounds(bounds))
+    }
+}
+
+impl TexCoordsFromBounds {
+    // Map the position within the bounds to texture coordinates, with the top left of the bounds
+    // mapped to the top left of the texture.
+    fn tex_coords(&self, position: lyon::math::Point) -> draw::mesh::vertex::TexCoords {
+        let TexCoordsFromBounds(ref bounds) = *self;
+        let map = |v: f32, start: f32, len: f32| if len > 0.0 { (v - start) / len } else { 0.5 };
+        let x = map(position.x, bounds.left(), bounds.w());
+        let y = map(bounds.top() - position.y, 0.0, bounds.h());
+        geom::pt2(x, y)
+    }
+}
+
 impl<'a, A> GeometryBuilder for MeshBuilder<'a, A> {
     fn begin_geometry(&mut self) {
         self.begin_vertex_count = self.mesh.points().len() as u32;
@@ -233,3 +258,49 @@ impl<'a> StrokeGeometryBuilder for MeshBuilder<'a, TexCoordsPerPoint> {
         Ok(id)
     }
 }
+
+impl<'a> FillGeometryBuilder for MeshBuilder<'a, TexCoordsFromBounds> {
+    fn add_fill_vertex(
+        &mut self,
+        position: lyon::math::Point,
+        _attrs: FillAttributes,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        // Retrieve the index.
+        let id = VertexId::from_usize(self.mesh.points().len());
+
+        // Construct and insert the point
+        let p = geom::Point3::from(geom::Point2::from(position));
+        let p = cgmath::Transform::transform_point(&self.transform, p.into());
+        let point = geom::vec3(p.x, p.y, p.z);
+        let tex_coords = self.attributes.tex_coords(position);
+        let color = draw::mesh::vertex::DEFAULT_VERTEX_COLOR;
+        let vertex = draw::mesh::vertex::new(point, color, tex_coords);
+        self.mesh.push_vertex(vertex);
+
+        // Return the index.
+        Ok(id)
+    }
+}
+
+impl<'a> StrokeGeometryBuilder for MeshBuilder<'a, TexCoordsFromBounds> {
+    fn add_stroke_vertex(
+        &mut self,
+        position: lyon::math::Point,
+        _attrs: StrokeAttributes,
+    ) -> Result<VertexId, GeometryBuilderError> {
+        // Retrieve the index.
+        let id = VertexId::from_usize(self.mesh.points().len());
+
+        // Construct and insert the point
+        let p = geom::Point3::from(geom::Point2::from(position));
+        let p = cgmath::Transform::transform_point(&self.transform, p.into());
+        let point = geom::vec3(p.x, p.y, p.z);
+        let tex_coords = self.attributes.tex_coords(position);
+        let color = draw::mesh::vertex::DEFAULT_VERTEX_COLOR;
+        let vertex = draw::mesh::vertex::new(point, color, tex_coords);
+        self.mesh.push_vertex(vertex);
+
+        // Return the index.
+        Ok(id)
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::geom;
 use crate::mesh::{self, MeshPoints, WithColors, WithIndices, WithTexCoords};
+use crate::serde_derive::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 
 pub mod builder;
@@ -112,6 +113,21 @@ impl<S> Mesh<S> {
         mesh::clear(self);
     }
 
+    // Mutable access to the indices, e.g. for converting triangles to edges in-place.
+    pub(crate) fn indices_mut(&mut self) -> &mut Indices {
+        self.mesh.indices_mut()
+    }
+
+    /// Reserve capacity for at least `additional` more vertices.
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        mesh::ReserveVertices::reserve_vertices(&mut self.mesh, additional);
+    }
+
+    /// Reserve capacity for at least `additional` more indices.
+    pub fn reserve_indices(&mut self, additional: usize) {
+        mesh::ReserveIndices::reserve_indices(&mut self.mesh, additional);
+    }
+
     /// Produce an iterator yielding all raw (non-index-order) vertices.
     pub fn raw_vertices(&self) -> mesh::RawVertices<&Self> {
         mesh::raw_vertices(self)
@@ -188,6 +204,58 @@ impl<S> Default for Mesh<S> {
     }
 }
 
+// The channels of a **Mesh** in the form in which they are serialized.
+#[derive(Serialize, Deserialize)]
+struct Channels<P, I, C, T> {
+    points: P,
+    indices: I,
+    colors: C,
+    tex_coords: T,
+}
+
+impl<S> serde::Serialize for Mesh<S>
+where
+    S: serde::Serialize,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: serde::Serializer,
+    {
+        let channels = Channels {
+            points: self.points(),
+            indices: self.indices(),
+            colors: self.colors(),
+            tex_coords: self.tex_coords(),
+        };
+        serde::Serialize::serialize(&channels, serializer)
+    }
+}
+
+impl<'de, S> serde::Deserialize<'de> for Mesh<S>
+where
+    S: Clone + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let Channels {
+            points,
+            indices,
+            colors,
+            tex_coords,
+        }: Channels<Points<S>, Indices, Colors, TexCoords<S>> =
+            serde::Deserialize::deserialize(deserializer)?;
+        if points.len() != colors.len() || points.len() != tex_coords.len() {
+            let msg = "mesh channels must contain the same number of vertices";
+            return Err(serde::de::Error::custom(msg));
+        }
+        let mut mesh = Mesh::default();
+        mesh.extend_from_slices(&points, &indices, &colors, &tex_coords);
+        Ok(mesh)
+    }
+}
+
 impl<S> Deref for Mesh<S> {
     type Target = MeshType<S>;
     fn deref(&self) -> &Self::Target {
@@ -276,6 +344,18 @@ impl<S> mesh::ClearVertices for Mesh<S> {
     }
 }
 
+impl<S> mesh::ReserveIndices for Mesh<S> {
+    fn reserve_indices(&mut self, additional: usize) {
+        self.mesh.reserve_indices(additional);
+    }
+}
+
+impl<S> mesh::ReserveVertices for Mesh<S> {
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.mesh.reserve_vertices(additional);
+    }
+}
+
 #[test]
 fn test_method_access() {
     let mesh: Mesh = Default::default();
@@ -4,6 +4,7 @@
 
 use crate::geom::{self, Point2};
 use crate::math::{deg_to_rad, turns_to_rad, BaseFloat, Matrix4, SquareMatrix};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 use lyon::path::PathEvent;
 use std::cell::RefCell;
@@ -16,16 +17,23 @@ pub use self::drawing::{Drawing, DrawingContext};
 use self::mesh::vertex::Color;
 pub use self::mesh::Mesh;
 use self::primitive::Primitive;
+pub use self::recording::Recording;
 pub use self::renderer::{Builder as RendererBuilder, Renderer};
+pub use self::retained::Retained;
 pub use self::theme::Theme;
 
 pub mod background;
 mod drawing;
 pub mod mesh;
+pub mod plotter_renderer;
 pub mod primitive;
 pub mod properties;
+mod recording;
 pub mod renderer;
+mod retained;
+pub(crate) mod serialize;
 pub mod theme;
+mod tiled;
 
 /// A simple API for drawing 2D and 3D graphics.
 ///
@@ -58,33 +66,71 @@ where
     state: Rc<RefCell<State<S>>>,
     /// The current context of this **Draw** instance.
     context: Context<S>,
+    /// The z index given to drawings made via this **Draw** instance.
+    layer: i32,
 }
 
 /// The current **Transform**, alpha **BlendDescriptor** and **Scissor** of a **Draw** instance.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Context<S = geom::scalar::Default> {
     pub transform: Matrix4<S>,
+    #[serde(with = "serialize::BlendDescriptor")]
     pub alpha_blend: wgpu::BlendDescriptor,
+    #[serde(with = "serialize::BlendDescriptor")]
     pub color_blend: wgpu::BlendDescriptor,
     pub scissor: Scissor<S>,
+    #[serde(with = "serialize::PrimitiveTopology")]
     pub topology: wgpu::PrimitiveTopology,
+    #[serde(with = "serialize::SamplerDescriptor")]
     pub sampler: wgpu::SamplerDescriptor,
+    /// Whether or not triangles should be converted to their unique edges before drawing.
+    pub wireframe: bool,
+    /// The comparison against the depth buffer that fragments must pass in order to be drawn.
+    #[serde(with = "serialize::CompareFunction")]
+    pub depth_compare: wgpu::CompareFunction,
+    /// Whether or not the depth of drawn fragments is written to the depth buffer.
+    pub depth_write: bool,
+    /// Whether drawings form or are clipped by a mask.
+    pub mask: Mask,
+    /// The opacity group to which drawings belong, if any.
+    pub group: Option<Group>,
 }
 
 /// Commands generated by drawings.
 ///
 /// During rendering, the list of **DrawCommand**s are converted into a list of **RenderCommands**
 /// that are directly associated with encodable render pass commands.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum DrawCommand<S = geom::scalar::Default> {
     /// Draw a primitive.
     Primitive(Primitive<S>),
     /// A change in the rendering context occurred.
     Context(Context<S>),
+    /// A change to a single part of the rendering context occurred.
+    ///
+    /// These are emitted in place of a full **Context** in the common case that only the
+    /// transform, blend or scissor changed between drawings.
+    ContextDelta(ContextDelta<S>),
+}
+
+/// A change to a single part of a **Draw**'s rendering **Context**.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ContextDelta<S = geom::scalar::Default> {
+    /// Only the transform changed.
+    Transform(Matrix4<S>),
+    /// Only the color and alpha blend descriptors changed.
+    Blend {
+        #[serde(with = "serialize::BlendDescriptor")]
+        color: wgpu::BlendDescriptor,
+        #[serde(with = "serialize::BlendDescriptor")]
+        alpha: wgpu::BlendDescriptor,
+    },
+    /// Only the scissor changed.
+    Scissor(Scissor<S>),
 }
 
 /// The scissor for a **Draw**'s render context.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Scissor<S = geom::scalar::Default> {
     /// The extent of the scissor matches the bounds of the target texture.
     Full,
@@ -94,6 +140,31 @@ pub enum Scissor<S = geom::scalar::Default> {
     NoOverlap,
 }
 
+/// The role of a **Draw**'s drawings with respect to masks created via `Draw::mask`.
+///
+/// Masks are implemented via the renderer's stencil buffer, in which each mask is identified by
+/// a unique, non-zero value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Mask {
+    /// Drawings neither form nor are clipped by a mask.
+    None,
+    /// Drawings form the region of the mask with the given ID. Their color is not drawn.
+    Write(u8),
+    /// Drawings are clipped to the region of the mask with the given ID.
+    Clip(u8),
+}
+
+/// A group of drawings that are rendered together and composited with a uniform opacity.
+///
+/// See `Draw::opacity`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Group {
+    /// Identifies the group among those created since the **Draw** was last reset.
+    pub id: u32,
+    /// The opacity with which the group is composited.
+    pub opacity: f32,
+}
+
 /// The inner state of the **Draw** type.
 ///
 /// The **Draw** type stores its **State** behind a **RefCell** - a type used for moving mutability
@@ -110,6 +181,9 @@ where
     last_draw_context: Option<Context<S>>,
     /// If `Some`, the **Draw** should first clear the frame's texture with the given color.
     background_color: Option<properties::LinSrgba>,
+    /// If `Some`, the frame should be filled with the given gradient or texture before all other
+    /// commands.
+    background_fill: Option<background::Fill>,
     /// Primitives that are in the process of being drawn.
     ///
     /// Keys are indices into the `draw_commands` Vec.
@@ -118,6 +192,18 @@ where
     ///
     /// An element may be `None` if it is a primitive in the process of being drawn.
     draw_commands: Vec<Option<DrawCommand<S>>>,
+    /// The z index of each drawing given one via `Drawing::z_index`.
+    ///
+    /// Keys are indices into the `draw_commands` Vec.
+    z_indices: HashMap<usize, i32>,
+    /// The fragment shader of each drawing given one via `Drawing::fragment_shader`.
+    ///
+    /// Keys are indices into the `draw_commands` Vec.
+    fragment_shaders: HashMap<usize, primitive::FragmentShader>,
+    /// The number of masks created since the last reset, used to produce unique mask IDs.
+    mask_count: u8,
+    /// The number of opacity groups created since the last reset, used to produce unique IDs.
+    group_count: u32,
     /// State made accessible via the `DrawingContext`.
     intermediary_state: RefCell<IntermediaryState<S>>,
     /// The theme containing default values.
@@ -125,7 +211,7 @@ where
 }
 
 /// State made accessible via the `DrawingContext`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IntermediaryState<S> {
     /// Buffers of vertex data that may be re-used for paths, meshes, etc between view calls.
     intermediary_mesh: Mesh<S>,
@@ -135,6 +221,8 @@ pub struct IntermediaryState<S> {
     path_points_colored_buffer: Vec<(Point2<S>, Color)>,
     /// A re-usable buffer for collecting textured polyline points.
     path_points_textured_buffer: Vec<(Point2<S>, Point2<S>)>,
+    /// A re-usable buffer for collecting polyline points with a stroke weight each.
+    path_points_weighted_buffer: Vec<(Point2<S>, f32)>,
     /// A buffer containing all text.
     text_buffer: String,
 }
@@ -145,6 +233,7 @@ impl<S> IntermediaryState<S> {
         self.path_event_buffer.clear();
         self.path_points_colored_buffer.clear();
         self.path_points_textured_buffer.clear();
+        self.path_points_weighted_buffer.clear();
         self.text_buffer.clear();
     }
 }
@@ -156,9 +245,14 @@ where
     // Resets all state within the `Draw` instance.
     fn reset(&mut self) {
         self.background_color = None;
+        self.background_fill = None;
         self.last_draw_context = None;
         self.drawing.clear();
         self.draw_commands.clear();
+        self.z_indices.clear();
+        self.fragment_shaders.clear();
+        self.mask_count = 0;
+        self.group_count = 0;
         self.intermediary_state.borrow_mut().reset();
     }
 
@@ -171,6 +265,18 @@ where
         mem::swap(&mut self.drawing, &mut drawing);
     }
 
+    // Stable sort the primitives by their z index, inserting context commands wherever the context
+    // of consecutive primitives differs.
+    //
+    // Returns the index at which each primitive was submitted, in sorted order.
+    fn sort_draw_commands(&mut self) -> Vec<usize> {
+        let z_indices = mem::replace(&mut self.z_indices, Default::default());
+        let mut sorted = Vec::with_capacity(self.draw_commands.len());
+        let order = sort_draw_commands(self.draw_commands.drain(..), &z_indices, &mut sorted);
+        self.draw_commands.extend(sorted.into_iter().map(Some));
+        order
+    }
+
     // Finish the drawing at the given node index if it is not yet complete.
     pub(crate) fn finish_drawing(&mut self, index: usize) {
         if let Some(primitive) = self.drawing.remove(&index) {
@@ -180,6 +286,10 @@ where
 
     // Insert the draw primitive command at the given index.
     fn insert_draw_command(&mut self, index: usize, prim: Primitive<S>) {
+        let prim = match self.fragment_shaders.remove(&index) {
+            Some(shader) => Primitive::Shaded(primitive::Shaded::new(prim, shader)),
+            None => prim,
+        };
         if let Some(elem) = self.draw_commands.get_mut(index) {
             *elem = Some(DrawCommand::Primitive(prim));
         }
@@ -202,6 +312,35 @@ where
         self.state.borrow_mut().reset();
     }
 
+    /// Reserve capacity for at least `primitives` more drawings and `vertices` more vertices.
+    ///
+    /// This pre-sizes the buffers used to record commands, meshes and paths, which is useful for
+    /// avoiding reallocation while drawing large scenes whose size is known ahead of time. The
+    /// vertex count is also used as an estimate for the number of path events and indices (three
+    /// per vertex) that will be submitted.
+    pub fn reserve(&self, primitives: usize, vertices: usize) {
+        let mut state = self.state.borrow_mut();
+        state.draw_commands.reserve(primitives);
+        let mut intermediary_state = state.intermediary_state.borrow_mut();
+        intermediary_state
+            .intermediary_mesh
+            .reserve_vertices(vertices);
+        intermediary_state
+            .intermediary_mesh
+            .reserve_indices(vertices * 3);
+        intermediary_state.path_event_buffer.reserve(vertices);
+        intermediary_state
+            .path_points_colored_buffer
+            .reserve(vertices);
+    }
+
+    /// Specify the **Theme** used to color primitives for which no color has been specified.
+    ///
+    /// Unlike the rest of the **Draw**'s state, the theme persists across calls to `reset`.
+    pub fn set_theme(&self, theme: Theme) {
+        self.state.borrow_mut().theme = theme;
+    }
+
     // Context changes.
 
     /// Produce a new **Draw** instance transformed by the given transform matrix.
@@ -421,9 +560,32 @@ where
 
     /// Produce a new **Draw** instance that will be cropped to the given rectangle.
     ///
+    /// This is short-hand for `scissor_local`.
+    pub fn scissor(&self, scissor: geom::Rect<S>) -> Self {
+        self.scissor_local(scissor)
+    }
+
+    /// Produce a new **Draw** instance that will be cropped to the given rectangle.
+    ///
+    /// The rectangle is specified within the current transformed space of the **Draw** instance.
+    /// As the scissor must be axis-aligned with the window, rotated or skewed rectangles are
+    /// cropped to their bounding rectangle.
+    ///
     /// If the current **Draw** instance already contains a scissor, the result will be the overlap
     /// between the original scissor and the new one.
-    pub fn scissor(&self, scissor: geom::Rect<S>) -> Self {
+    pub fn scissor_local(&self, scissor: geom::Rect<S>) -> Self {
+        let scissor = transform_rect_bounds(&self.context.transform, scissor);
+        self.scissor_window(scissor)
+    }
+
+    /// Produce a new **Draw** instance that will be cropped to the given rectangle.
+    ///
+    /// The rectangle is specified in window coordinates, ignoring the current transform of the
+    /// **Draw** instance.
+    ///
+    /// If the current **Draw** instance already contains a scissor, the result will be the overlap
+    /// between the original scissor and the new one.
+    pub fn scissor_window(&self, scissor: geom::Rect<S>) -> Self {
         let mut context = self.context.clone();
         context.scissor = match context.scissor {
             Scissor::Full => Scissor::Rect(scissor),
@@ -472,6 +634,118 @@ where
         self.primitive_topology(wgpu::PrimitiveTopology::TriangleList)
     }
 
+    /// Produce a new **Draw** instance.
+    ///
+    /// All drawing that occurs on the new instance will be rendered as a wireframe of the edges
+    /// of each triangle.
+    ///
+    /// Unlike `line_mode`, which interprets each pair of vertices as a line, the triangles of
+    /// each primitive are converted to their unique edges before drawing. This results in the
+    /// wireframe that is usually expected of filled shapes and meshes.
+    pub fn wireframe_mode(&self) -> Self {
+        let mut context = self.context.clone();
+        context.topology = wgpu::PrimitiveTopology::LineList;
+        context.wireframe = true;
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance where depth testing is enabled or disabled.
+    ///
+    /// When enabled, fragments are only drawn if they are at least as close as those already drawn,
+    /// and their depth is recorded. This allows 3D drawings to occlude one another correctly
+    /// regardless of the order in which they are submitted. When disabled, drawings are always
+    /// drawn over the top of one another in the order in which they are rendered, and their depth
+    /// is not recorded.
+    ///
+    /// Depth testing is enabled by default. As all 2D drawings share the same depth, they are
+    /// rendered in submission order either way.
+    pub fn depth_test(&self, enabled: bool) -> Self {
+        let mut context = self.context.clone();
+        if enabled {
+            context.depth_compare = wgpu::RenderPipelineBuilder::DEFAULT_DEPTH_COMPARE;
+            context.depth_write = true;
+        } else {
+            context.depth_compare = wgpu::CompareFunction::Always;
+            context.depth_write = false;
+        }
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance where fragments are drawn only if their depth passes the
+    /// given comparison against the depth already recorded.
+    ///
+    /// By default, this is `LessEqual`. This will cause the **draw::Renderer** to switch render
+    /// pipelines if the comparison differs from the previous drawing's.
+    pub fn depth_compare(&self, compare: wgpu::CompareFunction) -> Self {
+        let mut context = self.context.clone();
+        context.depth_compare = compare;
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance whose drawings are clipped to the region covered by the
+    /// drawings made within the given function.
+    ///
+    /// ```no_run
+    /// # use nannou::prelude::*;
+    /// # fn view(app: &App, frame: Frame) {
+    /// let draw = app.draw();
+    /// let masked = draw.mask(|d| {
+    ///     d.ellipse().radius(100.0);
+    /// });
+    /// masked.rect().w_h(300.0, 50.0).color(PLUM);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Unlike `scissor`, the mask may take any shape. The drawings that form the mask are not
+    /// themselves visible, and are not clipped by any mask of this **Draw**.
+    ///
+    /// Masks are stored within the renderer's stencil buffer, requiring its depth format to have a
+    /// stencil component as it does by default. Up to 255 masks may be created per frame, after
+    /// which mask IDs are re-used and later masks may reveal the regions of earlier ones.
+    pub fn mask<F>(&self, mask: F) -> Self
+    where
+        F: FnOnce(&Self),
+    {
+        let id = {
+            let mut state = self.state.borrow_mut();
+            state.mask_count = state.mask_count % std::u8::MAX + 1;
+            state.mask_count
+        };
+        let mut context = self.context.clone();
+        context.mask = Mask::Write(id);
+        mask(&self.context(context));
+        let mut context = self.context.clone();
+        context.mask = Mask::Clip(id);
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance whose drawings are composited as a single group with the
+    /// given opacity.
+    ///
+    /// Unlike reducing the alpha of each drawing, overlapping drawings within the group do not
+    /// show through one another. The group is rendered to an intermediate texture in a render
+    /// pass of its own, which is then composited in place of the group's first drawing.
+    ///
+    /// Groups do not nest. Calling `opacity` on a **Draw** that already belongs to a group
+    /// produces a new group whose opacity is the product of both. As grouped drawings are rendered
+    /// before all others, they are also assigned their picking IDs first.
+    pub fn opacity(&self, opacity: f32) -> Self {
+        let id = {
+            let mut state = self.state.borrow_mut();
+            let id = state.group_count;
+            state.group_count += 1;
+            id
+        };
+        let opacity = match self.context.group {
+            Some(ref group) => group.opacity * opacity,
+            None => opacity,
+        };
+        let mut context = self.context.clone();
+        context.group = Some(Group { id, opacity });
+        self.context(context)
+    }
+
     /// Produce a new **Draw** instance where all textures and textured vertices drawn will be
     /// sampled via a sampler of the given descriptor.
     pub fn sampler(&self, desc: wgpu::SamplerDescriptor) -> Self {
@@ -486,13 +760,35 @@ where
     fn primitive_topology(&self, topology: wgpu::PrimitiveTopology) -> Self {
         let mut context = self.context.clone();
         context.topology = topology;
+        context.wireframe = false;
         self.context(context)
     }
 
     /// Produce a new **Draw** instance with the given context.
     fn context(&self, context: Context<S>) -> Self {
         let state = self.state.clone();
-        Draw { state, context }
+        let layer = self.layer;
+        Draw {
+            state,
+            context,
+            layer,
+        }
+    }
+
+    // Layers.
+
+    /// Produce a new **Draw** instance whose drawings are rendered within the given layer.
+    ///
+    /// Drawings within a greater layer are rendered on top of those within a lesser one,
+    /// regardless of the order in which they were submitted. Drawings within the same layer retain
+    /// their submission order. By default, all drawings are within layer `0`.
+    ///
+    /// This is equivalent to calling `Drawing::z_index` on every drawing made via the resulting
+    /// **Draw**, and an individual drawing's `z_index` takes precedence over its layer.
+    pub fn layer(&self, layer: i32) -> Self {
+        let mut draw = self.clone();
+        draw.layer = layer;
+        draw
     }
 
     // Primitives.
@@ -512,9 +808,11 @@ where
             let mut state = self.state.borrow_mut();
             // If drawing with a different context, insert the necessary command to update it.
             if state.last_draw_context.as_ref() != Some(&self.context) {
-                state
-                    .draw_commands
-                    .push(Some(DrawCommand::Context(self.context.clone())));
+                let cmd = match state.last_draw_context {
+                    Some(ref last) => self.context.change_from(last),
+                    None => DrawCommand::Context(self.context.clone()),
+                };
+                state.draw_commands.push(Some(cmd));
                 state.last_draw_context = Some(self.context.clone());
             }
             // The primitive will be inserted in the next element.
@@ -522,6 +820,9 @@ where
             let primitive: Primitive<S> = primitive.into();
             state.draw_commands.push(None);
             state.drawing.insert(index, primitive);
+            if self.layer != 0 {
+                state.z_indices.insert(index, self.layer);
+            }
             index
         };
         drawing::new(self, index)
@@ -532,6 +833,11 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing an **Arc**.
+    pub fn arc(&self) -> Drawing<primitive::Arc<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing an **Ellipse**.
     pub fn ellipse(&self) -> Drawing<primitive::Ellipse<S>, S> {
         self.a(Default::default())
@@ -547,6 +853,11 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Bezier** curve.
+    pub fn bezier(&self) -> Drawing<primitive::Bezier<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Quad**.
     pub fn quad(&self) -> Drawing<primitive::Quad<S>, S> {
         self.a(Default::default())
@@ -557,16 +868,56 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Star**.
+    pub fn star(&self) -> Drawing<primitive::Star<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **RegularPolygon**.
+    pub fn regular_polygon(&self) -> Drawing<primitive::RegularPolygon<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Triangle**.
     pub fn tri(&self) -> Drawing<primitive::Tri<S>, S> {
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Cuboid**.
+    pub fn cuboid(&self) -> Drawing<primitive::Cuboid<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Cylinder**.
+    pub fn cylinder(&self) -> Drawing<primitive::Cylinder<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Cone**.
+    pub fn cone(&self) -> Drawing<primitive::Cone<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Sphere**.
+    pub fn sphere(&self) -> Drawing<primitive::Sphere<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Torus**.
+    pub fn torus(&self) -> Drawing<primitive::Torus<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Polygon**.
     pub fn polygon(&self) -> Drawing<primitive::PolygonInit<S>, S> {
         self.a(Default::default())
     }
 
+    /// Begin drawing a **PointCloud**.
+    pub fn point_cloud(&self) -> Drawing<primitive::PointCloud<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Mesh**.
     pub fn mesh(&self) -> Drawing<primitive::mesh::Vertexless, S> {
         self.a(Default::default())
@@ -579,6 +930,14 @@ where
         self.path().stroke()
     }
 
+    /// Begin drawing a **Polyline3d**.
+    ///
+    /// Unlike `polyline`, the points may be positioned anywhere in 3D space and the stroke is
+    /// always extruded to face the screen.
+    pub fn polyline_3d(&self) -> Drawing<primitive::Polyline3d<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Text**.
     pub fn text(&self, s: &str) -> Drawing<primitive::Text<S>, S> {
         let text = {
@@ -595,16 +954,109 @@ where
         self.a(primitive::Texture::new(view))
     }
 
+    /// Begin drawing an **Image** from a file path or an `image::DynamicImage`.
+    ///
+    /// The image is uploaded to a texture the first time it is drawn, after which the texture is
+    /// cached by the window's renderer and reused. Images are drawn at their own size unless
+    /// other dimensions are specified.
+    pub fn image<T>(&self, source: T) -> Drawing<primitive::Image<S>, S>
+    where
+        T: Into<primitive::ImageSource>,
+    {
+        self.a(primitive::Image::new(source.into()))
+    }
+
+    /// Begin drawing a single layer of an array texture via the given `D2Array` view.
+    ///
+    /// The layer may be selected via the `layer` method of the resulting drawing. This allows for
+    /// many images (e.g. the frames of a sprite sheet or the tiles of a tile map) to be stored
+    /// within a single texture. Drawings of any layer of the same view share a bind group and
+    /// may be batched into a single draw call.
+    ///
+    /// The view should be created once, e.g. via
+    /// `texture.view().dimension(wgpu::TextureViewDimension::D2Array).build()`, and reused between
+    /// frames. Array views are not supported by custom fragment shaders.
+    ///
+    /// **Panics** if the view's dimension is not `D2Array`.
+    pub fn texture_array(&self, view: &wgpu::TextureView) -> Drawing<primitive::Texture<S>, S> {
+        self.a(primitive::Texture::new_array(view))
+    }
+
+    /// Begin drawing an **Svg**.
+    ///
+    /// The SVG is drawn at its own size by default. Its paths keep the colors specified within
+    /// the file unless a color is given, in which case every fill and stroke takes that color.
+    #[cfg(feature = "svg")]
+    pub fn svg(&self, svg: &crate::svg::Svg) -> Drawing<primitive::Svg<S>, S> {
+        self.a(primitive::Svg::new(svg))
+    }
+
     /// Finish any drawings-in-progress and produce an iterator draining the inner draw commands
     /// and yielding them by value.
     pub fn drain_commands(&self) -> impl Iterator<Item = DrawCommand<S>> {
+        let mut cmds = vec![];
+        self.drain_commands_into(&mut cmds);
+        cmds.into_iter()
+    }
+
+    /// Finish any drawings-in-progress and move the inner draw commands onto the end of the given
+    /// buffer.
+    ///
+    /// Unlike `drain_commands`, this allows for re-using both the given buffer and the **Draw**'s
+    /// own command storage between frames, avoiding allocation once they have grown to fit the
+    /// scene.
+    pub fn drain_commands_into<T>(&self, commands: &mut Vec<T>)
+    where
+        T: From<DrawCommand<S>>,
+    {
+        self.drain_sorted_commands_into(commands);
+    }
+
+    // The same as `drain_commands_into`, but also returns the index at which each of the drained
+    // primitives was submitted, in the order in which they were drained.
+    //
+    // Returns `None` if the primitives were drained in the order in which they were submitted.
+    pub(crate) fn drain_sorted_commands_into<T>(&self, commands: &mut Vec<T>) -> Option<Vec<usize>>
+    where
+        T: From<DrawCommand<S>>,
+    {
         self.finish_remaining_drawings();
-        let cmds = {
-            let mut state = self.state.borrow_mut();
-            let empty = Vec::with_capacity(state.draw_commands.len());
-            std::mem::replace(&mut state.draw_commands, empty)
+        let mut state = self.state.borrow_mut();
+        let order = if !state.z_indices.is_empty() {
+            Some(state.sort_draw_commands())
+        } else {
+            None
         };
-        cmds.into_iter().filter_map(|opt| opt)
+        let cmds = state.draw_commands.drain(..).filter_map(|opt| opt);
+        commands.extend(cmds.map(T::from));
+        order
+    }
+
+    /// Finish any drawings-in-progress and produce an iterator yielding clones of the inner draw
+    /// commands in the order in which they will be rendered.
+    ///
+    /// Unlike `drain_commands`, the commands remain within the **Draw**, allowing a scene to be
+    /// inspected, or exported to some other format, and then rendered as usual.
+    pub fn commands(&self) -> impl Iterator<Item = DrawCommand<S>> {
+        self.finish_remaining_drawings();
+        let state = self.state.borrow();
+        let mut cmds = Vec::with_capacity(state.draw_commands.len());
+        if state.z_indices.is_empty() {
+            cmds.extend(state.draw_commands.iter().filter_map(|opt| opt.clone()));
+        } else {
+            let unsorted = state.draw_commands.iter().cloned();
+            sort_draw_commands(unsorted, &state.z_indices, &mut cmds);
+        }
+        cmds.into_iter()
+    }
+
+    /// The number of draw commands currently stored within the **Draw**, including those of any
+    /// drawings-in-progress.
+    ///
+    /// Note that this may differ slightly from the number yielded by `commands` when drawings are
+    /// given a `z_index`, as context commands are re-inserted while sorting.
+    pub fn command_count(&self) -> usize {
+        self.state.borrow().draw_commands.len()
     }
 
     /// Drain any remaining `drawing`s and convert them to draw commands.
@@ -613,18 +1065,126 @@ where
     }
 }
 
+// Stable sort the given commands' primitives by their z index, pushing the result onto `sorted`
+// with context commands wherever the context of consecutive primitives differs.
+//
+// Keys of `z_indices` are indices into `cmds`.
+//
+// Returns the index at which each primitive was submitted, i.e. the number of primitives that
+// preceded it within `cmds`, in sorted order.
+fn sort_draw_commands<S, I>(
+    cmds: I,
+    z_indices: &HashMap<usize, i32>,
+    sorted: &mut Vec<DrawCommand<S>>,
+) -> Vec<usize>
+where
+    S: BaseFloat,
+    I: IntoIterator<Item = Option<DrawCommand<S>>>,
+{
+    let mut ctxt = Context::default();
+    let mut prims = vec![];
+    for (index, cmd) in cmds.into_iter().enumerate() {
+        match cmd {
+            Some(DrawCommand::Context(c)) => ctxt = c,
+            Some(DrawCommand::ContextDelta(delta)) => delta.apply(&mut ctxt),
+            Some(DrawCommand::Primitive(prim)) => {
+                let z = z_indices.get(&index).cloned().unwrap_or(0);
+                prims.push((z, prims.len(), ctxt.clone(), prim));
+            }
+            None => (),
+        }
+    }
+    prims.sort_by_key(|&(z, _, _, _)| z);
+    let mut order = Vec::with_capacity(prims.len());
+    let mut last_ctxt: Option<Context<S>> = None;
+    for (_, submitted, ctxt, prim) in prims {
+        order.push(submitted);
+        if last_ctxt.as_ref() != Some(&ctxt) {
+            let cmd = match last_ctxt {
+                Some(ref last) => ctxt.change_from(last),
+                None => DrawCommand::Context(ctxt.clone()),
+            };
+            sorted.push(cmd);
+            last_ctxt = Some(ctxt);
+        }
+        sorted.push(DrawCommand::Primitive(prim));
+    }
+    order
+}
+
+// Transform the corners of the given rect and produce the bounding rect of the result.
+fn transform_rect_bounds<S>(transform: &Matrix4<S>, rect: geom::Rect<S>) -> geom::Rect<S>
+where
+    S: BaseFloat,
+{
+    let mut corners = rect.corners_iter().map(|p| {
+        let p = cgmath::Point3::new(p.x, p.y, S::zero());
+        let p = cgmath::Transform::transform_point(transform, p);
+        geom::pt2(p.x, p.y)
+    });
+    let first = corners.next().expect("a rect always has four corners");
+    corners.fold(geom::Rect::from_corners(first, first), |bounds, p| {
+        bounds.stretch_to_point(p)
+    })
+}
+
+impl<S> Context<S>
+where
+    S: BaseFloat,
+{
+    // Produce the smallest command that describes the change from the `prev` context to `self`.
+    fn change_from(&self, prev: &Self) -> DrawCommand<S> {
+        let transform = self.transform != prev.transform;
+        let blend = self.color_blend != prev.color_blend || self.alpha_blend != prev.alpha_blend;
+        let scissor = self.scissor != prev.scissor;
+        let other = self.topology != prev.topology
+            || self.sampler != prev.sampler
+            || self.wireframe != prev.wireframe
+            || self.depth_compare != prev.depth_compare
+            || self.depth_write != prev.depth_write
+            || self.mask != prev.mask
+            || self.group != prev.group;
+        let delta = match (transform, blend, scissor, other) {
+            (true, false, false, false) => ContextDelta::Transform(self.transform),
+            (false, true, false, false) => ContextDelta::Blend {
+                color: self.color_blend.clone(),
+                alpha: self.alpha_blend.clone(),
+            },
+            (false, false, true, false) => ContextDelta::Scissor(self.scissor),
+            _ => return DrawCommand::Context(self.clone()),
+        };
+        DrawCommand::ContextDelta(delta)
+    }
+}
+
+impl<S> ContextDelta<S> {
+    /// Apply the change to the given context.
+    pub fn apply(self, ctxt: &mut Context<S>) {
+        match self {
+            ContextDelta::Transform(transform) => ctxt.transform = transform,
+            ContextDelta::Blend { color, alpha } => {
+                ctxt.color_blend = color;
+                ctxt.alpha_blend = alpha;
+            }
+            ContextDelta::Scissor(scissor) => ctxt.scissor = scissor,
+        }
+    }
+}
+
 impl<S> Default for IntermediaryState<S> {
     fn default() -> Self {
         let intermediary_mesh = Default::default();
         let path_event_buffer = Default::default();
         let path_points_colored_buffer = Default::default();
         let path_points_textured_buffer = Default::default();
+        let path_points_weighted_buffer = Default::default();
         let text_buffer = Default::default();
         IntermediaryState {
             intermediary_mesh,
             path_event_buffer,
             path_points_colored_buffer,
             path_points_textured_buffer,
+            path_points_weighted_buffer,
             text_buffer,
         }
     }
@@ -637,17 +1197,27 @@ where
     fn default() -> Self {
         let last_draw_context = None;
         let background_color = Default::default();
+        let background_fill = Default::default();
         let draw_commands = Default::default();
+        let z_indices = Default::default();
+        let fragment_shaders = Default::default();
+        let mask_count = 0;
+        let group_count = 0;
         let drawing = Default::default();
         let intermediary_state = RefCell::new(Default::default());
         let theme = Default::default();
         State {
             last_draw_context,
             draw_commands,
+            z_indices,
+            fragment_shaders,
+            mask_count,
+            group_count,
             drawing,
             intermediary_state,
             theme,
             background_color,
+            background_fill,
         }
     }
 }
@@ -659,7 +1229,12 @@ where
     fn default() -> Self {
         let state: Rc<RefCell<State<S>>> = Rc::new(RefCell::new(Default::default()));
         let context = Default::default();
-        Draw { state, context }
+        let layer = 0;
+        Draw {
+            state,
+            context,
+            layer,
+        }
     }
 }
 
@@ -675,6 +1250,54 @@ where
             scissor: Scissor::Full,
             topology: wgpu::RenderPipelineBuilder::DEFAULT_PRIMITIVE_TOPOLOGY,
             sampler: wgpu::SamplerBuilder::new().into_descriptor(),
+            wireframe: false,
+            depth_compare: wgpu::RenderPipelineBuilder::DEFAULT_DEPTH_COMPARE,
+            depth_write: wgpu::RenderPipelineBuilder::DEFAULT_DEPTH_WRITE_ENABLED,
+            mask: Mask::None,
+            group: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The name of each primitive within the given commands, in order.
+    fn primitive_names(cmds: &[DrawCommand]) -> Vec<&'static str> {
+        cmds.iter()
+            .filter_map(|cmd| match *cmd {
+                DrawCommand::Primitive(Primitive::Ellipse(_)) => Some("ellipse"),
+                DrawCommand::Primitive(Primitive::Rect(_)) => Some("rect"),
+                DrawCommand::Primitive(Primitive::Tri(_)) => Some("tri"),
+                DrawCommand::Primitive(Primitive::Quad(_)) => Some("quad"),
+                DrawCommand::Primitive(_) => Some("other"),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_drain_unsorted_order() {
+        let draw = Draw::new();
+        draw.ellipse();
+        draw.rect();
+        let mut cmds = vec![];
+        assert_eq!(draw.drain_sorted_commands_into(&mut cmds), None);
+        assert_eq!(primitive_names(&cmds), ["ellipse", "rect"]);
+    }
+
+    #[test]
+    fn test_drain_sorted_order() {
+        // Each primitive's submission index is retained after sorting by z index and layer.
+        let draw = Draw::new();
+        draw.ellipse();
+        draw.rect().z_index(-1);
+        draw.layer(1).tri();
+        draw.quad();
+        let mut cmds = vec![];
+        let order = draw.drain_sorted_commands_into(&mut cmds);
+        assert_eq!(primitive_names(&cmds), ["rect", "ellipse", "quad", "tri"]);
+        assert_eq!(order, Some(vec![1, 0, 3, 2]));
+    }
+}
@@ -0,0 +1,414 @@
+//! Items related to tracing the stroked outlines of a **Draw**'s primitives into commands for pen
+//! plotters and CNC machines.
+
+use crate::draw;
+use crate::draw::mesh::vertex::Color;
+use crate::geom::{self, Point2};
+use crate::math::Matrix4;
+use lyon::path::PathEvent;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Draw API primitives whose stroked outline may be traced by the **PlotterRenderer**.
+pub trait PlotPrimitive {
+    /// Extend `events` with the primitive's stroked outline and return the primitive's local
+    /// transform.
+    ///
+    /// Returns `None` if the primitive is not stroked, in which case nothing is plotted.
+    fn plot_stroke(&self, ctxt: PlotContext, events: &mut Vec<PathEvent>) -> Option<Matrix4<f32>>;
+}
+
+/// The context provided to primitives to assist with tracing their strokes.
+pub struct PlotContext<'a> {
+    pub path_event_buffer: &'a [PathEvent],
+    pub path_points_colored_buffer: &'a [(Point2, Color)],
+    pub path_points_textured_buffer: &'a [(Point2, Point2)],
+    pub path_points_weighted_buffer: &'a [(Point2, f32)],
+}
+
+/// The command language produced by the **PlotterRenderer**.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// G-code, as understood by CNC machines and most hobbyist pen plotters (e.g. via GRBL).
+    GCode,
+    /// The Hewlett-Packard Graphics Language, as understood by most vintage pen plotters.
+    Hpgl,
+}
+
+/// Converts the stroked paths of a **Draw** into G-code or HPGL.
+///
+/// Only primitives with a stroke are plotted, including stroked paths, polylines, lines, arrows,
+/// arcs, béziers and the outlines of stroked polygons, rects, ellipses, stars, regular polygons,
+/// tris and quads. Fills, meshes, textures and text are ignored. Curves are flattened into line
+/// segments and dashes are applied.
+///
+/// The origin of the **Draw** is placed at the centre of the paper with the y axis pointing up.
+/// Points are not clipped to the paper.
+#[derive(Clone, Debug)]
+pub struct PlotterRenderer {
+    builder: Builder,
+}
+
+/// A type aimed at simplifying construction of a **PlotterRenderer**.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    pub format: Format,
+    pub feed_rate: f32,
+    pub pen_up: String,
+    pub pen_down: String,
+    pub paper_size: [f32; 2],
+    pub mm_per_point: f32,
+    pub tolerance: f32,
+}
+
+// The number of HPGL plotter units per millimetre.
+const HPGL_UNITS_PER_MM: f32 = 40.0;
+
+impl Builder {
+    /// The default format.
+    pub const DEFAULT_FORMAT: Format = Format::GCode;
+    /// The default speed at which the pen is moved while drawing in millimetres per minute.
+    pub const DEFAULT_FEED_RATE: f32 = 1_000.0;
+    /// The default G-code command used to lift the pen.
+    pub const DEFAULT_PEN_UP: &'static str = "G0 Z5";
+    /// The default G-code command used to lower the pen.
+    pub const DEFAULT_PEN_DOWN: &'static str = "G0 Z0";
+    /// The default paper size in millimetres (A4 portrait).
+    pub const DEFAULT_PAPER_SIZE: [f32; 2] = [210.0, 297.0];
+    /// The default number of millimetres per **Draw** point, assuming 96 points per inch.
+    pub const DEFAULT_MM_PER_POINT: f32 = 25.4 / 96.0;
+    /// The default maximum distance from a curve to its flattened approximation in points.
+    pub const DEFAULT_TOLERANCE: f32 = 0.1;
+
+    /// Begin building a new **PlotterRenderer** with the default parameters.
+    pub fn new() -> Self {
+        Builder {
+            format: Self::DEFAULT_FORMAT,
+            feed_rate: Self::DEFAULT_FEED_RATE,
+            pen_up: Self::DEFAULT_PEN_UP.to_string(),
+            pen_down: Self::DEFAULT_PEN_DOWN.to_string(),
+            paper_size: Self::DEFAULT_PAPER_SIZE,
+            mm_per_point: Self::DEFAULT_MM_PER_POINT,
+            tolerance: Self::DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// The command language to produce.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The speed at which the pen is moved while drawing in millimetres per minute.
+    ///
+    /// For HPGL this is converted to the pen velocity in centimetres per second.
+    pub fn feed_rate(mut self, feed_rate: f32) -> Self {
+        self.feed_rate = feed_rate;
+        self
+    }
+
+    /// The G-code command used to lift the pen, e.g. `"M3 S0"` for a servo driven pen.
+    ///
+    /// HPGL always uses its own `PU` command.
+    pub fn pen_up(mut self, command: &str) -> Self {
+        self.pen_up = command.to_string();
+        self
+    }
+
+    /// The G-code command used to lower the pen, e.g. `"M3 S90"` for a servo driven pen.
+    ///
+    /// HPGL always uses its own `PD` command.
+    pub fn pen_down(mut self, command: &str) -> Self {
+        self.pen_down = command.to_string();
+        self
+    }
+
+    /// The width and height of the paper in millimetres.
+    pub fn paper_size(mut self, [w, h]: [f32; 2]) -> Self {
+        self.paper_size = [w, h];
+        self
+    }
+
+    /// The number of millimetres per **Draw** point.
+    pub fn mm_per_point(mut self, mm: f32) -> Self {
+        self.mm_per_point = mm;
+        self
+    }
+
+    /// The maximum distance from a curve to its flattened approximation in points.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Build the **PlotterRenderer**.
+    pub fn build(self) -> PlotterRenderer {
+        PlotterRenderer { builder: self }
+    }
+}
+
+impl PlotterRenderer {
+    /// Write the stroked paths of the given **Draw** to the given writer.
+    ///
+    /// Unlike the GPU renderer, this does not drain the **Draw** of its commands, allowing the
+    /// same **Draw** to be plotted and rendered to a frame.
+    pub fn render<W>(&self, draw: &draw::Draw, w: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let polylines = self.trace(draw);
+        match self.builder.format {
+            Format::GCode => self.write_gcode(&polylines, w),
+            Format::Hpgl => self.write_hpgl(&polylines, w),
+        }
+    }
+
+    /// Write the stroked paths of the given **Draw** to a file at the given path.
+    pub fn render_to_file<P>(&self, draw: &draw::Draw, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::create(path)?;
+        self.render(draw, BufWriter::new(file))
+    }
+
+    // Trace the stroked outlines of all primitives into polylines in paper space (millimetres).
+    fn trace(&self, draw: &draw::Draw) -> Vec<Vec<Point2>> {
+        use lyon::path::iterator::PathIterator;
+
+        draw.finish_remaining_drawings();
+        let state = draw.state.borrow();
+        let intermediary_state = state.intermediary_state.borrow();
+
+        let [paper_w, paper_h] = self.builder.paper_size;
+        let mm_per_point = self.builder.mm_per_point;
+        let to_paper = |transform: &Matrix4<f32>, p: lyon::math::Point| -> Point2 {
+            let p = cgmath::Point3::new(p.x, p.y, 0.0);
+            let p = cgmath::Transform::transform_point(transform, p);
+            let x = p.x * mm_per_point + paper_w * 0.5;
+            let y = p.y * mm_per_point + paper_h * 0.5;
+            geom::pt2(x, y)
+        };
+
+        let mut ctxt = draw::Context::default();
+        let mut events = vec![];
+        let mut polylines = vec![];
+        for cmd in state.draw_commands.iter().filter_map(Option::as_ref) {
+            let prim = match *cmd {
+                draw::DrawCommand::Context(ref c) => {
+                    ctxt = c.clone();
+                    continue;
+                }
+                draw::DrawCommand::ContextDelta(ref delta) => {
+                    delta.clone().apply(&mut ctxt);
+                    continue;
+                }
+                draw::DrawCommand::Primitive(ref prim) => prim,
+            };
+
+            events.clear();
+            let plot_ctxt = PlotContext {
+                path_event_buffer: &intermediary_state.path_event_buffer,
+                path_points_colored_buffer: &intermediary_state.path_points_colored_buffer,
+                path_points_textured_buffer: &intermediary_state.path_points_textured_buffer,
+                path_points_weighted_buffer: &intermediary_state.path_points_weighted_buffer,
+            };
+            let local_transform = match prim.plot_stroke(plot_ctxt, &mut events) {
+                None => continue,
+                Some(transform) => transform,
+            };
+            let transform = ctxt.transform * local_transform;
+
+            let mut polyline: Vec<Point2> = vec![];
+            let flattened = events.iter().cloned().flattened(self.builder.tolerance);
+            for event in flattened {
+                match event {
+                    PathEvent::Begin { at } => polyline.push(to_paper(&transform, at)),
+                    PathEvent::Line { to, .. } => polyline.push(to_paper(&transform, to)),
+                    PathEvent::End { first, close, .. } => {
+                        if close {
+                            polyline.push(to_paper(&transform, first));
+                        }
+                        let polyline = std::mem::replace(&mut polyline, vec![]);
+                        if polyline.len() > 1 {
+                            polylines.push(polyline);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        polylines
+    }
+
+    fn write_gcode<W>(&self, polylines: &[Vec<Point2>], mut w: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let Builder {
+            ref pen_up,
+            ref pen_down,
+            feed_rate,
+            ..
+        } = self.builder;
+        writeln!(w, "G21")?;
+        writeln!(w, "G90")?;
+        writeln!(w, "{}", pen_up)?;
+        for polyline in polylines {
+            let start = polyline[0];
+            writeln!(w, "G0 X{:.3} Y{:.3}", start.x, start.y)?;
+            writeln!(w, "{}", pen_down)?;
+            for (i, p) in polyline.iter().skip(1).enumerate() {
+                match i {
+                    0 => writeln!(w, "G1 X{:.3} Y{:.3} F{}", p.x, p.y, feed_rate)?,
+                    _ => writeln!(w, "G1 X{:.3} Y{:.3}", p.x, p.y)?,
+                }
+            }
+            writeln!(w, "{}", pen_up)?;
+        }
+        writeln!(w, "G0 X0 Y0")?;
+        w.flush()
+    }
+
+    fn write_hpgl<W>(&self, polylines: &[Vec<Point2>], mut w: W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let to_units = |v: f32| (v * HPGL_UNITS_PER_MM).round() as i32;
+        // Millimetres per minute to centimetres per second.
+        let velocity = self.builder.feed_rate / 600.0;
+        writeln!(w, "IN;SP1;VS{:.1};", velocity)?;
+        for polyline in polylines {
+            let start = polyline[0];
+            write!(w, "PU{},{};PD", to_units(start.x), to_units(start.y))?;
+            for (i, p) in polyline.iter().skip(1).enumerate() {
+                if i > 0 {
+                    write!(w, ",")?;
+                }
+                write!(w, "{},{}", to_units(p.x), to_units(p.y))?;
+            }
+            writeln!(w, ";")?;
+        }
+        writeln!(w, "PU;SP0;")?;
+        w.flush()
+    }
+}
+
+impl PlotPrimitive for draw::Primitive {
+    fn plot_stroke(&self, ctxt: PlotContext, events: &mut Vec<PathEvent>) -> Option<Matrix4<f32>> {
+        match *self {
+            draw::Primitive::Arc(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Arrow(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Bezier(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Ellipse(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Line(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Path(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Polygon(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Quad(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Rect(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::RegularPolygon(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Shaded(ref prim) => prim.primitive().plot_stroke(ctxt, events),
+            draw::Primitive::Star(ref prim) => prim.plot_stroke(ctxt, events),
+            draw::Primitive::Tri(ref prim) => prim.plot_stroke(ctxt, events),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write<F>(builder: Builder, polylines: &[Vec<Point2>], f: F) -> String
+    where
+        F: Fn(&PlotterRenderer, &[Vec<Point2>], &mut Vec<u8>) -> io::Result<()>,
+    {
+        let renderer = builder.build();
+        let mut bytes = vec![];
+        f(&renderer, polylines, &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    fn square() -> Vec<Vec<Point2>> {
+        vec![vec![
+            geom::pt2(10.0, 10.0),
+            geom::pt2(20.0, 10.0),
+            geom::pt2(20.0, 20.5),
+        ]]
+    }
+
+    #[test]
+    fn test_write_gcode() {
+        let builder = Builder::new().feed_rate(1500.0);
+        let gcode = write(builder, &square(), |r, p, w| r.write_gcode(p, w));
+        let expected = "\
+G21
+G90
+G0 Z5
+G0 X10.000 Y10.000
+G0 Z0
+G1 X20.000 Y10.000 F1500
+G1 X20.000 Y20.500
+G0 Z5
+G0 X0 Y0
+";
+        assert_eq!(gcode, expected);
+    }
+
+    #[test]
+    fn test_write_gcode_custom_pen() {
+        let builder = Builder::new().pen_up("M3 S0").pen_down("M3 S90");
+        let gcode = write(builder, &square(), |r, p, w| r.write_gcode(p, w));
+        let lines: Vec<&str> = gcode.lines().collect();
+        assert_eq!(lines[2], "M3 S0");
+        assert_eq!(lines[4], "M3 S90");
+        assert_eq!(lines[7], "M3 S0");
+    }
+
+    #[test]
+    fn test_write_hpgl() {
+        let builder = Builder::new().format(Format::Hpgl).feed_rate(1500.0);
+        let hpgl = write(builder, &square(), |r, p, w| r.write_hpgl(p, w));
+        let expected = "\
+IN;SP1;VS2.5;
+PU400,400;PD800,400,800,820;
+PU;SP0;
+";
+        assert_eq!(hpgl, expected);
+    }
+
+    #[test]
+    fn test_write_empty() {
+        let gcode = write(Builder::new(), &[], |r, p, w| r.write_gcode(p, w));
+        assert_eq!(gcode, "G21\nG90\nG0 Z5\nG0 X0 Y0\n");
+        let hpgl = write(Builder::new(), &[], |r, p, w| r.write_hpgl(p, w));
+        assert_eq!(hpgl, "IN;SP1;VS1.7;\nPU;SP0;\n");
+    }
+
+    #[test]
+    fn test_trace_line_to_paper() {
+        // The origin of the **Draw** lies at the centre of the paper.
+        let draw = draw::Draw::new();
+        draw.line()
+            .start(geom::pt2(-10.0, 0.0))
+            .end(geom::pt2(10.0, 5.0));
+        // Unstroked primitives are not plotted.
+        draw.ellipse();
+        let renderer = Builder::new()
+            .paper_size([100.0, 200.0])
+            .mm_per_point(2.0)
+            .build();
+        let polylines = renderer.trace(&draw);
+        assert_eq!(
+            polylines,
+            vec![vec![geom::pt2(30.0, 100.0), geom::pt2(70.0, 110.0)]]
+        );
+    }
+}
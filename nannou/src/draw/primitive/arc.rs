@@ -0,0 +1,275 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw;
+use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPolygon};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{
+    ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
+use crate::draw::Drawing;
+use crate::geom::{self, pt2};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::tessellation::StrokeOptions;
+
+/// Properties related to drawing an **Arc**.
+///
+/// An arc is a section of the circumference of a circle, beginning at the start angle and sweeping
+/// counter-clockwise to the end angle. Angles are described in radians where `0.0` points along
+/// the positive x axis.
+///
+/// When filled, the ends of the arc are joined by a straight chord. Use `no_fill` along with one of
+/// the stroke methods to draw the arc as an open curve.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Arc<S = geom::scalar::Default> {
+    radius: Option<S>,
+    start_angle: Option<S>,
+    end_angle: Option<S>,
+    resolution: Option<usize>,
+    polygon: PolygonInit<S>,
+}
+
+/// The drawing context for an arc.
+pub type DrawingArc<'a, S = geom::scalar::Default> = Drawing<'a, Arc<S>, S>;
+
+/// The radius used if none is specified.
+pub const DEFAULT_RADIUS: f32 = 50.0;
+
+// Arc-specific methods.
+
+impl<S> Arc<S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.stroke_color(color)
+    }
+
+    /// The radius of the circle along which the arc is drawn.
+    ///
+    /// By default, this is `50.0`.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// The angle in radians at which the arc begins.
+    ///
+    /// By default, this is `0.0`.
+    pub fn start_angle(mut self, radians: S) -> Self {
+        self.start_angle = Some(radians);
+        self
+    }
+
+    /// The angle in radians at which the arc ends.
+    ///
+    /// If the end angle is less than the start angle, the arc sweeps clockwise. By default, the
+    /// arc sweeps one full turn from the start angle.
+    pub fn end_angle(mut self, radians: S) -> Self {
+        self.end_angle = Some(radians);
+        self
+    }
+
+    /// Short-hand for specifying both the `start_angle` and `end_angle` in radians.
+    pub fn angles(self, start: S, end: S) -> Self {
+        self.start_angle(start).end_angle(end)
+    }
+
+    /// The number of line segments used to draw the arc.
+    ///
+    /// By default, arc does not use a resolution, but rather uses a stroke tolerance to determine
+    /// how many vertices to use during tessellation.
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+impl Arc<f32> {
+    // The radius, start angle and sweep angle of the arc.
+    fn radius_start_sweep(&self) -> (f32, f32, f32) {
+        let radius = self.radius.map(f32::abs).unwrap_or(DEFAULT_RADIUS);
+        let start = self.start_angle.unwrap_or(0.0);
+        let end = self.end_angle.unwrap_or(start + std::f32::consts::PI * 2.0);
+        (radius, start, end - start)
+    }
+
+    // Describe the arc as a sequence of path events.
+    //
+    // Returns `None` if the arc has no radius or sweep.
+    fn path(&self) -> Option<lyon::path::Path> {
+        let (radius, start, sweep) = self.radius_start_sweep();
+        if radius <= 0.0 || sweep == 0.0 {
+            return None;
+        }
+        let mut builder = lyon::path::Path::builder();
+        match self.resolution {
+            None => {
+                let centre = lyon::math::point(0.0, 0.0);
+                let radii = lyon::math::vector(radius, radius);
+                let sweep_angle = lyon::math::Angle::radians(sweep);
+                let x_rotation = lyon::math::Angle::radians(0.0);
+                let (sin, cos) = start.sin_cos();
+                builder.move_to(lyon::math::point(cos * radius, sin * radius));
+                builder.arc(centre, radii, sweep_angle, x_rotation);
+            }
+            Some(resolution) => {
+                let segments = resolution.max(1);
+                for i in 0..=segments {
+                    let angle = start + sweep * i as f32 / segments as f32;
+                    let (sin, cos) = angle.sin_cos();
+                    let p = pt2(cos * radius, sin * radius).into();
+                    match i {
+                        0 => builder.move_to(p),
+                        _ => builder.line_to(p),
+                    };
+                }
+            }
+        }
+        Some(builder.build())
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Arc<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        match self.path() {
+            None => draw::renderer::PrimitiveRender::default(),
+            Some(path) => polygon::render_events_themed(
+                self.polygon.opts,
+                || (&path).into_iter(),
+                ctxt,
+                &draw::theme::Primitive::Arc,
+                mesh,
+            ),
+        }
+    }
+}
+
+impl draw::plotter_renderer::PlotPrimitive for Arc<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let path = self.path()?;
+        polygon::plot_events(&self.polygon.opts, &path, events)
+    }
+}
+
+impl<S> Default for Arc<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Arc {
+            radius: None,
+            start_angle: None,
+            end_angle: None,
+            resolution: None,
+            polygon: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Arc<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetPosition<S> for Arc<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Arc<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetDash for Arc<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetStroke for Arc<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetPolygon<S> for Arc<S> {
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S> {
+        SetPolygon::polygon_options_mut(&mut self.polygon)
+    }
+}
+
+// Primitive conversion.
+
+impl<S> From<Arc<S>> for Primitive<S> {
+    fn from(prim: Arc<S>) -> Self {
+        Primitive::Arc(prim)
+    }
+}
+
+impl<S> Into<Option<Arc<S>>> for Primitive<S> {
+    fn into(self) -> Option<Arc<S>> {
+        match self {
+            Primitive::Arc(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingArc<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.stroke(color))
+    }
+
+    /// The radius of the circle along which the arc is drawn.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// The angle in radians at which the arc begins.
+    pub fn start_angle(self, radians: S) -> Self {
+        self.map_ty(|ty| ty.start_angle(radians))
+    }
+
+    /// The angle in radians at which the arc ends.
+    pub fn end_angle(self, radians: S) -> Self {
+        self.map_ty(|ty| ty.end_angle(radians))
+    }
+
+    /// Short-hand for specifying both the `start_angle` and `end_angle` in radians.
+    pub fn angles(self, start: S, end: S) -> Self {
+        self.map_ty(|ty| ty.angles(start, end))
+    }
+
+    /// The number of line segments used to draw the arc.
+    pub fn resolution(self, resolution: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+}
@@ -3,16 +3,19 @@ use crate::draw::primitive::path;
 use crate::draw::primitive::Line;
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{orientation, position};
-use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition, SetStroke};
+use crate::draw::properties::{
+    ColorScalar, Dash, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
 use crate::draw::{self, Drawing};
 use crate::geom::{self, pt2, vec2, Point2};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// A path containing only two points - a start and end.
 ///
 /// A triangle is drawn on the end to indicate direction.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Arrow<S = geom::scalar::Default> {
     line: Line<S>,
     head_length: Option<S>,
@@ -131,6 +134,12 @@ where
     }
 }
 
+impl<S> SetDash for Arrow<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.line)
+    }
+}
+
 impl<S> SetStroke for Arrow<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.line)
@@ -170,42 +179,64 @@ impl<S> Into<Option<Arrow<S>>> for Primitive<S> {
     }
 }
 
-impl draw::renderer::RenderPrimitive for Arrow<f32> {
-    fn render_primitive(
-        self,
-        mut ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Arrow {
-            line,
-            head_length,
-            head_width,
-        } = self;
+// The geometry of an arrow's head and the remaining shaft.
+struct Head {
+    tri: [Point2; 3],
+    line_start: Point2,
+    line_end: Point2,
+    // The line should only be drawn if there is space after drawing the triangle.
+    draw_line: bool,
+}
+
+impl Arrow<f32> {
+    // Calculate the arrow head points, or `None` if the arrow has no length.
+    fn head(&self) -> Option<Head> {
+        let line = &self.line;
         let start = line.start.unwrap_or(pt2(0.0, 0.0));
         let end = line.end.unwrap_or(pt2(0.0, 0.0));
         if start == end {
-            return draw::renderer::PrimitiveRender::default();
+            return None;
         }
-
-        // Calculate the arrow head points.
         let line_w_2 = line.path.opts.line_width * 2.0;
         let line_w_4 = line_w_2 * 2.0;
-        let head_width = head_width.unwrap_or(line_w_2);
-        let head_length = head_length.unwrap_or(line_w_4);
+        let head_width = self.head_width.unwrap_or(line_w_2);
+        let head_length = self.head_length.unwrap_or(line_w_4);
         let line_dir = end - start;
         let line_dir_mag = line_dir.magnitude();
         let tri_len = head_length.min(line_dir_mag);
         let tri_dir_norm = line_dir.with_magnitude(tri_len);
         let tri_start = end - tri_dir_norm;
         let tri_end = end;
-        let line_start = start;
-        let line_end = tri_start;
         let tri_a = tri_end;
         let tri_w_dir = vec2(-tri_dir_norm.y, tri_dir_norm.x).with_magnitude(head_width);
         let tri_b = tri_start + tri_w_dir;
         let tri_c = tri_start - tri_w_dir;
-        // The line should only be drawn if there is space after drawing the triangle.
-        let draw_line = line_dir_mag > tri_len;
+        Some(Head {
+            tri: [tri_a, tri_b, tri_c],
+            line_start: start,
+            line_end: tri_start,
+            draw_line: line_dir_mag > tri_len,
+        })
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Arrow<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let head = match self.head() {
+            None => return draw::renderer::PrimitiveRender::default(),
+            Some(head) => head,
+        };
+        let Head {
+            tri: [tri_a, tri_b, tri_c],
+            line_start,
+            line_end,
+            draw_line,
+        } = head;
+        let line = self.line;
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
@@ -235,11 +266,14 @@ impl draw::renderer::RenderPrimitive for Arrow<f32> {
             let line_points = line_points.iter().cloned().map(Into::into);
             let close_line = false;
             let line_events = lyon::path::iterator::FromPolyline::new(close_line, line_points);
+            let options = path::Options::Stroke(line.path.opts);
+            let line_events =
+                path::dash_stroke_events(line_events, line.path.dash.as_ref(), &options);
             path::render_path_events(
                 line_events,
                 line.path.color,
                 transform,
-                path::Options::Stroke(line.path.opts),
+                options,
                 &ctxt.theme,
                 &draw::theme::Primitive::Arrow,
                 &mut ctxt.fill_tessellator,
@@ -252,6 +286,36 @@ impl draw::renderer::RenderPrimitive for Arrow<f32> {
     }
 }
 
+impl draw::plotter_renderer::PlotPrimitive for Arrow<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let head = self.head()?;
+        let stroke = &self.line.path;
+
+        // Outline the head.
+        let tri_points = head.tri.iter().cloned().map(Into::into);
+        events.extend(lyon::path::iterator::FromPolyline::new(true, tri_points));
+
+        // Trace the shaft.
+        if head.draw_line {
+            let line_points = [head.line_start, head.line_end];
+            let line_points = line_points.iter().cloned().map(Into::into);
+            let line_events = lyon::path::iterator::FromPolyline::new(false, line_points);
+            let options = path::Options::Stroke(stroke.opts);
+            events.extend(path::dash_stroke_events(
+                line_events,
+                stroke.dash.as_ref(),
+                &options,
+            ));
+        }
+
+        Some(stroke.position.transform() * stroke.orientation.transform())
+    }
+}
+
 impl<S> Default for Arrow<S>
 where
     S: Zero,
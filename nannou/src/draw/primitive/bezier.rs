@@ -0,0 +1,282 @@
+use crate::color::LinSrgba;
+use crate::draw::primitive::path;
+use crate::draw::primitive::{PathStroke, Primitive};
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{
+    ColorScalar, Dash, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point2};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::tessellation::StrokeOptions;
+
+/// A stroked curve made up of one or more quadratic or cubic bézier segments.
+///
+/// The curve begins at the `start` point and each segment continues from the end of the previous
+/// one. Curves are tessellated directly from their control points rather than from a pre-flattened
+/// polyline, so the stroke `tolerance` determines their smoothness.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bezier<S = geom::scalar::Default> {
+    pub path: PathStroke<S>,
+    pub start: Option<Point2<S>>,
+    pub segments: Vec<Segment<S>>,
+}
+
+/// A single segment of a **Bezier** curve, beginning at the end of the previous segment.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Segment<S = geom::scalar::Default> {
+    /// A quadratic bézier segment with a single control point.
+    Quadratic { ctrl: Point2<S>, to: Point2<S> },
+    /// A cubic bézier segment with two control points.
+    Cubic {
+        ctrl1: Point2<S>,
+        ctrl2: Point2<S>,
+        to: Point2<S>,
+    },
+}
+
+/// The drawing context for a bézier curve.
+pub type DrawingBezier<'a, S = geom::scalar::Default> = Drawing<'a, Bezier<S>, S>;
+
+impl<S> Bezier<S> {
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_path(|p| p.stroke_weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_path(|p| p.stroke_tolerance(tolerance))
+    }
+
+    /// Specify the start point of the curve.
+    pub fn start(mut self, start: Point2<S>) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Append a quadratic segment from the end of the curve to `to`, bending toward `ctrl`.
+    pub fn quadratic_to(mut self, ctrl: Point2<S>, to: Point2<S>) -> Self {
+        self.segments.push(Segment::Quadratic { ctrl, to });
+        self
+    }
+
+    /// Append a cubic segment from the end of the curve to `to`, bending toward `ctrl1` and then
+    /// `ctrl2`.
+    pub fn cubic_to(mut self, ctrl1: Point2<S>, ctrl2: Point2<S>, to: Point2<S>) -> Self {
+        self.segments.push(Segment::Cubic { ctrl1, ctrl2, to });
+        self
+    }
+
+    /// Describe the curve as a single cubic segment from `a` to `b` with control points `c1` and
+    /// `c2`.
+    ///
+    /// Replaces any previously specified segments.
+    pub fn points(mut self, a: Point2<S>, c1: Point2<S>, c2: Point2<S>, b: Point2<S>) -> Self {
+        self.segments.clear();
+        self.start(a).cubic_to(c1, c2, b)
+    }
+
+    /// Describe the curve as a single quadratic segment from `a` to `b` with control point `c`.
+    ///
+    /// Replaces any previously specified segments.
+    pub fn quadratic_points(mut self, a: Point2<S>, c: Point2<S>, b: Point2<S>) -> Self {
+        self.segments.clear();
+        self.start(a).quadratic_to(c, b)
+    }
+
+    // Map the inner `PathStroke<S>` using the given function.
+    fn map_path<F>(self, map: F) -> Self
+    where
+        F: FnOnce(PathStroke<S>) -> PathStroke<S>,
+    {
+        let Bezier {
+            path,
+            start,
+            segments,
+        } = self;
+        let path = map(path);
+        Bezier {
+            path,
+            start,
+            segments,
+        }
+    }
+}
+
+impl Bezier<f32> {
+    // Describe the curve as a lyon path, or `None` if there are no segments.
+    fn lyon_path(&self) -> Option<lyon::path::Path> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        let start = self.start.unwrap_or(geom::pt2(0.0, 0.0));
+        let mut builder = lyon::path::Path::builder();
+        builder.move_to(start.into());
+        for segment in &self.segments {
+            match *segment {
+                Segment::Quadratic { ctrl, to } => {
+                    builder.quadratic_bezier_to(ctrl.into(), to.into());
+                }
+                Segment::Cubic { ctrl1, ctrl2, to } => {
+                    builder.cubic_bezier_to(ctrl1.into(), ctrl2.into(), to.into());
+                }
+            }
+        }
+        Some(builder.build())
+    }
+}
+
+impl<'a, S> DrawingBezier<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_ty(|ty| ty.tolerance(tolerance))
+    }
+
+    /// Specify the start point of the curve.
+    pub fn start(self, start: Point2<S>) -> Self {
+        self.map_ty(|ty| ty.start(start))
+    }
+
+    /// Append a quadratic segment from the end of the curve to `to`, bending toward `ctrl`.
+    pub fn quadratic_to(self, ctrl: Point2<S>, to: Point2<S>) -> Self {
+        self.map_ty(|ty| ty.quadratic_to(ctrl, to))
+    }
+
+    /// Append a cubic segment from the end of the curve to `to`, bending toward `ctrl1` and then
+    /// `ctrl2`.
+    pub fn cubic_to(self, ctrl1: Point2<S>, ctrl2: Point2<S>, to: Point2<S>) -> Self {
+        self.map_ty(|ty| ty.cubic_to(ctrl1, ctrl2, to))
+    }
+
+    /// Describe the curve as a single cubic segment from `a` to `b` with control points `c1` and
+    /// `c2`.
+    pub fn points(self, a: Point2<S>, c1: Point2<S>, c2: Point2<S>, b: Point2<S>) -> Self {
+        self.map_ty(|ty| ty.points(a, c1, c2, b))
+    }
+
+    /// Describe the curve as a single quadratic segment from `a` to `b` with control point `c`.
+    pub fn quadratic_points(self, a: Point2<S>, c: Point2<S>, b: Point2<S>) -> Self {
+        self.map_ty(|ty| ty.quadratic_points(a, c, b))
+    }
+}
+
+impl<S> SetDash for Bezier<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.path)
+    }
+}
+
+impl<S> SetStroke for Bezier<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.path)
+    }
+}
+
+impl<S> SetOrientation<S> for Bezier<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.path)
+    }
+}
+
+impl<S> SetPosition<S> for Bezier<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.path)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Bezier<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.path)
+    }
+}
+
+impl<S> From<Bezier<S>> for Primitive<S> {
+    fn from(prim: Bezier<S>) -> Self {
+        Primitive::Bezier(prim)
+    }
+}
+
+impl<S> Into<Option<Bezier<S>>> for Primitive<S> {
+    fn into(self) -> Option<Bezier<S>> {
+        match self {
+            Primitive::Bezier(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Bezier<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let lyon_path = match self.lyon_path() {
+            None => return draw::renderer::PrimitiveRender::default(),
+            Some(lyon_path) => lyon_path,
+        };
+        let Bezier { path, .. } = self;
+        let options = path::Options::Stroke(path.opts);
+        let events = path::dash_stroke_events(&lyon_path, path.dash.as_ref(), &options);
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = path.position.transform() * path.orientation.transform();
+        let transform = global_transform * local_transform;
+
+        path::render_path_events(
+            events,
+            path.color,
+            transform,
+            options,
+            &ctxt.theme,
+            &draw::theme::Primitive::Bezier,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            mesh,
+        );
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl draw::plotter_renderer::PlotPrimitive for Bezier<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let lyon_path = self.lyon_path()?;
+        let stroke = &self.path;
+        let options = path::Options::Stroke(stroke.opts);
+        events.extend(path::dash_stroke_events(
+            &lyon_path,
+            stroke.dash.as_ref(),
+            &options,
+        ));
+        Some(stroke.position.transform() * stroke.orientation.transform())
+    }
+}
+
+impl<S> Default for Bezier<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Bezier {
+            path: Default::default(),
+            start: Default::default(),
+            segments: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::draw::primitive::cylinder::Frustum;
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+
+/// Properties related to drawing a **Cone**.
+///
+/// The cone is centred on its position and extends along the y axis, with its base at
+/// `-height / 2` and its apex at `height / 2`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cone<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    radius: Option<S>,
+    height: Option<S>,
+    resolution: Option<usize>,
+    cap: bool,
+    color: Option<LinSrgba>,
+}
+
+/// The drawing context for a **Cone**.
+pub type DrawingCone<'a, S = geom::scalar::Default> = Drawing<'a, Cone<S>, S>;
+
+/// The radius of the base used if none is specified.
+pub const DEFAULT_RADIUS: f32 = 50.0;
+
+/// The height used if none is specified.
+pub const DEFAULT_HEIGHT: f32 = 100.0;
+
+/// The number of segments around the circumference of the base if no resolution is specified.
+pub const DEFAULT_RESOLUTION: usize = 32;
+
+impl<S> Cone<S> {
+    /// The radius of the base of the cone.
+    ///
+    /// By default, this is `50.0`.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// The height of the cone from its base to its apex.
+    ///
+    /// By default, this is `100.0`.
+    pub fn height(mut self, height: S) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// The number of segments used to describe the circumference of the base.
+    ///
+    /// By default, this is `32`. Resolutions below `3` are clamped to `3`.
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Whether or not to close the base of the cone.
+    ///
+    /// By default, the base is closed.
+    pub fn cap(mut self, cap: bool) -> Self {
+        self.cap = cap;
+        self
+    }
+}
+
+impl<'a, S> DrawingCone<'a, S>
+where
+    S: BaseFloat,
+{
+    /// The radius of the base of the cone.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// The height of the cone from its base to its apex.
+    pub fn height(self, height: S) -> Self {
+        self.map_ty(|ty| ty.height(height))
+    }
+
+    /// The number of segments used to describe the circumference of the base.
+    pub fn resolution(self, resolution: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+
+    /// Whether or not to close the base of the cone.
+    pub fn cap(self, cap: bool) -> Self {
+        self.map_ty(|ty| ty.cap(cap))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Cone<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Cone {
+            position,
+            orientation,
+            radius,
+            height,
+            resolution,
+            cap,
+            color,
+        } = self;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = ctxt.transform * local_transform;
+        let frustum = Frustum {
+            bottom_radius: radius.map(f32::abs).unwrap_or(DEFAULT_RADIUS),
+            top_radius: 0.0,
+            height: height.unwrap_or(DEFAULT_HEIGHT),
+            resolution: resolution.unwrap_or(DEFAULT_RESOLUTION),
+            bottom_cap: cap,
+            top_cap: false,
+        };
+        let color =
+            color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&draw::theme::Primitive::Cone));
+        frustum.render(transform, color, mesh);
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for Cone<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Cone {
+            position: Default::default(),
+            orientation: Default::default(),
+            radius: None,
+            height: None,
+            resolution: None,
+            cap: true,
+            color: None,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Cone<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Cone<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Cone<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Cone<S>> for Primitive<S> {
+    fn from(prim: Cone<S>) -> Self {
+        Primitive::Cone(prim)
+    }
+}
+
+impl<S> Into<Option<Cone<S>>> for Primitive<S> {
+    fn into(self) -> Option<Cone<S>> {
+        match self {
+            Primitive::Cone(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
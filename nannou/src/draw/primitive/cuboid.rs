@@ -0,0 +1,246 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw::mesh::vertex;
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{dimension, orientation, position};
+use crate::draw::properties::{
+    ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition,
+};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, pt3, Vector2, Vector3};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+
+/// Properties related to drawing a **Cuboid**.
+///
+/// The cuboid is drawn as twelve triangles (two for each face) via the same triangle pipeline as
+/// all other primitives. Faces are depth tested against everything else drawn to the frame, so
+/// the order in which they are drawn does not affect which faces are visible.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cuboid<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    dimensions: dimension::Properties<S>,
+    color: Option<LinSrgba>,
+    face_colors: [Option<LinSrgba>; geom::cuboid::NUM_FACES as usize],
+    wireframe: bool,
+    weight: f32,
+}
+
+/// The drawing context for a **Cuboid**.
+pub type DrawingCuboid<'a, S = geom::scalar::Default> = Drawing<'a, Cuboid<S>, S>;
+
+/// The width, height and depth used for any dimension that is not specified.
+pub const DEFAULT_SIZE: f32 = 100.0;
+
+/// The default weight of the edges of a wireframe **Cuboid**.
+pub const DEFAULT_WEIGHT: f32 = 1.0;
+
+impl<S> Cuboid<S> {
+    /// Color the given face, overriding the color of the cuboid for that face.
+    pub fn face_color<C>(mut self, face: geom::cuboid::Face, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.face_colors[face as usize] = Some(color.into_lin_srgba());
+        self
+    }
+
+    /// Draw only the twelve edges of the cuboid rather than its faces.
+    ///
+    /// Edges are extruded in screen space so that they maintain their `weight` regardless of the
+    /// orientation of the cuboid. If no color is specified, the stroke color for cuboids is
+    /// retrieved from the inner `Theme`.
+    pub fn wireframe(mut self, wireframe: bool) -> Self {
+        self.wireframe = wireframe;
+        self
+    }
+
+    /// The thickness of the edges of a wireframe cuboid in pixels.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+impl<'a, S> DrawingCuboid<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Color the given face, overriding the color of the cuboid for that face.
+    pub fn face_color<C>(self, face: geom::cuboid::Face, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.face_color(face, color))
+    }
+
+    /// Draw only the twelve edges of the cuboid rather than its faces.
+    pub fn wireframe(self, wireframe: bool) -> Self {
+        self.map_ty(|ty| ty.wireframe(wireframe))
+    }
+
+    /// The thickness of the edges of a wireframe cuboid in pixels.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Cuboid<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Cuboid {
+            position,
+            orientation,
+            dimensions,
+            color,
+            face_colors,
+            wireframe,
+            weight,
+        } = self;
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let transform_point = |p: geom::Point3<f32>| -> geom::Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+
+        let w = dimensions.x.unwrap_or(DEFAULT_SIZE);
+        let h = dimensions.y.unwrap_or(DEFAULT_SIZE);
+        let d = dimensions.z.unwrap_or(DEFAULT_SIZE);
+        let cuboid = geom::Cuboid::from_xyz_whd(pt3(0.0, 0.0, 0.0), Vector3::new(w, h, d));
+        let tex_coords = vertex::default_tex_coords();
+        let theme_prim = draw::theme::Primitive::Cuboid;
+
+        if wireframe {
+            let color = color.unwrap_or_else(|| ctxt.theme.stroke_lin_srgba(&theme_prim));
+            let corners = cuboid.corners();
+            let half_weight = weight * 0.5;
+            for &(a, b) in EDGES.iter() {
+                let a = transform_point(corners[a]);
+                let b = transform_point(corners[b]);
+                // Extrude the edge perpendicular to the line as it appears on screen.
+                let dir = Vector2::new(b.x - a.x, b.y - a.y);
+                let mag = dir.magnitude();
+                let offset = match mag > 0.0 {
+                    true => Vector2::new(-dir.y, dir.x) * (half_weight / mag),
+                    false => Vector2::new(half_weight, 0.0),
+                };
+                let v_start = mesh.raw_vertex_count() as u32;
+                for &p in &[a, b] {
+                    let p0 = pt3(p.x + offset.x, p.y + offset.y, p.z);
+                    let p1 = pt3(p.x - offset.x, p.y - offset.y, p.z);
+                    mesh.push_vertex(((p0, color), tex_coords).into());
+                    mesh.push_vertex(((p1, color), tex_coords).into());
+                }
+                let indices = [0, 1, 2, 1, 3, 2];
+                mesh.extend_indices(indices.iter().map(|i| v_start + i));
+            }
+        } else {
+            let default_color = color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&theme_prim));
+            for &face in FACES.iter() {
+                let color = face_colors[face as usize].unwrap_or(default_color);
+                let quad = cuboid.face_quad(face);
+                let v_start = mesh.raw_vertex_count() as u32;
+                for p in quad.vertices() {
+                    let p = transform_point(p);
+                    mesh.push_vertex(((p, color), tex_coords).into());
+                }
+                let indices = [0, 1, 2, 0, 2, 3];
+                mesh.extend_indices(indices.iter().map(|i| v_start + i));
+            }
+        }
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+// Each face of a cuboid, in the order of the `geom::cuboid::Face` variants.
+const FACES: [geom::cuboid::Face; geom::cuboid::NUM_FACES as usize] = [
+    geom::cuboid::Face::Back,
+    geom::cuboid::Face::Right,
+    geom::cuboid::Face::Top,
+    geom::cuboid::Face::Front,
+    geom::cuboid::Face::Bottom,
+    geom::cuboid::Face::Left,
+];
+
+// The indices of the corners joined by each of the twelve edges of a cuboid.
+//
+// See `geom::Cuboid::corners` for the order of the corners.
+const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+impl<S> Default for Cuboid<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Cuboid {
+            position: Default::default(),
+            orientation: Default::default(),
+            dimensions: Default::default(),
+            color: None,
+            face_colors: [None; geom::cuboid::NUM_FACES as usize],
+            wireframe: false,
+            weight: DEFAULT_WEIGHT,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Cuboid<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Cuboid<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetDimensions<S> for Cuboid<S> {
+    fn properties(&mut self) -> &mut dimension::Properties<S> {
+        SetDimensions::properties(&mut self.dimensions)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Cuboid<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Cuboid<S>> for Primitive<S> {
+    fn from(prim: Cuboid<S>) -> Self {
+        Primitive::Cuboid(prim)
+    }
+}
+
+impl<S> Into<Option<Cuboid<S>>> for Primitive<S> {
+    fn into(self) -> Option<Cuboid<S>> {
+        match self {
+            Primitive::Cuboid(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, pt2, pt3, Point3};
+use crate::math::{BaseFloat, Matrix4, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Properties related to drawing a **Cylinder**.
+///
+/// The cylinder is centred on its position and extends along the y axis, with its bottom cap at
+/// `-height / 2` and its top cap at `height / 2`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cylinder<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    radius: Option<S>,
+    height: Option<S>,
+    resolution: Option<usize>,
+    top_cap: bool,
+    bottom_cap: bool,
+    color: Option<LinSrgba>,
+}
+
+/// The drawing context for a **Cylinder**.
+pub type DrawingCylinder<'a, S = geom::scalar::Default> = Drawing<'a, Cylinder<S>, S>;
+
+/// The radius used if none is specified.
+pub const DEFAULT_RADIUS: f32 = 50.0;
+
+/// The height used if none is specified.
+pub const DEFAULT_HEIGHT: f32 = 100.0;
+
+/// The number of segments around the circumference if no resolution is specified.
+pub const DEFAULT_RESOLUTION: usize = 32;
+
+impl<S> Cylinder<S> {
+    /// The radius of the cylinder.
+    ///
+    /// By default, this is `50.0`.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// The height of the cylinder along its y axis.
+    ///
+    /// By default, this is `100.0`.
+    pub fn height(mut self, height: S) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// The number of segments used to describe the circumference of the cylinder.
+    ///
+    /// By default, this is `32`. Resolutions below `3` are clamped to `3`.
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Whether or not to close both ends of the cylinder.
+    ///
+    /// By default, both ends are closed.
+    pub fn caps(self, caps: bool) -> Self {
+        self.top_cap(caps).bottom_cap(caps)
+    }
+
+    /// Whether or not to close the top end of the cylinder.
+    pub fn top_cap(mut self, cap: bool) -> Self {
+        self.top_cap = cap;
+        self
+    }
+
+    /// Whether or not to close the bottom end of the cylinder.
+    pub fn bottom_cap(mut self, cap: bool) -> Self {
+        self.bottom_cap = cap;
+        self
+    }
+}
+
+impl<'a, S> DrawingCylinder<'a, S>
+where
+    S: BaseFloat,
+{
+    /// The radius of the cylinder.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// The height of the cylinder along its y axis.
+    pub fn height(self, height: S) -> Self {
+        self.map_ty(|ty| ty.height(height))
+    }
+
+    /// The number of segments used to describe the circumference of the cylinder.
+    pub fn resolution(self, resolution: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+
+    /// Whether or not to close both ends of the cylinder.
+    pub fn caps(self, caps: bool) -> Self {
+        self.map_ty(|ty| ty.caps(caps))
+    }
+
+    /// Whether or not to close the top end of the cylinder.
+    pub fn top_cap(self, cap: bool) -> Self {
+        self.map_ty(|ty| ty.top_cap(cap))
+    }
+
+    /// Whether or not to close the bottom end of the cylinder.
+    pub fn bottom_cap(self, cap: bool) -> Self {
+        self.map_ty(|ty| ty.bottom_cap(cap))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Cylinder<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Cylinder {
+            position,
+            orientation,
+            radius,
+            height,
+            resolution,
+            top_cap,
+            bottom_cap,
+            color,
+        } = self;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = ctxt.transform * local_transform;
+        let radius = radius.map(f32::abs).unwrap_or(DEFAULT_RADIUS);
+        let frustum = Frustum {
+            bottom_radius: radius,
+            top_radius: radius,
+            height: height.unwrap_or(DEFAULT_HEIGHT),
+            resolution: resolution.unwrap_or(DEFAULT_RESOLUTION),
+            bottom_cap,
+            top_cap,
+        };
+        let color =
+            color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&draw::theme::Primitive::Cylinder));
+        frustum.render(transform, color, mesh);
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+// A circular frustum aligned with the y axis, shared between the cylinder and cone primitives.
+pub(crate) struct Frustum {
+    pub bottom_radius: f32,
+    pub top_radius: f32,
+    pub height: f32,
+    pub resolution: usize,
+    pub bottom_cap: bool,
+    pub top_cap: bool,
+}
+
+impl Frustum {
+    // Tessellate the frustum and push its triangles to the given mesh.
+    //
+    // Texture coordinates wrap around the side of the frustum with `x` following the angle around
+    // the y axis and `y` following the height. Caps are mapped as a circle inscribed within the
+    // texture.
+    pub fn render(&self, transform: Matrix4<f32>, color: LinSrgba, mesh: &mut draw::Mesh) {
+        let transform_point = |p: Point3<f32>| -> Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+        let segments = self.resolution.max(3);
+        let half_height = self.height * 0.5;
+        let unit_circle = (0..=segments).map(|i| {
+            let angle = i as f32 / segments as f32 * PI * 2.0;
+            let (sin, cos) = angle.sin_cos();
+            (cos, -sin)
+        });
+
+        // The side of the frustum, duplicating the first column of vertices at the seam.
+        let v_start = mesh.raw_vertex_count() as u32;
+        for (i, (x, z)) in unit_circle.clone().enumerate() {
+            let u = i as f32 / segments as f32;
+            let bottom = pt3(x * self.bottom_radius, -half_height, z * self.bottom_radius);
+            let top = pt3(x * self.top_radius, half_height, z * self.top_radius);
+            mesh.push_vertex(((transform_point(bottom), color), pt2(u, 0.0)).into());
+            mesh.push_vertex(((transform_point(top), color), pt2(u, 1.0)).into());
+        }
+        let side_indices = (0..segments as u32).flat_map(|i| {
+            let a = v_start + i * 2;
+            let (b, c, d) = (a + 1, a + 2, a + 3);
+            vec![a, c, d, a, d, b]
+        });
+        mesh.extend_indices(side_indices);
+
+        // Each cap as a triangle fan around its centre.
+        let caps = [
+            (self.bottom_cap, self.bottom_radius, -half_height),
+            (self.top_cap, self.top_radius, half_height),
+        ];
+        for &(cap, radius, y) in caps.iter() {
+            if !cap || radius <= 0.0 {
+                continue;
+            }
+            let v_start = mesh.raw_vertex_count() as u32;
+            let centre = transform_point(pt3(0.0, y, 0.0));
+            mesh.push_vertex(((centre, color), pt2(0.5, 0.5)).into());
+            for (x, z) in unit_circle.clone() {
+                let p = transform_point(pt3(x * radius, y, z * radius));
+                let tex_coords = pt2(x * 0.5 + 0.5, z * 0.5 + 0.5);
+                mesh.push_vertex(((p, color), tex_coords).into());
+            }
+            let cap_indices = (1..=segments as u32).flat_map(|i| vec![0, i, i + 1]);
+            mesh.extend_indices(cap_indices.map(|i| v_start + i));
+        }
+    }
+}
+
+impl<S> Default for Cylinder<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Cylinder {
+            position: Default::default(),
+            orientation: Default::default(),
+            radius: None,
+            height: None,
+            resolution: None,
+            top_cap: true,
+            bottom_cap: true,
+            color: None,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Cylinder<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Cylinder<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Cylinder<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Cylinder<S>> for Primitive<S> {
+    fn from(prim: Cylinder<S>) -> Self {
+        Primitive::Cylinder(prim)
+    }
+}
+
+impl<S> Into<Option<Cylinder<S>>> for Primitive<S> {
+    fn into(self) -> Option<Cylinder<S>> {
+        match self {
+            Primitive::Cylinder(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -4,18 +4,22 @@ use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPoly
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{dimension, orientation, position};
 use crate::draw::properties::{
-    spatial, ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition, SetStroke,
+    spatial, ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetDimensions, SetOrientation,
+    SetPosition, SetStroke,
 };
 use crate::draw::Drawing;
 use crate::geom::{self, Vector2};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// Properties related to drawing an **Ellipse**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Ellipse<S = geom::scalar::Default> {
     dimensions: spatial::dimension::Properties<S>,
     resolution: Option<usize>,
+    section: Option<(S, S)>,
+    pie: bool,
     polygon: PolygonInit<S>,
 }
 
@@ -50,68 +54,163 @@ where
         self.resolution = Some(resolution);
         self
     }
+
+    /// Only draw the section of the ellipse between the given angles in radians.
+    ///
+    /// Angles begin along the positive x axis and increase counter-clockwise. By default, the ends
+    /// of a filled section are joined by a straight chord and a stroked section is left open. Use
+    /// `pie` to join the ends via the centre instead.
+    pub fn section(mut self, start_radians: S, end_radians: S) -> Self {
+        self.section = Some((start_radians, end_radians));
+        self
+    }
+
+    /// Whether or not the ends of a `section` should be joined via the centre of the ellipse,
+    /// producing a pie slice rather than an arc.
+    pub fn pie(mut self, pie: bool) -> Self {
+        self.pie = pie;
+        self
+    }
 }
 
 // Trait implementations.
 
-impl draw::renderer::RenderPrimitive for Ellipse<f32> {
-    fn render_primitive(
-        self,
-        ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Ellipse {
-            dimensions,
-            polygon,
-            resolution,
-        } = self;
-
-        // First get the dimensions of the ellipse.
+impl Ellipse<f32> {
+    // The absolute width and height of the ellipse.
+    fn wh(&self) -> (f32, f32) {
+        let dimensions = &self.dimensions;
         let (maybe_x, maybe_y, maybe_z) = (dimensions.x, dimensions.y, dimensions.z);
         assert!(
             maybe_z.is_none(),
             "z dimension support for ellipse is unimplemented"
         );
-
         let w = maybe_x.map(f32::abs).unwrap_or(100.0);
         let h = maybe_y.map(f32::abs).unwrap_or(100.0);
-        match resolution {
+        (w, h)
+    }
+
+    // A path describing the ellipse's section, or `None` if no section was specified or it has no
+    // area.
+    fn section_path(&self, w: f32, h: f32) -> Option<lyon::path::Path> {
+        let (start, end) = self.section?;
+        let sweep = end - start;
+        if w <= 0.0 || h <= 0.0 || sweep == 0.0 {
+            return None;
+        }
+        let mut builder = lyon::path::Path::builder();
+        match self.resolution {
             None => {
-                // Determine the transform to apply to all points.
+                let centre = lyon::math::point(0.0, 0.0);
                 let radii = lyon::math::vector(w * 0.5, h * 0.5);
-                if radii.square_length() > 0.0 {
-                    let centre = lyon::math::point(0.0, 0.0);
-                    let mut builder = lyon::path::Path::builder();
-                    let sweep_angle = lyon::math::Angle::radians(std::f32::consts::PI * 2.0);
-                    let x_rotation = lyon::math::Angle::radians(0.0);
-                    let start = lyon::math::point(w * 0.5, 0.0);
-                    builder.move_to(start);
-                    builder.arc(centre, radii, sweep_angle, x_rotation);
-                    let path = builder.build();
-                    polygon::render_events_themed(
-                        polygon.opts,
-                        || (&path).into_iter(),
-                        ctxt,
-                        &draw::theme::Primitive::Ellipse,
-                        mesh,
-                    );
+                let sweep_angle = lyon::math::Angle::radians(sweep);
+                let x_rotation = lyon::math::Angle::radians(0.0);
+                let (sin, cos) = start.sin_cos();
+                builder.move_to(lyon::math::point(cos * radii.x, sin * radii.y));
+                builder.arc(centre, radii, sweep_angle, x_rotation);
+            }
+            Some(resolution) => {
+                let rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
+                let ellipse = geom::Ellipse::new(rect, resolution.max(1));
+                let mut points = ellipse.section(start, sweep).circumference();
+                builder.move_to(points.next()?.into());
+                for p in points {
+                    builder.line_to(p.into());
                 }
             }
+        }
+        if self.pie {
+            builder.line_to(lyon::math::point(0.0, 0.0));
+            builder.close();
+        }
+        Some(builder.build())
+    }
+}
+
+// A path describing an ellipse of the given size as a single arc, or `None` if the ellipse has no
+// area.
+fn arc_path(w: f32, h: f32) -> Option<lyon::path::Path> {
+    let radii = lyon::math::vector(w * 0.5, h * 0.5);
+    if radii.square_length() <= 0.0 {
+        return None;
+    }
+    let centre = lyon::math::point(0.0, 0.0);
+    let mut builder = lyon::path::Path::builder();
+    let sweep_angle = lyon::math::Angle::radians(std::f32::consts::PI * 2.0);
+    let x_rotation = lyon::math::Angle::radians(0.0);
+    let start = lyon::math::point(w * 0.5, 0.0);
+    builder.move_to(start);
+    builder.arc(centre, radii, sweep_angle, x_rotation);
+    Some(builder.build())
+}
+
+impl draw::renderer::RenderPrimitive for Ellipse<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let (w, h) = self.wh();
+        if self.section.is_some() {
+            return match self.section_path(w, h) {
+                None => draw::renderer::PrimitiveRender::default(),
+                Some(path) => polygon::render_events_themed(
+                    self.polygon.opts,
+                    || (&path).into_iter(),
+                    ctxt,
+                    &draw::theme::Primitive::Ellipse,
+                    mesh,
+                ),
+            };
+        }
+        match self.resolution {
+            None => match arc_path(w, h) {
+                None => draw::renderer::PrimitiveRender::default(),
+                Some(path) => polygon::render_events_themed(
+                    self.polygon.opts,
+                    || (&path).into_iter(),
+                    ctxt,
+                    &draw::theme::Primitive::Ellipse,
+                    mesh,
+                ),
+            },
             Some(resolution) => {
                 let rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
                 let ellipse = geom::Ellipse::new(rect, resolution);
                 let points = ellipse.circumference();
                 polygon::render_points_themed(
-                    polygon.opts,
+                    self.polygon.opts,
                     points,
                     ctxt,
                     &draw::theme::Primitive::Ellipse,
                     mesh,
-                );
+                )
             }
         }
+    }
+}
 
-        draw::renderer::PrimitiveRender::default()
+impl draw::plotter_renderer::PlotPrimitive for Ellipse<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let (w, h) = self.wh();
+        if self.section.is_some() {
+            let path = self.section_path(w, h)?;
+            return polygon::plot_events(&self.polygon.opts, &path, events);
+        }
+        match self.resolution {
+            None => {
+                let path = arc_path(w, h)?;
+                polygon::plot_events(&self.polygon.opts, &path, events)
+            }
+            Some(resolution) => {
+                let rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
+                let ellipse = geom::Ellipse::new(rect, resolution);
+                polygon::plot_points(&self.polygon.opts, ellipse.circumference(), events)
+            }
+        }
     }
 }
 
@@ -123,10 +222,14 @@ where
         let dimensions = Default::default();
         let polygon = Default::default();
         let resolution = Default::default();
+        let section = None;
+        let pie = false;
         Ellipse {
             dimensions,
             polygon,
             resolution,
+            section,
+            pie,
         }
     }
 }
@@ -155,6 +258,12 @@ impl<S> SetColor<ColorScalar> for Ellipse<S> {
     }
 }
 
+impl<S> SetDash for Ellipse<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
 impl<S> SetStroke for Ellipse<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.polygon)
@@ -207,4 +316,14 @@ where
     pub fn resolution(self, resolution: usize) -> Self {
         self.map_ty(|ty| ty.resolution(resolution))
     }
+
+    /// Only draw the section of the ellipse between the given angles in radians.
+    pub fn section(self, start_radians: S, end_radians: S) -> Self {
+        self.map_ty(|ty| ty.section(start_radians, end_radians))
+    }
+
+    /// Whether or not the ends of a `section` should be joined via the centre of the ellipse.
+    pub fn pie(self, pie: bool) -> Self {
+        self.map_ty(|ty| ty.pie(pie))
+    }
 }
@@ -0,0 +1,208 @@
+use crate::draw::primitive::{Primitive, Texture};
+use crate::draw::properties::spatial::{self, dimension, orientation, position};
+use crate::draw::properties::{SetDimensions, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::image;
+use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::wgpu;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Properties related to drawing an **Image**.
+///
+/// The image is uploaded to a texture the first time it is drawn. The texture is cached by the
+/// window's **draw::Renderer** and reused on subsequent frames, so that images may be drawn
+/// without creating and storing a texture within the model.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Image<S = geom::scalar::Default> {
+    source: ImageSource,
+    spatial: spatial::Properties<S>,
+    area: geom::Rect,
+}
+
+/// The drawing context for an **Image**.
+pub type DrawingImage<'a, S = geom::scalar::Default> = Drawing<'a, Image<S>, S>;
+
+/// The source from which an **Image** is loaded.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ImageSource {
+    /// An image file, loaded and cached by its path.
+    Path(PathBuf),
+    /// An image in memory, cached for as long as the `Arc` is kept alive elsewhere.
+    ///
+    /// Images given by value are wrapped in a new `Arc` and so are uploaded every time they are
+    /// drawn. Keep an `Arc` within the model to upload the image only once.
+    Image(#[serde(with = "crate::draw::serialize::resource")] Arc<image::DynamicImage>),
+}
+
+impl<S> Image<S> {
+    /// The source from which the image is loaded.
+    pub fn source(&self) -> &ImageSource {
+        &self.source
+    }
+
+    /// Specify the area of the image to draw.
+    ///
+    /// The bounds of the rectangle should represent the desired area as texture coordinates of the
+    /// image, ranging from (0.0, 0.0) in the bottom left to (1.0, 1.0) in the top right.
+    ///
+    /// By default, the area represents the full extent of the image.
+    pub fn area(mut self, rect: geom::Rect) -> Self {
+        self.area = rect;
+        self
+    }
+}
+
+impl<S> Image<S>
+where
+    S: BaseFloat,
+{
+    pub(crate) fn new(source: ImageSource) -> Self {
+        let spatial = Default::default();
+        let area = geom::Rect::from_x_y_w_h(0.5, 0.5, 1.0, 1.0);
+        Image {
+            source,
+            spatial,
+            area,
+        }
+    }
+
+    // Produce the texture primitive that draws the image once it has been uploaded to the given
+    // texture. Unspecified dimensions take the size of the texture.
+    pub(crate) fn to_texture(&self, texture_view: &wgpu::TextureView) -> Texture<S> {
+        let [w, h] = texture_view.size();
+        let mut spatial = self.spatial;
+        if spatial.dimensions.x.is_none() {
+            spatial.dimensions.x = Some(S::from(w).unwrap());
+        }
+        if spatial.dimensions.y.is_none() {
+            spatial.dimensions.y = Some(S::from(h).unwrap());
+        }
+        Texture::from_parts(texture_view.clone(), spatial, self.area)
+    }
+}
+
+impl<'a, S> DrawingImage<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the area of the image to draw.
+    ///
+    /// The bounds of the rectangle should represent the desired area as texture coordinates of the
+    /// image, ranging from (0.0, 0.0) in the bottom left to (1.0, 1.0) in the top right.
+    ///
+    /// By default, the area represents the full extent of the image.
+    pub fn area(self, rect: geom::Rect) -> Self {
+        self.map_ty(|ty| ty.area(rect))
+    }
+}
+
+// Images are converted to textures by the renderer before rendering. An image that could not be
+// loaded draws nothing.
+impl draw::renderer::RenderPrimitive for Image<f32> {
+    fn render_primitive(
+        self,
+        _ctxt: draw::renderer::RenderContext,
+        _mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> SetOrientation<S> for Image<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetPosition<S> for Image<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetDimensions<S> for Image<S> {
+    fn properties(&mut self) -> &mut dimension::Properties<S> {
+        SetDimensions::properties(&mut self.spatial)
+    }
+}
+
+impl fmt::Debug for ImageSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ImageSource::Path(ref path) => f.debug_tuple("Path").field(path).finish(),
+            ImageSource::Image(ref image) => {
+                let (w, h) = image::GenericImageView::dimensions(&**image);
+                write!(f, "Image({:p}, {}x{})", &**image, w, h)
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a Path> for ImageSource {
+    fn from(path: &'a Path) -> Self {
+        ImageSource::Path(path.to_path_buf())
+    }
+}
+
+impl<'a> From<&'a PathBuf> for ImageSource {
+    fn from(path: &'a PathBuf) -> Self {
+        ImageSource::Path(path.clone())
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(path: PathBuf) -> Self {
+        ImageSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a str> for ImageSource {
+    fn from(path: &'a str) -> Self {
+        ImageSource::Path(path.into())
+    }
+}
+
+impl From<String> for ImageSource {
+    fn from(path: String) -> Self {
+        ImageSource::Path(path.into())
+    }
+}
+
+impl From<image::DynamicImage> for ImageSource {
+    fn from(image: image::DynamicImage) -> Self {
+        ImageSource::Image(Arc::new(image))
+    }
+}
+
+impl From<Arc<image::DynamicImage>> for ImageSource {
+    fn from(image: Arc<image::DynamicImage>) -> Self {
+        ImageSource::Image(image)
+    }
+}
+
+impl<'a> From<&'a Arc<image::DynamicImage>> for ImageSource {
+    fn from(image: &'a Arc<image::DynamicImage>) -> Self {
+        ImageSource::Image(image.clone())
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Image<S>> for Primitive<S> {
+    fn from(prim: Image<S>) -> Self {
+        Primitive::Image(prim)
+    }
+}
+
+impl<S> Into<Option<Image<S>>> for Primitive<S> {
+    fn into(self) -> Option<Image<S>> {
+        match self {
+            Primitive::Image(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
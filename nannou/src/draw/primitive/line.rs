@@ -2,17 +2,20 @@ use crate::color::LinSrgba;
 use crate::draw::primitive::path;
 use crate::draw::primitive::{PathStroke, Primitive};
 use crate::draw::properties::spatial::{orientation, position};
-use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition, SetStroke};
+use crate::draw::properties::{
+    ColorScalar, Dash, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
 use crate::draw::{self, Drawing};
 use crate::geom::{self, pt2, Point2};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// A path containing only two points - a start and end.
 ///
 /// The usage of this type is almost identical to `PathStroke` but provides `start`, `end` and
 /// `points(a, b)` methods.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Line<S = geom::scalar::Default> {
     pub path: PathStroke<S>,
     pub start: Option<Point2<S>>,
@@ -91,6 +94,12 @@ where
     }
 }
 
+impl<S> SetDash for Line<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.path)
+    }
+}
+
 impl<S> SetStroke for Line<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.path)
@@ -146,6 +155,8 @@ impl draw::renderer::RenderPrimitive for Line<f32> {
         let points = [start, end];
         let points = points.iter().cloned().map(Into::into);
         let events = lyon::path::iterator::FromPolyline::new(close, points);
+        let options = path::Options::Stroke(path.opts);
+        let events = path::dash_stroke_events(events, path.dash.as_ref(), &options);
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
@@ -156,7 +167,7 @@ impl draw::renderer::RenderPrimitive for Line<f32> {
             events,
             path.color,
             transform,
-            path::Options::Stroke(path.opts),
+            options,
             &ctxt.theme,
             &draw::theme::Primitive::Line,
             &mut ctxt.fill_tessellator,
@@ -168,6 +179,30 @@ impl draw::renderer::RenderPrimitive for Line<f32> {
     }
 }
 
+impl draw::plotter_renderer::PlotPrimitive for Line<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let start = self.start.unwrap_or(pt2(0.0, 0.0));
+        let end = self.end.unwrap_or(pt2(0.0, 0.0));
+        if start == end {
+            return None;
+        }
+        let points = [start, end];
+        let points = points.iter().cloned().map(Into::into);
+        let line_events = lyon::path::iterator::FromPolyline::new(false, points);
+        let options = path::Options::Stroke(self.path.opts);
+        events.extend(path::dash_stroke_events(
+            line_events,
+            self.path.dash.as_ref(),
+            &options,
+        ));
+        Some(self.path.position.transform() * self.path.orientation.transform())
+    }
+}
+
 impl<S> Default for Line<S>
 where
     S: Zero,
@@ -3,18 +3,20 @@ use crate::draw::mesh::vertex::{self, Point, TexCoords, Vertex};
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{orientation, position};
 use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::recording;
 use crate::draw::{self, Drawing};
 use crate::geom;
-use crate::math::BaseFloat;
+use crate::math::{BaseFloat, Matrix4, SquareMatrix};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 use std::ops;
 
 /// The mesh type prior to being initialised with vertices or indices.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Vertexless;
 
 /// Properties related to drawing an arbitrary mesh of colours, geometry and texture.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Mesh<S = geom::scalar::Default> {
     position: position::Properties<S>,
     orientation: orientation::Properties<S>,
@@ -22,10 +24,21 @@ pub struct Mesh<S = geom::scalar::Default> {
     index_range: ops::Range<usize>,
     vertex_mode: draw::renderer::VertexMode,
     fill_color: Option<FillColor>,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
     texture_view: Option<wgpu::TextureView>,
+    instances: Vec<Instance<S>>,
 }
 
-#[derive(Clone, Debug, Default)]
+/// A single instance of a **Mesh**, drawn with its own transform and optionally its own color.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Instance<S = geom::scalar::Default> {
+    /// The transform applied to the mesh's vertices prior to its position and orientation.
+    pub transform: Matrix4<S>,
+    /// If `Some`, overrides the color of every vertex of this instance.
+    pub color: Option<LinSrgba>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 struct FillColor(Option<LinSrgba>);
 
 // A simple iterator for flattening a fixed-size array of indices.
@@ -343,8 +356,49 @@ where
             vertex_mode,
             fill_color,
             texture_view,
+            instances: Vec::new(),
         }
     }
+
+    /// Draw the mesh once for each of the given transforms.
+    ///
+    /// The vertices and indices of the mesh are only described and uploaded once. All instances
+    /// are drawn via a single instanced draw call, within which each instance's transform is
+    /// applied by the vertex shader.
+    pub fn instances<I>(mut self, transforms: I) -> Self
+    where
+        I: IntoIterator<Item = Matrix4<S>>,
+    {
+        let instances = transforms.into_iter().map(|transform| Instance {
+            transform,
+            color: None,
+        });
+        self.instances.extend(instances);
+        self
+    }
+
+    /// Draw the mesh once for each of the given transforms, coloring each instance uniformly.
+    ///
+    /// Each instance must be represented as a tuple containing the transform and the color in
+    /// that order, e.g. `(transform, color)`.
+    pub fn instances_colored<I, C>(mut self, instances: I) -> Self
+    where
+        I: IntoIterator<Item = (Matrix4<S>, C)>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let instances = instances.into_iter().map(|(transform, color)| Instance {
+            transform,
+            color: Some(color.into_lin_srgba()),
+        });
+        self.instances.extend(instances);
+        self
+    }
+
+    // Rebase the mesh's ranges into the intermediary mesh.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        recording::offset_range(&mut self.vertex_range, offsets.mesh_vertices);
+        recording::offset_range(&mut self.index_range, offsets.mesh_indices);
+    }
 }
 
 impl<'a, S> Drawing<'a, Vertexless, S>
@@ -503,6 +557,28 @@ where
     }
 }
 
+impl<'a, S> DrawingMesh<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Draw the mesh once for each of the given transforms.
+    pub fn instances<I>(self, transforms: I) -> Self
+    where
+        I: IntoIterator<Item = Matrix4<S>>,
+    {
+        self.map_ty(|ty| ty.instances(transforms))
+    }
+
+    /// Draw the mesh once for each of the given transforms, coloring each instance uniformly.
+    pub fn instances_colored<I, C>(self, instances: I) -> Self
+    where
+        I: IntoIterator<Item = (Matrix4<S>, C)>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.instances_colored(instances))
+    }
+}
+
 impl draw::renderer::RenderPrimitive for Mesh<f32> {
     fn render_primitive(
         self,
@@ -517,6 +593,7 @@ impl draw::renderer::RenderPrimitive for Mesh<f32> {
             vertex_mode,
             fill_color,
             texture_view,
+            instances,
         } = self;
 
         // Determine the transform to apply to vertices.
@@ -531,6 +608,39 @@ impl draw::renderer::RenderPrimitive for Mesh<f32> {
             .map(|i| ctxt.intermediary_mesh.indices()[i])
             .map(|i| new_mesh_vertex_start + i - old_mesh_vertex_start);
 
+        // The vertices are submitted once and drawn for each instance by the GPU, so they are
+        // left untransformed and the mesh's transform is applied along with each instance's.
+        if !instances.is_empty() {
+            let theme_prim = draw::theme::Primitive::Mesh;
+            let fill = fill_color.map(|fill| {
+                fill.0
+                    .unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&theme_prim))
+            });
+            let inner = ctxt.intermediary_mesh;
+            let range = vertex_range;
+            mesh.extend_vertices_from_slices(
+                &inner.points()[range.clone()],
+                &inner.colors()[range.clone()],
+                &inner.tex_coords()[range],
+            );
+            mesh.extend_indices(indices);
+            let instances = instances
+                .into_iter()
+                .map(|instance| Instance {
+                    transform: transform * instance.transform,
+                    color: instance.color.or(fill),
+                })
+                .collect();
+            return draw::renderer::PrimitiveRender {
+                texture_view,
+                texture_layer: 0,
+                vertex_mode,
+                topology: None,
+                fragment_shader: None,
+                instances,
+            };
+        }
+
         // A small function for transforming a point via the transform matrix.
         let transform_point = |p: geom::Point3<f32>| -> geom::Point3<f32> {
             let p = cgmath::Point3::new(p.x, p.y, p.z);
@@ -540,6 +650,18 @@ impl draw::renderer::RenderPrimitive for Mesh<f32> {
 
         // Color the vertices based on whether or not we should fill, then extend the mesh!
         match fill_color {
+            // If there is no transform to apply, the vertex channels can be copied directly from
+            // the intermediary mesh's ranges.
+            None if transform == Matrix4::identity() => {
+                let inner = ctxt.intermediary_mesh;
+                let range = vertex_range;
+                mesh.extend_vertices_from_slices(
+                    &inner.points()[range.clone()],
+                    &inner.colors()[range.clone()],
+                    &inner.tex_coords()[range],
+                );
+                mesh.extend_indices(indices);
+            }
             Some(fill) => {
                 let theme_prim = draw::theme::Primitive::Mesh;
                 let color = fill
@@ -565,7 +687,11 @@ impl draw::renderer::RenderPrimitive for Mesh<f32> {
 
         draw::renderer::PrimitiveRender {
             texture_view,
+            texture_layer: 0,
             vertex_mode,
+            topology: None,
+            fragment_shader: None,
+            instances: vec![],
         }
     }
 }
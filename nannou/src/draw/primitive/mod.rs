@@ -1,27 +1,58 @@
+pub mod arc;
 pub mod arrow;
+pub mod bezier;
+pub mod cone;
+pub mod cuboid;
+pub mod cylinder;
 pub mod ellipse;
+pub mod image;
 pub mod line;
 pub mod mesh;
 pub mod path;
+pub mod point_cloud;
 pub mod polygon;
+pub mod polyline3d;
 pub mod quad;
 pub mod rect;
+pub mod regular_polygon;
+pub mod shaded;
+pub mod sphere;
+pub mod star;
+#[cfg(feature = "svg")]
+pub mod svg;
 pub mod text;
 pub mod texture;
+pub mod torus;
 pub mod tri;
 
 use crate::geom;
+use crate::serde_derive::{Deserialize, Serialize};
 
+pub use self::arc::Arc;
 pub use self::arrow::Arrow;
+pub use self::bezier::Bezier;
+pub use self::cone::Cone;
+pub use self::cuboid::Cuboid;
+pub use self::cylinder::Cylinder;
 pub use self::ellipse::Ellipse;
+pub use self::image::{Image, ImageSource};
 pub use self::line::Line;
 pub use self::mesh::Mesh;
 pub use self::path::{Path, PathFill, PathInit, PathStroke};
+pub use self::point_cloud::PointCloud;
 pub use self::polygon::{Polygon, PolygonInit};
+pub use self::polyline3d::Polyline3d;
 pub use self::quad::Quad;
 pub use self::rect::Rect;
+pub use self::regular_polygon::RegularPolygon;
+pub use self::shaded::{FragmentShader, Shaded};
+pub use self::sphere::Sphere;
+pub use self::star::Star;
+#[cfg(feature = "svg")]
+pub use self::svg::Svg;
 pub use self::text::Text;
 pub use self::texture::Texture;
+pub use self::torus::Torus;
 pub use self::tri::Tri;
 
 /// A wrapper around all primitive sets of properties so that they may be stored within the
@@ -29,10 +60,16 @@ pub use self::tri::Tri;
 ///
 /// This also allows us to flush all pending drawings to the mesh if `Draw::to_frame` is called
 /// before their respective **Drawing** types are dropped.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Primitive<S = geom::scalar::Default> {
+    Arc(Arc<S>),
     Arrow(Arrow<S>),
+    Bezier(Bezier<S>),
+    Cone(Cone<S>),
+    Cuboid(Cuboid<S>),
+    Cylinder(Cylinder<S>),
     Ellipse(Ellipse<S>),
+    Image(Image<S>),
     Line(Line<S>),
     MeshVertexless(mesh::Vertexless),
     Mesh(Mesh<S>),
@@ -40,11 +77,21 @@ pub enum Primitive<S = geom::scalar::Default> {
     PathFill(PathFill<S>),
     PathStroke(PathStroke<S>),
     Path(Path<S>),
+    PointCloud(PointCloud<S>),
     PolygonInit(PolygonInit<S>),
     Polygon(Polygon<S>),
+    Polyline3d(Polyline3d<S>),
     Quad(Quad<S>),
     Rect(Rect<S>),
+    RegularPolygon(RegularPolygon<S>),
+    Retained(crate::draw::Retained),
+    Shaded(Shaded<S>),
+    Sphere(Sphere<S>),
+    Star(Star<S>),
+    #[cfg(feature = "svg")]
+    Svg(Svg<S>),
     Text(Text<S>),
     Texture(Texture<S>),
+    Torus(Torus<S>),
     Tri(Tri<S>),
 }
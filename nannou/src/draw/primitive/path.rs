@@ -1,17 +1,21 @@
 use crate::color::conv::IntoLinSrgba;
-use crate::color::LinSrgba;
+use crate::color::{ColorRamp, LinSrgba, Mix};
 use crate::draw::mesh::vertex::{Color, TexCoords};
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{orientation, position};
 use crate::draw::properties::{
-    ColorScalar, SetColor, SetFill, SetOrientation, SetPosition, SetStroke,
+    ColorScalar, Dash, Gradient, SetColor, SetDash, SetFill, SetOrientation, SetPosition, SetStroke,
 };
+use crate::draw::recording;
 use crate::draw::{self, Drawing, DrawingContext};
 use crate::geom::{self, Point2};
-use crate::math::{BaseFloat, Zero};
+use crate::math::{BaseFloat, SquareMatrix, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 use lyon::path::PathEvent;
 use lyon::tessellation::{FillOptions, FillTessellator, StrokeOptions, StrokeTessellator};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// A set of path tessellation options (FillOptions or StrokeOptions).
 pub trait TessellationOptions {
@@ -21,7 +25,7 @@ pub trait TessellationOptions {
     fn into_options(self) -> Options;
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) enum PathEventSource {
     /// Fetch events from `path_events_buffer`.
     Buffered(std::ops::Range<usize>),
@@ -35,6 +39,61 @@ pub(crate) enum PathEventSource {
         range: std::ops::Range<usize>,
         close: bool,
     },
+    /// Generate a stroke of varying weight from the `path_points_weighted_buffer`.
+    WeightedPoints {
+        range: std::ops::Range<usize>,
+        close: bool,
+    },
+}
+
+impl PathEventSource {
+    // Rebase the range into the relevant intermediary buffer.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        match *self {
+            PathEventSource::Buffered(ref mut range) => {
+                recording::offset_range(range, offsets.path_events)
+            }
+            PathEventSource::ColoredPoints { ref mut range, .. } => {
+                recording::offset_range(range, offsets.path_points_colored)
+            }
+            PathEventSource::TexturedPoints { ref mut range, .. } => {
+                recording::offset_range(range, offsets.path_points_textured)
+            }
+            PathEventSource::WeightedPoints { ref mut range, .. } => {
+                recording::offset_range(range, offsets.path_points_weighted)
+            }
+        }
+    }
+
+    // Collect the path events described by the source, ignoring any per-point colors, texture
+    // coordinates or weights.
+    pub(crate) fn to_events(
+        &self,
+        path_event_buffer: &[PathEvent],
+        path_points_colored_buffer: &[(Point2, Color)],
+        path_points_textured_buffer: &[(Point2, TexCoords)],
+        path_points_weighted_buffer: &[(Point2, f32)],
+    ) -> Vec<PathEvent> {
+        let to_point = |p: Point2| lyon::math::point(p.x, p.y);
+        match *self {
+            PathEventSource::Buffered(ref range) => path_event_buffer[range.clone()].to_vec(),
+            PathEventSource::ColoredPoints { ref range, close } => {
+                let points = path_points_colored_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                lyon::path::iterator::FromPolyline::new(close, points).collect()
+            }
+            PathEventSource::TexturedPoints { ref range, close } => {
+                let points = path_points_textured_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                lyon::path::iterator::FromPolyline::new(close, points).collect()
+            }
+            PathEventSource::WeightedPoints { ref range, close } => {
+                let points = path_points_weighted_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                lyon::path::iterator::FromPolyline::new(close, points).collect()
+            }
+        }
+    }
 }
 
 pub(crate) enum PathEventSourceIter<'a> {
@@ -51,16 +110,20 @@ pub(crate) enum PathEventSourceIter<'a> {
 
 /// The beginning of the path building process, prior to choosing the tessellation mode (fill or
 /// stroke).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PathInit<S = geom::scalar::Default>(std::marker::PhantomData<S>);
 
 /// A path drawing context ready to specify tessellation options.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PathOptions<T, S = geom::scalar::Default> {
     pub(crate) opts: T,
     pub(crate) color: Option<LinSrgba>,
     pub(crate) position: position::Properties<S>,
     pub(crate) orientation: orientation::Properties<S>,
+    pub(crate) dash: Option<Dash>,
+    pub(crate) stroke_gradient: Option<ColorRamp>,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
+    pub(crate) texture_view: Option<wgpu::TextureView>,
 }
 
 /// Mutable access to stroke and fill tessellators.
@@ -76,14 +139,17 @@ pub type PathFill<S = geom::scalar::Default> = PathOptions<FillOptions, S>;
 pub type PathStroke<S = geom::scalar::Default> = PathOptions<StrokeOptions, S>;
 
 /// Properties related to drawing a **Path**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Path<S = geom::scalar::Default> {
     color: Option<LinSrgba>,
     position: position::Properties<S>,
     orientation: orientation::Properties<S>,
     path_event_src: PathEventSource,
     options: Options,
+    dash: Option<Dash>,
+    stroke_gradient: Option<ColorRamp>,
     vertex_mode: draw::renderer::VertexMode,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
     texture_view: Option<wgpu::TextureView>,
 }
 
@@ -103,7 +169,7 @@ pub type DrawingPathFill<'a, S = geom::scalar::Default> = Drawing<'a, PathFill<S
 pub type DrawingPath<'a, S = geom::scalar::Default> = Drawing<'a, Path<S>, S>;
 
 /// Dynamically distinguish between fill and stroke tessellation options.
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Options {
     Fill(FillOptions),
     Stroke(StrokeOptions),
@@ -142,11 +208,17 @@ where
         let orientation = Default::default();
         let position = Default::default();
         let color = Default::default();
+        let dash = None;
+        let stroke_gradient = None;
+        let texture_view = None;
         PathOptions {
             opts,
             orientation,
             position,
             color,
+            dash,
+            stroke_gradient,
+            texture_view,
         }
     }
 }
@@ -167,6 +239,15 @@ impl<S> PathFill<S> {
     pub fn rule(self, rule: lyon::tessellation::FillRule) -> Self {
         self.fill_rule(rule)
     }
+
+    /// Fill the path with the given texture.
+    ///
+    /// Texture coordinates are produced by mapping the bounding rectangle of the path to the full
+    /// extent of the texture. To specify texture coordinates explicitly, see `points_textured`.
+    pub fn texture(mut self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.texture_view = Some(view.to_texture_view());
+        self
+    }
 }
 
 impl<S> PathStroke<S> {
@@ -179,6 +260,79 @@ impl<S> PathStroke<S> {
     pub fn tolerance(self, tolerance: f32) -> Self {
         self.stroke_tolerance(tolerance)
     }
+
+    /// Color the stroke with a gradient along the length of the path.
+    ///
+    /// Each stop is a `(offset, color)` pair, where an offset of `0.0` is the start of the path
+    /// and `1.0` is its end. Offsets are measured by arc length across all sub-paths, including
+    /// the closing segment of closed paths. The gradient takes precedence over any color,
+    /// per-point colors or texture.
+    pub fn stroke_gradient<I, C>(mut self, stops: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, C)>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.stroke_gradient = Some(ColorRamp::new(stops));
+        self
+    }
+
+    /// Submit a polyline of points, each with its own stroke weight.
+    ///
+    /// The weight is interpolated along each segment, allowing for strokes that taper and swell
+    /// along their length. Joins are mitered up to the stroke's miter limit and the ends of the
+    /// stroke are always squared off at the first and last points.
+    pub fn weighted_points<I, P>(self, ctxt: DrawingContext<S>, points: I) -> Path<S>
+    where
+        S: BaseFloat,
+        I: IntoIterator<Item = (P, f32)>,
+        P: Into<Point2<S>>,
+    {
+        self.weighted_points_inner(ctxt, false, points)
+    }
+
+    /// Submit a polyline of points, each with its own stroke weight.
+    ///
+    /// The path will automatically close from the end point to the start point.
+    pub fn weighted_points_closed<I, P>(self, ctxt: DrawingContext<S>, points: I) -> Path<S>
+    where
+        S: BaseFloat,
+        I: IntoIterator<Item = (P, f32)>,
+        P: Into<Point2<S>>,
+    {
+        self.weighted_points_inner(ctxt, true, points)
+    }
+
+    // Consumes an iterator of weighted points and buffers them for rendering.
+    fn weighted_points_inner<I, P>(self, ctxt: DrawingContext<S>, close: bool, points: I) -> Path<S>
+    where
+        S: BaseFloat,
+        I: IntoIterator<Item = (P, f32)>,
+        P: Into<Point2<S>>,
+    {
+        let DrawingContext {
+            path_points_weighted_buffer,
+            ..
+        } = ctxt;
+        let start = path_points_weighted_buffer.len();
+        let points = points.into_iter().map(|(p, w)| (p.into(), w));
+        path_points_weighted_buffer.extend(points);
+        let end = path_points_weighted_buffer.len();
+        let path_event_src = PathEventSource::WeightedPoints {
+            range: start..end,
+            close,
+        };
+        Path::new(
+            self.position,
+            self.orientation,
+            self.color,
+            path_event_src,
+            self.opts.into_options(),
+            self.dash,
+            self.stroke_gradient,
+            draw::renderer::VertexMode::Color,
+            self.texture_view,
+        )
+    }
 }
 
 impl<T, S> PathOptions<T, S>
@@ -203,8 +357,10 @@ where
             self.color,
             PathEventSource::Buffered(start..end),
             self.opts.into_options(),
+            self.dash,
+            self.stroke_gradient,
             draw::renderer::VertexMode::Color,
-            None,
+            self.texture_view,
         )
     }
 
@@ -332,8 +488,10 @@ where
             self.color,
             path_event_src,
             self.opts.into_options(),
+            self.dash,
+            self.stroke_gradient,
             draw::renderer::VertexMode::Color,
-            None,
+            self.texture_view,
         )
     }
 
@@ -369,12 +527,286 @@ where
             self.color,
             path_event_src,
             self.opts.into_options(),
+            self.dash,
+            self.stroke_gradient,
             draw::renderer::VertexMode::Texture,
             Some(texture_view),
         )
     }
 }
 
+/// An iterator yielding the events of a path, split into dashes if necessary.
+pub(crate) enum DashedEvents<I> {
+    Solid(I),
+    Dashed(std::vec::IntoIter<PathEvent>),
+}
+
+impl<I> Iterator for DashedEvents<I>
+where
+    I: Iterator<Item = PathEvent>,
+{
+    type Item = PathEvent;
+    fn next(&mut self) -> Option<Self::Item> {
+        match *self {
+            DashedEvents::Solid(ref mut events) => events.next(),
+            DashedEvents::Dashed(ref mut events) => events.next(),
+        }
+    }
+}
+
+// Tracks the position within a dash pattern while walking along a path.
+struct Dasher<'a> {
+    pattern: &'a [f32],
+    offset: f32,
+    index: usize,
+    remaining: f32,
+    // The start of the dash that is currently being emitted, if any.
+    dash_start: Option<lyon::math::Point>,
+}
+
+impl<'a> Dasher<'a> {
+    fn new(pattern: &'a [f32], offset: f32) -> Self {
+        let mut dasher = Dasher {
+            pattern,
+            offset,
+            index: 0,
+            remaining: 0.0,
+            dash_start: None,
+        };
+        dasher.reset();
+        dasher
+    }
+
+    // Return to the start of the pattern (plus the offset) for a new sub-path.
+    fn reset(&mut self) {
+        self.index = 0;
+        self.remaining = self.pattern[0];
+        self.dash_start = None;
+        let mut offset = self.offset;
+        while offset > 0.0 {
+            if offset >= self.remaining {
+                offset -= self.remaining;
+                self.advance();
+            } else {
+                self.remaining -= offset;
+                offset = 0.0;
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        self.index = (self.index + 1) % self.pattern.len();
+        self.remaining = self.pattern[self.index];
+    }
+
+    fn is_dash(&self) -> bool {
+        self.index % 2 == 0
+    }
+
+    // Walk along the line segment, emitting the parts that fall within dashes.
+    fn segment(
+        &mut self,
+        from: lyon::math::Point,
+        to: lyon::math::Point,
+        out: &mut Vec<PathEvent>,
+    ) {
+        let mut from = from;
+        let mut len = (to - from).length();
+        while len > 0.0 {
+            let step = self.remaining.min(len);
+            let p = from + (to - from) * (step / len);
+            if self.is_dash() && step > 0.0 {
+                if self.dash_start.is_none() {
+                    out.push(PathEvent::Begin { at: from });
+                    self.dash_start = Some(from);
+                }
+                out.push(PathEvent::Line { from, to: p });
+            }
+            self.remaining -= step;
+            len -= step;
+            from = p;
+            if self.remaining <= 0.0 {
+                self.end_dash(from, out);
+                self.advance();
+            }
+        }
+    }
+
+    // End the current dash at the given point, if there is one.
+    fn end_dash(&mut self, last: lyon::math::Point, out: &mut Vec<PathEvent>) {
+        if let Some(first) = self.dash_start.take() {
+            let close = false;
+            out.push(PathEvent::End { last, first, close });
+        }
+    }
+}
+
+/// Split the given path events into a sub-path for each dash of the given dash pattern.
+///
+/// Curves are flattened using the given tolerance. If the pattern is empty, contains negative
+/// lengths or sums to zero, the events are returned unchanged.
+pub fn dash_events<I>(events: I, dash: &Dash, tolerance: f32) -> Vec<PathEvent>
+where
+    I: IntoIterator<Item = PathEvent>,
+{
+    use lyon::path::iterator::PathIterator;
+
+    let (pattern, offset) = match dash_pattern(dash) {
+        None => return events.into_iter().collect(),
+        Some(pattern) => pattern,
+    };
+    let mut dasher = Dasher::new(&pattern, offset);
+    let mut dashed = vec![];
+    let mut last = None;
+    for event in events.into_iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { .. } => dasher.reset(),
+            PathEvent::Line { from, to } => dasher.segment(from, to, &mut dashed),
+            PathEvent::End {
+                last: end,
+                first,
+                close,
+            } => {
+                if close {
+                    dasher.segment(end, first, &mut dashed);
+                }
+                let end = if close { first } else { end };
+                dasher.end_dash(end, &mut dashed);
+            }
+            _ => (),
+        }
+        last = Some(event);
+    }
+    // Finish a dash left open by a path that was not explicitly ended.
+    if let Some(PathEvent::Line { to, .. }) = last {
+        dasher.end_dash(to, &mut dashed);
+    }
+    dashed
+}
+
+/// Split the given polyline into a polyline for each dash of the given dash pattern.
+///
+/// Each point carries an attribute, e.g. a color, that is interpolated via `lerp` for the points
+/// at either end of each dash. Each polyline is paired with whether or not it is closed. If the
+/// pattern is empty, contains negative lengths or sums to zero, the polyline is returned whole.
+pub(crate) fn dash_polyline<T, F>(
+    points: &[(lyon::math::Point, T)],
+    close: bool,
+    dash: &Dash,
+    lerp: F,
+) -> Vec<(Vec<(lyon::math::Point, T)>, bool)>
+where
+    T: Clone,
+    F: Fn(&T, &T, f32) -> T,
+{
+    let (pattern, offset) = match dash_pattern(dash) {
+        None => return vec![(points.to_vec(), close)],
+        Some(pattern) => pattern,
+    };
+    let mut dasher = Dasher::new(&pattern, offset);
+    let mut dashes: Vec<(Vec<(lyon::math::Point, T)>, bool)> = vec![];
+    let mut events = vec![];
+    let n = points.len();
+    let segments = match n {
+        0 | 1 => 0,
+        _ if close => n,
+        _ => n - 1,
+    };
+    for i in 0..segments {
+        let (a, ref attr_a) = points[i];
+        let (b, ref attr_b) = points[(i + 1) % n];
+        let len = (b - a).length();
+        let attr_at = |p: lyon::math::Point| {
+            let t = if len > 0.0 {
+                (p - a).length() / len
+            } else {
+                0.0
+            };
+            lerp(attr_a, attr_b, t)
+        };
+        dasher.segment(a, b, &mut events);
+        for event in events.drain(..) {
+            match event {
+                PathEvent::Begin { at } => dashes.push((vec![(at, attr_at(at))], false)),
+                PathEvent::Line { to, .. } => {
+                    if let Some((dash, _)) = dashes.last_mut() {
+                        if dash.last().map(|&(p, _)| p) != Some(to) {
+                            dash.push((to, attr_at(to)));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    dashes
+}
+
+// The given dash's pattern with an even number of lengths along with its offset into the pattern,
+// or `None` if the pattern has no effect.
+fn dash_pattern(dash: &Dash) -> Option<(Vec<f32>, f32)> {
+    let mut pattern = dash.pattern.clone();
+    let total: f32 = pattern.iter().sum();
+    if pattern.is_empty() || total <= 0.0 || pattern.iter().any(|&len| len < 0.0) {
+        return None;
+    }
+    // Odd patterns are repeated so that dashes and gaps alternate, matching SVG.
+    if pattern.len() % 2 == 1 {
+        pattern.extend(dash.pattern.iter().cloned());
+    }
+    let total: f32 = pattern.iter().sum();
+    let offset = dash.offset.rem_euclid(total);
+    Some((pattern, offset))
+}
+
+// Dash the given polyline if a dash is specified.
+fn dash_points<T, F>(
+    points: Vec<(Point2, T)>,
+    close: bool,
+    dash: Option<&Dash>,
+    lerp: F,
+) -> Vec<(Vec<(Point2, T)>, bool)>
+where
+    T: Clone,
+    F: Fn(&T, &T, f32) -> T,
+{
+    let dash = match dash {
+        None => return vec![(points, close)],
+        Some(dash) => dash,
+    };
+    let points: Vec<_> = points
+        .into_iter()
+        .map(|(p, attr)| (lyon::math::point(p.x, p.y), attr))
+        .collect();
+    dash_polyline(&points, close, dash, lerp)
+        .into_iter()
+        .map(|(points, close)| {
+            let points = points
+                .into_iter()
+                .map(|(p, attr)| (geom::pt2(p.x, p.y), attr))
+                .collect();
+            (points, close)
+        })
+        .collect()
+}
+
+// Dash the given events if a dash is specified for a stroke.
+pub(crate) fn dash_stroke_events<I>(
+    events: I,
+    dash: Option<&Dash>,
+    options: &Options,
+) -> DashedEvents<I::IntoIter>
+where
+    I: IntoIterator<Item = PathEvent>,
+{
+    match (dash, options) {
+        (Some(dash), Options::Stroke(opts)) => {
+            DashedEvents::Dashed(dash_events(events, dash, opts.tolerance).into_iter())
+        }
+        _ => DashedEvents::Solid(events.into_iter()),
+    }
+}
+
 pub(crate) fn render_path_events<I>(
     events: I,
     color: Option<LinSrgba>,
@@ -405,6 +837,414 @@ pub(crate) fn render_path_events<I>(
     }
 }
 
+// The reciprocal of the largest error in the offset of a radial gradient permitted along the edges
+// of gradient-colored triangles.
+const GRADIENT_RESOLUTION: f32 = 64.0;
+
+// The maximum number of passes in which the edges of radial gradient-colored triangles are split.
+const MAX_GRADIENT_SUBDIVISIONS: u32 = 12;
+
+// Vertices whose offset lies within this distance of a gradient stop are considered to lie on it.
+const GRADIENT_STOP_TOLERANCE: f32 = 1e-4;
+
+/// Tessellate the given path events and color the resulting geometry with the given gradient.
+///
+/// The path is tessellated in local coordinates, after which the triangles are cut along each of
+/// the gradient's stops. Radial gradients are further subdivided until linearly interpolating the
+/// offset along every edge stays within `1 / GRADIENT_RESOLUTION` of the exact offset. New vertices
+/// are shared between neighbouring triangles so that the resulting mesh has no T-junctions. Each
+/// vertex is then colored by sampling the gradient at its local position.
+pub(crate) fn render_path_events_gradient<I>(
+    events: I,
+    gradient: &Gradient,
+    transform: cgmath::Matrix4<f32>,
+    options: Options,
+    fill_tessellator: &mut lyon::tessellation::FillTessellator,
+    stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    mesh: &mut draw::Mesh,
+) where
+    I: IntoIterator<Item = lyon::path::PathEvent>,
+{
+    // Tessellate into a local mesh so that the gradient may be sampled in local coordinates.
+    let mut local = draw::Mesh::default();
+    let res = {
+        let identity = cgmath::Matrix4::identity();
+        let color = draw::mesh::vertex::DEFAULT_VERTEX_COLOR;
+        let mut mesh_builder = draw::mesh::MeshBuilder::single_color(&mut local, identity, color);
+        match options {
+            Options::Fill(options) => {
+                fill_tessellator.tessellate(events, &options, &mut mesh_builder)
+            }
+            Options::Stroke(options) => {
+                stroke_tessellator.tessellate(events, &options, &mut mesh_builder)
+            }
+        }
+    };
+    if let Err(err) = res {
+        eprintln!("failed to tessellate path: {:?}", err);
+        return;
+    }
+
+    // Subdivide triangles so that the gradient is adequately sampled.
+    let mut points: Vec<Point2> = local.points().iter().map(|p| geom::pt2(p.x, p.y)).collect();
+    let mut indices = local.indices().to_vec();
+    subdivide_gradient_triangles(gradient, &mut points, &mut indices);
+
+    let tex_coords = draw::mesh::vertex::default_tex_coords();
+    let v_start = mesh.raw_vertex_count() as u32;
+    let vertices = points.into_iter().map(|p| {
+        let color = gradient.color_at(p);
+        let p = cgmath::Point3::new(p.x, p.y, 0.0);
+        let p: geom::Point3 = cgmath::Transform::transform_point(&transform, p).into();
+        ((p, color), tex_coords).into()
+    });
+    let indices = indices.into_iter().map(|i| v_start + i);
+    mesh.extend(vertices, indices);
+}
+
+// Split the given triangles so that interpolating the gradient's color between their vertices
+// approximates the gradient.
+//
+// The color of a gradient varies linearly with its offset between each pair of stops, so the
+// triangles are cut wherever their edges cross the offset of a stop. The offset of a linear
+// gradient is affine, so this alone is exact. The edges of radial gradients are also split in
+// half until the offset along each of them is linear to within `1.0 / GRADIENT_RESOLUTION`.
+//
+// Edges are always split at vertices shared by the triangles on either side of the edge, so the
+// result never contains T-junctions.
+fn subdivide_gradient_triangles(
+    gradient: &Gradient,
+    points: &mut Vec<Point2>,
+    indices: &mut Vec<u32>,
+) {
+    let mut stops: Vec<f32> = gradient
+        .color_ramp()
+        .stops()
+        .iter()
+        .map(|&(offset, _)| offset)
+        .collect();
+    stops.dedup();
+    if stops.is_empty() {
+        return;
+    }
+    let mut splits = HashMap::new();
+
+    if let Gradient::Radial { center, .. } = *gradient {
+        // Triangles containing the center are first split at the center, as the gradient may
+        // otherwise lie entirely within a triangle without reaching any of its edges.
+        let contains_center = |tri: &[u32]| {
+            let p = |i: usize| points[tri[i] as usize];
+            let side = |a: Point2, b: Point2| {
+                (b.x - a.x) * (center.y - a.y) - (b.y - a.y) * (center.x - a.x)
+            };
+            let sides = [side(p(0), p(1)), side(p(1), p(2)), side(p(2), p(0))];
+            sides.iter().all(|&s| s > 0.0) || sides.iter().all(|&s| s < 0.0)
+        };
+        if indices.chunks_exact(3).any(&contains_center) {
+            let c = points.len() as u32;
+            let mut triangles = Vec::with_capacity(indices.len() + 6);
+            for tri in indices.chunks_exact(3) {
+                if contains_center(tri) {
+                    let [a, b, d] = [tri[0], tri[1], tri[2]];
+                    triangles.extend(&[a, b, c, b, d, c, d, a, c]);
+                } else {
+                    triangles.extend(tri);
+                }
+            }
+            *indices = triangles;
+            points.push(center);
+        }
+
+        let tolerance = 1.0 / GRADIENT_RESOLUTION;
+        for _ in 0..MAX_GRADIENT_SUBDIVISIONS {
+            cut_triangles_at_stops(gradient, &stops, points, indices);
+            splits.clear();
+            for (a, b) in triangle_edges(indices) {
+                let (pa, pb) = (points[a as usize], points[b as usize]);
+                if gradient.max_interpolation_error(pa, pb) > tolerance {
+                    splits.entry((a, b)).or_insert_with(|| {
+                        points.push((pa + pb) * 0.5);
+                        points.len() as u32 - 1
+                    });
+                }
+            }
+            if splits.is_empty() {
+                return;
+            }
+            // Also split the longest edge of each triangle that is split, so that triangles
+            // shrink rather than growing thinner with each pass.
+            loop {
+                let mut changed = false;
+                for tri in indices.chunks_exact(3) {
+                    let edges = [
+                        edge(tri[0], tri[1]),
+                        edge(tri[1], tri[2]),
+                        edge(tri[2], tri[0]),
+                    ];
+                    if !edges.iter().any(|e| splits.contains_key(e)) {
+                        continue;
+                    }
+                    let len2 = |&(a, b): &(u32, u32)| {
+                        (points[b as usize] - points[a as usize]).magnitude2()
+                    };
+                    let longest = edges
+                        .iter()
+                        .cloned()
+                        .max_by(|a, b| len2(a).partial_cmp(&len2(b)).unwrap_or(Ordering::Equal))
+                        .expect("triangle has three edges");
+                    if !splits.contains_key(&longest) {
+                        let (pa, pb) = (points[longest.0 as usize], points[longest.1 as usize]);
+                        points.push((pa + pb) * 0.5);
+                        splits.insert(longest, points.len() as u32 - 1);
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+            split_triangles(indices, &splits, points);
+        }
+    }
+
+    cut_triangles_at_stops(gradient, &stops, points, indices);
+}
+
+// Cut the triangles wherever their edges cross the offset of one of the given stops.
+fn cut_triangles_at_stops(
+    gradient: &Gradient,
+    stops: &[f32],
+    points: &mut Vec<Point2>,
+    indices: &mut Vec<u32>,
+) {
+    let mut splits = HashMap::new();
+    for &stop in stops {
+        splits.clear();
+        for (a, b) in triangle_edges(indices) {
+            let (pa, pb) = (points[a as usize], points[b as usize]);
+            let on_stop = |p: Point2| (gradient.offset(p) - stop).abs() <= GRADIENT_STOP_TOLERANCE;
+            if on_stop(pa) || on_stop(pb) {
+                continue;
+            }
+            if let Some(t) = gradient.crossing(pa, pb, stop) {
+                splits.entry((a, b)).or_insert_with(|| {
+                    points.push(pa + (pb - pa) * t);
+                    points.len() as u32 - 1
+                });
+            }
+        }
+        split_triangles(indices, &splits, points);
+    }
+}
+
+// The edge between the given vertices, with the lesser vertex index first.
+fn edge(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+// The edges of the given triangles.
+fn triangle_edges(indices: &[u32]) -> impl Iterator<Item = (u32, u32)> + '_ {
+    indices.chunks_exact(3).flat_map(|tri| {
+        vec![
+            edge(tri[0], tri[1]),
+            edge(tri[1], tri[2]),
+            edge(tri[2], tri[0]),
+        ]
+    })
+}
+
+// Replace each triangle with the triangles formed by splitting its edges at the given vertices.
+fn split_triangles(indices: &mut Vec<u32>, splits: &HashMap<(u32, u32), u32>, points: &[Point2]) {
+    if splits.is_empty() {
+        return;
+    }
+    let mut triangles = Vec::with_capacity(indices.len());
+    for tri in indices.chunks_exact(3) {
+        let vs = [tri[0], tri[1], tri[2]];
+        let split = |k: usize| splits.get(&edge(vs[k], vs[(k + 1) % 3])).cloned();
+        split_triangle(vs, [split(0), split(1), split(2)], points, &mut triangles);
+    }
+    *indices = triangles;
+}
+
+// Split the triangle `vs` at the vertices `ms`, where `ms[k]` lies on the edge from `vs[k]` to
+// `vs[(k + 1) % 3]` if that edge is split. The winding of the triangle is preserved.
+//
+// If two edges are split, the remaining quad is divided from the vertex on the longer edge.
+fn split_triangle(vs: [u32; 3], ms: [Option<u32>; 3], points: &[Point2], out: &mut Vec<u32>) {
+    match ms {
+        [None, None, None] => out.extend(vs.iter().cloned()),
+        [Some(m0), Some(m1), Some(m2)] => {
+            let [v0, v1, v2] = vs;
+            out.extend(&[v0, m0, m2, m0, v1, m1, m2, m1, v2, m0, m1, m2]);
+        }
+        _ => {
+            // Rotate the triangle so that the first edge is split and, if two edges are split,
+            // the last edge is not.
+            let r = match ms.iter().filter(|m| m.is_some()).count() {
+                1 => ms.iter().position(|m| m.is_some()),
+                _ => ms.iter().position(|m| m.is_none()).map(|k| (k + 1) % 3),
+            };
+            let r = r.expect("no split edge");
+            let v = |k: usize| vs[(k + r) % 3];
+            let m = |k: usize| ms[(k + r) % 3];
+            let (v0, v1, v2) = (v(0), v(1), v(2));
+            match (m(0), m(1)) {
+                (Some(m0), None) => out.extend(&[v0, m0, v2, m0, v1, v2]),
+                (Some(m0), Some(m1)) => {
+                    let len2 =
+                        |a: u32, b: u32| (points[b as usize] - points[a as usize]).magnitude2();
+                    if len2(v0, v1) > len2(v1, v2) {
+                        out.extend(&[m0, v1, m1, m0, m1, v2, v0, m0, v2]);
+                    } else {
+                        out.extend(&[m0, v1, m1, v0, m0, m1, v0, m1, v2]);
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+/// Tessellate the stroke of the given path events, coloring it by sampling the given ramp by arc
+/// length.
+///
+/// Curves are flattened using the tolerance of the stroke options. Offsets into the ramp are
+/// measured from the start of the first sub-path to the end of the last, so that `0.0` is the
+/// start of the path and `1.0` is its end. If a `dash` is given, the path is split into dashes
+/// after it is measured so that the gaps between dashes skip their part of the ramp.
+pub(crate) fn render_path_events_stroke_gradient<I>(
+    events: I,
+    dash: Option<&Dash>,
+    ramp: &ColorRamp,
+    transform: cgmath::Matrix4<f32>,
+    options: &StrokeOptions,
+    stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    mesh: &mut draw::Mesh,
+) where
+    I: IntoIterator<Item = lyon::path::PathEvent>,
+{
+    use lyon::path::iterator::PathIterator;
+
+    // Flatten the path into polylines, measuring the distance along the path to each point.
+    let mut polylines: Vec<(Vec<(lyon::math::Point, f32)>, bool)> = vec![];
+    let mut length = 0.0;
+    for event in events.into_iter().flattened(options.tolerance) {
+        match event {
+            PathEvent::Begin { at } => polylines.push((vec![(at, length)], false)),
+            PathEvent::Line { from, to } => {
+                length += (to - from).length();
+                if let Some((points, _)) = polylines.last_mut() {
+                    points.push((to, length));
+                }
+            }
+            PathEvent::End { last, first, close } => {
+                if let Some((points, closed)) = polylines.last_mut() {
+                    if close {
+                        length += (first - last).length();
+                        points.push((first, length));
+                        *closed = true;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if let Some(dash) = dash {
+        let lerp = |a: &f32, b: &f32, t: f32| a + (b - a) * t;
+        polylines = polylines
+            .iter()
+            .flat_map(|(points, close)| dash_polyline(points, *close, dash, lerp))
+            .collect();
+    }
+
+    // Build a path with a color attribute for each channel, sampled from the ramp.
+    let color_at = |distance: f32| {
+        let t = if length > 0.0 { distance / length } else { 0.0 };
+        let (r, g, b, a) = ramp.sample(t).into();
+        [r, g, b, a]
+    };
+    let channels = draw::mesh::vertex::COLOR_CHANNEL_COUNT;
+    let mut path_builder = lyon::path::Path::builder_with_attributes(channels);
+    for (points, close) in polylines {
+        let mut iter = points.into_iter();
+        let (first, distance) = match iter.next() {
+            None => continue,
+            Some(first) => first,
+        };
+        path_builder.move_to(first, &color_at(distance));
+        for (point, distance) in iter {
+            path_builder.line_to(point, &color_at(distance));
+        }
+        // The closing segment has already been added so that its end may take the final color.
+        if close {
+            path_builder.close();
+        }
+    }
+    let path = path_builder.build();
+
+    // Extend the mesh with the built path.
+    let mut mesh_builder = draw::mesh::MeshBuilder::color_per_point(mesh, transform);
+    let res = stroke_tessellator.tessellate_with_ids(
+        path.id_iter(),
+        &path,
+        Some(&path),
+        options,
+        &mut mesh_builder,
+    );
+    if let Err(err) = res {
+        eprintln!("failed to tessellate path: {:?}", err);
+    }
+}
+
+/// The bounding rectangle of the given path events, or `None` if there are no events.
+///
+/// Curves are flattened using the given tolerance so that the bounds fit the curve itself rather
+/// than its control points.
+pub(crate) fn path_events_bounds<I>(events: I, tolerance: f32) -> Option<geom::Rect>
+where
+    I: IntoIterator<Item = lyon::path::PathEvent>,
+{
+    use lyon::path::iterator::PathIterator;
+
+    let mut points = events
+        .into_iter()
+        .flattened(tolerance)
+        .filter_map(|event| match event {
+            PathEvent::Begin { at } => Some(geom::pt2(at.x, at.y)),
+            PathEvent::Line { to, .. } => Some(geom::pt2(to.x, to.y)),
+            _ => None,
+        });
+    let first = points.next()?;
+    let init = geom::Rect::from_x_y_w_h(first.x, first.y, 0.0, 0.0);
+    Some(points.fold(init, |rect, p| rect.stretch_to_point(p)))
+}
+
+/// Tessellate the given path events with texture coordinates produced by mapping the given bounds
+/// to the full extent of the texture.
+pub(crate) fn render_path_events_textured<I>(
+    events: I,
+    bounds: geom::Rect,
+    transform: cgmath::Matrix4<f32>,
+    options: Options,
+    fill_tessellator: &mut lyon::tessellation::FillTessellator,
+    stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    mesh: &mut draw::Mesh,
+) where
+    I: IntoIterator<Item = lyon::path::PathEvent>,
+{
+    let mut mesh_builder = draw::mesh::MeshBuilder::tex_coords_from_bounds(mesh, transform, bounds);
+    let res = match options {
+        Options::Fill(options) => fill_tessellator.tessellate(events, &options, &mut mesh_builder),
+        Options::Stroke(options) => {
+            stroke_tessellator.tessellate(events, &options, &mut mesh_builder)
+        }
+    };
+    if let Err(err) = res {
+        eprintln!("failed to tessellate path: {:?}", err);
+    }
+}
+
 pub(crate) fn render_path_points_colored<I>(
     points_colored: I,
     close: bool,
@@ -483,6 +1323,147 @@ pub(crate) fn render_path_points_textured<I>(
     }
 }
 
+/// Stroke a polyline of points, each with its own weight.
+///
+/// Each point is extruded either side of the line by half of its weight, joining segments with a
+/// miter clamped to the `miter_limit` of the given options. Consecutive duplicate points are
+/// ignored. If a `gradient` is given, it is sampled by arc length in place of `color`. If a `dash`
+/// is given, each dash is stroked separately with the weights interpolated at its ends.
+pub(crate) fn render_path_points_weighted<I>(
+    points_weighted: I,
+    close: bool,
+    color: LinSrgba,
+    gradient: Option<&ColorRamp>,
+    dash: Option<&Dash>,
+    transform: cgmath::Matrix4<f32>,
+    options: &StrokeOptions,
+    mesh: &mut draw::Mesh,
+) where
+    I: IntoIterator<Item = (Point2, f32)>,
+{
+    let mut points: Vec<(Point2, f32)> = Vec::new();
+    for (p, w) in points_weighted {
+        match points.last() {
+            Some(&(last, _)) if last == p => continue,
+            _ => points.push((p, w)),
+        }
+    }
+    if close && points.len() > 2 && points.first().map(|f| f.0) == points.last().map(|l| l.0) {
+        points.pop();
+    }
+    let len = points.len();
+    if len < 2 {
+        return;
+    }
+
+    // The distance along the line to each point, used to sample the gradient.
+    let mut distances = Vec::with_capacity(len);
+    let mut length = 0.0;
+    for i in 0..len {
+        if i > 0 {
+            length += (points[i].0 - points[i - 1].0).magnitude();
+        }
+        distances.push(length);
+    }
+    if close {
+        length += (points[0].0 - points[len - 1].0).magnitude();
+    }
+    let color_at = |distance: f32| match gradient {
+        Some(ramp) if length > 0.0 => ramp.sample(distance / length),
+        Some(ramp) => ramp.sample(0.0),
+        None => color,
+    };
+
+    // Pair each point with its weight and distance, interpolating both at the ends of dashes.
+    let points: Vec<_> = points
+        .into_iter()
+        .zip(distances)
+        .map(|((p, weight), distance)| (p, (weight, distance)))
+        .collect();
+    let lerp =
+        |a: &(f32, f32), b: &(f32, f32), t: f32| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t);
+    for (points, close) in dash_points(points, close, dash, lerp) {
+        extrude_points_weighted(&points, close, &color_at, transform, options, mesh);
+    }
+}
+
+// Extrude each point of the polyline either side of the line by half of its weight, coloring it
+// by its distance along the line.
+fn extrude_points_weighted<F>(
+    points: &[(Point2, (f32, f32))],
+    close: bool,
+    color_at: F,
+    transform: cgmath::Matrix4<f32>,
+    options: &StrokeOptions,
+    mesh: &mut draw::Mesh,
+) where
+    F: Fn(f32) -> LinSrgba,
+{
+    let len = points.len();
+    if len < 2 {
+        return;
+    }
+
+    let transform_point = |p: Point2| -> geom::Point3 {
+        let p = cgmath::Point3::new(p.x, p.y, 0.0);
+        let p = cgmath::Transform::transform_point(&transform, p);
+        p.into()
+    };
+    let normal = |a: Point2, b: Point2| -> Point2 {
+        let d = (b - a).normalize();
+        geom::vec2(-d.y, d.x)
+    };
+
+    // Extrude each point either side of the line.
+    let v_start = mesh.raw_vertex_count() as u32;
+    for i in 0..len {
+        let (p, (weight, distance)) = points[i];
+        let color = color_at(distance);
+        let prev = match i {
+            0 if close => Some(points[len - 1].0),
+            0 => None,
+            _ => Some(points[i - 1].0),
+        };
+        let next = match i {
+            _ if i == len - 1 && close => Some(points[0].0),
+            _ if i == len - 1 => None,
+            _ => Some(points[i + 1].0),
+        };
+        let half_weight = weight * 0.5;
+        let n_prev = prev.map(|prev| normal(prev, p));
+        let n_next = next.map(|next| normal(p, next));
+        let offset = match (n_prev, n_next) {
+            (Some(a), Some(b)) => {
+                let sum = a + b;
+                let mag = sum.magnitude();
+                if mag > 0.0 {
+                    let miter = sum / mag;
+                    let cos = miter.dot(b).max(1.0 / options.miter_limit);
+                    miter * (half_weight / cos)
+                } else {
+                    b * half_weight
+                }
+            }
+            (Some(n), None) | (None, Some(n)) => n * half_weight,
+            (None, None) => geom::vec2(0.0, 0.0),
+        };
+        let tex_coords = draw::mesh::vertex::default_tex_coords();
+        let a = transform_point(p + offset);
+        let b = transform_point(p - offset);
+        mesh.push_vertex(((a, color), tex_coords).into());
+        mesh.push_vertex(((b, color), tex_coords).into());
+    }
+
+    // Two triangles for each segment.
+    let segments = if close { len } else { len - 1 };
+    for i in 0..segments as u32 {
+        let j = (i + 1) % len as u32;
+        let (a0, a1) = (v_start + i * 2, v_start + i * 2 + 1);
+        let (b0, b1) = (v_start + j * 2, v_start + j * 2 + 1);
+        mesh.extend_indices([a0, a1, b0, a1, b1, b0].iter().cloned());
+    }
+}
+
 pub(crate) fn render_path_source(
     // TODO:
     path_src: PathEventSourceIter,
@@ -540,6 +1521,8 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
             orientation,
             path_event_src,
             options,
+            dash,
+            stroke_gradient,
             vertex_mode,
             texture_view,
         } = self;
@@ -549,8 +1532,101 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
         let local_transform = position.transform() * orientation.transform();
         let transform = global_transform * local_transform;
 
+        // Only strokes may be dashed.
+        let dash = match options {
+            Options::Stroke(_) => dash.as_ref(),
+            Options::Fill(_) => None,
+        };
+
+        // A gradient along the stroke replaces all other coloring.
+        if let (Some(ramp), Options::Stroke(ref opts)) = (stroke_gradient.as_ref(), options) {
+            let to_point = |p: Point2| lyon::math::point(p.x, p.y);
+            let stroke_tessellator = &mut ctxt.stroke_tessellator;
+            match path_event_src {
+                PathEventSource::Buffered(range) => {
+                    let events = ctxt.path_event_buffer[range].iter().cloned();
+                    render_path_events_stroke_gradient(
+                        events,
+                        dash,
+                        ramp,
+                        transform,
+                        opts,
+                        stroke_tessellator,
+                        mesh,
+                    );
+                }
+                PathEventSource::ColoredPoints { range, close } => {
+                    let points = ctxt.path_points_colored_buffer[range].iter();
+                    let points = points.map(|&(p, _)| to_point(p));
+                    let events = lyon::path::iterator::FromPolyline::new(close, points);
+                    render_path_events_stroke_gradient(
+                        events,
+                        dash,
+                        ramp,
+                        transform,
+                        opts,
+                        stroke_tessellator,
+                        mesh,
+                    );
+                }
+                PathEventSource::TexturedPoints { range, close } => {
+                    let points = ctxt.path_points_textured_buffer[range].iter();
+                    let points = points.map(|&(p, _)| to_point(p));
+                    let events = lyon::path::iterator::FromPolyline::new(close, points);
+                    render_path_events_stroke_gradient(
+                        events,
+                        dash,
+                        ramp,
+                        transform,
+                        opts,
+                        stroke_tessellator,
+                        mesh,
+                    );
+                }
+                PathEventSource::WeightedPoints { range, close } => {
+                    let points = ctxt.path_points_weighted_buffer[range].iter().cloned();
+                    let color = ramp.sample(0.0);
+                    let gradient = Some(ramp);
+                    render_path_points_weighted(
+                        points, close, color, gradient, dash, transform, opts, mesh,
+                    );
+                }
+            }
+            return draw::renderer::PrimitiveRender::default();
+        }
+
+        // Textures specified via `texture` map the bounds of the path to the full texture.
+        match (texture_view.as_ref(), &path_event_src) {
+            (_, PathEventSource::TexturedPoints { .. }) | (None, _) => (),
+            (Some(view), src) => {
+                let events = src.to_events(
+                    ctxt.path_event_buffer,
+                    ctxt.path_points_colored_buffer,
+                    ctxt.path_points_textured_buffer,
+                    ctxt.path_points_weighted_buffer,
+                );
+                let tolerance = match options {
+                    Options::Fill(ref opts) => opts.tolerance,
+                    Options::Stroke(ref opts) => opts.tolerance,
+                };
+                if let Some(bounds) = path_events_bounds(events.iter().cloned(), tolerance) {
+                    let events = dash_stroke_events(events, dash, &options);
+                    render_path_events_textured(
+                        events,
+                        bounds,
+                        transform,
+                        options,
+                        &mut ctxt.fill_tessellator,
+                        &mut ctxt.stroke_tessellator,
+                        mesh,
+                    );
+                }
+                return draw::renderer::PrimitiveRender::texture(view.clone());
+            }
+        }
+
         // A function for rendering the path.
-        let render =
+        let mut render =
             |src: PathEventSourceIter,
              theme: &draw::Theme,
              fill_tessellator: &mut lyon::tessellation::FillTessellator,
@@ -564,13 +1640,14 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
                     &draw::theme::Primitive::Path,
                     fill_tessellator,
                     stroke_tessellator,
-                    mesh,
+                    &mut *mesh,
                 )
             };
 
         match path_event_src {
             PathEventSource::Buffered(range) => {
-                let mut events = ctxt.path_event_buffer[range].iter().cloned();
+                let events = ctxt.path_event_buffer[range].iter().cloned();
+                let mut events = dash_stroke_events(events, dash, &options);
                 let src = PathEventSourceIter::Events(&mut events);
                 render(
                     src,
@@ -580,40 +1657,113 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
                 );
             }
             PathEventSource::ColoredPoints { range, close } => {
-                let mut points_colored = ctxt.path_points_colored_buffer[range].iter().cloned();
-                let src = PathEventSourceIter::ColoredPoints {
-                    points: &mut points_colored,
-                    close,
-                };
-                render(
-                    src,
-                    &ctxt.theme,
-                    &mut ctxt.fill_tessellator,
-                    &mut ctxt.stroke_tessellator,
-                );
+                let points = ctxt.path_points_colored_buffer[range].to_vec();
+                let lerp = |a: &Color, b: &Color, t: f32| a.mix(b, t);
+                for (points, close) in dash_points(points, close, dash, lerp) {
+                    let src = PathEventSourceIter::ColoredPoints {
+                        points: &mut points.into_iter(),
+                        close,
+                    };
+                    render(
+                        src,
+                        &ctxt.theme,
+                        &mut ctxt.fill_tessellator,
+                        &mut ctxt.stroke_tessellator,
+                    );
+                }
             }
             PathEventSource::TexturedPoints { range, close } => {
-                let mut points_textured = ctxt.path_points_textured_buffer[range].iter().cloned();
-                let src = PathEventSourceIter::TexturedPoints {
-                    points: &mut points_textured,
-                    close,
-                };
-                render(
-                    src,
-                    &ctxt.theme,
-                    &mut ctxt.fill_tessellator,
-                    &mut ctxt.stroke_tessellator,
-                );
+                let points = ctxt.path_points_textured_buffer[range].to_vec();
+                let lerp = |a: &TexCoords, b: &TexCoords, t: f32| *a + (*b - *a) * t;
+                for (points, close) in dash_points(points, close, dash, lerp) {
+                    let src = PathEventSourceIter::TexturedPoints {
+                        points: &mut points.into_iter(),
+                        close,
+                    };
+                    render(
+                        src,
+                        &ctxt.theme,
+                        &mut ctxt.fill_tessellator,
+                        &mut ctxt.stroke_tessellator,
+                    );
+                }
+            }
+            PathEventSource::WeightedPoints { range, close } => {
+                let points = ctxt.path_points_weighted_buffer[range].iter().cloned();
+                match options {
+                    Options::Stroke(ref opts) => {
+                        let theme_prim = draw::theme::Primitive::Path;
+                        let color =
+                            color.unwrap_or_else(|| ctxt.theme.stroke_lin_srgba(&theme_prim));
+                        render_path_points_weighted(
+                            points, close, color, None, dash, transform, opts, mesh,
+                        );
+                    }
+                    Options::Fill(_) => {
+                        let points = points.map(|(p, _)| lyon::math::point(p.x, p.y));
+                        let mut events = lyon::path::iterator::FromPolyline::new(close, points);
+                        let src = PathEventSourceIter::Events(&mut events);
+                        render(
+                            src,
+                            &ctxt.theme,
+                            &mut ctxt.fill_tessellator,
+                            &mut ctxt.stroke_tessellator,
+                        );
+                    }
+                }
             }
         }
 
         draw::renderer::PrimitiveRender {
             texture_view,
+            texture_layer: 0,
             vertex_mode,
+            topology: None,
+            fragment_shader: None,
+            instances: vec![],
         }
     }
 }
 
+impl draw::plotter_renderer::PlotPrimitive for Path<f32> {
+    fn plot_stroke(
+        &self,
+        ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        if let Options::Fill(_) = self.options {
+            return None;
+        }
+        let to_point = |p: Point2| lyon::math::point(p.x, p.y);
+        let dash = self.dash.as_ref();
+        match self.path_event_src {
+            PathEventSource::Buffered(ref range) => {
+                let src = ctxt.path_event_buffer[range.clone()].iter().cloned();
+                events.extend(dash_stroke_events(src, dash, &self.options));
+            }
+            PathEventSource::ColoredPoints { ref range, close } => {
+                let points = ctxt.path_points_colored_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                let src = lyon::path::iterator::FromPolyline::new(close, points);
+                events.extend(dash_stroke_events(src, dash, &self.options));
+            }
+            PathEventSource::TexturedPoints { ref range, close } => {
+                let points = ctxt.path_points_textured_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                let src = lyon::path::iterator::FromPolyline::new(close, points);
+                events.extend(dash_stroke_events(src, dash, &self.options));
+            }
+            PathEventSource::WeightedPoints { ref range, close } => {
+                let points = ctxt.path_points_weighted_buffer[range.clone()].iter();
+                let points = points.map(|&(p, _)| to_point(p));
+                let src = lyon::path::iterator::FromPolyline::new(close, points);
+                events.extend(dash_stroke_events(src, dash, &self.options));
+            }
+        }
+        Some(self.position.transform() * self.orientation.transform())
+    }
+}
+
 /// Create a lyon path for the given iterator of colored points.
 pub fn points_colored_to_lyon_path<I>(points_colored: I, close: bool) -> Option<lyon::path::Path>
 where
@@ -689,6 +1839,8 @@ where
         color: Option<LinSrgba>,
         path_event_src: PathEventSource,
         options: Options,
+        dash: Option<Dash>,
+        stroke_gradient: Option<ColorRamp>,
         vertex_mode: draw::renderer::VertexMode,
         texture_view: Option<wgpu::TextureView>,
     ) -> Self {
@@ -698,10 +1850,22 @@ where
             position,
             path_event_src,
             options,
+            dash,
+            stroke_gradient,
             vertex_mode,
             texture_view,
         }
     }
+
+    // The source of the path's events within the intermediary state.
+    pub(crate) fn path_event_src(&self) -> &PathEventSource {
+        &self.path_event_src
+    }
+
+    // Rebase the path's range into the intermediary state.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        self.path_event_src.offset_ranges(offsets);
+    }
 }
 
 impl<'a, S> DrawingPathInit<'a, S>
@@ -738,6 +1902,14 @@ where
     pub fn rule(self, rule: lyon::tessellation::FillRule) -> Self {
         self.map_ty(|ty| ty.rule(rule))
     }
+
+    /// Fill the path with the given texture.
+    ///
+    /// Texture coordinates are produced by mapping the bounding rectangle of the path to the full
+    /// extent of the texture.
+    pub fn texture(self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.map_ty(|ty| ty.texture(view))
+    }
 }
 
 impl<'a, S> DrawingPathStroke<'a, S>
@@ -753,6 +1925,41 @@ where
     pub fn tolerance(self, tolerance: f32) -> Self {
         self.map_ty(|ty| ty.stroke_tolerance(tolerance))
     }
+
+    /// Color the stroke with a gradient along the length of the path.
+    ///
+    /// Each stop is a `(offset, color)` pair, where an offset of `0.0` is the start of the path
+    /// and `1.0` is its end.
+    pub fn stroke_gradient<I, C>(self, stops: I) -> Self
+    where
+        I: IntoIterator<Item = (f32, C)>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.stroke_gradient(stops))
+    }
+
+    /// Submit a polyline of points, each with its own stroke weight.
+    ///
+    /// Each point must be represented as a tuple containing the point and the weight in that
+    /// order, e.g. `(point, weight)`.
+    pub fn weighted_points<I, P>(self, points: I) -> DrawingPath<'a, S>
+    where
+        I: IntoIterator<Item = (P, f32)>,
+        P: Into<Point2<S>>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.weighted_points(ctxt, points))
+    }
+
+    /// Submit a polyline of points, each with its own stroke weight.
+    ///
+    /// The path will automatically close from the end point to the start point.
+    pub fn weighted_points_closed<I, P>(self, points: I) -> DrawingPath<'a, S>
+    where
+        I: IntoIterator<Item = (P, f32)>,
+        P: Into<Point2<S>>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.weighted_points_closed(ctxt, points))
+    }
 }
 
 impl<'a, T, S> DrawingPathOptions<'a, T, S>
@@ -853,6 +2060,12 @@ impl<S> SetFill for PathFill<S> {
     }
 }
 
+impl<S> SetDash for PathStroke<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.dash)
+    }
+}
+
 impl<S> SetStroke for PathStroke<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         &mut self.opts
@@ -985,3 +2198,300 @@ impl<S> Into<Option<Path<S>>> for Primitive<S> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyon::math::point;
+
+    // An open path through the given points.
+    fn polyline(points: &[(f32, f32)]) -> Vec<PathEvent> {
+        let points: Vec<_> = points.iter().map(|&(x, y)| point(x, y)).collect();
+        let mut events = vec![PathEvent::Begin { at: points[0] }];
+        for w in points.windows(2) {
+            events.push(PathEvent::Line {
+                from: w[0],
+                to: w[1],
+            });
+        }
+        let (first, last) = (points[0], points[points.len() - 1]);
+        let close = false;
+        events.push(PathEvent::End { last, first, close });
+        events
+    }
+
+    // The length of each dash, i.e. each sub-path, within the dashed events.
+    fn dash_lengths(events: &[PathEvent]) -> Vec<f32> {
+        let mut lengths = vec![];
+        for event in events {
+            match *event {
+                PathEvent::Begin { .. } => lengths.push(0.0),
+                PathEvent::Line { from, to } => {
+                    *lengths.last_mut().unwrap() += (to - from).length()
+                }
+                PathEvent::End { .. } => (),
+                ref event => panic!("unexpected event {:?}", event),
+            }
+        }
+        lengths
+    }
+
+    fn dash(pattern: &[f32], offset: f32) -> Dash {
+        let pattern = pattern.to_vec();
+        Dash { pattern, offset }
+    }
+
+    fn assert_lengths(lengths: &[f32], expected: &[f32]) {
+        assert_eq!(
+            lengths.len(),
+            expected.len(),
+            "{:?} != {:?}",
+            lengths,
+            expected
+        );
+        for (a, b) in lengths.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-4, "{:?} != {:?}", lengths, expected);
+        }
+    }
+
+    #[test]
+    fn test_dash_across_join() {
+        // A path of length 6 with a corner at 3. The first dash spans the corner.
+        let events = polyline(&[(0.0, 0.0), (3.0, 0.0), (3.0, 3.0)]);
+        let dashed = dash_events(events, &dash(&[4.0, 1.0], 0.0), 0.01);
+        assert_lengths(&dash_lengths(&dashed), &[4.0, 1.0]);
+        // The dash spanning the corner is a single sub-path of two lines.
+        let corner = PathEvent::Line {
+            from: point(3.0, 0.0),
+            to: point(3.0, 1.0),
+        };
+        assert_eq!(dashed[2], corner);
+    }
+
+    #[test]
+    fn test_dash_ends_at_join() {
+        // A dash ending exactly at a corner does not continue into the next segment.
+        let events = polyline(&[(0.0, 0.0), (2.0, 0.0), (2.0, 4.0)]);
+        let dashed = dash_events(events, &dash(&[2.0, 1.0], 0.0), 0.01);
+        assert_lengths(&dash_lengths(&dashed), &[2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_zero_length_dash() {
+        // Zero-length dashes produce no degenerate sub-paths.
+        let events = polyline(&[(0.0, 0.0), (6.0, 0.0)]);
+        let dashed = dash_events(events, &dash(&[0.0, 2.0], 0.0), 0.01);
+        assert!(dashed.is_empty(), "{:?}", dashed);
+    }
+
+    #[test]
+    fn test_zero_length_gap() {
+        // Zero-length gaps split the stroke into dashes that touch.
+        let events = polyline(&[(0.0, 0.0), (6.0, 0.0)]);
+        let dashed = dash_events(events, &dash(&[2.0, 0.0], 0.0), 0.01);
+        assert_lengths(&dash_lengths(&dashed), &[2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_offset_larger_than_pattern() {
+        // An offset of 5 into a pattern of length 4 is equivalent to an offset of 1.
+        let events = polyline(&[(0.0, 0.0), (8.0, 0.0)]);
+        let dashed = dash_events(events.clone(), &dash(&[2.0, 2.0], 5.0), 0.01);
+        assert_lengths(&dash_lengths(&dashed), &[1.0, 2.0, 1.0]);
+        let equivalent = dash_events(events, &dash(&[2.0, 2.0], 1.0), 0.01);
+        assert_eq!(dashed, equivalent);
+    }
+
+    #[test]
+    fn test_negative_offset() {
+        // A negative offset shifts the pattern forwards along the path.
+        let events = polyline(&[(0.0, 0.0), (8.0, 0.0)]);
+        let dashed = dash_events(events, &dash(&[2.0, 2.0], -1.0), 0.01);
+        assert_lengths(&dash_lengths(&dashed), &[2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_solid() {
+        let events = polyline(&[(0.0, 0.0), (8.0, 0.0)]);
+        for pattern in &[&[][..], &[0.0, 0.0], &[2.0, -1.0]] {
+            let dashed = dash_events(events.clone(), &dash(pattern, 0.0), 0.01);
+            assert_eq!(dashed, events);
+        }
+    }
+
+    #[test]
+    fn test_dash_polyline_interpolates_attributes() {
+        // The attribute of each point is its distance along the polyline.
+        let points = [
+            (point(0.0, 0.0), 0.0),
+            (point(3.0, 0.0), 3.0),
+            (point(3.0, 3.0), 6.0),
+        ];
+        let lerp = |a: &f32, b: &f32, t: f32| a + (b - a) * t;
+        let dashes = dash_polyline(&points, false, &dash(&[4.0, 1.0], 0.0), lerp);
+        let expected = [
+            vec![(0.0, 0.0, 0.0), (3.0, 0.0, 3.0), (3.0, 1.0, 4.0)],
+            vec![(3.0, 2.0, 5.0), (3.0, 3.0, 6.0)],
+        ];
+        assert_eq!(dashes.len(), expected.len(), "{:?}", dashes);
+        for ((dash, close), expected) in dashes.iter().zip(&expected) {
+            assert!(!close);
+            assert_eq!(dash.len(), expected.len(), "{:?}", dashes);
+            for (&(p, attr), &(x, y, e)) in dash.iter().zip(expected) {
+                let error = (p.x - x).abs() + (p.y - y).abs() + (attr - e).abs();
+                assert!(error < 1e-4, "{:?}", dashes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dash_weighted_points() {
+        // A closed square with sides of length 2, dashed across two of its corners.
+        let points = vec![
+            (geom::pt2(0.0, 0.0), 1.0),
+            (geom::pt2(2.0, 0.0), 1.0),
+            (geom::pt2(2.0, 2.0), 1.0),
+            (geom::pt2(0.0, 2.0), 1.0),
+        ];
+        let color = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        let transform = cgmath::Matrix4::identity();
+        let options = StrokeOptions::default();
+        let mut mesh = draw::Mesh::default();
+        let dash = dash(&[2.0, 2.0], 3.0);
+        render_path_points_weighted(
+            points.clone(),
+            true,
+            color,
+            None,
+            Some(&dash),
+            transform,
+            &options,
+            &mut mesh,
+        );
+        // Two dashes of three points, each extruded to two vertices and stroked with two
+        // triangles per segment.
+        assert_eq!(mesh.raw_vertex_count(), 2 * 3 * 2);
+        assert_eq!(mesh.indices().len(), 2 * 2 * 6);
+        let mut solid = draw::Mesh::default();
+        render_path_points_weighted(
+            points, true, color, None, None, transform, &options, &mut solid,
+        );
+        assert_eq!(solid.raw_vertex_count(), 4 * 2);
+    }
+
+    #[test]
+    fn test_dashed_stroke_gradient_measures_gaps() {
+        // The first half of the line is a dash, so only the first half of the ramp is sampled.
+        let events = polyline(&[(0.0, 0.0), (10.0, 0.0)]);
+        let black = LinSrgba::new(0.0, 0.0, 0.0, 1.0);
+        let white = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        let ramp = ColorRamp::new(vec![(0.0, black), (1.0, white)]);
+        let mut mesh = draw::Mesh::default();
+        render_path_events_stroke_gradient(
+            events,
+            Some(&dash(&[5.0, 5.0], 0.0)),
+            &ramp,
+            cgmath::Matrix4::identity(),
+            &StrokeOptions::default(),
+            &mut StrokeTessellator::new(),
+            &mut mesh,
+        );
+        assert!(mesh.points().iter().all(|p| p.x <= 5.0 + 1e-4));
+        for (p, c) in mesh.points().iter().zip(mesh.colors()) {
+            assert!((c.red - p.x / 10.0).abs() < 1e-4, "{:?} at {:?}", c, p);
+        }
+    }
+
+    // Two triangles forming a square of the given half size.
+    fn square(half: f32) -> (Vec<Point2>, Vec<u32>) {
+        let points = vec![
+            geom::pt2(-half, -half),
+            geom::pt2(half, -half),
+            geom::pt2(half, half),
+            geom::pt2(-half, half),
+        ];
+        (points, vec![0, 1, 2, 0, 2, 3])
+    }
+
+    // A gradient ramp with a stop at each of the given offsets.
+    fn stops(gradient: Gradient, offsets: &[f32]) -> Gradient {
+        offsets.iter().fold(gradient, |gradient, &offset| {
+            gradient.stop(offset, LinSrgba::new(offset, offset, offset, 1.0))
+        })
+    }
+
+    // Asserts that each edge is shared by at most two triangles and that the edges belonging to a
+    // single triangle, i.e. those without a neighbour due to a T-junction, span the given outline.
+    fn assert_conforming(points: &[Point2], indices: &[u32], perimeter: f32) {
+        let mut counts = HashMap::new();
+        for e in triangle_edges(indices) {
+            *counts.entry(e).or_insert(0) += 1;
+        }
+        assert!(counts.values().all(|&count| count <= 2));
+        let boundary: f32 = counts
+            .iter()
+            .filter(|&(_, &count)| count == 1)
+            .map(|(&(a, b), _)| (points[b as usize] - points[a as usize]).magnitude())
+            .sum();
+        assert!((boundary - perimeter).abs() < 1e-3 * perimeter);
+    }
+
+    #[test]
+    fn test_linear_gradient_cut_at_stops() {
+        let gradient = Gradient::linear(geom::pt2(-1.0, -0.5), geom::pt2(1.0, 0.5));
+        let gradient = stops(gradient, &[0.0, 0.25, 1.0]);
+        let (mut points, mut indices) = square(1.0);
+        subdivide_gradient_triangles(&gradient, &mut points, &mut indices);
+        assert_conforming(&points, &indices, 8.0);
+        // Each triangle lies between a single pair of stops, so interpolation is exact.
+        let intervals = [(-1e9, 0.0), (0.0, 0.25), (0.25, 1.0), (1.0, 1e9)];
+        for tri in indices.chunks_exact(3) {
+            let offsets: Vec<f32> = tri
+                .iter()
+                .map(|&i| gradient.offset(points[i as usize]))
+                .collect();
+            let within = |&(min, max): &(f32, f32)| {
+                let eps = GRADIENT_STOP_TOLERANCE;
+                offsets.iter().all(|&o| o >= min - eps && o <= max + eps)
+            };
+            assert!(intervals.iter().any(within));
+        }
+    }
+
+    #[test]
+    fn test_gradient_beyond_stops_is_not_subdivided() {
+        let gradient = Gradient::linear(geom::pt2(2.0, 0.0), geom::pt2(3.0, 0.0));
+        let gradient = stops(gradient, &[0.0, 1.0]);
+        let (mut points, mut indices) = square(1.0);
+        subdivide_gradient_triangles(&gradient, &mut points, &mut indices);
+        assert_eq!(indices, vec![0, 1, 2, 0, 2, 3]);
+        let gradient = stops(Gradient::radial(geom::pt2(5.0, 5.0), 1.0), &[0.0, 1.0]);
+        subdivide_gradient_triangles(&gradient, &mut points, &mut indices);
+        assert_eq!(points.len(), 4);
+    }
+
+    #[test]
+    fn test_radial_gradient_subdivision() {
+        let gradient = stops(Gradient::radial(geom::pt2(0.2, 0.1), 1.0), &[0.0, 0.5, 1.0]);
+        let (mut points, mut indices) = square(1.0);
+        subdivide_gradient_triangles(&gradient, &mut points, &mut indices);
+        assert_conforming(&points, &indices, 8.0);
+        for (a, b) in triangle_edges(&indices) {
+            let (a, b) = (points[a as usize], points[b as usize]);
+            let error = gradient.max_interpolation_error(a, b);
+            assert!(error <= 1.0 / GRADIENT_RESOLUTION);
+        }
+    }
+
+    #[test]
+    fn test_radial_gradient_within_triangle() {
+        // The gradient does not reach any of the square's edges.
+        let center = geom::pt2(0.3, 0.1);
+        let gradient = stops(Gradient::radial(center, 0.1), &[0.0, 1.0]);
+        let (mut points, mut indices) = square(10.0);
+        subdivide_gradient_triangles(&gradient, &mut points, &mut indices);
+        assert_conforming(&points, &indices, 80.0);
+        assert!(points.contains(&center));
+        assert!(indices.len() > 6);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw::mesh::vertex::{self, Point};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::recording;
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::wgpu;
+use std::ops;
+
+/// A large set of points, drawn without any tessellation.
+///
+/// By default each point is drawn as a single pixel using the **PointList** primitive topology,
+/// regardless of the topology of the current draw context. This allows for drawing millions of
+/// points per frame at the cost of a single vertex and index each.
+///
+/// If a `size` is specified, either for the whole cloud or per point, each point is instead drawn
+/// as a square of the given size in pixels that always faces the viewer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PointCloud<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    vertex_range: ops::Range<usize>,
+    color: Option<LinSrgba>,
+    colored_points: bool,
+    size: Option<f32>,
+    sizes: Vec<f32>,
+}
+
+/// The drawing context for a **PointCloud**.
+pub type DrawingPointCloud<'a, S = geom::scalar::Default> = Drawing<'a, PointCloud<S>, S>;
+
+impl<S> PointCloud<S> {
+    /// Draw every point as a square of the given size in pixels.
+    ///
+    /// By default, points are drawn as single pixels using the **PointList** topology.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Specify the size of each point in pixels.
+    ///
+    /// The sizes are matched to points in order. Points without a matching size fall back to the
+    /// size specified via `size`, or `1.0` if there is none.
+    pub fn sizes<I>(mut self, sizes: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.sizes = sizes.into_iter().collect();
+        self
+    }
+}
+
+impl<S> PointCloud<S>
+where
+    S: BaseFloat,
+{
+    /// Describe the point cloud with a sequence of points.
+    ///
+    /// All points are colored with a single color. If a color is not specified via one of the
+    /// builder methods, the fill color for point clouds is retrieved from the inner `Theme`.
+    pub fn points<I>(self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Point<S>>,
+    {
+        let points = points
+            .into_iter()
+            .map(|p| (p.into(), vertex::DEFAULT_VERTEX_COLOR));
+        let mut cloud = self.points_inner(inner_mesh, points);
+        cloud.colored_points = false;
+        cloud
+    }
+
+    /// Describe the point cloud with a sequence of colored points.
+    ///
+    /// Each of the points must be represented as a tuple containing the point and the color in
+    /// that order, e.g. `(point, color)`.
+    pub fn points_colored<I, P, C>(self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: Into<Point<S>>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let points = points
+            .into_iter()
+            .map(|(p, c)| (p.into(), c.into_lin_srgba()));
+        let mut cloud = self.points_inner(inner_mesh, points);
+        cloud.colored_points = true;
+        cloud
+    }
+
+    fn points_inner<I>(mut self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: Iterator<Item = (Point<S>, LinSrgba)>,
+    {
+        let v_start = inner_mesh.points().len();
+        let vertices = points.map(|(point, color)| {
+            let tex_coords = vertex::default_tex_coords();
+            ((point, color), tex_coords).into()
+        });
+        inner_mesh.extend_vertices(vertices);
+        let v_end = inner_mesh.points().len();
+        self.vertex_range = v_start..v_end;
+        self
+    }
+
+    // Rebase the point cloud's range into the intermediary mesh.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        recording::offset_range(&mut self.vertex_range, offsets.mesh_vertices);
+    }
+}
+
+impl<'a, S> DrawingPointCloud<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Draw every point as a square of the given size in pixels.
+    pub fn size(self, size: f32) -> Self {
+        self.map_ty(|ty| ty.size(size))
+    }
+
+    /// Specify the size of each point in pixels.
+    pub fn sizes<I>(self, sizes: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.map_ty(|ty| ty.sizes(sizes))
+    }
+
+    /// Describe the point cloud with a sequence of points.
+    ///
+    /// The given iterator may yield any type that can be converted directly into `Point3`s.
+    pub fn points<I>(self, points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Point<S>>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.points(ctxt.mesh, points))
+    }
+
+    /// Describe the point cloud with a sequence of colored points.
+    ///
+    /// Each of the points must be represented as a tuple containing the point and the color in
+    /// that order, e.g. `(point, color)`. `point` may be of any type that implements
+    /// `Into<Point3>` and `color` may be of any type that implements `IntoLinSrgba`.
+    pub fn points_colored<I, P, C>(self, points: I) -> Self
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: Into<Point<S>>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.points_colored(ctxt.mesh, points))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for PointCloud<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let PointCloud {
+            position,
+            orientation,
+            vertex_range,
+            color,
+            colored_points,
+            size,
+            sizes,
+        } = self;
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let transform_point = |p: geom::Point3<f32>| -> geom::Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+
+        let inner = ctxt.intermediary_mesh;
+        // A color specified via the builder takes precedence over the colors of the points.
+        let uniform_color = match color {
+            Some(color) => Some(color),
+            None if colored_points => None,
+            None => Some(
+                ctxt.theme
+                    .fill_lin_srgba(&draw::theme::Primitive::PointCloud),
+            ),
+        };
+        let points = vertex_range.clone().map(|i| {
+            let p = transform_point(inner.points()[i]);
+            let color = uniform_color.unwrap_or_else(|| inner.colors()[i]);
+            (p, color)
+        });
+        let tex_coords = vertex::default_tex_coords();
+
+        // Without any size, submit a single vertex per point.
+        if size.is_none() && sizes.is_empty() {
+            let v_start = mesh.raw_vertex_count() as u32;
+            let len = vertex_range.len() as u32;
+            let vertices = points.map(|pc| (pc, tex_coords).into());
+            mesh.extend(vertices, v_start..v_start + len);
+            let mut render = draw::renderer::PrimitiveRender::default();
+            render.topology = Some(wgpu::PrimitiveTopology::PointList);
+            return render;
+        }
+
+        // Otherwise, extrude each point into a square in screen space.
+        let default_size = size.unwrap_or(1.0);
+        for (i, (p, color)) in points.enumerate() {
+            let half_size = sizes.get(i).cloned().unwrap_or(default_size) * 0.5;
+            let v_start = mesh.raw_vertex_count() as u32;
+            for &(x, y) in &[(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+                let p = geom::pt3(p.x + x * half_size, p.y + y * half_size, p.z);
+                mesh.push_vertex(((p, color), tex_coords).into());
+            }
+            let indices = [0, 1, 2, 0, 2, 3];
+            mesh.extend_indices(indices.iter().map(|i| v_start + i));
+        }
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for PointCloud<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        PointCloud {
+            position: Default::default(),
+            orientation: Default::default(),
+            vertex_range: 0..0,
+            color: None,
+            colored_points: false,
+            size: None,
+            sizes: Vec::new(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for PointCloud<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for PointCloud<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for PointCloud<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<PointCloud<S>> for Primitive<S> {
+    fn from(prim: PointCloud<S>) -> Self {
+        Primitive::PointCloud(prim)
+    }
+}
+
+impl<S> Into<Option<PointCloud<S>>> for Primitive<S> {
+    fn into(self) -> Option<PointCloud<S>> {
+        match self {
+            Primitive::PointCloud(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
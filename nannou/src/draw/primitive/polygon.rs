@@ -5,14 +5,17 @@ use crate::draw::primitive::path::{self, PathEventSource};
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{orientation, position};
 use crate::draw::properties::{
-    ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition, SetStroke,
+    ColorScalar, Dash, Gradient, LinSrgba, SetColor, SetDash, SetFill, SetOrientation, SetPosition,
+    SetStroke,
 };
+use crate::draw::recording;
 use crate::draw::{self, Drawing};
 use crate::geom::{self, Point2};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 use lyon::path::PathEvent;
-use lyon::tessellation::StrokeOptions;
+use lyon::tessellation::{FillOptions, StrokeOptions};
 
 /// A trait implemented for all polygon draw primitives.
 pub trait SetPolygon<S>: Sized {
@@ -37,6 +40,25 @@ pub trait SetPolygon<S>: Sized {
         self
     }
 
+    /// Fill the polygon with the given linear or radial gradient.
+    ///
+    /// The gradient is described in the local coordinates of the polygon and takes precedence
+    /// over any fill color.
+    fn fill_gradient(mut self, gradient: Gradient) -> Self {
+        self.polygon_options_mut().fill_gradient = Some(gradient);
+        self
+    }
+
+    /// Fill the polygon with the given texture.
+    ///
+    /// Texture coordinates are produced by mapping the bounding rectangle of the polygon to the
+    /// full extent of the texture. The stroke, if any, is also textured. To specify texture
+    /// coordinates explicitly, see the `points_textured` method of `draw.polygon()`.
+    fn texture(mut self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.polygon_options_mut().texture_view = Some(view.to_texture_view());
+        self
+    }
+
     /// Specify the whole set of polygon options.
     fn polygon_options(mut self, opts: PolygonOptions<S>) -> Self {
         *self.polygon_options_mut() = opts;
@@ -45,13 +67,13 @@ pub trait SetPolygon<S>: Sized {
 }
 
 /// State related to drawing a **Polygon**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PolygonInit<S = geom::scalar::Default> {
     pub(crate) opts: PolygonOptions<S>,
 }
 
 /// The set of options shared by all polygon types.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PolygonOptions<S = geom::scalar::Default> {
     pub position: position::Properties<S>,
     pub orientation: orientation::Properties<S>,
@@ -59,13 +81,19 @@ pub struct PolygonOptions<S = geom::scalar::Default> {
     pub stroke_color: Option<LinSrgba>,
     pub color: Option<LinSrgba>,
     pub stroke: Option<StrokeOptions>,
+    pub fill: Option<FillOptions>,
+    pub fill_gradient: Option<Gradient>,
+    pub dash: Option<Dash>,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
+    pub texture_view: Option<wgpu::TextureView>,
 }
 
 /// A polygon with vertices already submitted.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Polygon<S = geom::scalar::Default> {
     opts: PolygonOptions<S>,
     path_event_src: PathEventSource,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
     texture_view: Option<wgpu::TextureView>,
 }
 
@@ -185,7 +213,8 @@ pub fn render_events_themed<F, I>(
     mut ctxt: draw::renderer::RenderContext,
     theme_primitive: &draw::theme::Primitive,
     mesh: &mut draw::Mesh,
-) where
+) -> draw::renderer::PrimitiveRender
+where
     F: Fn() -> I,
     I: Iterator<Item = lyon::path::PathEvent>,
 {
@@ -196,6 +225,10 @@ pub fn render_events_themed<F, I>(
         stroke_color,
         color,
         stroke,
+        fill,
+        fill_gradient,
+        dash,
+        texture_view,
     } = opts;
 
     // Determine the transform to apply to all points.
@@ -203,36 +236,63 @@ pub fn render_events_themed<F, I>(
     let local_transform = position.transform() * orientation.transform();
     let transform = global_transform * local_transform;
 
+    if let Some(texture_view) = texture_view {
+        render_textured(
+            events,
+            no_fill,
+            fill,
+            stroke,
+            dash.as_ref(),
+            transform,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            mesh,
+        );
+        return draw::renderer::PrimitiveRender::texture(texture_view);
+    }
+
     // A function for rendering the path.
-    let mut render =
-        |opts: path::Options,
-         color: Option<LinSrgba>,
-         theme: &draw::Theme,
-         fill_tessellator: &mut lyon::tessellation::FillTessellator,
-         stroke_tessellator: &mut lyon::tessellation::StrokeTessellator| {
-            path::render_path_events(
+    let render = |opts: path::Options,
+                  color: Option<LinSrgba>,
+                  theme: &draw::Theme,
+                  fill_tessellator: &mut lyon::tessellation::FillTessellator,
+                  stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+                  mesh: &mut draw::Mesh| {
+        path::render_path_events(
+            path::dash_stroke_events(events(), dash.as_ref(), &opts),
+            color,
+            transform,
+            opts,
+            theme,
+            theme_primitive,
+            fill_tessellator,
+            stroke_tessellator,
+            mesh,
+        )
+    };
+
+    // Do the fill tessellation first.
+    if !no_fill {
+        let opts = path::Options::Fill(fill.unwrap_or_default());
+        match fill_gradient {
+            Some(ref gradient) => path::render_path_events_gradient(
                 events(),
-                color,
+                gradient,
                 transform,
                 opts,
-                theme,
-                theme_primitive,
-                fill_tessellator,
-                stroke_tessellator,
+                &mut ctxt.fill_tessellator,
+                &mut ctxt.stroke_tessellator,
                 mesh,
-            )
-        };
-
-    // Do the fill tessellation first.
-    if !no_fill {
-        let opts = path::Options::Fill(lyon::tessellation::FillOptions::default());
-        render(
-            opts,
-            color,
-            &ctxt.theme,
-            &mut ctxt.fill_tessellator,
-            &mut ctxt.stroke_tessellator,
-        );
+            ),
+            None => render(
+                opts,
+                color,
+                &ctxt.theme,
+                &mut ctxt.fill_tessellator,
+                &mut ctxt.stroke_tessellator,
+                mesh,
+            ),
+        }
     }
 
     // Do the stroke tessellation on top.
@@ -245,8 +305,11 @@ pub fn render_events_themed<F, I>(
             &ctxt.theme,
             &mut ctxt.fill_tessellator,
             &mut ctxt.stroke_tessellator,
+            mesh,
         );
     }
+
+    draw::renderer::PrimitiveRender::default()
 }
 
 pub fn render_points_themed<I>(
@@ -255,7 +318,8 @@ pub fn render_points_themed<I>(
     ctxt: draw::renderer::RenderContext,
     theme_primitive: &draw::theme::Primitive,
     mesh: &mut draw::Mesh,
-) where
+) -> draw::renderer::PrimitiveRender
+where
     I: Clone + Iterator<Item = Point2>,
 {
     render_events_themed(
@@ -264,7 +328,137 @@ pub fn render_points_themed<I>(
         ctxt,
         theme_primitive,
         mesh,
-    );
+    )
+}
+
+/// Render a closed polygon whose fill interpolates between the colors of the given points.
+///
+/// The colors take precedence over any fill color, while a fill gradient or texture takes
+/// precedence over the colors. The stroke, if any, is rendered as for `render_points_themed`.
+pub fn render_points_colored_themed<I>(
+    mut opts: PolygonOptions,
+    points_colored: I,
+    mut ctxt: draw::renderer::RenderContext,
+    theme_primitive: &draw::theme::Primitive,
+    mesh: &mut draw::Mesh,
+) -> draw::renderer::PrimitiveRender
+where
+    I: Clone + Iterator<Item = (Point2, LinSrgba)>,
+{
+    if !opts.no_fill && opts.fill_gradient.is_none() && opts.texture_view.is_none() {
+        let local_transform = opts.position.transform() * opts.orientation.transform();
+        let transform = ctxt.transform * local_transform;
+        let fill = path::Options::Fill(opts.fill.unwrap_or_default());
+        path::render_path_points_colored(
+            points_colored.clone(),
+            true,
+            transform,
+            fill,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            mesh,
+        );
+        opts.no_fill = true;
+    }
+    let points = points_colored.map(|(p, _)| p);
+    render_points_themed(opts, points, ctxt, theme_primitive, mesh)
+}
+
+// Render the fill and stroke of a textured polygon, mapping the bounds of the path to the full
+// extent of the texture.
+fn render_textured<F, I>(
+    events: F,
+    no_fill: bool,
+    fill: Option<FillOptions>,
+    stroke: Option<StrokeOptions>,
+    dash: Option<&Dash>,
+    transform: crate::math::Matrix4<f32>,
+    fill_tessellator: &mut lyon::tessellation::FillTessellator,
+    stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    mesh: &mut draw::Mesh,
+) where
+    F: Fn() -> I,
+    I: Iterator<Item = PathEvent>,
+{
+    let fill = fill.unwrap_or_default();
+    let bounds = match path::path_events_bounds(events(), fill.tolerance) {
+        None => return,
+        Some(bounds) => bounds,
+    };
+    if !no_fill {
+        path::render_path_events_textured(
+            events(),
+            bounds,
+            transform,
+            path::Options::Fill(fill),
+            fill_tessellator,
+            stroke_tessellator,
+            mesh,
+        );
+    }
+    if let Some(stroke_opts) = stroke {
+        let opts = path::Options::Stroke(stroke_opts);
+        path::render_path_events_textured(
+            path::dash_stroke_events(events(), dash, &opts),
+            bounds,
+            transform,
+            opts,
+            fill_tessellator,
+            stroke_tessellator,
+            mesh,
+        );
+    }
+}
+
+/// Extend `out` with the stroked outline described by the given events for plotting.
+///
+/// Returns the local transform of the polygon, or `None` if the polygon has no stroke.
+pub fn plot_events<I>(
+    opts: &PolygonOptions,
+    events: I,
+    out: &mut Vec<PathEvent>,
+) -> Option<crate::math::Matrix4<f32>>
+where
+    I: IntoIterator<Item = PathEvent>,
+{
+    let stroke_opts = opts.stroke?;
+    let path_opts = path::Options::Stroke(stroke_opts);
+    out.extend(path::dash_stroke_events(
+        events,
+        opts.dash.as_ref(),
+        &path_opts,
+    ));
+    Some(opts.position.transform() * opts.orientation.transform())
+}
+
+/// Extend `out` with the stroked outline of the closed polygon described by the given points for
+/// plotting.
+pub fn plot_points<I>(
+    opts: &PolygonOptions,
+    points: I,
+    out: &mut Vec<PathEvent>,
+) -> Option<crate::math::Matrix4<f32>>
+where
+    I: IntoIterator<Item = Point2>,
+{
+    let points = points.into_iter().map(|p| p.into());
+    plot_events(
+        opts,
+        lyon::path::iterator::FromPolyline::closed(points),
+        out,
+    )
+}
+
+impl<S> Polygon<S> {
+    // The source of the polygon's path events within the intermediary state.
+    pub(crate) fn path_event_src(&self) -> &PathEventSource {
+        &self.path_event_src
+    }
+
+    // Rebase the polygon's range into the intermediary state.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        self.path_event_src.offset_ranges(offsets);
+    }
 }
 
 impl Polygon<f32> {
@@ -284,6 +478,10 @@ impl Polygon<f32> {
                     stroke_color,
                     color,
                     stroke,
+                    fill,
+                    fill_gradient,
+                    dash,
+                    texture_view: opts_texture_view,
                 },
             texture_view,
         } = self;
@@ -293,6 +491,7 @@ impl Polygon<f32> {
             path_event_buffer,
             path_points_colored_buffer,
             path_points_textured_buffer,
+            path_points_weighted_buffer,
             transform,
             theme,
             ..
@@ -303,74 +502,133 @@ impl Polygon<f32> {
         let local_transform = position.transform() * orientation.transform();
         let transform = global_transform * local_transform;
 
+        // A texture specified via the polygon options replaces any texture coordinates.
+        if let Some(texture_view) = opts_texture_view {
+            let events = path_event_src.to_events(
+                path_event_buffer,
+                path_points_colored_buffer,
+                path_points_textured_buffer,
+                path_points_weighted_buffer,
+            );
+            render_textured(
+                || events.iter().cloned(),
+                no_fill,
+                fill,
+                stroke,
+                dash.as_ref(),
+                transform,
+                fill_tessellator,
+                stroke_tessellator,
+                mesh,
+            );
+            return draw::renderer::PrimitiveRender::texture(texture_view);
+        }
+
         // A function for rendering the path.
-        let mut render =
-            |src: path::PathEventSourceIter,
-             opts: path::Options,
-             color: Option<LinSrgba>,
-             theme: &draw::Theme,
-             fill_tessellator: &mut lyon::tessellation::FillTessellator,
-             stroke_tessellator: &mut lyon::tessellation::StrokeTessellator| {
-                path::render_path_source(
-                    src,
-                    color,
+        let render = |src: path::PathEventSourceIter,
+                      opts: path::Options,
+                      color: Option<LinSrgba>,
+                      theme: &draw::Theme,
+                      fill_tessellator: &mut lyon::tessellation::FillTessellator,
+                      stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+                      mesh: &mut draw::Mesh| {
+            path::render_path_source(
+                src,
+                color,
+                transform,
+                opts,
+                theme,
+                theme_primitive,
+                fill_tessellator,
+                stroke_tessellator,
+                mesh,
+            )
+        };
+
+        // Do the fill tessellation first.
+        if !no_fill {
+            let opts = path::Options::Fill(fill.unwrap_or_default());
+            // Gradients replace the colors of the points, so only the outline is required.
+            if let Some(ref gradient) = fill_gradient {
+                let events = path_event_src.to_events(
+                    path_event_buffer,
+                    path_points_colored_buffer,
+                    path_points_textured_buffer,
+                    path_points_weighted_buffer,
+                );
+                path::render_path_events_gradient(
+                    events,
+                    gradient,
                     transform,
                     opts,
-                    theme,
-                    theme_primitive,
                     fill_tessellator,
                     stroke_tessellator,
                     mesh,
-                )
-            };
-
-        // Do the fill tessellation first.
-        if !no_fill {
-            let opts = path::Options::Fill(lyon::tessellation::FillOptions::default());
-            match path_event_src {
-                PathEventSource::Buffered(ref range) => {
-                    let mut events = path_event_buffer[range.clone()].iter().cloned();
-                    let src = path::PathEventSourceIter::Events(&mut events);
-                    render(
-                        src,
-                        opts,
-                        color,
-                        theme,
-                        fill_tessellator,
-                        stroke_tessellator,
-                    );
-                }
-                PathEventSource::ColoredPoints { ref range, close } => {
-                    let mut points_colored =
-                        path_points_colored_buffer[range.clone()].iter().cloned();
-                    let src = path::PathEventSourceIter::ColoredPoints {
-                        points: &mut points_colored,
-                        close,
-                    };
-                    render(
-                        src,
-                        opts,
-                        color,
-                        theme,
-                        fill_tessellator,
-                        stroke_tessellator,
-                    );
-                }
-                PathEventSource::TexturedPoints { ref range, close } => {
-                    let mut textured_points =
-                        path_points_textured_buffer[range.clone()].iter().cloned();
-                    let src = path::PathEventSourceIter::TexturedPoints {
-                        points: &mut textured_points,
-                        close,
-                    };
-                    render(
-                        src,
-                        opts,
-                        color,
-                        theme,
-                        fill_tessellator,
-                        stroke_tessellator,
-                    );
+                );
+            } else {
+                match path_event_src {
+                    PathEventSource::Buffered(ref range) => {
+                        let mut events = path_event_buffer[range.clone()].iter().cloned();
+                        let src = path::PathEventSourceIter::Events(&mut events);
+                        render(
+                            src,
+                            opts,
+                            color,
+                            theme,
+                            fill_tessellator,
+                            stroke_tessellator,
+                            mesh,
+                        );
+                    }
+                    PathEventSource::ColoredPoints { ref range, close } => {
+                        let mut points_colored =
+                            path_points_colored_buffer[range.clone()].iter().cloned();
+                        let src = path::PathEventSourceIter::ColoredPoints {
+                            points: &mut points_colored,
+                            close,
+                        };
+                        render(
+                            src,
+                            opts,
+                            color,
+                            theme,
+                            fill_tessellator,
+                            stroke_tessellator,
+                            mesh,
+                        );
+                    }
+                    PathEventSource::TexturedPoints { ref range, close } => {
+                        let mut textured_points =
+                            path_points_textured_buffer[range.clone()].iter().cloned();
+                        let src = path::PathEventSourceIter::TexturedPoints {
+                            points: &mut textured_points,
+                            close,
+                        };
+                        render(
+                            src,
+                            opts,
+                            color,
+                            theme,
+                            fill_tessellator,
+                            stroke_tessellator,
+                            mesh,
+                        );
+                    }
+                    PathEventSource::WeightedPoints { ref range, close } => {
+                        let points = path_points_weighted_buffer[range.clone()].iter();
+                        let points = points.map(|&(p, _)| lyon::math::point(p.x, p.y));
+                        let mut events = lyon::path::iterator::FromPolyline::new(close, points);
+                        let src = path::PathEventSourceIter::Events(&mut events);
+                        render(
+                            src,
+                            opts,
+                            color,
+                            theme,
+                            fill_tessellator,
+                            stroke_tessellator,
+                            mesh,
+                        );
+                    }
                 }
             }
         }
@@ -380,7 +638,8 @@ impl Polygon<f32> {
             let opts = path::Options::Stroke(stroke_opts);
             match path_event_src {
                 PathEventSource::Buffered(range) => {
-                    let mut events = path_event_buffer[range].iter().cloned();
+                    let events = path_event_buffer[range].iter().cloned();
+                    let mut events = path::dash_stroke_events(events, dash.as_ref(), &opts);
                     let src = path::PathEventSourceIter::Events(&mut events);
                     render(
                         src,
@@ -389,6 +648,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        mesh,
                     );
                 }
                 PathEventSource::ColoredPoints { range, close } => {
@@ -409,6 +669,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        mesh,
                     );
                 }
                 PathEventSource::TexturedPoints { range, close } => {
@@ -424,6 +685,22 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        mesh,
+                    );
+                }
+                PathEventSource::WeightedPoints { range, close } => {
+                    let points = path_points_weighted_buffer[range].iter();
+                    let points = points.map(|&(p, _)| lyon::math::point(p.x, p.y));
+                    let mut events = lyon::path::iterator::FromPolyline::new(close, points);
+                    let src = path::PathEventSourceIter::Events(&mut events);
+                    render(
+                        src,
+                        opts,
+                        stroke_color,
+                        theme,
+                        fill_tessellator,
+                        stroke_tessellator,
+                        mesh,
                     );
                 }
             }
@@ -433,7 +710,11 @@ impl Polygon<f32> {
             None => draw::renderer::PrimitiveRender::default(),
             Some(texture_view) => draw::renderer::PrimitiveRender {
                 texture_view: Some(texture_view),
+                texture_layer: 0,
                 vertex_mode: draw::renderer::VertexMode::Texture,
+                topology: None,
+                fragment_shader: None,
+                instances: vec![],
             },
         }
     }
@@ -449,6 +730,38 @@ impl draw::renderer::RenderPrimitive for Polygon<f32> {
     }
 }
 
+impl draw::plotter_renderer::PlotPrimitive for Polygon<f32> {
+    fn plot_stroke(
+        &self,
+        ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        // As with rendering, dashes only apply to buffered events.
+        let (points, close) = match self.path_event_src {
+            PathEventSource::Buffered(ref range) => {
+                let src = ctxt.path_event_buffer[range.clone()].iter().cloned();
+                return plot_events(&self.opts, src, events);
+            }
+            PathEventSource::ColoredPoints { ref range, close } => {
+                let points = ctxt.path_points_colored_buffer[range.clone()].iter();
+                (points.map(|&(p, _)| p).collect::<Vec<_>>(), close)
+            }
+            PathEventSource::TexturedPoints { ref range, close } => {
+                let points = ctxt.path_points_textured_buffer[range.clone()].iter();
+                (points.map(|&(p, _)| p).collect::<Vec<_>>(), close)
+            }
+            PathEventSource::WeightedPoints { ref range, close } => {
+                let points = ctxt.path_points_weighted_buffer[range.clone()].iter();
+                (points.map(|&(p, _)| p).collect::<Vec<_>>(), close)
+            }
+        };
+        self.opts.stroke?;
+        let points = points.into_iter().map(|p| p.into());
+        events.extend(lyon::path::iterator::FromPolyline::new(close, points));
+        Some(self.opts.position.transform() * self.opts.orientation.transform())
+    }
+}
+
 impl<'a, S, T> Drawing<'a, T, S>
 where
     S: BaseFloat,
@@ -471,6 +784,22 @@ where
         self.map_ty(|ty| ty.stroke_color(color))
     }
 
+    /// Fill the polygon with the given linear or radial gradient.
+    ///
+    /// The gradient is described in the local coordinates of the polygon and takes precedence
+    /// over any fill color.
+    pub fn fill_gradient(self, gradient: Gradient) -> Self {
+        self.map_ty(|ty| ty.fill_gradient(gradient))
+    }
+
+    /// Fill the polygon with the given texture.
+    ///
+    /// Texture coordinates are produced by mapping the bounding rectangle of the polygon to the
+    /// full extent of the texture.
+    pub fn texture(self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.map_ty(|ty| ty.texture(view))
+    }
+
     /// Specify the whole set of polygon options.
     pub fn polygon_options(self, opts: PolygonOptions<S>) -> Self {
         self.map_ty(|ty| ty.polygon_options(opts))
@@ -556,6 +885,10 @@ where
         let color = None;
         let stroke_color = None;
         let stroke = None;
+        let fill = None;
+        let fill_gradient = None;
+        let dash = None;
+        let texture_view = None;
         PolygonOptions {
             position,
             orientation,
@@ -563,6 +896,10 @@ where
             color,
             stroke_color,
             stroke,
+            fill,
+            fill_gradient,
+            dash,
+            texture_view,
         }
     }
 }
@@ -603,6 +940,30 @@ impl<S> SetStroke for PolygonInit<S> {
     }
 }
 
+impl<S> SetDash for PolygonOptions<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.dash)
+    }
+}
+
+impl<S> SetDash for PolygonInit<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.opts.dash)
+    }
+}
+
+impl<S> SetFill for PolygonOptions<S> {
+    fn fill_options_mut(&mut self) -> &mut FillOptions {
+        SetFill::fill_options_mut(&mut self.fill)
+    }
+}
+
+impl<S> SetFill for PolygonInit<S> {
+    fn fill_options_mut(&mut self) -> &mut FillOptions {
+        SetFill::fill_options_mut(&mut self.opts.fill)
+    }
+}
+
 impl<S> SetOrientation<S> for Polygon<S> {
     fn properties(&mut self) -> &mut orientation::Properties<S> {
         SetOrientation::properties(&mut self.opts.orientation)
@@ -0,0 +1,309 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw::mesh::vertex::{self, Point};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::recording;
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Vector2};
+use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
+use std::ops;
+
+/// A polyline through 3D space whose stroke is extruded in screen space.
+///
+/// Rather than tessellating the stroke in the plane of the points, each vertex is extruded
+/// perpendicular to the line as it appears on screen, after the transform has been applied. The
+/// result is a ribbon that always faces the viewer and that maintains its weight regardless of the
+/// orientation of the line.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Polyline3d<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    vertex_range: ops::Range<usize>,
+    color: Option<LinSrgba>,
+    colored_points: bool,
+    weight: f32,
+    closed: bool,
+}
+
+/// The drawing context for a **Polyline3d**.
+pub type DrawingPolyline3d<'a, S = geom::scalar::Default> = Drawing<'a, Polyline3d<S>, S>;
+
+/// The default weight of a **Polyline3d**, matching the default stroke weight of a path.
+pub const DEFAULT_WEIGHT: f32 = 1.0;
+
+// The maximum length of a miter relative to half of the line weight. Joins with sharper angles are
+// clamped in order to avoid long spikes.
+const MITER_LIMIT: f32 = 4.0;
+
+impl<S> Polyline3d<S> {
+    /// Specify the thickness of the line in pixels.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Whether or not the last point should be joined back to the first.
+    pub fn closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+}
+
+impl<S> Polyline3d<S>
+where
+    S: BaseFloat,
+{
+    /// Describe the polyline with a sequence of points.
+    ///
+    /// The whole line is colored with a single color. If a color is not specified via one of the
+    /// builder methods, the stroke color for paths is retrieved from the inner `Theme`.
+    pub fn points<I>(self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Point<S>>,
+    {
+        let points = points
+            .into_iter()
+            .map(|p| (p.into(), vertex::DEFAULT_VERTEX_COLOR));
+        let mut polyline = self.points_inner(inner_mesh, points);
+        polyline.colored_points = false;
+        polyline
+    }
+
+    /// Describe the polyline with a sequence of colored points.
+    ///
+    /// Each of the points must be represented as a tuple containing the point and the color in
+    /// that order, e.g. `(point, color)`. Colors are interpolated along each segment.
+    pub fn points_colored<I, P, C>(self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: Into<Point<S>>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let points = points
+            .into_iter()
+            .map(|(p, c)| (p.into(), c.into_lin_srgba()));
+        let mut polyline = self.points_inner(inner_mesh, points);
+        polyline.colored_points = true;
+        polyline
+    }
+
+    fn points_inner<I>(mut self, inner_mesh: &mut draw::Mesh<S>, points: I) -> Self
+    where
+        I: Iterator<Item = (Point<S>, LinSrgba)>,
+    {
+        let v_start = inner_mesh.points().len();
+        let vertices = points.map(|(point, color)| {
+            let tex_coords = vertex::default_tex_coords();
+            ((point, color), tex_coords).into()
+        });
+        inner_mesh.extend_vertices(vertices);
+        let v_end = inner_mesh.points().len();
+        self.vertex_range = v_start..v_end;
+        self
+    }
+
+    // Rebase the polyline's range into the intermediary mesh.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        recording::offset_range(&mut self.vertex_range, offsets.mesh_vertices);
+    }
+}
+
+impl<'a, S> DrawingPolyline3d<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the thickness of the line in pixels.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+
+    /// Whether or not the last point should be joined back to the first.
+    pub fn closed(self, closed: bool) -> Self {
+        self.map_ty(|ty| ty.closed(closed))
+    }
+
+    /// Describe the polyline with a sequence of points.
+    ///
+    /// The given iterator may yield any type that can be converted directly into `Point3`s.
+    pub fn points<I>(self, points: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Point<S>>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.points(ctxt.mesh, points))
+    }
+
+    /// Describe the polyline with a sequence of colored points.
+    ///
+    /// Each of the points must be represented as a tuple containing the point and the color in
+    /// that order, e.g. `(point, color)`. `point` may be of any type that implements
+    /// `Into<Point3>` and `color` may be of any type that implements `IntoLinSrgba`.
+    pub fn points_colored<I, P, C>(self, points: I) -> Self
+    where
+        I: IntoIterator<Item = (P, C)>,
+        P: Into<Point<S>>,
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.points_colored(ctxt.mesh, points))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Polyline3d<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Polyline3d {
+            position,
+            orientation,
+            vertex_range,
+            color,
+            colored_points,
+            weight,
+            closed,
+        } = self;
+
+        let len = vertex_range.len();
+        if len < 2 {
+            return draw::renderer::PrimitiveRender::default();
+        }
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let transform_point = |p: geom::Point3<f32>| -> geom::Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+
+        let inner = ctxt.intermediary_mesh;
+        let points: Vec<_> = vertex_range
+            .clone()
+            .map(|i| transform_point(inner.points()[i]))
+            .collect();
+        // A color specified via the builder takes precedence over the colors of the points.
+        let uniform_color = match color {
+            Some(color) => Some(color),
+            None if colored_points => None,
+            None => Some(ctxt.theme.stroke_lin_srgba(&draw::theme::Primitive::Path)),
+        };
+
+        // The unit normal of the segment between two points as it appears on screen.
+        let normal = |a: geom::Point3<f32>, b: geom::Point3<f32>| -> Option<Vector2<f32>> {
+            let d = Vector2::new(b.x - a.x, b.y - a.y);
+            let mag = d.magnitude();
+            if mag > 0.0 {
+                Some(Vector2::new(-d.y / mag, d.x / mag))
+            } else {
+                None
+            }
+        };
+
+        // Extrude each point either side of the line, joining segments with a clamped miter.
+        let half_weight = weight * 0.5;
+        let v_start = mesh.raw_vertex_count() as u32;
+        for i in 0..len {
+            let p = points[i];
+            let prev = match i {
+                0 if closed => Some(points[len - 1]),
+                0 => None,
+                _ => Some(points[i - 1]),
+            };
+            let next = match i {
+                _ if i == len - 1 && closed => Some(points[0]),
+                _ if i == len - 1 => None,
+                _ => Some(points[i + 1]),
+            };
+            let n_prev = prev.and_then(|prev| normal(prev, p));
+            let n_next = next.and_then(|next| normal(p, next));
+            let offset = match (n_prev, n_next) {
+                (Some(a), Some(b)) => {
+                    let sum = a + b;
+                    let mag = sum.magnitude();
+                    if mag > 0.0 {
+                        let miter = sum / mag;
+                        let cos = miter.dot(b).max(1.0 / MITER_LIMIT);
+                        miter * (half_weight / cos)
+                    } else {
+                        b * half_weight
+                    }
+                }
+                (Some(n), None) | (None, Some(n)) => n * half_weight,
+                (None, None) => Vector2::new(0.0, 0.0),
+            };
+            let color = uniform_color.unwrap_or_else(|| inner.colors()[vertex_range.start + i]);
+            let tex_coords = vertex::default_tex_coords();
+            let a = geom::pt3(p.x + offset.x, p.y + offset.y, p.z);
+            let b = geom::pt3(p.x - offset.x, p.y - offset.y, p.z);
+            mesh.push_vertex(((a, color), tex_coords).into());
+            mesh.push_vertex(((b, color), tex_coords).into());
+        }
+
+        // Two triangles for each segment.
+        let segments = if closed { len } else { len - 1 };
+        for i in 0..segments as u32 {
+            let j = (i + 1) % len as u32;
+            let (a0, a1) = (v_start + i * 2, v_start + i * 2 + 1);
+            let (b0, b1) = (v_start + j * 2, v_start + j * 2 + 1);
+            mesh.extend_indices([a0, a1, b0, a1, b1, b0].iter().cloned());
+        }
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for Polyline3d<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Polyline3d {
+            position: Default::default(),
+            orientation: Default::default(),
+            vertex_range: 0..0,
+            color: None,
+            colored_points: false,
+            weight: DEFAULT_WEIGHT,
+            closed: false,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Polyline3d<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Polyline3d<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Polyline3d<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Polyline3d<S>> for Primitive<S> {
+    fn from(prim: Polyline3d<S>) -> Self {
+        Primitive::Polyline3d(prim)
+    }
+}
+
+impl<S> Into<Option<Polyline3d<S>>> for Primitive<S> {
+    fn into(self) -> Option<Polyline3d<S>> {
+        match self {
+            Primitive::Polyline3d(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
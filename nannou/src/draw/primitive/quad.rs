@@ -3,19 +3,22 @@ use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPoly
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{dimension, orientation, position};
 use crate::draw::properties::{
-    spatial, ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition, SetStroke,
+    spatial, ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetDimensions, SetOrientation,
+    SetPosition, SetStroke,
 };
 use crate::draw::{self, Drawing};
 use crate::geom::{self, Point2, Vector2};
 use crate::math::{BaseFloat, ElementWise};
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// Properties related to drawing a **Quad**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Quad<S = geom::scalar::Default> {
     quad: geom::Quad<Point2<S>>,
     polygon: PolygonInit<S>,
     dimensions: spatial::dimension::Properties<S>,
+    colors: Option<[LinSrgba; 4]>,
 }
 
 /// The drawing context for a `Quad`.
@@ -44,23 +47,31 @@ impl<S> Quad<S> {
         self.quad = geom::Quad([a, b, c, d]);
         self
     }
+
+    /// Color each of the four vertices (corners) of the quad, in the same order as `points`.
+    ///
+    /// The fill interpolates between the colors of the corners. These colors take precedence over
+    /// the fill color.
+    pub fn colors<C>(mut self, a: C, b: C, c: C, d: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let a = a.into_lin_srgba();
+        let b = b.into_lin_srgba();
+        let c = c.into_lin_srgba();
+        let d = d.into_lin_srgba();
+        self.colors = Some([a, b, c, d]);
+        self
+    }
 }
 
 // Trait implementations.
 
-impl draw::renderer::RenderPrimitive for Quad<f32> {
-    fn render_primitive(
-        self,
-        ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Quad {
-            mut quad,
-            polygon,
-            dimensions,
-        } = self;
-
-        // If dimensions were specified, scale the points to those dimensions.
+impl Quad<f32> {
+    // The quad's points, scaled to the dimensions if any were specified.
+    fn scaled_quad(&self) -> geom::Quad<Point2> {
+        let mut quad = self.quad;
+        let dimensions = &self.dimensions;
         let (maybe_x, maybe_y, _maybe_z) = (dimensions.x, dimensions.y, dimensions.z);
         if maybe_x.is_some() || maybe_y.is_some() {
             let cuboid = quad.bounding_rect();
@@ -79,17 +90,48 @@ impl draw::renderer::RenderPrimitive for Quad<f32> {
             let new_d = translate(d);
             quad = geom::Quad([new_a, new_b, new_c, new_d]);
         }
+        quad
+    }
+}
 
-        let points = quad.vertices();
-        polygon::render_points_themed(
-            polygon.opts,
-            points,
-            ctxt,
-            &draw::theme::Primitive::Quad,
-            mesh,
-        );
+impl draw::renderer::RenderPrimitive for Quad<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let points = self.scaled_quad().vertices();
+        let theme_primitive = &draw::theme::Primitive::Quad;
+        match self.colors {
+            Some(colors) => {
+                let points_colored = points.zip(colors.iter().cloned());
+                polygon::render_points_colored_themed(
+                    self.polygon.opts,
+                    points_colored,
+                    ctxt,
+                    theme_primitive,
+                    mesh,
+                )
+            }
+            None => polygon::render_points_themed(
+                self.polygon.opts,
+                points,
+                ctxt,
+                theme_primitive,
+                mesh,
+            ),
+        }
+    }
+}
 
-        draw::renderer::PrimitiveRender::default()
+impl draw::plotter_renderer::PlotPrimitive for Quad<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let points = self.scaled_quad().vertices();
+        polygon::plot_points(&self.polygon.opts, points, events)
     }
 }
 
@@ -100,10 +142,12 @@ where
     fn from(quad: geom::Quad<Point2<S>>) -> Self {
         let polygon = Default::default();
         let dimensions = Default::default();
+        let colors = None;
         Quad {
             polygon,
             dimensions,
             quad,
+            colors,
         }
     }
 }
@@ -154,6 +198,12 @@ impl<S> SetColor<ColorScalar> for Quad<S> {
     }
 }
 
+impl<S> SetDash for Quad<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
 impl<S> SetStroke for Quad<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.polygon)
@@ -196,4 +246,14 @@ where
     {
         self.map_ty(|ty| ty.points(a, b, c, d))
     }
+
+    /// Color each of the four vertices (corners) of the quad, in the same order as `points`.
+    ///
+    /// The fill interpolates between the colors of the corners.
+    pub fn colors<C>(self, a: C, b: C, c: C, d: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.colors(a, b, c, d))
+    }
 }
@@ -1,19 +1,24 @@
 use crate::color::conv::IntoLinSrgba;
+use crate::color::Mix;
 use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPolygon};
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{dimension, orientation, position};
 use crate::draw::properties::{
-    ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition, SetStroke,
+    ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetDimensions, SetOrientation, SetPosition,
+    SetStroke,
 };
 use crate::draw::{self, Drawing};
 use crate::geom::{self, Vector2};
 use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// Properties related to drawing a **Rect**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Rect<S = geom::scalar::Default> {
     dimensions: dimension::Properties<S>,
+    corner_radii: Option<[S; 4]>,
+    corner_colors: Option<[LinSrgba; 4]>,
     polygon: PolygonInit<S>,
 }
 
@@ -30,6 +35,46 @@ impl<S> Rect<S> {
     {
         self.stroke_color(color)
     }
+
+    /// Round each of the corners of the rect with the given radius.
+    pub fn corner_radius(self, radius: S) -> Self
+    where
+        S: Copy,
+    {
+        self.corner_radii(radius, radius, radius, radius)
+    }
+
+    /// Round the corners of the rect with the given radii, starting from the top left corner and
+    /// continuing clockwise.
+    ///
+    /// Each radius is clamped to half of the shortest side of the rect.
+    pub fn corner_radii(
+        mut self,
+        top_left: S,
+        top_right: S,
+        bottom_right: S,
+        bottom_left: S,
+    ) -> Self {
+        self.corner_radii = Some([top_left, top_right, bottom_right, bottom_left]);
+        self
+    }
+
+    /// Color each of the corners of the rect, starting from the top left corner and continuing
+    /// clockwise.
+    ///
+    /// The fill interpolates between the colors of the corners. These colors take precedence over
+    /// the fill color.
+    pub fn colors<C>(mut self, top_left: C, top_right: C, bottom_right: C, bottom_left: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let tl = top_left.into_lin_srgba();
+        let tr = top_right.into_lin_srgba();
+        let br = bottom_right.into_lin_srgba();
+        let bl = bottom_left.into_lin_srgba();
+        self.corner_colors = Some([tl, tr, br, bl]);
+        self
+    }
 }
 
 impl<'a, S> DrawingRect<'a, S>
@@ -43,38 +88,161 @@ where
     {
         self.map_ty(|ty| ty.stroke(color))
     }
-}
 
-impl draw::renderer::RenderPrimitive for Rect<f32> {
-    fn render_primitive(
-        self,
-        ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Rect {
-            polygon,
-            dimensions,
-        } = self;
+    /// Round each of the corners of the rect with the given radius.
+    pub fn corner_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.corner_radius(radius))
+    }
+
+    /// Round the corners of the rect with the given radii, starting from the top left corner and
+    /// continuing clockwise.
+    pub fn corner_radii(self, top_left: S, top_right: S, bottom_right: S, bottom_left: S) -> Self {
+        self.map_ty(|ty| ty.corner_radii(top_left, top_right, bottom_right, bottom_left))
+    }
 
+    /// Color each of the corners of the rect, starting from the top left corner and continuing
+    /// clockwise.
+    ///
+    /// The fill interpolates between the colors of the corners.
+    pub fn colors<C>(self, top_left: C, top_right: C, bottom_right: C, bottom_left: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.colors(top_left, top_right, bottom_right, bottom_left))
+    }
+}
+
+impl Rect<f32> {
+    // The rect described by the dimensions, centred on the origin.
+    fn rect(&self) -> geom::Rect {
         // If dimensions were specified, scale the points to those dimensions.
-        let (maybe_x, maybe_y, maybe_z) = (dimensions.x, dimensions.y, dimensions.z);
+        let (maybe_x, maybe_y, maybe_z) = (self.dimensions.x, self.dimensions.y, self.dimensions.z);
         assert!(
             maybe_z.is_none(),
             "z dimension support for rect is unimplemented"
         );
         let w = maybe_x.unwrap_or(100.0);
         let h = maybe_y.unwrap_or(100.0);
-        let rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
-        let points = rect.corners().vertices();
-        polygon::render_points_themed(
-            polygon.opts,
-            points,
-            ctxt,
-            &draw::theme::Primitive::Rect,
-            mesh,
-        );
+        geom::Rect::from_wh(Vector2 { x: w, y: h })
+    }
+
+    // The outline of the rect with its corners rounded, or `None` if no corners are rounded.
+    fn rounded_path(&self) -> Option<lyon::path::Path> {
+        let radii = self.corner_radii?;
+        let rect = self.rect().absolute();
+        let max_radius = rect.w().min(rect.h()) * 0.5;
+        let clamp = |radius: f32| radius.abs().min(max_radius);
+        let [tl, tr, br, bl] = radii;
+        let (tl, tr, br, bl) = (clamp(tl), clamp(tr), clamp(br), clamp(bl));
+        if tl == 0.0 && tr == 0.0 && br == 0.0 && bl == 0.0 {
+            return None;
+        }
+        let (l, r, b, t) = rect.l_r_b_t();
+        let mut builder = lyon::path::Path::builder();
+        let quarter = lyon::math::Angle::radians(-std::f32::consts::PI * 0.5);
+        let x_rotation = lyon::math::Angle::radians(0.0);
+        let corner = |builder: &mut lyon::path::Builder, cx: f32, cy: f32, radius: f32| {
+            if radius > 0.0 {
+                let centre = lyon::math::point(cx, cy);
+                let radii = lyon::math::vector(radius, radius);
+                builder.arc(centre, radii, quarter, x_rotation);
+            }
+        };
+        builder.move_to(lyon::math::point(l + tl, t));
+        builder.line_to(lyon::math::point(r - tr, t));
+        corner(&mut builder, r - tr, t - tr, tr);
+        builder.line_to(lyon::math::point(r, b + br));
+        corner(&mut builder, r - br, b + br, br);
+        builder.line_to(lyon::math::point(l + bl, b));
+        corner(&mut builder, l + bl, b + bl, bl);
+        builder.line_to(lyon::math::point(l, t - tl));
+        corner(&mut builder, l + tl, t - tl, tl);
+        builder.close();
+        Some(builder.build())
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Rect<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        match (self.rounded_path(), self.corner_colors) {
+            // Interpolate the corner colors across the flattened outline of the rounded rect.
+            (Some(path), Some([tl, tr, br, bl])) => {
+                use lyon::path::iterator::PathIterator;
+                let rect = self.rect().absolute();
+                let tolerance = self.polygon.opts.fill.unwrap_or_default().tolerance;
+                let points = (&path)
+                    .into_iter()
+                    .flattened(tolerance)
+                    .filter_map(|event| match event {
+                        lyon::path::PathEvent::Line { to, .. } => Some(geom::pt2(to.x, to.y)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let points_colored = points.into_iter().map(|p| {
+                    let u = (p.x - rect.left()) / rect.w();
+                    let v = (p.y - rect.bottom()) / rect.h();
+                    let top = tl.mix(&tr, u);
+                    let bottom = bl.mix(&br, u);
+                    (p, bottom.mix(&top, v))
+                });
+                polygon::render_points_colored_themed(
+                    self.polygon.opts,
+                    points_colored,
+                    ctxt,
+                    &draw::theme::Primitive::Rect,
+                    mesh,
+                )
+            }
+            (Some(path), None) => polygon::render_events_themed(
+                self.polygon.opts,
+                || (&path).into_iter(),
+                ctxt,
+                &draw::theme::Primitive::Rect,
+                mesh,
+            ),
+            (None, Some(colors)) => {
+                let points = self.rect().corners().vertices();
+                let points_colored = points.zip(colors.iter().cloned());
+                polygon::render_points_colored_themed(
+                    self.polygon.opts,
+                    points_colored,
+                    ctxt,
+                    &draw::theme::Primitive::Rect,
+                    mesh,
+                )
+            }
+            (None, None) => {
+                let rect = self.rect();
+                let points = rect.corners().vertices();
+                polygon::render_points_themed(
+                    self.polygon.opts,
+                    points,
+                    ctxt,
+                    &draw::theme::Primitive::Rect,
+                    mesh,
+                )
+            }
+        }
+    }
+}
 
-        draw::renderer::PrimitiveRender::default()
+impl draw::plotter_renderer::PlotPrimitive for Rect<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        match self.rounded_path() {
+            Some(path) => polygon::plot_events(&self.polygon.opts, &path, events),
+            None => {
+                let points = self.rect().corners().vertices();
+                polygon::plot_points(&self.polygon.opts, points, events)
+            }
+        }
     }
 }
 
@@ -95,8 +263,12 @@ where
     fn default() -> Self {
         let dimensions = <_>::default();
         let polygon = <_>::default();
+        let corner_radii = None;
+        let corner_colors = None;
         Rect {
             dimensions,
+            corner_radii,
+            corner_colors,
             polygon,
         }
     }
@@ -126,6 +298,12 @@ impl<S> SetColor<ColorScalar> for Rect<S> {
     }
 }
 
+impl<S> SetDash for Rect<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
 impl<S> SetStroke for Rect<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.polygon)
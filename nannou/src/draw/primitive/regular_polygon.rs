@@ -0,0 +1,224 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw;
+use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPolygon};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{
+    ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
+use crate::draw::Drawing;
+use crate::geom::{self, pt2, Point2};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::tessellation::StrokeOptions;
+
+/// Properties related to drawing a **RegularPolygon**.
+///
+/// All sides of the polygon have equal length and the first vertex lies directly above the
+/// centre.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegularPolygon<S = geom::scalar::Default> {
+    sides: usize,
+    radius: Option<S>,
+    rotation: Option<S>,
+    polygon: PolygonInit<S>,
+}
+
+/// The drawing context for a regular polygon.
+pub type DrawingRegularPolygon<'a, S = geom::scalar::Default> = Drawing<'a, RegularPolygon<S>, S>;
+
+/// The number of sides used if none is specified.
+pub const DEFAULT_SIDES: usize = 6;
+
+/// The radius used if none is specified.
+pub const DEFAULT_RADIUS: f32 = 50.0;
+
+// RegularPolygon-specific methods.
+
+impl<S> RegularPolygon<S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.stroke_color(color)
+    }
+
+    /// The number of sides of the polygon.
+    ///
+    /// Polygons with fewer than three sides are not drawn.
+    pub fn sides(mut self, sides: usize) -> Self {
+        self.sides = sides;
+        self
+    }
+
+    /// The distance from the centre to each vertex.
+    ///
+    /// By default, this is `50.0`.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Rotate the vertices of the polygon counter-clockwise around its centre by the given
+    /// radians.
+    ///
+    /// This is applied to the vertices of the polygon prior to its orientation.
+    pub fn rotation(mut self, radians: S) -> Self {
+        self.rotation = Some(radians);
+        self
+    }
+}
+
+impl RegularPolygon<f32> {
+    // The vertices of the polygon.
+    fn vertices(&self) -> Option<Vec<Point2>> {
+        if self.sides < 3 {
+            return None;
+        }
+        let radius = self.radius.unwrap_or(DEFAULT_RADIUS);
+        let rotation = self.rotation.unwrap_or(0.0);
+        let step = std::f32::consts::PI * 2.0 / self.sides as f32;
+        let vertices = (0..self.sides)
+            .map(|i| {
+                let angle = std::f32::consts::FRAC_PI_2 + rotation + step * i as f32;
+                let (sin, cos) = angle.sin_cos();
+                pt2(cos * radius, sin * radius)
+            })
+            .collect();
+        Some(vertices)
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for RegularPolygon<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        match self.vertices() {
+            None => draw::renderer::PrimitiveRender::default(),
+            Some(vertices) => polygon::render_points_themed(
+                self.polygon.opts,
+                vertices.into_iter(),
+                ctxt,
+                &draw::theme::Primitive::RegularPolygon,
+                mesh,
+            ),
+        }
+    }
+}
+
+impl draw::plotter_renderer::PlotPrimitive for RegularPolygon<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let vertices = self.vertices()?;
+        polygon::plot_points(&self.polygon.opts, vertices, events)
+    }
+}
+
+impl<S> Default for RegularPolygon<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        RegularPolygon {
+            sides: DEFAULT_SIDES,
+            radius: None,
+            rotation: None,
+            polygon: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for RegularPolygon<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetPosition<S> for RegularPolygon<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for RegularPolygon<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetDash for RegularPolygon<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetStroke for RegularPolygon<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetPolygon<S> for RegularPolygon<S> {
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S> {
+        SetPolygon::polygon_options_mut(&mut self.polygon)
+    }
+}
+
+// Primitive conversion.
+
+impl<S> From<RegularPolygon<S>> for Primitive<S> {
+    fn from(prim: RegularPolygon<S>) -> Self {
+        Primitive::RegularPolygon(prim)
+    }
+}
+
+impl<S> Into<Option<RegularPolygon<S>>> for Primitive<S> {
+    fn into(self) -> Option<RegularPolygon<S>> {
+        match self {
+            Primitive::RegularPolygon(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingRegularPolygon<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.stroke(color))
+    }
+
+    /// The number of sides of the polygon.
+    pub fn sides(self, sides: usize) -> Self {
+        self.map_ty(|ty| ty.sides(sides))
+    }
+
+    /// The distance from the centre to each vertex.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// Rotate the vertices of the polygon counter-clockwise around its centre by the given
+    /// radians.
+    pub fn rotation(self, radians: S) -> Self {
+        self.map_ty(|ty| ty.rotation(radians))
+    }
+}
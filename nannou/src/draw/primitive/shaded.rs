@@ -0,0 +1,77 @@
+use crate::draw::primitive::Primitive;
+use crate::draw::{self, renderer::RenderPrimitive};
+use crate::geom;
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::wgpu;
+use std::sync::Arc;
+
+/// A user-provided fragment shader with which a drawing is rendered, along with the uniform data
+/// made available to it.
+///
+/// See `Drawing::fragment_shader`.
+#[derive(Clone, Debug)]
+pub struct FragmentShader {
+    /// The SPIR-V fragment shader module.
+    pub module: Arc<wgpu::ShaderModule>,
+    /// The bytes of the uniform buffer bound at set 3, binding 0, if any.
+    pub uniforms: Option<Vec<u8>>,
+}
+
+/// A primitive rendered via a user-provided **FragmentShader** in place of the default.
+///
+/// Drawings given a `fragment_shader` are wrapped in this type once they are finished.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Shaded<S = geom::scalar::Default> {
+    primitive: Box<Primitive<S>>,
+    #[serde(with = "crate::draw::serialize::resource")]
+    shader: FragmentShader,
+}
+
+impl<S> Shaded<S> {
+    pub(crate) fn new(primitive: Primitive<S>, shader: FragmentShader) -> Self {
+        let primitive = Box::new(primitive);
+        Shaded { primitive, shader }
+    }
+
+    /// The primitive whose geometry is rendered via the shader.
+    pub fn primitive(&self) -> &Primitive<S> {
+        &self.primitive
+    }
+
+    /// The shader with which the primitive is rendered.
+    pub fn shader(&self) -> &FragmentShader {
+        &self.shader
+    }
+
+    pub(crate) fn primitive_mut(&mut self) -> &mut Primitive<S> {
+        &mut self.primitive
+    }
+}
+
+impl RenderPrimitive for Shaded<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Shaded { primitive, shader } = self;
+        let mut render = (*primitive).render_primitive(ctxt, mesh);
+        render.fragment_shader = Some(shader);
+        render
+    }
+}
+
+impl<S> From<Shaded<S>> for Primitive<S> {
+    fn from(prim: Shaded<S>) -> Self {
+        Primitive::Shaded(prim)
+    }
+}
+
+impl<S> Into<Option<Shaded<S>>> for Primitive<S> {
+    fn into(self) -> Option<Shaded<S>> {
+        match self {
+            Primitive::Shaded(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use crate::draw::mesh::vertex::TexCoords;
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, pt2, pt3, Point3};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::wgpu;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+/// Properties related to drawing a **Sphere**.
+///
+/// By default the sphere is tessellated as a UV sphere, where vertices lie at the intersections of
+/// lines of latitude and longitude. Alternatively, `subdivisions` may be used to tessellate an
+/// icosphere, whose triangles are more evenly distributed.
+///
+/// Texture coordinates map the full extent of a texture around the sphere using an
+/// equirectangular projection, where `x` follows the longitude and `y` the latitude.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sphere<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    radius: Option<S>,
+    tessellation: Tessellation,
+    color: Option<LinSrgba>,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
+    texture_view: Option<wgpu::TextureView>,
+}
+
+/// The method used to tessellate a **Sphere** into triangles.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Tessellation {
+    /// A UV sphere with the given number of segments around the equator.
+    ///
+    /// Half as many rings are used from pole to pole.
+    Uv { resolution: usize },
+    /// An icosahedron whose triangles are subdivided the given number of times.
+    Ico { subdivisions: usize },
+}
+
+/// The drawing context for a **Sphere**.
+pub type DrawingSphere<'a, S = geom::scalar::Default> = Drawing<'a, Sphere<S>, S>;
+
+/// The radius used if none is specified.
+pub const DEFAULT_RADIUS: f32 = 50.0;
+
+/// The number of segments around the equator of a UV sphere if no resolution is specified.
+pub const DEFAULT_RESOLUTION: usize = 32;
+
+// The maximum number of icosphere subdivisions. Each subdivision quadruples the triangle count.
+const MAX_SUBDIVISIONS: usize = 8;
+
+impl<S> Sphere<S> {
+    /// The radius of the sphere.
+    ///
+    /// By default, this is `50.0`.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Tessellate the sphere as a UV sphere with the given number of segments around its equator.
+    ///
+    /// Resolutions below `3` are clamped to `3`.
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.tessellation = Tessellation::Uv { resolution };
+        self
+    }
+
+    /// Tessellate the sphere as an icosphere, subdividing each triangle of an icosahedron the
+    /// given number of times.
+    ///
+    /// Subdivisions are clamped to a maximum of `8`.
+    pub fn subdivisions(mut self, subdivisions: usize) -> Self {
+        self.tessellation = Tessellation::Ico { subdivisions };
+        self
+    }
+
+    /// Wrap the given texture around the sphere.
+    pub fn texture(mut self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.texture_view = Some(view.to_texture_view());
+        self
+    }
+}
+
+impl<'a, S> DrawingSphere<'a, S>
+where
+    S: BaseFloat,
+{
+    /// The radius of the sphere.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// Tessellate the sphere as a UV sphere with the given number of segments around its equator.
+    pub fn resolution(self, resolution: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+
+    /// Tessellate the sphere as an icosphere, subdividing each triangle of an icosahedron the
+    /// given number of times.
+    pub fn subdivisions(self, subdivisions: usize) -> Self {
+        self.map_ty(|ty| ty.subdivisions(subdivisions))
+    }
+
+    /// Wrap the given texture around the sphere.
+    pub fn texture(self, view: &dyn wgpu::ToTextureView) -> Self {
+        self.map_ty(|ty| ty.texture(view))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Sphere<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Sphere {
+            position,
+            orientation,
+            radius,
+            tessellation,
+            color,
+            texture_view,
+        } = self;
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let transform_point = |p: Point3<f32>| -> Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+
+        // Generate the unit sphere.
+        let (points, tex_coords, indices) = match tessellation {
+            Tessellation::Uv { resolution } => uv_sphere(resolution),
+            Tessellation::Ico { subdivisions } => ico_sphere(subdivisions),
+        };
+
+        let radius = radius.map(f32::abs).unwrap_or(DEFAULT_RADIUS);
+        let color =
+            color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&draw::theme::Primitive::Sphere));
+        let v_start = mesh.raw_vertex_count() as u32;
+        let vertices = points.into_iter().zip(tex_coords).map(|(p, tc)| {
+            let p = transform_point(p * radius);
+            ((p, color), tc).into()
+        });
+        mesh.extend(vertices, indices.into_iter().map(|i| v_start + i));
+
+        match texture_view {
+            Some(view) => draw::renderer::PrimitiveRender::texture(view),
+            None => draw::renderer::PrimitiveRender::default(),
+        }
+    }
+}
+
+// The points, texture coordinates and indices of a unit UV sphere.
+//
+// Each ring duplicates its first vertex so that texture coordinates may wrap around the seam.
+fn uv_sphere(resolution: usize) -> (Vec<Point3>, Vec<TexCoords>, Vec<u32>) {
+    let segments = resolution.max(3);
+    let rings = (segments / 2).max(2);
+    let mut points = Vec::with_capacity((rings + 1) * (segments + 1));
+    let mut tex_coords = Vec::with_capacity(points.capacity());
+    for ring in 0..=rings {
+        let v = ring as f32 / rings as f32;
+        let latitude = (v - 0.5) * PI;
+        let (y, ring_radius) = latitude.sin_cos();
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let longitude = u * PI * 2.0;
+            let (sin, cos) = longitude.sin_cos();
+            points.push(pt3(cos * ring_radius, y, -sin * ring_radius));
+            tex_coords.push(pt2(u, v));
+        }
+    }
+    let stride = segments as u32 + 1;
+    let mut indices = Vec::with_capacity(rings * segments * 6);
+    for ring in 0..rings as u32 {
+        for segment in 0..segments as u32 {
+            let a = ring * stride + segment;
+            let b = a + 1;
+            let c = a + stride;
+            let d = c + 1;
+            indices.extend_from_slice(&[a, b, d, a, d, c]);
+        }
+    }
+    (points, tex_coords, indices)
+}
+
+// The points, texture coordinates and indices of a unit icosphere.
+//
+// Texture coordinates are derived from the spherical coordinates of each vertex. As vertices are
+// shared between triangles, textures may appear stretched across the seam at the back of the
+// sphere.
+fn ico_sphere(subdivisions: usize) -> (Vec<Point3>, Vec<TexCoords>, Vec<u32>) {
+    let t = (1.0 + 5.0f32.sqrt()) * 0.5;
+    let mut points: Vec<Point3> = [
+        (-1.0, t, 0.0),
+        (1.0, t, 0.0),
+        (-1.0, -t, 0.0),
+        (1.0, -t, 0.0),
+        (0.0, -1.0, t),
+        (0.0, 1.0, t),
+        (0.0, -1.0, -t),
+        (0.0, 1.0, -t),
+        (t, 0.0, -1.0),
+        (t, 0.0, 1.0),
+        (-t, 0.0, -1.0),
+        (-t, 0.0, 1.0),
+    ]
+    .iter()
+    .map(|&(x, y, z)| pt3(x, y, z).normalize())
+    .collect();
+    let mut indices: Vec<u32> = vec![
+        0, 11, 5, 0, 5, 1, 0, 1, 7, 0, 7, 10, 0, 10, 11, 1, 5, 9, 5, 11, 4, 11, 10, 2, 10, 7, 6, 7,
+        1, 8, 3, 9, 4, 3, 4, 2, 3, 2, 6, 3, 6, 8, 3, 8, 9, 4, 9, 5, 2, 4, 11, 6, 2, 10, 8, 6, 7, 9,
+        8, 1,
+    ];
+
+    // Split each triangle into four, sharing the midpoints of edges between triangles.
+    for _ in 0..subdivisions.min(MAX_SUBDIVISIONS) {
+        let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut midpoint = |a: u32, b: u32, points: &mut Vec<Point3>| -> u32 {
+            let key = (a.min(b), a.max(b));
+            *midpoints.entry(key).or_insert_with(|| {
+                let p = (points[a as usize] + points[b as usize]) * 0.5;
+                points.push(p.normalize());
+                points.len() as u32 - 1
+            })
+        };
+        let mut subdivided = Vec::with_capacity(indices.len() * 4);
+        for tri in indices.chunks_exact(3) {
+            let (a, b, c) = (tri[0], tri[1], tri[2]);
+            let ab = midpoint(a, b, &mut points);
+            let bc = midpoint(b, c, &mut points);
+            let ca = midpoint(c, a, &mut points);
+            subdivided.extend_from_slice(&[a, ab, ca, b, bc, ab, c, ca, bc, ab, bc, ca]);
+        }
+        indices = subdivided;
+    }
+
+    let tex_coords = points
+        .iter()
+        .map(|p| {
+            let u = (-p.z).atan2(p.x) / (PI * 2.0);
+            let u = if u < 0.0 { u + 1.0 } else { u };
+            let v = p.y.asin() / PI + 0.5;
+            pt2(u, v)
+        })
+        .collect();
+    (points, tex_coords, indices)
+}
+
+impl<S> Default for Sphere<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Sphere {
+            position: Default::default(),
+            orientation: Default::default(),
+            radius: None,
+            tessellation: Tessellation::Uv {
+                resolution: DEFAULT_RESOLUTION,
+            },
+            color: None,
+            texture_view: None,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Sphere<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Sphere<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Sphere<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Sphere<S>> for Primitive<S> {
+    fn from(prim: Sphere<S>) -> Self {
+        Primitive::Sphere(prim)
+    }
+}
+
+impl<S> Into<Option<Sphere<S>>> for Primitive<S> {
+    fn into(self) -> Option<Sphere<S>> {
+        match self {
+            Primitive::Sphere(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
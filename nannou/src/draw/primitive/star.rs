@@ -0,0 +1,245 @@
+use crate::color::conv::IntoLinSrgba;
+use crate::draw;
+use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPolygon};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{
+    ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetOrientation, SetPosition, SetStroke,
+};
+use crate::draw::Drawing;
+use crate::geom::{self, pt2, Point2};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::tessellation::StrokeOptions;
+
+/// Properties related to drawing a **Star**.
+///
+/// The star's points alternate between the outer and inner radius, beginning with an outer point
+/// directly above the centre.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Star<S = geom::scalar::Default> {
+    points: usize,
+    inner_radius: Option<S>,
+    outer_radius: Option<S>,
+    rotation: Option<S>,
+    polygon: PolygonInit<S>,
+}
+
+/// The drawing context for a star.
+pub type DrawingStar<'a, S = geom::scalar::Default> = Drawing<'a, Star<S>, S>;
+
+/// The number of points used if none is specified.
+pub const DEFAULT_POINTS: usize = 5;
+
+/// The outer radius used if none is specified.
+pub const DEFAULT_OUTER_RADIUS: f32 = 50.0;
+
+/// The inner radius as a fraction of the outer radius, used if no inner radius is specified.
+pub const DEFAULT_INNER_RADIUS_RATIO: f32 = 0.5;
+
+// Star-specific methods.
+
+impl<S> Star<S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.stroke_color(color)
+    }
+
+    /// The number of outer points on the star.
+    ///
+    /// Stars with fewer than two points are not drawn.
+    pub fn points(mut self, points: usize) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// The distance from the centre to each of the inner vertices.
+    ///
+    /// By default, this is half of the outer radius.
+    pub fn inner_radius(mut self, radius: S) -> Self {
+        self.inner_radius = Some(radius);
+        self
+    }
+
+    /// The distance from the centre to the tip of each point.
+    ///
+    /// By default, this is `50.0`.
+    pub fn outer_radius(mut self, radius: S) -> Self {
+        self.outer_radius = Some(radius);
+        self
+    }
+
+    /// Rotate the points of the star counter-clockwise around its centre by the given radians.
+    ///
+    /// This is applied to the vertices of the star prior to its orientation.
+    pub fn rotation(mut self, radians: S) -> Self {
+        self.rotation = Some(radians);
+        self
+    }
+}
+
+impl Star<f32> {
+    // The vertices of the star, alternating between outer and inner points.
+    fn vertices(&self) -> Option<Vec<Point2>> {
+        if self.points < 2 {
+            return None;
+        }
+        let outer = self.outer_radius.unwrap_or(DEFAULT_OUTER_RADIUS);
+        let inner = self
+            .inner_radius
+            .unwrap_or(outer * DEFAULT_INNER_RADIUS_RATIO);
+        let rotation = self.rotation.unwrap_or(0.0);
+        let n_vertices = self.points * 2;
+        let step = std::f32::consts::PI * 2.0 / n_vertices as f32;
+        let vertices = (0..n_vertices)
+            .map(|i| {
+                let radius = if i % 2 == 0 { outer } else { inner };
+                let angle = std::f32::consts::FRAC_PI_2 + rotation + step * i as f32;
+                let (sin, cos) = angle.sin_cos();
+                pt2(cos * radius, sin * radius)
+            })
+            .collect();
+        Some(vertices)
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Star<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        match self.vertices() {
+            None => draw::renderer::PrimitiveRender::default(),
+            Some(vertices) => polygon::render_points_themed(
+                self.polygon.opts,
+                vertices.into_iter(),
+                ctxt,
+                &draw::theme::Primitive::Star,
+                mesh,
+            ),
+        }
+    }
+}
+
+impl draw::plotter_renderer::PlotPrimitive for Star<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let vertices = self.vertices()?;
+        polygon::plot_points(&self.polygon.opts, vertices, events)
+    }
+}
+
+impl<S> Default for Star<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Star {
+            points: DEFAULT_POINTS,
+            inner_radius: None,
+            outer_radius: None,
+            rotation: None,
+            polygon: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Star<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetPosition<S> for Star<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.polygon)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Star<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetDash for Star<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetStroke for Star<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.polygon)
+    }
+}
+
+impl<S> SetPolygon<S> for Star<S> {
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S> {
+        SetPolygon::polygon_options_mut(&mut self.polygon)
+    }
+}
+
+// Primitive conversion.
+
+impl<S> From<Star<S>> for Primitive<S> {
+    fn from(prim: Star<S>) -> Self {
+        Primitive::Star(prim)
+    }
+}
+
+impl<S> Into<Option<Star<S>>> for Primitive<S> {
+    fn into(self) -> Option<Star<S>> {
+        match self {
+            Primitive::Star(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingStar<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Stroke the outline with the given color.
+    pub fn stroke<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.stroke(color))
+    }
+
+    /// The number of outer points on the star.
+    pub fn points(self, points: usize) -> Self {
+        self.map_ty(|ty| ty.points(points))
+    }
+
+    /// The distance from the centre to each of the inner vertices.
+    pub fn inner_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.inner_radius(radius))
+    }
+
+    /// The distance from the centre to the tip of each point.
+    pub fn outer_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.outer_radius(radius))
+    }
+
+    /// Rotate the points of the star counter-clockwise around its centre by the given radians.
+    pub fn rotation(self, radians: S) -> Self {
+        self.map_ty(|ty| ty.rotation(radians))
+    }
+}
@@ -0,0 +1,143 @@
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{self, dimension, orientation, position};
+use crate::draw::properties::{
+    ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition,
+};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::svg;
+
+/// Properties related to drawing an **Svg**.
+///
+/// The SVG is drawn centred on its position at the size given by its `width` and `height`
+/// attributes, unless other dimensions are specified.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Svg<S = geom::scalar::Default> {
+    svg: svg::Svg,
+    spatial: spatial::Properties<S>,
+    color: Option<LinSrgba>,
+}
+
+/// The drawing context for an **Svg**.
+pub type DrawingSvg<'a, S = geom::scalar::Default> = Drawing<'a, Svg<S>, S>;
+
+impl<S> Svg<S>
+where
+    S: BaseFloat,
+{
+    pub(crate) fn new(svg: &svg::Svg) -> Self {
+        let size = svg.size();
+        let w = S::from(size.x).unwrap();
+        let h = S::from(size.y).unwrap();
+        let spatial = spatial::Properties::default().w_h(w, h);
+        let color = None;
+        Svg {
+            svg: svg.clone(),
+            spatial,
+            color,
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Svg<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Svg {
+            svg,
+            spatial,
+            color,
+        } = self;
+        let spatial::Properties {
+            dimensions,
+            position,
+            orientation,
+        } = spatial;
+
+        // Scale the paths from the SVG's own size to the specified dimensions.
+        let size = svg.size();
+        let w = dimensions.x.unwrap_or(size.x);
+        let h = dimensions.y.unwrap_or(size.y);
+        let scale_x = if size.x != 0.0 { w / size.x } else { 1.0 };
+        let scale_y = if size.y != 0.0 { h / size.y } else { 1.0 };
+        let scale = cgmath::Matrix4::from_nonuniform_scale(scale_x, scale_y, 1.0);
+
+        // Determine the transform to apply to all points.
+        let global_transform = *ctxt.transform;
+        let local_transform = position.transform() * orientation.transform() * scale;
+        let transform = global_transform * local_transform;
+
+        // Tessellate the fill of each path followed by its stroke, in document order.
+        for path in svg.paths() {
+            let events = path.events.iter().cloned();
+            if let Some((fill_color, ref opts)) = path.fill {
+                let color = color.unwrap_or(fill_color);
+                let mut builder = draw::mesh::MeshBuilder::single_color(mesh, transform, color);
+                if let Err(err) =
+                    ctxt.fill_tessellator
+                        .tessellate(events.clone(), opts, &mut builder)
+                {
+                    eprintln!("failed to tessellate svg path fill: {:?}", err);
+                }
+            }
+            if let Some((stroke_color, ref opts)) = path.stroke {
+                let color = color.unwrap_or(stroke_color);
+                let mut builder = draw::mesh::MeshBuilder::single_color(mesh, transform, color);
+                if let Err(err) = ctxt
+                    .stroke_tessellator
+                    .tessellate(events, opts, &mut builder)
+                {
+                    eprintln!("failed to tessellate svg path stroke: {:?}", err);
+                }
+            }
+        }
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> SetOrientation<S> for Svg<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetPosition<S> for Svg<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetDimensions<S> for Svg<S> {
+    fn properties(&mut self) -> &mut dimension::Properties<S> {
+        SetDimensions::properties(&mut self.spatial)
+    }
+}
+
+/// Recolor every fill and stroke of the SVG with a single color.
+impl<S> SetColor<ColorScalar> for Svg<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Svg<S>> for Primitive<S> {
+    fn from(prim: Svg<S>) -> Self {
+        Primitive::Svg(prim)
+    }
+}
+
+impl<S> Into<Option<Svg<S>>> for Primitive<S> {
+    fn into(self) -> Option<Svg<S>> {
+        match self {
+            Primitive::Svg(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -4,22 +4,55 @@ use crate::draw::properties::spatial::{self, dimension, orientation, position};
 use crate::draw::properties::{
     ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition,
 };
+use crate::draw::recording;
 use crate::draw::{self, theme, Drawing};
-use crate::geom::{self, Vector2};
+use crate::geom::{self, Point2, Vector2};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::text::{self, Align, Font, FontSize, Justify, Layout, Scalar, Wrap};
+use lyon::path::PathEvent;
+use std::cmp::Ordering;
 
 /// Properties related to drawing the **Text** primitive.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Text<S = geom::scalar::Default> {
     spatial: spatial::Properties<S>,
     style: Style,
     // The byte range into the `Draw` context's text buffer.
     text: std::ops::Range<usize>,
+    // The range into the `Draw` context's path event buffer for the path along which the text is
+    // laid out, if any.
+    path: Option<std::ops::Range<usize>>,
+    // Additional spans of text that follow the initial text, each with their own styling.
+    spans: Vec<Span>,
+    // Whether or not glyphs are drawn via signed distance fields.
+    sdf: bool,
+}
+
+/// A span of text with its own color, font and font size, appended to a **Text** via `span`.
+///
+/// Properties that are not specified fall back to those of the **Text**.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Span {
+    pub color: Option<LinSrgba>,
+    #[serde(with = "crate::draw::serialize::optional_resource")]
+    pub font: Option<Font>,
+    pub font_size: Option<FontSize>,
+    // The byte range into the `Draw` context's text buffer.
+    text: std::ops::Range<usize>,
+}
+
+// A run of glyphs that share a font and color, positioned within the pixel space of the output
+// attachment.
+#[derive(Clone, Debug)]
+pub(crate) struct GlyphRun {
+    pub font_index: usize,
+    pub color: Option<LinSrgba>,
+    pub glyphs: Vec<text::PositionedGlyph>,
 }
 
 /// Styling properties for the **Text** primitive.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Style {
     pub color: Option<LinSrgba>,
     pub layout: text::layout::Builder,
@@ -28,6 +61,19 @@ pub struct Style {
 /// The drawing context for the **Text** primitive.
 pub type DrawingText<'a, S = geom::scalar::Default> = Drawing<'a, Text<S>, S>;
 
+/// The width and height of the layout rect for text whose dimensions are not specified.
+pub const DEFAULT_LAYOUT_SIZE: f32 = 200.0;
+
+/// The tolerance with which the curves of a path are flattened when laying out text along it.
+pub const PATH_TOLERANCE: f32 = 0.1;
+
+// A flattened path along which the glyphs of some text may be placed.
+struct PathSampler {
+    // Each line segment alongside the distance along the path at which it begins.
+    segments: Vec<(Point2, Point2, f32)>,
+    length: f32,
+}
+
 impl<S> Text<S> {
     /// Begin drawing some text.
     pub fn new(ctxt: DrawingContext<S>, text: &str) -> Self
@@ -40,13 +86,80 @@ impl<S> Text<S> {
         let text = start..end;
         let spatial = Default::default();
         let style = Default::default();
+        let path = None;
+        let spans = vec![];
+        let sdf = false;
         Text {
             spatial,
             style,
             text,
+            path,
+            spans,
+            sdf,
+        }
+    }
+
+    // Rebase the text's byte range into the intermediary text buffer.
+    pub(crate) fn offset_ranges(&mut self, offsets: &recording::Offsets) {
+        recording::offset_range(&mut self.text, offsets.text);
+        if let Some(ref mut path) = self.path {
+            recording::offset_range(path, offsets.path_events);
+        }
+        for span in &mut self.spans {
+            recording::offset_range(&mut span.text, offsets.text);
         }
     }
 
+    /// Append a span of text that may be styled separately from the rest of the text.
+    ///
+    /// Following calls to `color`, `font` and `font_size` apply to the most recently appended
+    /// span rather than the whole text, e.g. `.span("foo").color(RED).span("bar").font(other)`.
+    /// Layout parameters such as wrapping and justification always apply to the whole text.
+    pub fn span(mut self, ctxt: DrawingContext<S>, text: &str) -> Self {
+        let start = ctxt.text_buffer.len();
+        ctxt.text_buffer.push_str(text);
+        let end = ctxt.text_buffer.len();
+        self.spans.push(Span {
+            color: None,
+            font: None,
+            font_size: None,
+            text: start..end,
+        });
+        self
+    }
+
+    /// Lay out each glyph along the given path, rotating it to follow the path's direction.
+    ///
+    /// The *x* axis of the text's layout rect is mapped onto the distance along the path, with
+    /// the left edge of the rect at the start of the path. Each line's baseline follows the path,
+    /// with subsequent lines offset perpendicular to it. Glyphs whose centre falls beyond either
+    /// end of the path are not drawn.
+    ///
+    /// The path is described in the same local coordinates as the text.
+    pub fn along_path<I>(mut self, ctxt: DrawingContext<S>, events: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: std::borrow::Borrow<PathEvent>,
+    {
+        use std::borrow::Borrow;
+        let start = ctxt.path_event_buffer.len();
+        let events = events.into_iter().map(|e| *e.borrow());
+        ctxt.path_event_buffer.extend(events);
+        let end = ctxt.path_event_buffer.len();
+        self.path = Some(start..end);
+        self
+    }
+
+    /// Draw the glyphs via signed distance fields rather than rasterizing them at their size.
+    ///
+    /// Each glyph is rasterized once into a distance field from which its edges are reconstructed
+    /// by the fragment shader at any scale. This keeps text crisp when it is scaled up, rotated or
+    /// zoomed by the draw transform, at the cost of slightly softer corners at small sizes.
+    pub fn sdf(mut self) -> Self {
+        self.sdf = true;
+        self
+    }
+
     // Apply the given function to the inner text layout.
     fn map_layout<F>(mut self, map: F) -> Self
     where
@@ -57,8 +170,16 @@ impl<S> Text<S> {
     }
 
     /// The font size to use for the text.
-    pub fn font_size(self, size: FontSize) -> Self {
-        self.map_layout(|l| l.font_size(size))
+    ///
+    /// If a span has been appended, this applies to the most recent span.
+    pub fn font_size(mut self, size: FontSize) -> Self {
+        match self.spans.last_mut() {
+            Some(span) => {
+                span.font_size = Some(size);
+                self
+            }
+            None => self.map_layout(|l| l.font_size(size)),
+        }
     }
 
     /// Specify whether or not text should be wrapped around some width and how to do so.
@@ -90,8 +211,27 @@ impl<S> Text<S> {
     }
 
     /// A method for specifying the `Font` used for displaying the `Text`.
-    pub fn font(self, font: Font) -> Self {
-        self.map_layout(|l| l.font(font))
+    ///
+    /// If a span has been appended, this applies to the most recent span.
+    pub fn font(mut self, font: Font) -> Self {
+        match self.spans.last_mut() {
+            Some(span) => {
+                span.font = Some(font);
+                self
+            }
+            None => self.map_layout(|l| l.font(font)),
+        }
+    }
+
+    /// Specify the fonts to fall back to, in order, for characters that the font has no glyph
+    /// for, e.g. CJK characters or symbols.
+    ///
+    /// The fallback applies to the whole text, including all spans.
+    pub fn font_fallback<I>(self, fonts: I) -> Self
+    where
+        I: IntoIterator<Item = Font>,
+    {
+        self.map_layout(|l| l.font_fallback(fonts))
     }
 
     /// Describe the end along the *x* axis to which the text should be aligned.
@@ -114,11 +254,34 @@ impl<S> Text<S> {
         self.map_layout(|l| l.right_justify())
     }
 
+    /// Align wrapped lines to both ends of the bounding **Rect**'s *x* axis range.
+    pub fn full_justify(self) -> Self {
+        self.map_layout(|l| l.full_justify())
+    }
+
     /// Specify how much vertical space should separate each line of text.
     pub fn line_spacing(self, spacing: Scalar) -> Self {
         self.map_layout(|l| l.line_spacing(spacing))
     }
 
+    /// Specify how much additional horizontal space should separate each character.
+    pub fn letter_spacing(self, spacing: Scalar) -> Self {
+        self.map_layout(|l| l.letter_spacing(spacing))
+    }
+
+    /// Specify how much additional horizontal space should follow each whitespace character.
+    pub fn word_spacing(self, spacing: Scalar) -> Self {
+        self.map_layout(|l| l.word_spacing(spacing))
+    }
+
+    /// Specify the maximum width of each line of text.
+    ///
+    /// Lines are wrapped and justified within the smaller of this width and the width of the
+    /// **Text**.
+    pub fn max_width(self, max_width: Scalar) -> Self {
+        self.map_layout(|l| l.max_width(Some(max_width)))
+    }
+
     /// Specify how the whole text should be aligned along the y axis of its bounding rectangle
     pub fn y_align(self, align: Align) -> Self {
         self.map_layout(|l| l.y_align(align))
@@ -182,11 +345,28 @@ where
         self.map_ty(|ty| ty.font(font))
     }
 
+    /// Specify the fonts to fall back to, in order, for characters that the font has no glyph
+    /// for, e.g. CJK characters or symbols.
+    pub fn font_fallback<I>(self, fonts: I) -> Self
+    where
+        I: IntoIterator<Item = text::Font>,
+    {
+        self.map_ty(|ty| ty.font_fallback(fonts))
+    }
+
     /// Build the **Text** with the given **Style**.
     pub fn with_style(self, style: Style) -> Self {
         self.map_ty(|ty| ty.with_style(style))
     }
 
+    /// Draw the glyphs via signed distance fields so that the text stays crisp when scaled up,
+    /// rotated or zoomed.
+    ///
+    /// See `Text::sdf` for details.
+    pub fn sdf(self) -> Self {
+        self.map_ty(|ty| ty.sdf())
+    }
+
     /// Describe the end along the *x* axis to which the text should be aligned.
     pub fn justify(self, justify: text::Justify) -> Self {
         self.map_ty(|ty| ty.justify(justify))
@@ -207,11 +387,31 @@ where
         self.map_ty(|ty| ty.right_justify())
     }
 
+    /// Align wrapped lines to both ends of the bounding **Rect**'s *x* axis range.
+    pub fn full_justify(self) -> Self {
+        self.map_ty(|ty| ty.full_justify())
+    }
+
     /// Specify how much vertical space should separate each line of text.
     pub fn line_spacing(self, spacing: text::Scalar) -> Self {
         self.map_ty(|ty| ty.line_spacing(spacing))
     }
 
+    /// Specify how much additional horizontal space should separate each character.
+    pub fn letter_spacing(self, spacing: text::Scalar) -> Self {
+        self.map_ty(|ty| ty.letter_spacing(spacing))
+    }
+
+    /// Specify how much additional horizontal space should follow each whitespace character.
+    pub fn word_spacing(self, spacing: text::Scalar) -> Self {
+        self.map_ty(|ty| ty.word_spacing(spacing))
+    }
+
+    /// Specify the maximum width of each line of text.
+    pub fn max_width(self, max_width: text::Scalar) -> Self {
+        self.map_ty(|ty| ty.max_width(max_width))
+    }
+
     /// Specify how the whole text should be aligned along the y axis of its bounding rectangle
     pub fn y_align_text(self, align: Align) -> Self {
         self.map_ty(|ty| ty.y_align(align))
@@ -238,88 +438,237 @@ where
     pub fn layout(self, layout: &Layout) -> Self {
         self.map_ty(|ty| ty.layout(layout))
     }
+
+    /// Append a span of text that may be styled separately from the rest of the text.
+    ///
+    /// Following calls to `color`, `font` and `font_size` apply to the most recently appended
+    /// span. See `Text::span` for details.
+    pub fn span(self, text: &str) -> Self {
+        self.map_ty_with_context(|ty, ctxt| ty.span(ctxt, text))
+    }
+
+    /// Lay out each glyph along the given path, rotating it to follow the path's direction.
+    ///
+    /// The given iterator may yield either `PathEvent`s or references to them, e.g.
+    /// `.along_path(&events)`. See `Text::along_path` for details.
+    pub fn along_path<I>(self, events: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: std::borrow::Borrow<PathEvent>,
+    {
+        self.map_ty_with_context(|ty, ctxt| ty.along_path(ctxt, events))
+    }
 }
 
-impl draw::renderer::RenderPrimitive for Text<f32> {
-    fn render_primitive(
-        self,
-        ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Text {
-            spatial,
-            style,
-            text,
-        } = self;
-        let Style { color, layout } = style;
-        let layout = layout.build();
+impl Text<f32> {
+    // Lay out the text, producing a run of glyphs positioned within the pixel space of the output
+    // attachment for the initial text and for each span.
+    pub(crate) fn layout_glyphs(
+        &self,
+        text_buffer: &str,
+        output_attachment_size: Vector2,
+        output_attachment_scale_factor: f32,
+    ) -> Vec<GlyphRun> {
+        let layout = self.style.layout.clone().build();
+        let rect = self.layout_rect();
+        let text_str = &text_buffer[self.text.clone()];
+
+        // Plain text is laid out with a single font and font size.
+        if self.spans.is_empty() && layout.font_fallback.is_empty() {
+            let text = text::text(text_str).layout(&layout).build(rect);
+            let font_id = text::font::id(text.font());
+            let glyphs = text
+                .rt_glyphs(output_attachment_size, output_attachment_scale_factor)
+                .collect();
+            let run = GlyphRun {
+                font_index: font_id.index(),
+                color: None,
+                glyphs,
+            };
+            return vec![run];
+        }
+
+        let initial = text::rich::Span {
+            text: text_str,
+            font: None,
+            font_size: None,
+        };
+        let spans = self.spans.iter().map(|span| text::rich::Span {
+            text: &text_buffer[span.text.clone()],
+            font: span.font.as_ref(),
+            font_size: span.font_size,
+        });
+        let spans: Vec<_> = Some(initial).into_iter().chain(spans).collect();
+        let text = text::rich::Text::new(&spans, &layout, rect);
+
+        // Convert nannou coordinates to rusttype pixel coordinates.
+        let scale_factor = output_attachment_scale_factor;
+        let (half_w, half_h) = (
+            output_attachment_size.x / 2.0,
+            output_attachment_size.y / 2.0,
+        );
+        let to_rt_point = |p: geom::Point2| {
+            text::rt::point(
+                (p.x + half_w) * scale_factor,
+                (-p.y + half_h) * scale_factor,
+            )
+        };
+
+        // Group consecutive glyphs that share a span and font into runs.
+        let mut runs: Vec<GlyphRun> = vec![];
+        let mut last = None;
+        for g in text.glyphs() {
+            let font = &text.fonts()[g.font];
+            if last != Some((g.span, g.font)) {
+                let color = match g.span {
+                    0 => None,
+                    i => self.spans[i - 1].color,
+                };
+                runs.push(GlyphRun {
+                    font_index: text::font::id(font).index(),
+                    color,
+                    glyphs: vec![],
+                });
+                last = Some((g.span, g.font));
+            }
+            let scale = text::f32_pt_to_scale(g.font_size as f32 * scale_factor);
+            let glyph = font
+                .glyph(g.ch)
+                .scaled(scale)
+                .positioned(to_rt_point(g.position));
+            if let Some(run) = runs.last_mut() {
+                run.glyphs.push(glyph.standalone());
+            }
+        }
+        runs
+    }
+
+    // Whether or not the glyphs are drawn via signed distance fields.
+    pub(crate) fn is_sdf(&self) -> bool {
+        self.sdf
+    }
+
+    // The rect within which the text is laid out, centred on the origin.
+    fn layout_rect(&self) -> geom::Rect {
         let (maybe_x, maybe_y, maybe_z) = (
-            spatial.dimensions.x,
-            spatial.dimensions.y,
-            spatial.dimensions.z,
+            self.spatial.dimensions.x,
+            self.spatial.dimensions.y,
+            self.spatial.dimensions.z,
         );
         assert!(
             maybe_z.is_none(),
             "z dimension support for text is unimplemented"
         );
-        let w = maybe_x
-            .map(|s| <f32 as crate::math::NumCast>::from(s).unwrap())
-            .unwrap_or(200.0);
-        let h = maybe_y
-            .map(|s| <f32 as crate::math::NumCast>::from(s).unwrap())
-            .unwrap_or(200.0);
-        let rect: geom::Rect = geom::Rect::from_wh(Vector2 { x: w, y: h });
-        let color = color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&theme::Primitive::Text));
-
-        let text_str = &ctxt.text_buffer[text.clone()];
-        let text = text::text(text_str).layout(&layout).build(rect);
-
-        // Queue the glyphs to be cached
-        let font_id = text::font::id(text.font());
-        let positioned_glyphs: Vec<_> = text
-            .rt_glyphs(
-                ctxt.output_attachment_size,
-                ctxt.output_attachment_scale_factor,
-            )
-            .collect();
-        for glyph in positioned_glyphs.iter() {
-            ctxt.glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+        let w = maybe_x.unwrap_or(DEFAULT_LAYOUT_SIZE);
+        let h = maybe_y.unwrap_or(DEFAULT_LAYOUT_SIZE);
+        geom::Rect::from_wh(Vector2 { x: w, y: h })
+    }
+}
+
+impl PathSampler {
+    // Flatten the given path events into line segments.
+    fn new<I>(events: I, tolerance: f32) -> Self
+    where
+        I: IntoIterator<Item = PathEvent>,
+    {
+        use lyon::path::iterator::PathIterator;
+
+        let mut segments = vec![];
+        let mut length = 0.0;
+        let mut push = |from: lyon::math::Point, to: lyon::math::Point| {
+            let (from, to) = (geom::pt2(from.x, from.y), geom::pt2(to.x, to.y));
+            let segment_length = (to - from).magnitude();
+            if segment_length > 0.0 {
+                segments.push((from, to, length));
+                length += segment_length;
+            }
+        };
+        for event in events.into_iter().flattened(tolerance) {
+            match event {
+                PathEvent::Line { from, to } => push(from, to),
+                PathEvent::End {
+                    last,
+                    first,
+                    close: true,
+                } => push(last, first),
+                _ => (),
+            }
+        }
+        PathSampler { segments, length }
+    }
+
+    // The point at the given distance along the path alongside the unit direction of the path at
+    // that point, or `None` if the distance lies beyond either end of the path.
+    fn sample(&self, distance: f32) -> Option<(Point2, Vector2)> {
+        if distance < 0.0 || distance > self.length {
+            return None;
         }
+        let ix = match self.segments.binary_search_by(|&(_, _, start)| {
+            start.partial_cmp(&distance).unwrap_or(Ordering::Less)
+        }) {
+            Ok(ix) => ix,
+            Err(ix) => ix.checked_sub(1)?,
+        };
+        let (from, to, start) = *self.segments.get(ix)?;
+        let direction = (to - from).normalize();
+        Some((from + direction * (distance - start), direction))
+    }
+}
 
-        // Cache the enqueued glyphs within the pixel buffer.
-        let (glyph_cache_w, _) = ctxt.glyph_cache.dimensions();
-        {
-            let draw::renderer::RenderContext {
-                glyph_cache:
-                    &mut draw::renderer::GlyphCache {
-                        ref mut cache,
-                        ref mut pixel_buffer,
-                        ref mut requires_upload,
-                        ..
-                    },
-                ..
-            } = ctxt;
-            let glyph_cache_w = glyph_cache_w as usize;
-            let res = cache.cache_queued(|rect, data| {
-                let width = (rect.max.x - rect.min.x) as usize;
-                let height = (rect.max.y - rect.min.y) as usize;
-                let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
-                let mut src_ix = 0;
-                for _ in 0..height {
-                    let dst_range = dst_ix..dst_ix + width;
-                    let src_range = src_ix..src_ix + width;
-                    let dst_slice = &mut pixel_buffer[dst_range];
-                    let src_slice = &data[src_range];
-                    dst_slice.copy_from_slice(src_slice);
-                    dst_ix += glyph_cache_w;
-                    src_ix += width;
+impl draw::renderer::RenderPrimitive for Text<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let glyph_cache = ctxt
+            .glyph_cache
+            .take()
+            .expect("text must be rendered with access to the glyph cache");
+        let sdf = self.sdf;
+
+        // Use the glyphs laid out as a part of a batch if there are any, otherwise lay them out.
+        // The glyphs of SDF text are cached within the SDF atlas below as they are drawn instead.
+        let runs = match glyph_cache.batched_glyphs.pop_front() {
+            Some(batched) if batched.cached || sdf => batched.runs,
+            maybe_batched => {
+                let runs = match maybe_batched {
+                    Some(batched) => batched.runs,
+                    None => self.layout_glyphs(
+                        ctxt.text_buffer,
+                        ctxt.output_attachment_size,
+                        ctxt.output_attachment_scale_factor,
+                    ),
+                };
+
+                if !sdf {
+                    // Queue the glyphs to be cached
+                    for run in runs.iter() {
+                        for glyph in run.glyphs.iter() {
+                            glyph_cache.queue_glyph(run.font_index, glyph.clone());
+                        }
+                    }
+
+                    // Cache the enqueued glyphs within the pixel buffer.
+                    if let Err(err) = glyph_cache.cache_queued() {
+                        eprintln!("failed to cache queued glyphs: {}", err);
+                    }
                 }
-                *requires_upload = true;
-            });
-            if let Err(err) = res {
-                eprintln!("failed to cache queued glyphs: {}", err);
+                runs
             }
-        }
+        };
+
+        // The path along which glyphs are placed, if any.
+        let layout_left = self.layout_rect().left();
+        let along_path = self.path.clone().map(|range| {
+            let events = ctxt.path_event_buffer[range].iter().cloned();
+            PathSampler::new(events, PATH_TOLERANCE)
+        });
+
+        let Text { spatial, style, .. } = self;
+        let color = style
+            .color
+            .unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&theme::Primitive::Text));
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
@@ -330,19 +679,77 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
         let scale_factor = ctxt.output_attachment_scale_factor;
         let (out_w, out_h) = ctxt.output_attachment_size.into();
         let [half_out_w, half_out_h] = [out_w as f32 / 2.0, out_h as f32 / 2.0];
-        let to_nannou_rect = |screen_rect: text::rt::Rect<i32>| {
-            let l = screen_rect.min.x as f32 / scale_factor - half_out_w;
-            let r = screen_rect.max.x as f32 / scale_factor - half_out_w;
-            let t = -(screen_rect.min.y as f32 / scale_factor - half_out_h);
-            let b = -(screen_rect.max.y as f32 / scale_factor - half_out_h);
+        let to_nannou_rect = |screen_rect: text::rt::Rect<f32>| {
+            let l = screen_rect.min.x / scale_factor - half_out_w;
+            let r = screen_rect.max.x / scale_factor - half_out_w;
+            let t = -(screen_rect.min.y / scale_factor - half_out_h);
+            let b = -(screen_rect.max.y / scale_factor - half_out_h);
             geom::Rect::from_corners(geom::pt2(l, b), geom::pt2(r, t))
         };
+        let to_nannou_point = |screen_point: text::rt::Point<f32>| {
+            let x = screen_point.x / scale_factor - half_out_w;
+            let y = -(screen_point.y / scale_factor - half_out_h);
+            geom::pt2(x, y)
+        };
+
+        // The baseline of the first line, from which the path offset of other lines is measured.
+        let first_baseline = runs
+            .iter()
+            .filter_map(|run| run.glyphs.first())
+            .next()
+            .map(|g| to_nannou_point(g.position()).y)
+            .unwrap_or(0.0);
 
         // Extend the mesh with a rect for each displayed glyph.
-        for g in positioned_glyphs {
-            if let Ok(Some((uv_rect, screen_rect))) = ctxt.glyph_cache.rect_for(font_id.index(), &g)
-            {
+        let glyphs = runs.into_iter().flat_map(|run| {
+            let GlyphRun {
+                font_index,
+                color: run_color,
+                glyphs,
+            } = run;
+            let color = run_color.unwrap_or(color);
+            glyphs.into_iter().map(move |g| (font_index, color, g))
+        });
+        for (font_index, color, g) in glyphs {
+            // Look up the glyph within the SDF atlas, or within the glyph cache in pixels.
+            let rects = if sdf {
+                glyph_cache.sdf.rect_for(font_index, &g).ok()
+            } else {
+                glyph_cache.rect_for(font_index, &g).ok().map(|rects| {
+                    rects.map(|(uv_rect, screen_rect)| {
+                        let min =
+                            text::rt::point(screen_rect.min.x as f32, screen_rect.min.y as f32);
+                        let max =
+                            text::rt::point(screen_rect.max.x as f32, screen_rect.max.y as f32);
+                        (uv_rect, text::rt::Rect { min, max })
+                    })
+                })
+            };
+            if let Some(Some((uv_rect, screen_rect))) = rects {
                 let rect = to_nannou_rect(screen_rect);
+                let mut corners = [
+                    rect.top_left(),
+                    rect.bottom_left(),
+                    rect.bottom_right(),
+                    rect.top_right(),
+                ];
+
+                // Move the glyph onto the path, rotating it about the centre of its baseline.
+                if let Some(ref path) = along_path {
+                    let origin = to_nannou_point(g.position());
+                    let advance = g.unpositioned().h_metrics().advance_width / scale_factor;
+                    let anchor = geom::pt2(origin.x + advance / 2.0, origin.y);
+                    let (point, direction) = match path.sample(anchor.x - layout_left) {
+                        None => continue,
+                        Some(sample) => sample,
+                    };
+                    let normal = geom::vec2(-direction.y, direction.x);
+                    let center = point + normal * (anchor.y - first_baseline);
+                    for corner in corners.iter_mut() {
+                        let d = *corner - anchor;
+                        *corner = center + direction * d.x + normal * d.y;
+                    }
+                }
 
                 // Create a mesh-compatible vertex from the position and tex_coords.
                 let v = |position, tex_coords: [f32; 2]| -> draw::mesh::Vertex {
@@ -359,10 +766,11 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
                 let uv_b = uv_rect.max.y;
 
                 // Insert the vertices.
-                let bottom_left = v(rect.bottom_left(), [uv_l, uv_b]);
-                let bottom_right = v(rect.bottom_right(), [uv_r, uv_b]);
-                let top_left = v(rect.top_left(), [uv_l, uv_t]);
-                let top_right = v(rect.top_right(), [uv_r, uv_t]);
+                let [tl, bl, br, tr] = corners;
+                let bottom_left = v(bl, [uv_l, uv_b]);
+                let bottom_right = v(br, [uv_r, uv_b]);
+                let top_left = v(tl, [uv_l, uv_t]);
+                let top_right = v(tr, [uv_r, uv_t]);
                 let start_ix = mesh.points().len() as u32;
                 mesh.push_vertex(top_left);
                 mesh.push_vertex(bottom_left);
@@ -383,7 +791,11 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
             }
         }
 
-        draw::renderer::PrimitiveRender::text()
+        if sdf {
+            draw::renderer::PrimitiveRender::sdf_text()
+        } else {
+            draw::renderer::PrimitiveRender::text()
+        }
     }
 }
 
@@ -407,7 +819,11 @@ impl<S> SetDimensions<S> for Text<S> {
 
 impl<S> SetColor<ColorScalar> for Text<S> {
     fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
-        SetColor::rgba_mut(&mut self.style.color)
+        // Colors apply to the most recently appended span, if any.
+        match self.spans.last_mut() {
+            Some(span) => SetColor::rgba_mut(&mut span.color),
+            None => SetColor::rgba_mut(&mut self.style.color),
+        }
     }
 }
 
@@ -5,14 +5,18 @@ use crate::draw::properties::{SetDimensions, SetOrientation, SetPosition};
 use crate::draw::{self, Drawing};
 use crate::geom::{self, Vector2};
 use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 
 /// Properties related to drawing a **Rect**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Texture<S = geom::scalar::Default> {
+    #[serde(with = "crate::draw::serialize::resource")]
     texture_view: wgpu::TextureView,
     spatial: spatial::Properties<S>,
     area: geom::Rect,
+    // The layer to draw if the view is a `D2Array` view.
+    layer: u32,
 }
 
 /// The drawing context for a Rect.
@@ -39,12 +43,60 @@ where
             end: 1.0,
         };
         let area = geom::Rect { x, y };
+        let layer = 0;
         Self {
             texture_view,
             spatial,
             area,
+            layer,
         }
     }
+
+    // Initialise the texture primitive from the given view, spatial properties and area.
+    pub(crate) fn from_parts(
+        texture_view: wgpu::TextureView,
+        spatial: spatial::Properties<S>,
+        area: geom::Rect,
+    ) -> Self {
+        let layer = 0;
+        Self {
+            texture_view,
+            spatial,
+            area,
+            layer,
+        }
+    }
+
+    // Initialise the texture primitive from the first layer of the given `D2Array` view.
+    pub(crate) fn new_array(view: &wgpu::TextureView) -> Self {
+        assert_eq!(
+            view.dimension(),
+            wgpu::TextureViewDimension::D2Array,
+            "`draw.texture_array` requires a `D2Array` texture view",
+        );
+        Self::new(view)
+    }
+
+    /// Specify the area of the texture to draw in texels, e.g. a single cell of a sprite sheet.
+    ///
+    /// Texel coordinates range from (0.0, 0.0) in the bottom left of the texture, to (width,
+    /// height) in the top right of the texture, matching the orientation used by `area`.
+    ///
+    /// The dimensions of the drawing are set to the size of the rect. These may be overridden by
+    /// specifying dimensions after calling this method.
+    ///
+    /// The location of each image packed into a `wgpu::TextureAtlas` may be retrieved via
+    /// `atlas.src_rect(index)`.
+    pub fn src_rect(self, rect: geom::Rect) -> Self {
+        let [w, h] = self.texture_view.size();
+        let (w, h) = (w as f32, h as f32);
+        let bottom_left = geom::pt2(rect.left() / w, rect.bottom() / h);
+        let top_right = geom::pt2(rect.right() / w, rect.top() / h);
+        let area = geom::Rect::from_corners(bottom_left, top_right);
+        let rect_w = S::from(rect.w().abs()).unwrap();
+        let rect_h = S::from(rect.h().abs()).unwrap();
+        self.area(area).w_h(rect_w, rect_h)
+    }
 }
 
 impl<S> Texture<S> {
@@ -61,6 +113,25 @@ impl<S> Texture<S> {
         self.area = rect;
         self
     }
+
+    /// Specify the layer of the array texture to draw.
+    ///
+    /// This only applies to drawings begun via `draw.texture_array`. By default, the first layer
+    /// is drawn.
+    ///
+    /// **Panics** if `layer` is not less than the view's layer count. Views that are not array
+    /// views have a single layer.
+    pub fn layer(mut self, layer: u32) -> Self {
+        let layer_count = self.texture_view.array_layer_count();
+        assert!(
+            layer < layer_count,
+            "layer {} is out of range for texture view with {} layers",
+            layer,
+            layer_count,
+        );
+        self.layer = layer;
+        self
+    }
 }
 
 impl<'a, S> DrawingTexture<'a, S>
@@ -79,6 +150,31 @@ where
     pub fn area(self, rect: geom::Rect) -> Self {
         self.map_ty(|ty| ty.area(rect))
     }
+
+    /// Specify the area of the texture to draw in texels, e.g. a single cell of a sprite sheet.
+    ///
+    /// Texel coordinates range from (0.0, 0.0) in the bottom left of the texture, to (width,
+    /// height) in the top right of the texture, matching the orientation used by `area`.
+    ///
+    /// The dimensions of the drawing are set to the size of the rect. These may be overridden by
+    /// specifying dimensions after calling this method.
+    ///
+    /// The location of each image packed into a `wgpu::TextureAtlas` may be retrieved via
+    /// `atlas.src_rect(index)`.
+    pub fn src_rect(self, rect: geom::Rect) -> Self {
+        self.map_ty(|ty| ty.src_rect(rect))
+    }
+
+    /// Specify the layer of the array texture to draw.
+    ///
+    /// This only applies to drawings begun via `draw.texture_array`. By default, the first layer
+    /// is drawn.
+    ///
+    /// **Panics** if `layer` is not less than the view's layer count. Views that are not array
+    /// views have a single layer.
+    pub fn layer(self, layer: u32) -> Self {
+        self.map_ty(|ty| ty.layer(layer))
+    }
 }
 
 impl draw::renderer::RenderPrimitive for Texture<f32> {
@@ -91,6 +187,7 @@ impl draw::renderer::RenderPrimitive for Texture<f32> {
             texture_view,
             spatial,
             area,
+            layer,
         } = self;
         let spatial::Properties {
             dimensions,
@@ -129,7 +226,7 @@ impl draw::renderer::RenderPrimitive for Texture<f32> {
             mesh,
         );
 
-        draw::renderer::PrimitiveRender::texture(texture_view)
+        draw::renderer::PrimitiveRender::texture_array(texture_view, layer)
     }
 }
 
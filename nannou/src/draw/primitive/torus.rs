@@ -0,0 +1,230 @@
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, pt2, pt3, Point3};
+use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Properties related to drawing a **Torus**.
+///
+/// The torus is centred on its position and lies in the xy plane, so that by default it appears
+/// as a ring when viewed along the z axis.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Torus<S = geom::scalar::Default> {
+    position: position::Properties<S>,
+    orientation: orientation::Properties<S>,
+    major_radius: Option<S>,
+    minor_radius: Option<S>,
+    rings: Option<usize>,
+    sides: Option<usize>,
+    color: Option<LinSrgba>,
+}
+
+/// The drawing context for a **Torus**.
+pub type DrawingTorus<'a, S = geom::scalar::Default> = Drawing<'a, Torus<S>, S>;
+
+/// The distance from the centre of the torus to the centre of its tube if none is specified.
+pub const DEFAULT_MAJOR_RADIUS: f32 = 50.0;
+
+/// The radius of the tube if none is specified.
+pub const DEFAULT_MINOR_RADIUS: f32 = 20.0;
+
+/// The number of segments around the centre of the torus if none is specified.
+pub const DEFAULT_RINGS: usize = 32;
+
+/// The number of segments around the tube if none is specified.
+pub const DEFAULT_SIDES: usize = 16;
+
+impl<S> Torus<S> {
+    /// The distance from the centre of the torus to the centre of its tube.
+    ///
+    /// By default, this is `50.0`.
+    pub fn major_radius(mut self, radius: S) -> Self {
+        self.major_radius = Some(radius);
+        self
+    }
+
+    /// The radius of the tube.
+    ///
+    /// By default, this is `20.0`.
+    pub fn minor_radius(mut self, radius: S) -> Self {
+        self.minor_radius = Some(radius);
+        self
+    }
+
+    /// Short-hand for specifying both the `major_radius` and `minor_radius`.
+    pub fn radii(self, major: S, minor: S) -> Self {
+        self.major_radius(major).minor_radius(minor)
+    }
+
+    /// The number of segments around the centre of the torus.
+    ///
+    /// By default, this is `32`. Values below `3` are clamped to `3`.
+    pub fn rings(mut self, rings: usize) -> Self {
+        self.rings = Some(rings);
+        self
+    }
+
+    /// The number of segments around the tube.
+    ///
+    /// By default, this is `16`. Values below `3` are clamped to `3`.
+    pub fn sides(mut self, sides: usize) -> Self {
+        self.sides = Some(sides);
+        self
+    }
+
+    /// Short-hand for specifying both the number of `rings` and `sides`.
+    pub fn resolution(self, rings: usize, sides: usize) -> Self {
+        self.rings(rings).sides(sides)
+    }
+}
+
+impl<'a, S> DrawingTorus<'a, S>
+where
+    S: BaseFloat,
+{
+    /// The distance from the centre of the torus to the centre of its tube.
+    pub fn major_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.major_radius(radius))
+    }
+
+    /// The radius of the tube.
+    pub fn minor_radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.minor_radius(radius))
+    }
+
+    /// Short-hand for specifying both the `major_radius` and `minor_radius`.
+    pub fn radii(self, major: S, minor: S) -> Self {
+        self.map_ty(|ty| ty.radii(major, minor))
+    }
+
+    /// The number of segments around the centre of the torus.
+    pub fn rings(self, rings: usize) -> Self {
+        self.map_ty(|ty| ty.rings(rings))
+    }
+
+    /// The number of segments around the tube.
+    pub fn sides(self, sides: usize) -> Self {
+        self.map_ty(|ty| ty.sides(sides))
+    }
+
+    /// Short-hand for specifying both the number of `rings` and `sides`.
+    pub fn resolution(self, rings: usize, sides: usize) -> Self {
+        self.map_ty(|ty| ty.resolution(rings, sides))
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Torus<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Torus {
+            position,
+            orientation,
+            major_radius,
+            minor_radius,
+            rings,
+            sides,
+            color,
+        } = self;
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let transform_point = |p: Point3<f32>| -> Point3<f32> {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(&transform, p);
+            p.into()
+        };
+
+        let major_radius = major_radius.map(f32::abs).unwrap_or(DEFAULT_MAJOR_RADIUS);
+        let minor_radius = minor_radius.map(f32::abs).unwrap_or(DEFAULT_MINOR_RADIUS);
+        let rings = rings.unwrap_or(DEFAULT_RINGS).max(3);
+        let sides = sides.unwrap_or(DEFAULT_SIDES).max(3);
+        let color =
+            color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(&draw::theme::Primitive::Torus));
+
+        // A grid of vertices, duplicating the first row and column so that texture coordinates
+        // may wrap around both seams. `x` follows the rings and `y` follows the sides.
+        let v_start = mesh.raw_vertex_count() as u32;
+        for ring in 0..=rings {
+            let u = ring as f32 / rings as f32;
+            let (ring_sin, ring_cos) = (u * PI * 2.0).sin_cos();
+            for side in 0..=sides {
+                let v = side as f32 / sides as f32;
+                let (side_sin, side_cos) = (v * PI * 2.0).sin_cos();
+                let r = major_radius + minor_radius * side_cos;
+                let p = pt3(r * ring_cos, r * ring_sin, minor_radius * side_sin);
+                mesh.push_vertex(((transform_point(p), color), pt2(u, v)).into());
+            }
+        }
+        let stride = sides as u32 + 1;
+        let indices = (0..rings as u32).flat_map(|ring| {
+            (0..sides as u32).flat_map(move |side| {
+                let a = v_start + ring * stride + side;
+                let b = a + 1;
+                let c = a + stride;
+                let d = c + 1;
+                vec![a, c, d, a, d, b]
+            })
+        });
+        mesh.extend_indices(indices);
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for Torus<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Torus {
+            position: Default::default(),
+            orientation: Default::default(),
+            major_radius: None,
+            minor_radius: None,
+            rings: None,
+            sides: None,
+            color: None,
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Torus<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for Torus<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Torus<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
+
+impl<S> From<Torus<S>> for Primitive<S> {
+    fn from(prim: Torus<S>) -> Self {
+        Primitive::Torus(prim)
+    }
+}
+
+impl<S> Into<Option<Torus<S>>> for Primitive<S> {
+    fn into(self) -> Option<Torus<S>> {
+        match self {
+            Primitive::Torus(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
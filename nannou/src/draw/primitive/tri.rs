@@ -3,19 +3,22 @@ use crate::draw::primitive::polygon::{self, PolygonInit, PolygonOptions, SetPoly
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{dimension, orientation, position};
 use crate::draw::properties::{
-    ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition, SetStroke,
+    ColorScalar, Dash, LinSrgba, SetColor, SetDash, SetDimensions, SetOrientation, SetPosition,
+    SetStroke,
 };
 use crate::draw::{self, Drawing};
 use crate::geom::{self, Point2, Vector2};
 use crate::math::{BaseFloat, ElementWise};
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::StrokeOptions;
 
 /// Properties related to drawing a **Tri**.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Tri<S = geom::scalar::Default> {
     tri: geom::Tri<Point2<S>>,
     dimensions: dimension::Properties<S>,
     polygon: PolygonInit<S>,
+    colors: Option<[LinSrgba; 3]>,
 }
 
 /// The drawing context for a `Tri`.
@@ -43,6 +46,21 @@ impl<S> Tri<S> {
         self.tri = geom::Tri([a, b, c]);
         self
     }
+
+    /// Color each of the three vertices (corners) of the triangle, in the same order as `points`.
+    ///
+    /// The fill interpolates between the colors of the corners. These colors take precedence over
+    /// the fill color.
+    pub fn colors<C>(mut self, a: C, b: C, c: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let a = a.into_lin_srgba();
+        let b = b.into_lin_srgba();
+        let c = c.into_lin_srgba();
+        self.colors = Some([a, b, c]);
+        self
+    }
 }
 
 // Drawing methods.
@@ -66,23 +84,26 @@ where
     {
         self.map_ty(|ty| ty.points(a, b, c))
     }
+
+    /// Color each of the three vertices (corners) of the triangle, in the same order as `points`.
+    ///
+    /// The fill interpolates between the colors of the corners.
+    pub fn colors<C>(self, a: C, b: C, c: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.colors(a, b, c))
+    }
 }
 
 // Trait implementations.
 
-impl draw::renderer::RenderPrimitive for Tri<f32> {
-    fn render_primitive(
-        self,
-        ctxt: draw::renderer::RenderContext,
-        mesh: &mut draw::Mesh,
-    ) -> draw::renderer::PrimitiveRender {
-        let Tri {
-            mut tri,
-            dimensions,
-            polygon,
-        } = self;
+impl Tri<f32> {
+    // The tri's points, scaled to the dimensions if any were specified.
+    fn scaled_tri(&self) -> geom::Tri<Point2> {
+        let mut tri = self.tri;
+        let dimensions = &self.dimensions;
         let (maybe_x, maybe_y, _maybe_z) = (dimensions.x, dimensions.y, dimensions.z);
-        // If dimensions were specified, scale the points to those dimensions.
         if maybe_x.is_some() || maybe_y.is_some() {
             let cuboid = tri.bounding_rect();
             let centroid = tri.centroid();
@@ -99,16 +120,48 @@ impl draw::renderer::RenderPrimitive for Tri<f32> {
             let new_c = translate(c);
             tri = geom::Tri([new_a, new_b, new_c]);
         }
-        let points = tri.vertices();
-        polygon::render_points_themed(
-            polygon.opts,
-            points,
-            ctxt,
-            &draw::theme::Primitive::Tri,
-            mesh,
-        );
+        tri
+    }
+}
 
-        draw::renderer::PrimitiveRender::default()
+impl draw::renderer::RenderPrimitive for Tri<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let points = self.scaled_tri().vertices();
+        let theme_primitive = &draw::theme::Primitive::Tri;
+        match self.colors {
+            Some(colors) => {
+                let points_colored = points.zip(colors.iter().cloned());
+                polygon::render_points_colored_themed(
+                    self.polygon.opts,
+                    points_colored,
+                    ctxt,
+                    theme_primitive,
+                    mesh,
+                )
+            }
+            None => polygon::render_points_themed(
+                self.polygon.opts,
+                points,
+                ctxt,
+                theme_primitive,
+                mesh,
+            ),
+        }
+    }
+}
+
+impl draw::plotter_renderer::PlotPrimitive for Tri<f32> {
+    fn plot_stroke(
+        &self,
+        _ctxt: draw::plotter_renderer::PlotContext,
+        events: &mut Vec<lyon::path::PathEvent>,
+    ) -> Option<crate::math::Matrix4<f32>> {
+        let points = self.scaled_tri().vertices();
+        polygon::plot_points(&self.polygon.opts, points, events)
     }
 }
 
@@ -119,10 +172,12 @@ where
     fn from(tri: geom::Tri<Point2<S>>) -> Self {
         let dimensions = <_>::default();
         let polygon = <_>::default();
+        let colors = None;
         Tri {
             tri,
             dimensions,
             polygon,
+            colors,
         }
     }
 }
@@ -173,6 +228,12 @@ impl<S> SetColor<ColorScalar> for Tri<S> {
     }
 }
 
+impl<S> SetDash for Tri<S> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        SetDash::dash_mut(&mut self.polygon)
+    }
+}
+
 impl<S> SetStroke for Tri<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.polygon)
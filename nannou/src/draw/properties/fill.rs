@@ -22,7 +22,10 @@ pub trait SetFill: Sized {
 
     /// Specify the rule used to determine what is inside and what is outside of the shape.
     ///
-    /// Currently, only the `EvenOdd` rule is implemented.
+    /// Self-intersecting shapes are split at their intersections before the rule is applied. Use
+    /// `NonZero` for shapes like stars and scribbles to fill all enclosed regions.
+    ///
+    /// Default value: `EvenOdd`.
     fn fill_rule(mut self, rule: lyon::tessellation::FillRule) -> Self {
         self.fill_options_mut().fill_rule = rule;
         self
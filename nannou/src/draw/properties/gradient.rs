@@ -0,0 +1,187 @@
+use crate::color::{ColorRamp, IntoLinSrgba};
+use crate::draw::properties::{ColorScalar, LinSrgba};
+use crate::geom::Point2;
+use crate::serde_derive::{Deserialize, Serialize};
+
+/// A gradient with which the fill of a shape may be colored.
+///
+/// Points are described in the local coordinates of the shape, prior to the shape's position and
+/// orientation being applied. Colors are sampled from a **ColorRamp** and so are interpolated in
+/// linear sRGB space, with areas beyond the first and last stops taking the color of the nearest
+/// stop.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Gradient {
+    /// Interpolate along the line from `start` (at `0.0`) to `end` (at `1.0`).
+    Linear {
+        start: Point2,
+        end: Point2,
+        ramp: ColorRamp,
+    },
+    /// Interpolate outward from `center` (at `0.0`) to the circle of the given `radius` (at
+    /// `1.0`).
+    Radial {
+        center: Point2,
+        radius: f32,
+        ramp: ColorRamp,
+    },
+}
+
+impl Gradient {
+    /// A linear gradient from `start` to `end` without any stops.
+    pub fn linear(start: Point2, end: Point2) -> Self {
+        let ramp = ColorRamp::default();
+        Gradient::Linear { start, end, ramp }
+    }
+
+    /// A radial gradient centered on `center` without any stops.
+    pub fn radial(center: Point2, radius: f32) -> Self {
+        let ramp = ColorRamp::default();
+        Gradient::Radial {
+            center,
+            radius,
+            ramp,
+        }
+    }
+
+    /// Add a color stop at the given offset between `0.0` and `1.0`.
+    pub fn stop<C>(mut self, offset: f32, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let ramp = self.ramp_mut();
+        let stop = (offset, color.into_lin_srgba());
+        let stops = ramp.stops().iter().cloned().chain(Some(stop));
+        *ramp = ColorRamp::new(stops);
+        self
+    }
+
+    /// Replace all stops with those of the given ramp.
+    pub fn ramp(mut self, ramp: ColorRamp) -> Self {
+        *self.ramp_mut() = ramp;
+        self
+    }
+
+    /// The ramp from which the gradient's colors are sampled.
+    pub fn color_ramp(&self) -> &ColorRamp {
+        match *self {
+            Gradient::Linear { ref ramp, .. } | Gradient::Radial { ref ramp, .. } => ramp,
+        }
+    }
+
+    fn ramp_mut(&mut self) -> &mut ColorRamp {
+        match *self {
+            Gradient::Linear { ref mut ramp, .. } | Gradient::Radial { ref mut ramp, .. } => ramp,
+        }
+    }
+
+    /// The position of the given point along the gradient, where `0.0` is the start and `1.0`
+    /// is the end.
+    pub fn offset(&self, point: Point2) -> f32 {
+        match *self {
+            Gradient::Linear { start, end, .. } => {
+                let dir = end - start;
+                let len2 = dir.magnitude2();
+                if len2 > 0.0 {
+                    (point - start).dot(dir) / len2
+                } else {
+                    0.0
+                }
+            }
+            Gradient::Radial { center, radius, .. } => {
+                if radius > 0.0 {
+                    (point - center).magnitude() / radius
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// The position along the line segment from `a` to `b`, between `0.0` and `1.0`, at which the
+    /// offset of the gradient crosses `offset`.
+    ///
+    /// Returns `None` unless the offsets at `a` and `b` lie on opposite sides of `offset`.
+    pub(crate) fn crossing(&self, a: Point2, b: Point2, offset: f32) -> Option<f32> {
+        let (oa, ob) = (self.offset(a) - offset, self.offset(b) - offset);
+        if !(oa < 0.0 && ob > 0.0 || oa > 0.0 && ob < 0.0) {
+            return None;
+        }
+        let t = match *self {
+            Gradient::Linear { .. } => oa / (oa - ob),
+            // Find the point at which the segment meets the circle of the offset.
+            Gradient::Radial { center, radius, .. } => {
+                let dir = b - a;
+                let from_center = a - center;
+                let len2 = dir.magnitude2();
+                let half_b = from_center.dot(dir);
+                let c = from_center.magnitude2() - (offset * radius) * (offset * radius);
+                let root = (half_b * half_b - len2 * c).max(0.0).sqrt();
+                // The segment leaves the circle if it starts within it and enters it otherwise.
+                if oa < 0.0 {
+                    (root - half_b) / len2
+                } else {
+                    (-root - half_b) / len2
+                }
+            }
+        };
+        Some(t.max(0.0).min(1.0))
+    }
+
+    /// The largest difference between the offset of any point on the line segment from `a` to
+    /// `b` and the offset linearly interpolated between those of `a` and `b`.
+    ///
+    /// Offsets are clamped to the range of the stops, beyond which the color is uniform. This is
+    /// always `0.0` for linear gradients.
+    pub(crate) fn max_interpolation_error(&self, a: Point2, b: Point2) -> f32 {
+        let (center, radius) = match *self {
+            Gradient::Linear { .. } => return 0.0,
+            Gradient::Radial { center, radius, .. } => (center, radius),
+        };
+        let stops = self.color_ramp().stops();
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => return 0.0,
+        };
+        let dir = b - a;
+        let len2 = dir.magnitude2();
+        if len2 <= 0.0 || radius <= 0.0 {
+            return 0.0;
+        }
+        // The distance to the center is convex along the segment, so the error is greatest either
+        // where the slope of the offset matches that of the interpolated offset, where the
+        // segment is closest to the center, or where the offset is clamped.
+        let t0 = (center - a).dot(dir) / len2;
+        let dist2 = (a + dir * t0 - center).magnitude2();
+        let offset = |t: f32| {
+            let distance = (dist2 + len2 * (t - t0) * (t - t0)).sqrt();
+            (distance / radius).max(first).min(last)
+        };
+        let (start, end) = (offset(0.0), offset(1.0));
+        let slope = (end - start) * radius;
+        let tangent = if len2 > slope * slope {
+            t0 + slope * dist2.sqrt() / (len2 * (len2 - slope * slope)).sqrt()
+        } else {
+            t0
+        };
+        let clamp = |stop: f32| ((stop * radius) * (stop * radius) - dist2) / len2;
+        let (first, last) = (clamp(first).sqrt(), clamp(last).sqrt());
+        let ts = [t0, tangent, t0 - first, t0 + first, t0 - last, t0 + last];
+        ts.iter()
+            .filter(|&&t| t >= 0.0 && t <= 1.0)
+            .map(|&t| (start + (end - start) * t - offset(t)).abs())
+            .fold(0.0, f32::max)
+    }
+
+    /// The color of the gradient at the given point.
+    pub fn color_at(&self, point: Point2) -> LinSrgba {
+        self.color_ramp().sample(self.offset(point))
+    }
+
+    /// The distance over which the gradient interpolates from its first to its last offset.
+    pub fn extent(&self) -> f32 {
+        match *self {
+            Gradient::Linear { start, end, .. } => (end - start).magnitude(),
+            Gradient::Radial { radius, .. } => radius.abs(),
+        }
+    }
+}
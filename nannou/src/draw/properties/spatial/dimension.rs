@@ -1,7 +1,8 @@
 use crate::geom::{self, Vector2, Vector3};
+use crate::serde_derive::{Deserialize, Serialize};
 
 /// Dimension properties for **Drawing** a **Primitive**.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Properties<S = geom::scalar::Default> {
     pub x: Option<S>,
     pub y: Option<S>,
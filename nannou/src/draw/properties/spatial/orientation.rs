@@ -1,8 +1,9 @@
 use crate::geom::{self, Point3, Vector3};
 use crate::math::{deg_to_rad, turns_to_rad, Angle, BaseFloat, Euler, Quaternion, Rad, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 
 /// Orientation properties for **Drawing** a **Primitive**.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Properties<S = geom::scalar::Default> {
     /// The orientation described by an angle along each axis.
     Axes(Vector3<S>),
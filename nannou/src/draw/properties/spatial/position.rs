@@ -2,9 +2,10 @@
 
 use crate::geom::{self, Point2, Point3};
 use crate::math::{BaseFloat, Zero};
+use crate::serde_derive::{Deserialize, Serialize};
 
 /// Position properties for **Drawing** a **Primitive**.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Properties<S = geom::scalar::Default> {
     pub point: Point3<S>,
 }
@@ -1,3 +1,4 @@
+use crate::serde_derive::{Deserialize, Serialize};
 use lyon::tessellation::{LineCap, LineJoin, StrokeOptions};
 
 /// Nodes that support stroke tessellation.
@@ -156,3 +157,50 @@ impl SetStroke for Option<StrokeOptions> {
         self.get_or_insert_with(Default::default)
     }
 }
+
+/// A pattern of dashes and gaps along a stroke, matching the SVG `stroke-dasharray` and
+/// `stroke-dashoffset` properties.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Dash {
+    /// Alternating lengths of dashes and gaps, beginning with a dash.
+    ///
+    /// Patterns with an odd number of lengths are repeated to produce an even number.
+    pub pattern: Vec<f32>,
+    /// The distance into the pattern at which the stroke begins.
+    pub offset: f32,
+}
+
+/// Nodes that support dashed strokes.
+///
+/// This trait allows the `Drawing` context to automatically provide the following builder methods
+/// for all primitives that support dashing their stroke.
+pub trait SetDash: Sized {
+    /// Provide a mutable reference to the dash field.
+    fn dash_mut(&mut self) -> &mut Option<Dash>;
+
+    /// Stroke with dashes of length `on` separated by gaps of length `off`.
+    fn dash(self, on: f32, off: f32) -> Self {
+        self.dash_pattern(&[on, off])
+    }
+
+    /// Stroke with the given alternating lengths of dashes and gaps, beginning with a dash.
+    ///
+    /// An empty pattern, or one whose lengths sum to zero, produces a solid stroke.
+    fn dash_pattern(mut self, pattern: &[f32]) -> Self {
+        let dash = self.dash_mut().get_or_insert_with(Default::default);
+        dash.pattern = pattern.to_vec();
+        self
+    }
+
+    /// The distance into the dash pattern at which the stroke begins.
+    fn dash_offset(mut self, offset: f32) -> Self {
+        self.dash_mut().get_or_insert_with(Default::default).offset = offset;
+        self
+    }
+}
+
+impl SetDash for Option<Dash> {
+    fn dash_mut(&mut self) -> &mut Option<Dash> {
+        self
+    }
+}
@@ -0,0 +1,452 @@
+//! Items related to recording drawings on worker threads before submitting them to a **Draw**.
+
+use crate::draw::primitive::Primitive;
+use crate::draw::{
+    transform_rect_bounds, Context, ContextDelta, Draw, DrawCommand, Group, IntermediaryState,
+    Mask, Scissor,
+};
+use crate::geom;
+use crate::math::BaseFloat;
+use crate::serde_derive::{Deserialize, Serialize};
+use std::ops;
+
+/// A list of draw commands along with the intermediary vertex, path and text data that they refer
+/// to.
+///
+/// **Draw** is backed by an `Rc<RefCell<_>>` and in turn may only be used on the thread on which
+/// it was created. A **Recording** on the other hand may be sent between threads. This allows
+/// for composing drawings across many worker threads, each with their own **Draw**, before
+/// appending the results to the **Draw** that is rendered to the frame.
+///
+/// ```no_run
+/// # use nannou::prelude::*;
+/// # fn view(app: &App, frame: Frame) {
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         std::thread::spawn(move || {
+///             let draw = Draw::new();
+///             draw.ellipse().x(i as f32 * 100.0);
+///             draw.take_recording()
+///         })
+///     })
+///     .collect();
+///
+/// let draw = app.draw();
+/// for handle in handles {
+///     draw.append(handle.join().unwrap());
+/// }
+/// draw.to_frame(app, &frame).unwrap();
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// A **Recording** may also be serialized with serde, e.g. via `nannou::io::save_to_json`, so that
+/// a frame's drawing can be saved to disk and replayed later by deserializing it and appending it
+/// to a **Draw**. Serialization fails if any of the recorded drawings refer to a texture or a font,
+/// as these have no serializable representation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recording<S = geom::scalar::Default> {
+    commands: Vec<DrawCommand<S>>,
+    intermediary_state: IntermediaryState<S>,
+}
+
+/// The lengths of each of a **Draw**'s intermediary buffers prior to appending a **Recording**.
+///
+/// Used to rebase the ranges held by the recorded primitives.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Offsets {
+    pub mesh_vertices: usize,
+    pub mesh_indices: usize,
+    pub path_events: usize,
+    pub path_points_colored: usize,
+    pub path_points_textured: usize,
+    pub path_points_weighted: usize,
+    pub text: usize,
+}
+
+impl<S> Recording<S> {
+    /// The number of recorded commands, including context changes.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Whether or not the recording contains any commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl<S> Draw<S>
+where
+    S: BaseFloat,
+{
+    /// Finish any drawings-in-progress and take all commands drawn so far, along with the data
+    /// they refer to, into a **Recording**.
+    ///
+    /// The **Draw**'s background and theme are left untouched.
+    pub fn take_recording(&self) -> Recording<S> {
+        let commands = self.drain_commands().collect();
+        let mut state = self.state.borrow_mut();
+        state.last_draw_context = None;
+        let intermediary_state = std::mem::replace(
+            &mut *state.intermediary_state.borrow_mut(),
+            Default::default(),
+        );
+        Recording {
+            commands,
+            intermediary_state,
+        }
+    }
+
+    /// Finish any drawings-in-progress and produce a **Recording** of clones of all commands drawn
+    /// so far, along with the data they refer to.
+    ///
+    /// Unlike `take_recording`, the commands remain within the **Draw**.
+    pub fn recording(&self) -> Recording<S> {
+        let commands = self.commands().collect();
+        let state = self.state.borrow();
+        let intermediary_state = state.intermediary_state.borrow().clone();
+        Recording {
+            commands,
+            intermediary_state,
+        }
+    }
+
+    /// Produce a new **Draw** with the same background as this one and a copy of all commands
+    /// drawn so far, leaving this **Draw** untouched.
+    ///
+    /// This allows for rendering the same drawing more than once, e.g. to several windows, as
+    /// rendering a **Draw** consumes its commands.
+    pub fn duplicate(&self) -> Draw<S> {
+        let recording = self.recording();
+        let draw = Draw::new();
+        {
+            let state = self.state.borrow();
+            let mut new_state = draw.state.borrow_mut();
+            new_state.background_color = state.background_color;
+            new_state.background_fill = state.background_fill.clone();
+        }
+        draw.append(recording);
+        draw
+    }
+
+    /// Append the given **Recording** to the end of this **Draw**'s commands.
+    ///
+    /// The context of this **Draw** instance is applied to all recorded drawings:
+    ///
+    /// - Its transform is applied to the recorded transforms and scissors.
+    /// - Recorded scissors are cropped to its scissor.
+    /// - Recorded drawings without a group of their own join its opacity group, if any. Recorded
+    ///   groups within it are composited with the product of both opacities.
+    /// - Recorded drawings without a mask of their own form or are clipped by its mask, if any.
+    /// - Its blend, topology, sampler, depth and wireframe settings apply to recorded drawings
+    ///   that use the defaults for them.
+    ///
+    /// The opacity groups and masks of the recording are given new IDs following those already
+    /// created by this **Draw**, so that they remain distinct from its own and from those of other
+    /// appended recordings.
+    pub fn append(&self, recording: Recording<S>) {
+        let Recording {
+            commands,
+            intermediary_state,
+        } = recording;
+        let mut state = self.state.borrow_mut();
+        let offsets = state
+            .intermediary_state
+            .borrow_mut()
+            .append(&intermediary_state);
+        let (group_count, mask_count) = (state.group_count, state.mask_count);
+        let mut recorded_groups = 0;
+        let mut recorded_masks = 0;
+        let cmds = commands.into_iter().map(|cmd| match cmd {
+            DrawCommand::Context(mut ctxt) => {
+                if let Some(ref mut group) = ctxt.group {
+                    recorded_groups = recorded_groups.max(group.id + 1);
+                    group.id += group_count;
+                }
+                ctxt.mask = match ctxt.mask {
+                    Mask::None => Mask::None,
+                    Mask::Write(id) => {
+                        recorded_masks = recorded_masks.max(id);
+                        Mask::Write(shift_mask_id(id, mask_count))
+                    }
+                    Mask::Clip(id) => {
+                        recorded_masks = recorded_masks.max(id);
+                        Mask::Clip(shift_mask_id(id, mask_count))
+                    }
+                };
+                DrawCommand::Context(nest_context(&self.context, ctxt))
+            }
+            DrawCommand::ContextDelta(ContextDelta::Transform(transform)) => {
+                let transform = self.context.transform * transform;
+                DrawCommand::ContextDelta(ContextDelta::Transform(transform))
+            }
+            DrawCommand::ContextDelta(ContextDelta::Blend { color, alpha }) => {
+                let default = Context::<S>::default();
+                let color = or_outer(color, &default.color_blend, &self.context.color_blend);
+                let alpha = or_outer(alpha, &default.alpha_blend, &self.context.alpha_blend);
+                DrawCommand::ContextDelta(ContextDelta::Blend { color, alpha })
+            }
+            DrawCommand::ContextDelta(ContextDelta::Scissor(scissor)) => {
+                let scissor = nest_scissor(&self.context, scissor);
+                DrawCommand::ContextDelta(ContextDelta::Scissor(scissor))
+            }
+            DrawCommand::Primitive(mut prim) => {
+                offset_primitive(&mut prim, &offsets);
+                DrawCommand::Primitive(prim)
+            }
+        });
+        state.draw_commands.extend(cmds.map(Some));
+        state.group_count += recorded_groups;
+        state.mask_count = shift_mask_id(recorded_masks, mask_count);
+        // Ensure the next drawing re-submits its context.
+        state.last_draw_context = None;
+    }
+}
+
+// Nest a recorded context within the context of the **Draw** to which it is appended.
+//
+// See `Draw::append` for how each property is combined.
+fn nest_context<S>(outer: &Context<S>, inner: Context<S>) -> Context<S>
+where
+    S: BaseFloat,
+{
+    let default = Context::<S>::default();
+    let group = match (outer.group, inner.group) {
+        (Some(outer), Some(inner)) => Some(Group {
+            id: inner.id,
+            opacity: outer.opacity * inner.opacity,
+        }),
+        (outer, None) => outer,
+        (None, inner) => inner,
+    };
+    let mask = match inner.mask {
+        Mask::None => outer.mask,
+        mask => mask,
+    };
+    Context {
+        transform: outer.transform * inner.transform,
+        alpha_blend: or_outer(inner.alpha_blend, &default.alpha_blend, &outer.alpha_blend),
+        color_blend: or_outer(inner.color_blend, &default.color_blend, &outer.color_blend),
+        scissor: nest_scissor(outer, inner.scissor),
+        topology: or_outer(inner.topology, &default.topology, &outer.topology),
+        sampler: or_outer(inner.sampler, &default.sampler, &outer.sampler),
+        wireframe: inner.wireframe || outer.wireframe,
+        depth_compare: or_outer(
+            inner.depth_compare,
+            &default.depth_compare,
+            &outer.depth_compare,
+        ),
+        depth_write: or_outer(inner.depth_write, &default.depth_write, &outer.depth_write),
+        mask,
+        group,
+    }
+}
+
+// Transform a recorded scissor by the outer context's transform and crop it to its scissor.
+fn nest_scissor<S>(outer: &Context<S>, inner: Scissor<S>) -> Scissor<S>
+where
+    S: BaseFloat,
+{
+    let rect = match inner {
+        Scissor::Full => return outer.scissor,
+        Scissor::NoOverlap => return Scissor::NoOverlap,
+        Scissor::Rect(rect) => transform_rect_bounds(&outer.transform, rect),
+    };
+    match outer.scissor {
+        Scissor::Full => Scissor::Rect(rect),
+        Scissor::Rect(outer) => outer
+            .overlap(rect)
+            .map(Scissor::Rect)
+            .unwrap_or(Scissor::NoOverlap),
+        Scissor::NoOverlap => Scissor::NoOverlap,
+    }
+}
+
+// The recorded value, or the outer value if the recorded value is the default.
+fn or_outer<T>(inner: T, default: &T, outer: &T) -> T
+where
+    T: Clone + PartialEq,
+{
+    if inner == *default {
+        outer.clone()
+    } else {
+        inner
+    }
+}
+
+// Shift the given mask ID past the `count` masks already created, wrapping as `Draw::mask` does.
+//
+// An ID of `0`, i.e. no masks, maps to `count` itself.
+fn shift_mask_id(id: u8, count: u8) -> u8 {
+    if id == 0 {
+        return count;
+    }
+    let max = std::u8::MAX as u32;
+    ((id as u32 - 1 + count as u32) % max + 1) as u8
+}
+
+impl<S> IntermediaryState<S>
+where
+    S: Clone,
+{
+    // Append the buffers of `other` to the end of those of `self`, returning the offsets at which
+    // the data of `other` begins.
+    pub(crate) fn append(&mut self, other: &Self) -> Offsets {
+        let offsets = Offsets {
+            mesh_vertices: self.intermediary_mesh.raw_vertex_count(),
+            mesh_indices: self.intermediary_mesh.indices().len(),
+            path_events: self.path_event_buffer.len(),
+            path_points_colored: self.path_points_colored_buffer.len(),
+            path_points_textured: self.path_points_textured_buffer.len(),
+            path_points_weighted: self.path_points_weighted_buffer.len(),
+            text: self.text_buffer.len(),
+        };
+        let mesh = &other.intermediary_mesh;
+        self.intermediary_mesh.extend_vertices_from_slices(
+            mesh.points(),
+            mesh.colors(),
+            mesh.tex_coords(),
+        );
+        let vertex_offset = offsets.mesh_vertices as u32;
+        self.intermediary_mesh
+            .extend_indices(mesh.indices().iter().map(|&i| vertex_offset + i));
+        self.path_event_buffer
+            .extend(other.path_event_buffer.iter().cloned());
+        self.path_points_colored_buffer
+            .extend(other.path_points_colored_buffer.iter().cloned());
+        self.path_points_textured_buffer
+            .extend(other.path_points_textured_buffer.iter().cloned());
+        self.path_points_weighted_buffer
+            .extend(other.path_points_weighted_buffer.iter().cloned());
+        self.text_buffer.push_str(&other.text_buffer);
+        offsets
+    }
+}
+
+/// Shift the given range by the given offset.
+pub(crate) fn offset_range(range: &mut ops::Range<usize>, offset: usize) {
+    range.start += offset;
+    range.end += offset;
+}
+
+// Rebase the ranges into the intermediary buffers held by the given primitive.
+fn offset_primitive<S>(prim: &mut Primitive<S>, offsets: &Offsets)
+where
+    S: BaseFloat,
+{
+    match *prim {
+        Primitive::Mesh(ref mut mesh) => mesh.offset_ranges(offsets),
+        Primitive::Path(ref mut path) => path.offset_ranges(offsets),
+        Primitive::PointCloud(ref mut cloud) => cloud.offset_ranges(offsets),
+        Primitive::Polygon(ref mut polygon) => polygon.offset_ranges(offsets),
+        Primitive::Polyline3d(ref mut polyline) => polyline.offset_ranges(offsets),
+        Primitive::Text(ref mut text) => text.offset_ranges(offsets),
+        Primitive::Shaded(ref mut shaded) => offset_primitive(shaded.primitive_mut(), offsets),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Matrix4;
+
+    // The group and mask of each context within the given **Draw**'s commands, in order.
+    fn contexts(draw: &Draw) -> Vec<(Option<u32>, Mask)> {
+        draw.drain_commands()
+            .filter_map(|cmd| match cmd {
+                DrawCommand::Context(ctxt) => Some((ctxt.group.map(|g| g.id), ctxt.mask)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // A recording of a single grouped and a single masked drawing.
+    fn record() -> Recording {
+        let draw = Draw::new();
+        draw.opacity(0.5).ellipse();
+        draw.mask(|d| {
+            d.rect();
+        })
+        .tri();
+        draw.take_recording()
+    }
+
+    #[test]
+    fn test_append_grouped_recordings() {
+        let draw = Draw::new();
+        draw.opacity(0.25).rect();
+        draw.append(record());
+        draw.append(record());
+        let groups: Vec<_> = contexts(&draw).iter().filter_map(|c| c.0).collect();
+        assert_eq!(groups, vec![0, 1, 2]);
+        assert_eq!(draw.state.borrow().group_count, 3);
+    }
+
+    #[test]
+    fn test_append_masked_recordings() {
+        let draw = Draw::new();
+        draw.append(record());
+        draw.append(record());
+        let masks: Vec<_> = contexts(&draw)
+            .into_iter()
+            .map(|c| c.1)
+            .filter(|&m| m != Mask::None)
+            .collect();
+        let expected = vec![Mask::Write(1), Mask::Clip(1), Mask::Write(2), Mask::Clip(2)];
+        assert_eq!(masks, expected);
+        assert_eq!(draw.state.borrow().mask_count, 2);
+    }
+
+    #[test]
+    fn test_append_within_context() {
+        let draw = Draw::new();
+        let rect = geom::Rect::from_w_h(10.0, 10.0);
+        let target = draw.scissor(rect).opacity(0.5).x(20.0);
+        target.append(record());
+        let ctxts: Vec<_> = draw
+            .drain_commands()
+            .filter_map(|cmd| match cmd {
+                DrawCommand::Context(ctxt) => Some(ctxt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ctxts.len(), 3);
+        for ctxt in &ctxts {
+            assert_eq!(ctxt.scissor, Scissor::Rect(rect));
+            assert_eq!(ctxt.transform.w.x, 20.0);
+        }
+        // The recorded group is composited with both opacities.
+        assert_eq!(ctxts[0].group.map(|g| (g.id, g.opacity)), Some((1, 0.25)));
+        // The remaining drawings join the target's group.
+        assert_eq!(ctxts[1].group.map(|g| (g.id, g.opacity)), Some((0, 0.5)));
+        assert_eq!(ctxts[2].group.map(|g| (g.id, g.opacity)), Some((0, 0.5)));
+    }
+
+    #[test]
+    fn test_nest_scissor() {
+        let rect = geom::Rect::from_w_h(10.0, 10.0);
+        let outer = Context {
+            transform: Matrix4::from_translation([5.0, 0.0, 0.0].into()),
+            scissor: Scissor::Rect(rect),
+            ..Default::default()
+        };
+        assert_eq!(nest_scissor(&outer, Scissor::Full), Scissor::Rect(rect));
+        let inner = Scissor::Rect(rect);
+        let expected = geom::Rect::from_x_y_w_h(2.5, 0.0, 5.0, 10.0);
+        assert_eq!(nest_scissor(&outer, inner), Scissor::Rect(expected));
+        let inner = Scissor::Rect(geom::Rect::from_x_y_w_h(20.0, 0.0, 10.0, 10.0));
+        assert_eq!(nest_scissor(&outer, inner), Scissor::NoOverlap);
+    }
+
+    #[test]
+    fn test_shift_mask_id() {
+        assert_eq!(shift_mask_id(0, 7), 7);
+        assert_eq!(shift_mask_id(1, 0), 1);
+        assert_eq!(shift_mask_id(3, 4), 7);
+        // IDs wrap around to `1`, as `0` is reserved for unmasked drawings.
+        assert_eq!(shift_mask_id(1, 255), 1);
+        assert_eq!(shift_mask_id(10, 250), 5);
+    }
+}
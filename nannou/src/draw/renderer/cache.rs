@@ -0,0 +1,417 @@
+//! A cache of tessellated primitives, allowing unchanged primitives to skip tessellation on
+//! subsequent frames.
+
+use crate::color::Srgba;
+use crate::draw;
+use crate::draw::mesh::vertex::{self, Color};
+use crate::draw::primitive::path::PathEventSource;
+use crate::draw::primitive::{Path, Polygon};
+use crate::draw::renderer::PrimitiveRender;
+use crate::geom::Point2;
+use crate::math::Matrix4;
+use lyon::path::PathEvent;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Stores the vertices and indices of tessellated primitives between frames.
+///
+/// Entries are keyed by the primitive's parameters (including its tessellation tolerance), its
+/// transform, the data it references within the **Draw**'s intermediary buffers and the theme.
+/// Entries that were not used during the most recent fill are evicted.
+///
+/// Only paths and polygons are cached. Building a key copies the primitive's path data, so the
+/// cache only pays off for scenes with many complex paths that are unchanged between frames. As a
+/// result, the cache is disabled by default.
+#[derive(Debug)]
+pub struct TessellationCache {
+    entries: HashMap<Key, Entry>,
+    enabled: bool,
+    stats: Stats,
+}
+
+/// Statistics describing the use of the **TessellationCache** during the most recent fill.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Stats {
+    /// The number of primitives that were copied from the cache rather than tessellated.
+    pub hits: usize,
+    /// The number of primitives that were tessellated and inserted into the cache.
+    pub misses: usize,
+    /// The number of entries retained by the cache.
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    points: Vec<vertex::Point>,
+    colors: Vec<Color>,
+    tex_coords: Vec<vertex::TexCoords>,
+    indices: Vec<u32>,
+    render: PrimitiveRender,
+    used: bool,
+}
+
+/// Identifies a tessellated primitive within the **TessellationCache**.
+///
+/// Two keys are equal only if all of the parameters that affect tessellation are equal, so that a
+/// hash collision can never result in the wrong geometry being drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Key {
+    prim: KeyPrimitive,
+    transform: Matrix4<f32>,
+    data: KeyData,
+    // The theme's fill and stroke colors for the primitive's kind.
+    theme: [Srgba; 2],
+}
+
+// The cacheable primitives.
+#[derive(Clone, Debug, PartialEq)]
+enum KeyPrimitive {
+    Path(Path<f32>),
+    Polygon(Polygon<f32>),
+}
+
+// The data referenced by the primitive within the **Draw**'s intermediary buffers.
+#[derive(Clone, Debug, PartialEq)]
+enum KeyData {
+    Events(Vec<PathEvent>),
+    ColoredPoints(Vec<(Point2, Color)>),
+    TexturedPoints(Vec<(Point2, Point2)>),
+    WeightedPoints(Vec<(Point2, f32)>),
+}
+
+impl TessellationCache {
+    /// Whether or not the cache is currently used while filling.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the cache. Disabling the cache also clears it.
+    ///
+    /// By default, the cache is disabled.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Statistics describing the use of the cache during the most recent fill.
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Remove all entries from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.stats.entries = 0;
+    }
+
+    /// Whether or not the cache contains an entry for the given key.
+    pub(crate) fn contains(&self, key: &Key) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    // Called at the beginning of `Renderer::fill`.
+    pub(crate) fn begin_fill(&mut self) {
+        self.stats = Stats::default();
+        for entry in self.entries.values_mut() {
+            entry.used = false;
+        }
+    }
+
+    // Called at the end of `Renderer::fill`. Evicts all entries that went unused.
+    pub(crate) fn end_fill(&mut self) {
+        self.entries.retain(|_, entry| entry.used);
+        self.stats.entries = self.entries.len();
+    }
+
+    // Extend the mesh with the entry for the given key if there is one.
+    pub(crate) fn extend_mesh(
+        &mut self,
+        key: &Key,
+        mesh: &mut draw::Mesh,
+    ) -> Option<PrimitiveRender> {
+        let entry = self.entries.get_mut(key)?;
+        entry.used = true;
+        self.stats.hits += 1;
+        let offset = mesh.raw_vertex_count() as u32;
+        mesh.extend_vertices_from_slices(&entry.points, &entry.colors, &entry.tex_coords);
+        mesh.extend_indices(entry.indices.iter().map(|&i| offset + i));
+        Some(entry.render.clone())
+    }
+
+    // Insert the vertices and indices that were added to the mesh since the given starting
+    // vertex and index.
+    pub(crate) fn insert(
+        &mut self,
+        key: Key,
+        mesh: &draw::Mesh,
+        vertex_start: usize,
+        index_start: usize,
+        render: &PrimitiveRender,
+    ) {
+        let offset = vertex_start as u32;
+        let entry = Entry {
+            points: mesh.points()[vertex_start..].to_vec(),
+            colors: mesh.colors()[vertex_start..].to_vec(),
+            tex_coords: mesh.tex_coords()[vertex_start..].to_vec(),
+            indices: mesh.indices()[index_start..]
+                .iter()
+                .map(|&i| i - offset)
+                .collect(),
+            render: render.clone(),
+            used: true,
+        };
+        self.stats.misses += 1;
+        self.entries.insert(key, entry);
+    }
+}
+
+impl Default for TessellationCache {
+    fn default() -> Self {
+        TessellationCache {
+            entries: Default::default(),
+            enabled: false,
+            stats: Default::default(),
+        }
+    }
+}
+
+// Floats are compared by value. A key containing NaN is not equal to itself and so never results
+// in a hit. Its entry goes unused and is evicted at the end of the following fill.
+impl Eq for Key {}
+
+// Only the transform, the referenced data and the theme are hashed. The remaining parameters are
+// compared on lookup.
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let transform: &[f32; 16] = self.transform.as_ref();
+        hash_f32s(transform, state);
+        match self.data {
+            KeyData::Events(ref events) => {
+                state.write_usize(events.len());
+                for event in events {
+                    hash_event(event, state);
+                }
+            }
+            KeyData::ColoredPoints(ref points) => {
+                state.write_usize(points.len());
+                for &(p, c) in points {
+                    let (r, g, b, a) = c.into_components();
+                    hash_f32s(&[p.x, p.y, r, g, b, a], state);
+                }
+            }
+            KeyData::TexturedPoints(ref points) => {
+                state.write_usize(points.len());
+                for &(p, t) in points {
+                    hash_f32s(&[p.x, p.y, t.x, t.y], state);
+                }
+            }
+            KeyData::WeightedPoints(ref points) => {
+                state.write_usize(points.len());
+                for &(p, w) in points {
+                    hash_f32s(&[p.x, p.y, w], state);
+                }
+            }
+        }
+        for c in &self.theme {
+            let (r, g, b, a) = c.into_components();
+            hash_f32s(&[r, g, b, a], state);
+        }
+    }
+}
+
+// Hash the given floats consistently with their `PartialEq` implementation, i.e. `0.0` and `-0.0`
+// produce the same hash.
+fn hash_f32s<H: Hasher>(fs: &[f32], state: &mut H) {
+    for &f in fs {
+        state.write_u32((f + 0.0).to_bits());
+    }
+}
+
+fn hash_event<H: Hasher>(event: &PathEvent, state: &mut H) {
+    match *event {
+        PathEvent::Begin { at } => {
+            state.write_u8(0);
+            hash_f32s(&[at.x, at.y], state);
+        }
+        PathEvent::Line { from, to } => {
+            state.write_u8(1);
+            hash_f32s(&[from.x, from.y, to.x, to.y], state);
+        }
+        PathEvent::Quadratic { from, ctrl, to } => {
+            state.write_u8(2);
+            hash_f32s(&[from.x, from.y, ctrl.x, ctrl.y, to.x, to.y], state);
+        }
+        PathEvent::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } => {
+            state.write_u8(3);
+            let ps = [from, ctrl1, ctrl2, to];
+            for p in &ps {
+                hash_f32s(&[p.x, p.y], state);
+            }
+        }
+        PathEvent::End { last, first, close } => {
+            state.write_u8(4);
+            hash_f32s(&[last.x, last.y, first.x, first.y], state);
+            close.hash(state);
+        }
+    }
+}
+
+/// Produce the key for the given primitive drawn with the given transform.
+///
+/// Returns `None` for primitives that should not be cached, either because they do not require
+/// tessellation (e.g. meshes), because they depend on state outside of the **Draw** (e.g. text
+/// and the glyph cache) or because they are cheap enough to tessellate that a key would cost more
+/// than it saves (e.g. ellipses and rects).
+pub(crate) fn key(
+    prim: &draw::Primitive,
+    transform: &Matrix4<f32>,
+    intermediary_state: &draw::IntermediaryState<f32>,
+    theme: &draw::Theme,
+) -> Option<Key> {
+    let (prim, path_event_src, kind) = match *prim {
+        draw::Primitive::Path(ref path) => (
+            KeyPrimitive::Path(path.clone()),
+            path.path_event_src(),
+            draw::theme::Primitive::Path,
+        ),
+        draw::Primitive::Polygon(ref polygon) => (
+            KeyPrimitive::Polygon(polygon.clone()),
+            polygon.path_event_src(),
+            draw::theme::Primitive::Polygon,
+        ),
+        _ => return None,
+    };
+    let data = match *path_event_src {
+        PathEventSource::Buffered(ref range) => {
+            KeyData::Events(intermediary_state.path_event_buffer[range.clone()].to_vec())
+        }
+        PathEventSource::ColoredPoints { ref range, .. } => KeyData::ColoredPoints(
+            intermediary_state.path_points_colored_buffer[range.clone()].to_vec(),
+        ),
+        PathEventSource::TexturedPoints { ref range, .. } => KeyData::TexturedPoints(
+            intermediary_state.path_points_textured_buffer[range.clone()].to_vec(),
+        ),
+        PathEventSource::WeightedPoints { ref range, .. } => KeyData::WeightedPoints(
+            intermediary_state.path_points_weighted_buffer[range.clone()].to_vec(),
+        ),
+    };
+    // Primitives without a color fall back to the theme.
+    let theme = [theme.fill_srgba(&kind), theme.stroke_srgba(&kind)];
+    Some(Key {
+        prim,
+        transform: *transform,
+        data,
+        theme,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{BLUE, RED};
+    use crate::geom::pt2;
+    use crate::math::SquareMatrix;
+    use std::collections::hash_map::DefaultHasher;
+
+    // The key of each primitive submitted by the given function.
+    fn keys<F>(f: F) -> Vec<Option<Key>>
+    where
+        F: FnOnce(&draw::Draw),
+    {
+        let draw = draw::Draw::new();
+        f(&draw);
+        let cmds: Vec<_> = draw.commands().collect();
+        let state = draw.state.borrow();
+        let intermediary_state = state.intermediary_state.borrow();
+        let transform = Matrix4::identity();
+        cmds.iter()
+            .filter_map(|cmd| match *cmd {
+                draw::DrawCommand::Primitive(ref prim) => {
+                    Some(key(prim, &transform, &intermediary_state, &state.theme))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn hash(key: &Key) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn line(draw: &draw::Draw) {
+        draw.polyline()
+            .weight(2.0)
+            .points(vec![pt2(0.0, 0.0), pt2(10.0, 5.0), pt2(20.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_key_equal() {
+        let a = keys(line).remove(0).unwrap();
+        let b = keys(line).remove(0).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn test_key_points_differ() {
+        let a = keys(line).remove(0).unwrap();
+        let b = keys(|draw| {
+            draw.polyline()
+                .weight(2.0)
+                .points(vec![pt2(0.0, 0.0), pt2(10.0, 6.0), pt2(20.0, 0.0)]);
+        })
+        .remove(0)
+        .unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_key_parameters_differ() {
+        // The same points with a different color or weight must not share an entry.
+        let points = || vec![pt2(0.0, 0.0), pt2(10.0, 5.0)];
+        let ks = keys(|draw| {
+            draw.polyline().color(RED).points(points());
+            draw.polyline().color(BLUE).points(points());
+            draw.polyline().color(RED).weight(3.0).points(points());
+        });
+        let ks: Vec<Key> = ks.into_iter().map(Option::unwrap).collect();
+        assert_ne!(ks[0], ks[1]);
+        assert_ne!(ks[0], ks[2]);
+        assert_ne!(ks[1], ks[2]);
+    }
+
+    #[test]
+    fn test_key_theme_differs() {
+        let a = keys(line).remove(0).unwrap();
+        let b = keys(|draw| {
+            let mut theme = draw::Theme::default();
+            theme
+                .stroke_color
+                .primitive
+                .insert(draw::theme::Primitive::Path, Srgba::new(1.0, 0.0, 0.0, 1.0));
+            draw.set_theme(theme);
+            line(draw);
+        })
+        .remove(0)
+        .unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_key_uncached_primitives() {
+        let ks = keys(|draw| {
+            draw.ellipse();
+            draw.rect();
+        });
+        assert!(ks.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,228 @@
+//! Rendering of the opacity groups created via `Draw::opacity`.
+//!
+//! The drawings of each group are rendered to an intermediate texture within a render pass of
+//! their own. The texture is then scaled by the group's opacity and composited onto the output as
+//! a single textured quad in place of the group's first drawing.
+
+use super::{FillCommand, FillPrimitive, Renderer};
+use crate::draw;
+use crate::geom::Rect;
+use crate::wgpu;
+
+/// The intermediate texture to which the drawings of a group are rendered.
+#[derive(Debug)]
+pub(crate) struct Target {
+    // Multisampled if the renderer is, in which case it is resolved to `resolve`.
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    resolve: Option<(wgpu::Texture, wgpu::TextureView)>,
+}
+
+// The commands of a single group, collected while arranging the fill commands.
+struct Commands {
+    id: u32,
+    opacity: f32,
+    cmds: Vec<FillCommand>,
+    last_ctxt: Option<draw::Context>,
+}
+
+impl Target {
+    fn new(
+        device: &wgpu::Device,
+        size: [u32; 2],
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let usage = wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED;
+        let texture = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(format)
+            .sample_count(sample_count)
+            .usage(usage)
+            .build(device);
+        let texture_view = texture.view().build();
+        let resolve = if sample_count > 1 {
+            let texture = wgpu::TextureBuilder::new()
+                .size(size)
+                .format(format)
+                .usage(usage)
+                .build(device);
+            let texture_view = texture.view().build();
+            Some((texture, texture_view))
+        } else {
+            None
+        };
+        Target {
+            texture,
+            texture_view,
+            resolve,
+        }
+    }
+
+    /// The view to render to, along with the view to resolve to if the target is multisampled.
+    pub(crate) fn attachments(&self) -> (&wgpu::TextureView, Option<&wgpu::TextureView>) {
+        let resolve = self.resolve.as_ref().map(|&(_, ref view)| view);
+        (&self.texture_view, resolve)
+    }
+
+    // The view sampled when compositing the group.
+    fn sampled_view(&self) -> &wgpu::TextureView {
+        match self.resolve {
+            Some((_, ref view)) => view,
+            None => &self.texture_view,
+        }
+    }
+}
+
+impl Renderer {
+    // Move the commands of each opacity group ahead of all others, each preceded by a
+    // `BeginPass` and followed by the commands that scale the group by its opacity. The remaining
+    // commands follow a final `BeginPass`, with the commands compositing each group inserted in
+    // place of the group's first drawing.
+    //
+    // The commands are left untouched if no groups are drawn. The `viewport` is the region of the
+    // output covered by the output attachment, over which each group is composited.
+    pub(super) fn arrange_groups(
+        &mut self,
+        device: &wgpu::Device,
+        cmds: &mut Vec<FillCommand>,
+        size_px: [u32; 2],
+        viewport: Rect,
+    ) {
+        // A change of group always produces a full context command.
+        let has_groups = cmds.iter().any(|cmd| match *cmd {
+            FillCommand::Context(ref ctxt) => ctxt.group.is_some(),
+            _ => false,
+        });
+        if !has_groups {
+            self.group_targets.clear();
+            return;
+        }
+
+        let mut groups: Vec<Commands> = vec![];
+        let mut main = Vec::with_capacity(cmds.len() + 1);
+        main.push(FillCommand::Context(Default::default()));
+        let mut ctxt = draw::Context::default();
+        for cmd in cmds.drain(..) {
+            let group = match cmd {
+                FillCommand::Context(ref c) => {
+                    ctxt = c.clone();
+                    None
+                }
+                FillCommand::ContextDelta(ref delta) => {
+                    delta.clone().apply(&mut ctxt);
+                    None
+                }
+                _ => ctxt.group,
+            };
+            let group = match group {
+                None => {
+                    main.push(cmd);
+                    continue;
+                }
+                Some(group) => group,
+            };
+            let ix = match groups.iter().position(|g| g.id == group.id) {
+                Some(ix) => ix,
+                None => {
+                    let ix = groups.len();
+                    let format = self.output_color_format;
+                    let sample_count = self.sample_count;
+                    let stale = self
+                        .group_targets
+                        .get(ix)
+                        .map(|target| {
+                            target.texture.size() != size_px
+                                || target.texture.format() != format
+                                || target.texture.sample_count() != sample_count
+                        })
+                        .unwrap_or(true);
+                    if stale {
+                        let target = Target::new(device, size_px, format, sample_count);
+                        if ix < self.group_targets.len() {
+                            self.group_targets[ix] = target;
+                        } else {
+                            self.group_targets.push(target);
+                        }
+                    }
+                    let view = self.group_targets[ix].sampled_view();
+                    main.extend(composite_commands(view, viewport));
+                    main.push(FillCommand::Context(ctxt.clone()));
+                    groups.push(Commands {
+                        id: group.id,
+                        opacity: group.opacity,
+                        cmds: vec![],
+                        last_ctxt: None,
+                    });
+                    ix
+                }
+            };
+            let group = &mut groups[ix];
+            if group.last_ctxt.as_ref() != Some(&ctxt) {
+                group.cmds.push(FillCommand::Context(ctxt.clone()));
+                group.last_ctxt = Some(ctxt.clone());
+            }
+            group.cmds.push(cmd);
+        }
+        self.group_targets.truncate(groups.len());
+
+        for group in groups {
+            cmds.push(FillCommand::BeginPass);
+            cmds.extend(group.cmds);
+            cmds.extend(scale_commands(group.opacity, viewport));
+        }
+        cmds.push(FillCommand::BeginPass);
+        cmds.extend(main);
+    }
+}
+
+// The commands that composite the premultiplied contents of a group's target over the given
+// viewport of the output.
+fn composite_commands(
+    texture_view: &wgpu::TextureView,
+    viewport: Rect,
+) -> impl Iterator<Item = FillCommand> {
+    let blend = wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let draw = draw::Draw::new()
+        .color_blend(blend.clone())
+        .alpha_blend(blend)
+        .depth_test(false);
+    draw.texture(texture_view)
+        .xy(viewport.xy())
+        .wh(viewport.wh());
+    draw.drain_commands().map(to_fill_command)
+}
+
+// The commands that scale all channels of a group's target by the group's opacity.
+//
+// The viewport of the target is covered by a rect whose alpha is the opacity, blended such that
+// the destination is multiplied by the source alpha.
+fn scale_commands(opacity: f32, viewport: Rect) -> impl Iterator<Item = FillCommand> {
+    let blend = wgpu::BlendDescriptor {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::SrcAlpha,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let draw = draw::Draw::new()
+        .color_blend(blend.clone())
+        .alpha_blend(blend)
+        .depth_test(false);
+    draw.rect()
+        .xy(viewport.xy())
+        .wh(viewport.wh())
+        .rgba(0.0, 0.0, 0.0, opacity);
+    draw.drain_commands().map(to_fill_command)
+}
+
+fn to_fill_command(cmd: draw::DrawCommand) -> FillCommand {
+    match cmd {
+        draw::DrawCommand::Primitive(prim) => {
+            FillCommand::Primitive(FillPrimitive::Composite(prim))
+        }
+        cmd => cmd.into(),
+    }
+}
@@ -0,0 +1,120 @@
+//! A cache of the textures uploaded for images drawn via `draw.image`.
+
+use crate::draw::primitive::ImageSource;
+use crate::image;
+use crate::wgpu;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Stores the textures of images drawn via `draw.image` between frames.
+///
+/// Images loaded from a file are keyed by their path and are retained until the cache is cleared.
+/// Images given in memory are keyed by their `Arc` and are evicted once the cache holds the only
+/// remaining reference.
+#[derive(Debug, Default)]
+pub struct ImageCache {
+    entries: HashMap<Key, Option<Entry>>,
+    // Textures created during the most recent fill whose data is yet to be uploaded.
+    pending_uploads: Vec<(wgpu::Texture, Vec<u8>)>,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Key {
+    Path(PathBuf),
+    Image(usize),
+}
+
+#[derive(Debug)]
+struct Entry {
+    // Keeps the texture alive for as long as its view is cached.
+    _texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    // Keeps the address of an in-memory image unique while it is cached.
+    _image: Option<Arc<image::DynamicImage>>,
+}
+
+impl ImageCache {
+    /// The number of images retained by the cache, including those that failed to load.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether or not the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all entries from the cache, e.g. in order to reload images that have changed on
+    /// disk.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Remove the entry for the image file at the given path, if there is one.
+    pub fn remove_path(&mut self, path: &std::path::Path) {
+        self.entries.remove(&Key::Path(path.to_path_buf()));
+    }
+
+    // Called at the beginning of `Renderer::fill`. Evicts in-memory images that are no longer
+    // referenced outside of the cache.
+    pub(crate) fn begin_fill(&mut self) {
+        self.entries.retain(|_, entry| match *entry {
+            Some(Entry {
+                _image: Some(ref image),
+                ..
+            }) => Arc::strong_count(image) > 1,
+            _ => true,
+        });
+    }
+
+    // Retrieve the texture view for the given image, loading the image and creating its texture if
+    // necessary. The texture's data is uploaded during `encode_uploads`.
+    //
+    // Returns `None` if the image could not be loaded.
+    pub(crate) fn texture_view(
+        &mut self,
+        device: &wgpu::Device,
+        source: &ImageSource,
+    ) -> Option<wgpu::TextureView> {
+        let key = match *source {
+            ImageSource::Path(ref path) => Key::Path(path.clone()),
+            ImageSource::Image(ref image) => Key::Image(&**image as *const _ as usize),
+        };
+        let pending_uploads = &mut self.pending_uploads;
+        let entry = self.entries.entry(key).or_insert_with(|| {
+            let (rgba, shared) = match *source {
+                ImageSource::Path(ref path) => match image::open(path) {
+                    Ok(image) => (image.to_rgba(), None),
+                    Err(err) => {
+                        eprintln!("failed to load image {}: {}", path.display(), err);
+                        return None;
+                    }
+                },
+                ImageSource::Image(ref image) => (image.to_rgba(), Some(image.clone())),
+            };
+            let texture = wgpu::TextureBuilder::from_image_view(&rgba)
+                .usage(wgpu::TextureUsage::COPY_DST | wgpu::TextureUsage::SAMPLED)
+                .build(device);
+            let texture_view = texture.view().build();
+            pending_uploads.push((texture.clone(), rgba.into_raw()));
+            Some(Entry {
+                _texture: texture,
+                texture_view,
+                _image: shared,
+            })
+        });
+        entry.as_ref().map(|entry| entry.texture_view.clone())
+    }
+
+    // Encode the upload of the data of all textures created since the last call.
+    pub(crate) fn encode_uploads(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        for (texture, data) in self.pending_uploads.drain(..) {
+            texture.upload_data(device, encoder, &data);
+        }
+    }
+}
@@ -1,16 +1,34 @@
+use crate::color::ColorVisionDeficiency;
 use crate::draw;
 use crate::draw::mesh::vertex::Color;
 use crate::frame::Frame;
 use crate::geom::{self, Point2, Rect, Vector2};
-use crate::math::{map_range, Matrix4};
+use crate::math::{map_range, Matrix4, SquareMatrix};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::text;
 use crate::wgpu;
 use lyon::path::PathEvent;
 use lyon::tessellation::{FillTessellator, StrokeTessellator};
-use std::collections::HashMap;
-use std::fmt;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Write};
 use std::hash::{Hash, Hasher};
+use std::io;
 use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub use self::cache::TessellationCache;
+pub use self::image_cache::ImageCache;
+pub use self::picking::PrimitiveId;
+pub use self::sdf::SdfGlyphCache;
+
+pub mod cache;
+mod group;
+pub mod image_cache;
+pub mod picking;
+pub(crate) mod retained;
+pub mod sdf;
 
 /// Draw API primitives that may be rendered via the **Renderer** type.
 pub trait RenderPrimitive {
@@ -19,6 +37,7 @@ pub trait RenderPrimitive {
 }
 
 /// Information about the way in which a primitive was rendered.
+#[derive(Clone, Debug)]
 pub struct PrimitiveRender {
     /// Whether or not a specific texture must be available when this primitive is drawn.
     ///
@@ -26,8 +45,27 @@ pub struct PrimitiveRender {
     /// command will be encoded that switches from the previous texture's bind group to the new
     /// one.
     pub texture_view: Option<wgpu::TextureView>,
+    /// The layer to sample if the `texture_view` is a `D2Array` view.
+    ///
+    /// This allows for drawing many layers of the same array texture without switching bind
+    /// groups.
+    pub texture_layer: u32,
     /// The way in which vertices should be coloured in the fragment shader.
     pub vertex_mode: VertexMode,
+    /// The primitive topology with which the primitive's indices should be drawn.
+    ///
+    /// If `None`, the topology of the current draw context is used.
+    pub topology: Option<wgpu::PrimitiveTopology>,
+    /// The user fragment shader with which the primitive should be drawn, if any.
+    ///
+    /// If `None`, the renderer's default fragment shader is used.
+    pub fragment_shader: Option<draw::primitive::FragmentShader>,
+    /// The instances with which the primitive's vertices should be drawn.
+    ///
+    /// If empty, the vertices are drawn once as they are. Otherwise the vertices are drawn once
+    /// per instance via a draw call of their own, each transformed by the instance's transform
+    /// and optionally coloured by the instance's color within the vertex shader.
+    pub instances: Vec<draw::primitive::mesh::Instance>,
 }
 
 /// The context provided to primitives to assist with the rendering process.
@@ -37,13 +75,26 @@ pub struct RenderContext<'a> {
     pub path_event_buffer: &'a [PathEvent],
     pub path_points_colored_buffer: &'a [(Point2, Color)],
     pub path_points_textured_buffer: &'a [(Point2, Point2)],
+    pub path_points_weighted_buffer: &'a [(Point2, f32)],
     pub text_buffer: &'a str,
     pub theme: &'a draw::Theme,
-    pub glyph_cache: &'a mut GlyphCache,
+    /// Access to the glyph cache, required by primitives that render text.
+    ///
+    /// This is `None` while tessellating primitives in parallel, during which primitives that
+    /// require the glyph cache are rendered separately.
+    pub glyph_cache: Option<&'a mut GlyphCache>,
     pub fill_tessellator: &'a mut FillTessellator,
     pub stroke_tessellator: &'a mut StrokeTessellator,
     pub output_attachment_size: Vector2, // logical coords
     pub output_attachment_scale_factor: f32,
+    /// The rect covered by the full output in the coordinates of the **Draw**.
+    ///
+    /// This is larger than the output attachment when only a region of the output is rendered,
+    /// e.g. a single tile via `Draw::to_image_tiled`. See `Renderer::set_full_output_rect`.
+    pub full_output_rect: Rect,
+    /// The point in the coordinates of the **Draw** that lies at the center of the output
+    /// attachment. See `Renderer::set_projection_offset`.
+    pub projection_offset: Vector2,
 }
 
 pub struct GlyphCache {
@@ -53,10 +104,25 @@ pub struct GlyphCache {
     pub pixel_buffer: Vec<u8>,
     /// Will be set to `true` after the cache has been updated if the texture requires re-uploading.
     pub requires_upload: bool,
+    /// The signed distance fields of the glyphs of text drawn with `sdf` enabled.
+    pub sdf: SdfGlyphCache,
+    // The glyphs of each text primitive laid out and cached ahead of time during `fill`, in the
+    // order in which the text primitives are rendered.
+    pub(crate) batched_glyphs: VecDeque<BatchedGlyphs>,
+}
+
+/// The glyphs of a single text primitive, laid out ahead of rendering as part of a batch.
+#[derive(Clone, Debug)]
+pub(crate) struct BatchedGlyphs {
+    /// The glyphs positioned within the pixel space of the output attachment, grouped into runs
+    /// that share a font and color.
+    pub runs: Vec<draw::primitive::text::GlyphRun>,
+    /// Whether or not the glyphs were successfully cached along with the rest of the batch.
+    pub cached: bool,
 }
 
 /// A top-level indicator of whether or not
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[repr(u32)]
 pub enum VertexMode {
     /// Use the color values and ignore the texture coordinates.
@@ -67,6 +133,11 @@ pub enum VertexMode {
     ///
     /// Uses the color values, but multiplies the alpha by the glyph cache texture's red value.
     Text = 2,
+    /// A special mode used by the text primitive when drawing via signed distance fields.
+    ///
+    /// Uses the color values, but multiplies the alpha by the coverage reconstructed from the
+    /// signed distance field texture's red value.
+    SdfText = 3,
 }
 
 /// A helper type aimed at simplifying the rendering of conrod primitives via wgpu.
@@ -75,9 +146,15 @@ pub struct Renderer {
     glyph_cache: GlyphCache,
     vs_mod: wgpu::ShaderModule,
     fs_mod: wgpu::ShaderModule,
+    // The fragment shader used for drawing `D2Array` texture views.
+    fs_array_mod: wgpu::ShaderModule,
+    // The user fragment shaders used by the pipelines, keyed by address. Each is retained for as
+    // long as a pipeline refers to it so that its address may not be reused by another shader.
+    fragment_shaders: HashMap<usize, Arc<wgpu::ShaderModule>>,
     // One pipeline per unique Pipeline ID (combination of blend, topology and component type).
     pipelines: HashMap<PipelineId, wgpu::RenderPipeline>,
     glyph_cache_texture: wgpu::Texture,
+    sdf_glyph_cache_texture: wgpu::Texture,
     depth_texture: wgpu::Texture,
     depth_texture_view: wgpu::TextureView,
     default_texture: wgpu::Texture,
@@ -87,21 +164,66 @@ pub struct Renderer {
     text_bind_group_layout: wgpu::BindGroupLayout,
     text_bind_group: wgpu::BindGroup,
     texture_samplers: HashMap<SamplerId, wgpu::Sampler>,
-    texture_bind_group_layouts: HashMap<wgpu::TextureComponentType, wgpu::BindGroupLayout>,
+    texture_bind_group_layouts: HashMap<TextureLayoutId, wgpu::BindGroupLayout>,
     texture_bind_groups: HashMap<BindGroupId, wgpu::BindGroup>,
+    shader_uniform_bind_group_layout: wgpu::BindGroupLayout,
+    // The uniforms bound for user fragment shaders during the last fill. The first is a zeroed
+    // buffer bound for shaders that were not given any uniforms.
+    shader_uniforms: Vec<(wgpu::Buffer, wgpu::BindGroup)>,
     output_color_format: wgpu::TextureFormat,
     sample_count: u32,
     scale_factor: f32,
+    // The point in the **Draw**'s coordinates that lies at the center of the output attachment.
+    projection_offset: Vector2,
+    // The offset with which the uniforms were last uploaded.
+    uniforms_projection_offset: Vector2,
+    // The rect covered by the full output, if the output attachment renders only a region of it.
+    full_output_rect: Option<Rect>,
     render_commands: Vec<RenderCommand>,
     mesh: draw::Mesh,
-    vertex_mode_buffer: Vec<VertexMode>,
+    // The vertex mode of each vertex, packed with its texture layer via `pack_vertex_mode`.
+    vertex_mode_buffer: Vec<u32>,
+    // The attributes of each instance drawn. The first is the identity instance used by all
+    // primitives that are not instanced.
+    instance_buffer: Vec<Instance>,
     uniform_buffer: wgpu::Buffer,
+    color_vision_deficiency: Option<ColorVisionDeficiency>,
+    tessellation_cache: TessellationCache,
+    image_cache: ImageCache,
+    fill_commands: Vec<FillCommand>,
+    // The intermediate target of each opacity group drawn during the last fill.
+    group_targets: Vec<group::Target>,
+    // The index of the first render command of each pass, if there is more than one.
+    //
+    // The pass of each opacity group precedes the final pass, which renders to the output.
+    pass_starts: Vec<usize>,
+    // The baked retained drawings drawn during the last fill.
+    retained: Vec<Arc<retained::Baked>>,
+    picking_enabled: bool,
+    picking: Option<picking::Picking>,
+    pick_id_buffer: Vec<u32>,
+    #[cfg(feature = "profile")]
+    timings: Timings,
+}
+
+/// The CPU time spent within each stage of the most recent call to `encode_render_pass`.
+#[cfg(feature = "profile")]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    /// Converting the **Draw**'s commands into vertices and render commands, including
+    /// tessellation.
+    pub fill: Duration,
+    /// Creating any new render pipelines, samplers and bind groups required by the commands.
+    pub resources: Duration,
+    /// Uploading the vertex data and encoding the render pass.
+    pub encode: Duration,
 }
 
 /// A type aimed at simplifying construction of a `draw::Renderer`.
 #[derive(Clone, Debug)]
 pub struct Builder {
     pub depth_format: wgpu::TextureFormat,
+    pub sample_count: Option<u32>,
     pub glyph_cache_size: [u32; 2],
     pub glyph_cache_scale_tolerance: f32,
     pub glyph_cache_position_tolerance: f32,
@@ -116,11 +238,86 @@ enum RenderCommand {
     SetBindGroup(BindGroupId),
     /// Set the rectangular scissor.
     SetScissor(Scissor),
+    /// Set the stencil value identifying the current mask.
+    SetStencilReference(u32),
+    /// Change the bind group of the uniforms for a user fragment shader.
+    SetShaderUniforms(usize),
+    /// Draw the baked retained drawing at the given index.
+    DrawRetained(usize),
     /// Draw the given vertex range.
     DrawIndexed {
         start_vertex: i32,
         index_range: std::ops::Range<u32>,
+        instance_range: std::ops::Range<u32>,
+    },
+}
+
+/// The commands iterated over while filling the render commands.
+#[derive(Debug)]
+enum FillCommand {
+    Context(draw::Context),
+    ContextDelta(draw::ContextDelta),
+    Primitive(FillPrimitive),
+    /// A primitive that has already been tessellated into its own mesh chunk.
+    ///
+    /// The chunk is inserted into the tessellation cache under `key`, if any.
+    #[cfg(feature = "parallel")]
+    Tessellated {
+        chunk: draw::Mesh,
+        render: PrimitiveRender,
+        key: Option<cache::Key>,
+        pick_id: u32,
     },
+    /// A primitive whose tessellation is already stored within the tessellation cache.
+    #[cfg(feature = "parallel")]
+    Cached {
+        key: cache::Key,
+        pick_id: u32,
+    },
+    /// Begin a new render pass, e.g. the pass in which an opacity group is rendered.
+    BeginPass,
+}
+
+/// Primitives that may be rendered during `Renderer::fill`.
+#[derive(Debug)]
+enum FillPrimitive {
+    /// The background fill, rendered before the **Draw**'s commands.
+    Background(draw::background::Fill),
+    /// A primitive submitted via the **Draw** API, along with the raw ID with which it is picked.
+    Draw(draw::Primitive, u32),
+    /// A primitive used to render an opacity group, which is not pickable.
+    Composite(draw::Primitive),
+}
+
+/// The GPU resources required by the render commands produced during a fill.
+#[derive(Clone, Debug, Default)]
+struct Resources {
+    pipelines: HashMap<PipelineId, (wgpu::BlendDescriptor, wgpu::BlendDescriptor)>,
+    tex_views: HashMap<wgpu::TextureViewId, wgpu::TextureView>,
+    tex_sampler_combos: HashMap<BindGroupId, PipelineId>,
+    samplers: HashMap<SamplerId, wgpu::SamplerDescriptor>,
+    fragment_shaders: HashMap<usize, Arc<wgpu::ShaderModule>>,
+}
+
+/// The vertex and index buffers of a filled mesh.
+#[derive(Debug)]
+struct MeshBuffers {
+    points: wgpu::Buffer,
+    colors: wgpu::Buffer,
+    tex_coords: wgpu::Buffer,
+    modes: wgpu::Buffer,
+    instances: wgpu::Buffer,
+    indices: wgpu::Buffer,
+}
+
+// The per-instance attributes of the vertex shader.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+struct Instance {
+    transform: [[f32; 4]; 4],
+    color: Color,
+    // Whether or not `color` replaces the color of each vertex. Must be either 0 or 1.
+    color_mode: u32,
 }
 
 /// The position and dimensions of the scissor.
@@ -135,6 +332,21 @@ pub struct Scissor {
 #[derive(Debug)]
 pub struct DrawError;
 
+/// Statistics describing a single render of a **Draw** via the **Renderer**.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RenderResult {
+    /// The number of vertices uploaded to the GPU.
+    pub vertex_count: usize,
+    /// The number of indices uploaded to the GPU.
+    pub index_count: usize,
+    /// The number of times the render pipeline was switched during the render pass.
+    pub pipeline_switches: usize,
+    /// The number of indexed draw calls encoded within the render pass.
+    pub draw_calls: usize,
+    /// The CPU time spent filling and encoding the render pass.
+    pub cpu_time: Duration,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 struct Uniforms {
@@ -154,6 +366,7 @@ struct Uniforms {
 
 type SamplerId = u64;
 type BindGroupId = (SamplerId, wgpu::TextureViewId);
+type TextureLayoutId = (wgpu::TextureComponentType, wgpu::TextureViewDimension);
 type BlendId = u64;
 type ColorId = BlendId;
 type AlphaId = BlendId;
@@ -165,6 +378,28 @@ struct PipelineId {
     alpha_id: AlphaId,
     topology: wgpu::PrimitiveTopology,
     texture_component_type: wgpu::TextureComponentType,
+    // Either `D2` or `D2Array`.
+    texture_view_dimension: wgpu::TextureViewDimension,
+    depth_compare: wgpu::CompareFunction,
+    depth_write: bool,
+    mask: MaskMode,
+    // The address of the user fragment shader, if any.
+    fragment_shader: Option<usize>,
+}
+
+impl PipelineId {
+    // The bind group layout used for the pipeline's texture.
+    fn texture_layout_id(&self) -> TextureLayoutId {
+        (self.texture_component_type, self.texture_view_dimension)
+    }
+}
+
+/// The role of a pipeline's primitives with respect to the stencil buffer.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+enum MaskMode {
+    None,
+    Write,
+    Clip,
 }
 
 impl Default for PrimitiveRender {
@@ -176,28 +411,208 @@ impl Default for PrimitiveRender {
 impl RenderPrimitive for draw::Primitive {
     fn render_primitive(self, ctxt: RenderContext, mesh: &mut draw::Mesh) -> PrimitiveRender {
         match self {
+            draw::Primitive::Arc(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Arrow(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Bezier(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Cone(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Cuboid(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Cylinder(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Mesh(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Path(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::PointCloud(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Polygon(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Polyline3d(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Tri(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Ellipse(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Image(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Quad(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Rect(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::RegularPolygon(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Shaded(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Sphere(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Star(prim) => prim.render_primitive(ctxt, mesh),
+            #[cfg(feature = "svg")]
+            draw::Primitive::Svg(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Line(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Text(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Texture(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Torus(prim) => prim.render_primitive(ctxt, mesh),
             _ => PrimitiveRender::default(),
         }
     }
 }
 
+impl FillPrimitive {
+    // The key under which the primitive's tessellation is stored within the cache, if it may be
+    // cached at all.
+    fn cache_key(
+        &self,
+        cache: &TessellationCache,
+        transform: &Matrix4<f32>,
+        intermediary_state: &draw::IntermediaryState<f32>,
+        theme: &draw::Theme,
+    ) -> Option<cache::Key> {
+        match *self {
+            FillPrimitive::Draw(ref prim, _) if cache.is_enabled() => {
+                cache::key(prim, transform, intermediary_state, theme)
+            }
+            _ => None,
+        }
+    }
+
+    // Whether or not the primitive is used to render an opacity group.
+    #[cfg(feature = "parallel")]
+    fn is_composite(&self) -> bool {
+        match *self {
+            FillPrimitive::Composite(_) => true,
+            _ => false,
+        }
+    }
+
+    // The raw ID with which the primitive is rendered for picking.
+    fn pick_id(&self) -> u32 {
+        match *self {
+            FillPrimitive::Draw(_, pick_id) => pick_id,
+            _ => picking::NO_ID,
+        }
+    }
+
+    // Whether or not the primitive is a retained drawing, which requires no tessellation.
+    #[cfg(feature = "parallel")]
+    fn is_retained(&self) -> bool {
+        match *self {
+            FillPrimitive::Draw(draw::Primitive::Retained(_), _) => true,
+            _ => false,
+        }
+    }
+
+    // Whether or not the primitive requires access to the glyph cache in order to render.
+    #[cfg(feature = "parallel")]
+    fn requires_glyph_cache(&self) -> bool {
+        match *self {
+            FillPrimitive::Draw(ref prim, _) => text_primitive(prim).is_some(),
+            _ => false,
+        }
+    }
+}
+
+impl Resources {
+    // Include the resources required by another fill, e.g. that of a retained drawing.
+    fn extend(&mut self, other: &Resources) {
+        let Resources {
+            ref pipelines,
+            ref tex_views,
+            ref tex_sampler_combos,
+            ref samplers,
+            ref fragment_shaders,
+        } = *other;
+        self.pipelines
+            .extend(pipelines.iter().map(|(&id, blends)| (id, blends.clone())));
+        self.tex_views
+            .extend(tex_views.iter().map(|(&id, view)| (id, view.clone())));
+        self.tex_sampler_combos.extend(tex_sampler_combos);
+        self.samplers
+            .extend(samplers.iter().map(|(&id, desc)| (id, desc.clone())));
+        self.fragment_shaders
+            .extend(fragment_shaders.iter().map(|(&id, s)| (id, s.clone())));
+    }
+}
+
+impl MeshBuffers {
+    // Upload the given vertex data, returning `None` if there are no vertices.
+    fn new(
+        device: &wgpu::Device,
+        points: &[draw::mesh::vertex::Point],
+        colors: &[Color],
+        tex_coords: &[draw::mesh::vertex::TexCoords],
+        modes: &[u32],
+        instances: &[Instance],
+        indices: &[u32],
+    ) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let vertex_usage = wgpu::BufferUsage::VERTEX;
+        let points = device.create_buffer_with_data(points_as_bytes(points), vertex_usage);
+        let colors = device.create_buffer_with_data(colors_as_bytes(colors), vertex_usage);
+        let tex_coords =
+            device.create_buffer_with_data(tex_coords_as_bytes(tex_coords), vertex_usage);
+        let modes = device.create_buffer_with_data(vertex_modes_as_bytes(modes), vertex_usage);
+        let instances = device.create_buffer_with_data(instances_as_bytes(instances), vertex_usage);
+        let indices =
+            device.create_buffer_with_data(indices_as_bytes(indices), wgpu::BufferUsage::INDEX);
+        Some(MeshBuffers {
+            points,
+            colors,
+            tex_coords,
+            modes,
+            instances,
+            indices,
+        })
+    }
+
+    // Set the buffers on the given render pass.
+    fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_index_buffer(&self.indices, 0, 0);
+        render_pass.set_vertex_buffer(0, &self.points, 0, 0);
+        render_pass.set_vertex_buffer(1, &self.colors, 0, 0);
+        render_pass.set_vertex_buffer(2, &self.tex_coords, 0, 0);
+        render_pass.set_vertex_buffer(3, &self.modes, 0, 0);
+        render_pass.set_vertex_buffer(4, &self.instances, 0, 0);
+    }
+}
+
+impl Instance {
+    // The instance with which primitives that are not instanced are drawn.
+    fn identity() -> Self {
+        Self::new(&Matrix4::identity(), None)
+    }
+
+    // An instance with the given transform that, if `Some`, overrides the color of each vertex.
+    fn new(transform: &Matrix4<f32>, color: Option<Color>) -> Self {
+        let (color, color_mode) = match color {
+            Some(color) => (color, 1),
+            None => (Color::new(1.0, 1.0, 1.0, 1.0), 0),
+        };
+        Instance {
+            transform: (*transform).into(),
+            color,
+            color_mode,
+        }
+    }
+}
+
+impl RenderPrimitive for FillPrimitive {
+    fn render_primitive(self, ctxt: RenderContext, mesh: &mut draw::Mesh) -> PrimitiveRender {
+        match self {
+            FillPrimitive::Background(prim) => prim.render_primitive(ctxt, mesh),
+            FillPrimitive::Draw(prim, _) => prim.render_primitive(ctxt, mesh),
+            FillPrimitive::Composite(prim) => prim.render_primitive(ctxt, mesh),
+        }
+    }
+}
+
+// Primitives are assigned their pick ID once drained. See `Renderer::fill_render_commands`.
+impl From<draw::DrawCommand> for FillCommand {
+    fn from(cmd: draw::DrawCommand) -> Self {
+        match cmd {
+            draw::DrawCommand::Context(ctxt) => FillCommand::Context(ctxt),
+            draw::DrawCommand::ContextDelta(delta) => FillCommand::ContextDelta(delta),
+            draw::DrawCommand::Primitive(prim) => {
+                FillCommand::Primitive(FillPrimitive::Draw(prim, picking::NO_ID))
+            }
+        }
+    }
+}
+
 impl fmt::Debug for GlyphCache {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("GlyphCache")
             .field("cache", &self.cache.dimensions())
             .field("pixel_buffer", &self.pixel_buffer.len())
             .field("requires_upload", &self.requires_upload)
+            .field("sdf", &self.sdf)
+            .field("batched_glyphs", &self.batched_glyphs.len())
             .finish()
     }
 }
@@ -207,7 +622,11 @@ impl PrimitiveRender {
     pub fn vertex_mode(vertex_mode: VertexMode) -> Self {
         PrimitiveRender {
             texture_view: None,
+            texture_layer: 0,
             vertex_mode,
+            topology: None,
+            fragment_shader: None,
+            instances: vec![],
         }
     }
 
@@ -216,15 +635,28 @@ impl PrimitiveRender {
     }
 
     pub fn texture(texture_view: wgpu::TextureView) -> Self {
+        Self::texture_array(texture_view, 0)
+    }
+
+    /// Sample the given layer of a `D2Array` texture view.
+    pub fn texture_array(texture_view: wgpu::TextureView, layer: u32) -> Self {
         PrimitiveRender {
             vertex_mode: VertexMode::Texture,
             texture_view: Some(texture_view),
+            texture_layer: layer,
+            topology: None,
+            fragment_shader: None,
+            instances: vec![],
         }
     }
 
     pub fn text() -> Self {
         Self::vertex_mode(VertexMode::Text)
     }
+
+    pub fn sdf_text() -> Self {
+        Self::vertex_mode(VertexMode::SdfText)
+    }
 }
 
 impl Builder {
@@ -243,6 +675,7 @@ impl Builder {
     pub fn new() -> Self {
         Self {
             depth_format: Self::DEFAULT_DEPTH_FORMAT,
+            sample_count: None,
             glyph_cache_size: Self::DEFAULT_GLYPH_CACHE_SIZE,
             glyph_cache_scale_tolerance: Self::DEFAULT_GLYPH_CACHE_SCALE_TOLERANCE,
             glyph_cache_position_tolerance: Self::DEFAULT_GLYPH_CACHE_POSITION_TOLERANCE,
@@ -251,11 +684,22 @@ impl Builder {
 
     /// Specify the texture format that should be used to represent depth data in the renderer's
     /// inner `depth_texture`.
+    ///
+    /// Masks created via `Draw::mask` require a format with a stencil component.
     pub fn depth_format(mut self, format: wgpu::TextureFormat) -> Self {
         self.depth_format = format;
         self
     }
 
+    /// The number of MSAA samples with which the renderer should render.
+    ///
+    /// By default, the sample count of the output attachment described to `build` is used. The
+    /// sample count may be changed later via `Renderer::set_sample_count`.
+    pub fn sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = Some(sample_count);
+        self
+    }
+
     /// The dimensions of the texture used to cache glyphs.
     ///
     /// Some text-heavy apps may require a text cache larger than the default size in order to run
@@ -315,7 +759,7 @@ impl Builder {
             device,
             output_attachment_size,
             output_scale_factor,
-            sample_count,
+            self.sample_count.unwrap_or(sample_count),
             output_color_format,
             self.depth_format,
             self.glyph_cache_size,
@@ -336,17 +780,52 @@ impl GlyphCache {
             .into();
         let pixel_buffer = vec![0u8; w as usize * h as usize];
         let requires_upload = false;
+        let sdf = SdfGlyphCache::new(size);
+        let batched_glyphs = Default::default();
         GlyphCache {
             cache,
             pixel_buffer,
             requires_upload,
+            sdf,
+            batched_glyphs,
         }
     }
+
+    /// Cache all glyphs that have been queued, writing any newly rasterized glyphs to the pixel
+    /// buffer.
+    pub fn cache_queued(&mut self) -> Result<(), text::rt::gpu_cache::CacheWriteErr> {
+        let GlyphCache {
+            ref mut cache,
+            ref mut pixel_buffer,
+            ref mut requires_upload,
+            ..
+        } = *self;
+        let glyph_cache_w = cache.dimensions().0 as usize;
+        cache.cache_queued(|rect, data| {
+            let width = (rect.max.x - rect.min.x) as usize;
+            let height = (rect.max.y - rect.min.y) as usize;
+            let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
+            let mut src_ix = 0;
+            for _ in 0..height {
+                let dst_range = dst_ix..dst_ix + width;
+                let src_range = src_ix..src_ix + width;
+                let dst_slice = &mut pixel_buffer[dst_range];
+                let src_slice = &data[src_range];
+                dst_slice.copy_from_slice(src_slice);
+                dst_ix += glyph_cache_w;
+                src_ix += width;
+            }
+            *requires_upload = true;
+        })?;
+        Ok(())
+    }
 }
 
 impl Renderer {
     /// The default depth format
-    pub const DEFAULT_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+    ///
+    /// This includes a stencil component, used to implement masks.
+    pub const DEFAULT_DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
     /// The default size for the inner glyph cache.
     pub const DEFAULT_GLYPH_CACHE_SIZE: [u32; 2] = [1024; 2];
     /// The default scale tolerance for the glyph cache.
@@ -365,7 +844,8 @@ impl Renderer {
     /// The `depth_format` will be used to construct a depth texture for depth testing.
     ///
     /// The `glyph_cache_size` will be used to create a texture on which glyphs will be stored for
-    /// efficient look-up.
+    /// efficient look-up, along with another of the same size for the signed distance fields of
+    /// glyphs.
     pub fn new(
         device: &wgpu::Device,
         output_attachment_size: [u32; 2],
@@ -387,6 +867,8 @@ impl Renderer {
         // Load shader modules.
         let vs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert.spv"));
         let fs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag.spv"));
+        let fs_array_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_array.spv"));
 
         // Create the glyph cache texture.
         let text_sampler = wgpu::SamplerBuilder::new().build(device);
@@ -396,6 +878,12 @@ impl Renderer {
             .format(Self::GLYPH_CACHE_TEXTURE_FORMAT)
             .build(device);
         let glyph_cache_texture_view = glyph_cache_texture.create_default_view();
+        let sdf_glyph_cache_texture = wgpu::TextureBuilder::new()
+            .size(glyph_cache_size)
+            .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
+            .format(Self::GLYPH_CACHE_TEXTURE_FORMAT)
+            .build(device);
+        let sdf_glyph_cache_texture_view = sdf_glyph_cache_texture.create_default_view();
 
         // Create the depth texture.
         let depth_texture =
@@ -410,7 +898,12 @@ impl Renderer {
         let default_texture_view = default_texture.view().build();
 
         // Initial uniform buffer values. These will be overridden on draw.
-        let uniforms = create_uniforms(output_attachment_size, output_scale_factor);
+        let projection_offset = Vector2::new(0.0, 0.0);
+        let uniforms = create_uniforms(
+            output_attachment_size,
+            output_scale_factor,
+            projection_offset,
+        );
         let uniforms_bytes = uniforms_as_bytes(&uniforms);
         let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
         let uniform_buffer = device.create_buffer_with_data(uniforms_bytes, usage);
@@ -427,8 +920,15 @@ impl Renderer {
             &text_bind_group_layout,
             &text_sampler,
             &glyph_cache_texture_view,
+            &sdf_glyph_cache_texture_view,
         );
 
+        // Bind group for the uniforms of user fragment shaders, zeroed for shaders without any.
+        let shader_uniform_bind_group_layout = create_shader_uniform_bind_group_layout(device);
+        let default_shader_uniforms =
+            create_shader_uniforms(device, &shader_uniform_bind_group_layout, &[0; 16]);
+        let shader_uniforms = vec![default_shader_uniforms];
+
         // Initialise the sampler set with the default sampler.
         let sampler_desc = wgpu::SamplerBuilder::new().into_descriptor();
         let sampler_id = sampler_descriptor_hash(&sampler_desc);
@@ -440,17 +940,34 @@ impl Renderer {
 
         // Pipeline per unique pipelin ID.
         let pipelines = HashMap::default();
+        let fragment_shaders = HashMap::default();
 
         let texture_samplers = Some((sampler_id, texture_sampler)).into_iter().collect();
         let render_commands = vec![];
         let mesh = Default::default();
         let vertex_mode_buffer = vec![];
+        let instance_buffer = vec![Instance::identity()];
+        let color_vision_deficiency = None;
+        let tessellation_cache = Default::default();
+        let image_cache = Default::default();
+        let fill_commands = vec![];
+        let group_targets = vec![];
+        let pass_starts = vec![];
+        let retained = vec![];
+        let picking_enabled = false;
+        let picking = None;
+        let pick_id_buffer = vec![];
+        #[cfg(feature = "profile")]
+        let timings = Default::default();
 
         Self {
             vs_mod,
             fs_mod,
+            fs_array_mod,
+            fragment_shaders,
             glyph_cache,
             glyph_cache_texture,
+            sdf_glyph_cache_texture,
             depth_texture,
             depth_texture_view,
             default_texture,
@@ -462,22 +979,223 @@ impl Renderer {
             texture_samplers,
             texture_bind_group_layouts,
             texture_bind_groups,
+            shader_uniform_bind_group_layout,
+            shader_uniforms,
             pipelines,
             output_color_format,
             sample_count,
             scale_factor: output_scale_factor,
+            projection_offset,
+            uniforms_projection_offset: projection_offset,
+            full_output_rect: None,
             render_commands,
             mesh,
             vertex_mode_buffer,
+            instance_buffer,
             uniform_buffer,
+            color_vision_deficiency,
+            tessellation_cache,
+            image_cache,
+            fill_commands,
+            group_targets,
+            pass_starts,
+            retained,
+            picking_enabled,
+            picking,
+            pick_id_buffer,
+            #[cfg(feature = "profile")]
+            timings,
+        }
+    }
+
+    /// Simulate the given color vision deficiency when rendering.
+    ///
+    /// The simulation is applied to vertex colors and the background clear color. Textures are
+    /// currently drawn unaffected. Specify `None` to disable the simulation.
+    pub fn set_color_vision_deficiency(&mut self, cvd: Option<ColorVisionDeficiency>) {
+        self.color_vision_deficiency = cvd;
+    }
+
+    /// The color vision deficiency currently simulated by the renderer, if any.
+    pub fn color_vision_deficiency(&self) -> Option<ColorVisionDeficiency> {
+        self.color_vision_deficiency
+    }
+
+    /// Offset the projection so that the given point, rather than the origin, lies at the center
+    /// of the output attachment.
+    ///
+    /// The point is described in the coordinates of the **Draw**. This allows for rendering some
+    /// region of a larger output, e.g. a single tile of an image that is too large to render in
+    /// one pass. See `Draw::to_image_tiled`.
+    pub fn set_projection_offset(&mut self, offset: Vector2) {
+        self.projection_offset = offset;
+    }
+
+    /// The point in the coordinates of the **Draw** that lies at the center of the output.
+    pub fn projection_offset(&self) -> Vector2 {
+        self.projection_offset
+    }
+
+    /// Specify the rect covered by the full output in the coordinates of the **Draw**, of which
+    /// the output attachment renders the region centered on the projection offset.
+    ///
+    /// Backgrounds are laid out across this rect so that each region of the output continues the
+    /// same background. If `None`, the default, the full output is the output attachment itself.
+    pub fn set_full_output_rect(&mut self, rect: Option<Rect>) {
+        self.full_output_rect = rect;
+    }
+
+    /// The rect covered by the full output, if it differs from the output attachment.
+    pub fn full_output_rect(&self) -> Option<Rect> {
+        self.full_output_rect
+    }
+
+    /// The number of MSAA samples with which the renderer renders.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Change the number of MSAA samples with which the renderer renders.
+    ///
+    /// The output attachments subsequently passed to `encode_render_pass` must have the same
+    /// sample count. The `render_to_*` methods call this automatically with the sample count of
+    /// their target. If the count differs from the current count, all render pipelines and the
+    /// depth texture are recreated, so changing it every frame is best avoided.
+    pub fn set_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        if sample_count == self.sample_count {
+            return;
+        }
+        self.sample_count = sample_count;
+        self.pipelines.clear();
+        let size = self.depth_texture.size();
+        let depth_format = self.depth_texture.format();
+        self.depth_texture = create_depth_texture(device, size, depth_format, sample_count);
+        self.depth_texture_view = self.depth_texture.view().build();
+    }
+
+    /// The cache of tessellated primitives that is reused between calls to `fill`.
+    ///
+    /// Useful for inspecting the cache's hit and miss statistics.
+    pub fn tessellation_cache(&self) -> &TessellationCache {
+        &self.tessellation_cache
+    }
+
+    /// Mutable access to the tessellation cache, e.g. for clearing or enabling it.
+    pub fn tessellation_cache_mut(&mut self) -> &mut TessellationCache {
+        &mut self.tessellation_cache
+    }
+
+    /// The textures of images drawn via `draw.image`, reused between calls to `fill`.
+    pub fn image_cache(&self) -> &ImageCache {
+        &self.image_cache
+    }
+
+    /// Mutable access to the image cache, e.g. for clearing it so that images are reloaded.
+    pub fn image_cache_mut(&mut self) -> &mut ImageCache {
+        &mut self.image_cache
+    }
+
+    /// The CPU time spent within each stage of the most recent call to `encode_render_pass`.
+    #[cfg(feature = "profile")]
+    pub fn timings(&self) -> Timings {
+        self.timings
+    }
+
+    /// Enable or disable the rendering of primitive IDs for picking.
+    ///
+    /// While enabled, each render pass is followed by a second pass in which every primitive is
+    /// drawn with its unique **PrimitiveId** to an offscreen texture. This allows `pick` to
+    /// determine which primitive is visible at any given point. By default this is `false`.
+    pub fn set_picking_enabled(&mut self, enabled: bool) {
+        self.picking_enabled = enabled;
+        if !enabled {
+            self.picking = None;
+        }
+    }
+
+    /// Whether or not primitive IDs are rendered for picking.
+    pub fn is_picking_enabled(&self) -> bool {
+        self.picking_enabled
+    }
+
+    /// The ID of the topmost primitive at the given point within the most recent render.
+    ///
+    /// The point is described in the coordinates of the **Draw**, i.e. logical pixels with the
+    /// origin at the center of the output and the y axis pointing upwards.
+    ///
+    /// Returns `None` if picking is disabled, if there is no primitive at the point or if the
+    /// point is outside of the output. Note that textures and text are picked via their
+    /// rectangular bounds, rather than by their alpha.
+    ///
+    /// This blocks until the ID is read back from the GPU, so must only be called after the
+    /// commands encoded by the most recent render have been submitted.
+    pub fn pick(&self, device: &wgpu::Device, point: Point2) -> Option<PrimitiveId> {
+        let picking = self.picking.as_ref()?;
+        let [w_px, h_px] = picking.size();
+        let point = point - self.projection_offset;
+        let x = (point.x * self.scale_factor + w_px as f32 * 0.5).floor();
+        let y = (h_px as f32 * 0.5 - point.y * self.scale_factor).floor();
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+        picking.pick(device, [x as u32, y as u32])
+    }
+
+    /// Write the renderer's CPU-side state from the most recent render to the given directory.
+    ///
+    /// This writes the following files, overwriting any that already exist:
+    ///
+    /// - `render_commands.txt`: the list of render commands that were encoded, along with the size
+    ///   of the vertex and index data.
+    /// - `glyph_cache.png`: the contents of the glyph cache as a greyscale image.
+    /// - `sdf_glyph_cache.png`: the signed distance fields of the SDF glyph cache as a greyscale
+    ///   image.
+    ///
+    /// This is intended for diagnosing rendering issues. See `Window::dump_render_debug` for
+    /// dumping a window's frame textures alongside these.
+    pub fn dump_debug(&self, dir: &Path) -> io::Result<()> {
+        let mut commands = String::new();
+        writeln!(commands, "sample_count: {}", self.sample_count).ok();
+        writeln!(commands, "scale_factor: {}", self.scale_factor).ok();
+        writeln!(commands, "vertices: {}", self.mesh.raw_vertex_count()).ok();
+        writeln!(commands, "indices: {}", self.mesh.indices().len()).ok();
+        writeln!(commands, "render_commands:").ok();
+        for cmd in &self.render_commands {
+            writeln!(commands, "    {:?}", cmd).ok();
         }
+        std::fs::write(dir.join("render_commands.txt"), commands)?;
+
+        let [w, h] = self.glyph_cache_texture.size();
+        let glyph_cache = image::GrayImage::from_raw(w, h, self.glyph_cache.pixel_buffer.clone())
+            .ok_or_else(|| {
+            let msg = "glyph cache pixel buffer does not match texture size";
+            io::Error::new(io::ErrorKind::Other, msg)
+        })?;
+        glyph_cache
+            .save(dir.join("glyph_cache.png"))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+        let [w, h] = self.sdf_glyph_cache_texture.size();
+        let sdf_pixels = self.glyph_cache.sdf.pixel_buffer.clone();
+        let sdf_glyph_cache = image::GrayImage::from_raw(w, h, sdf_pixels).ok_or_else(|| {
+            let msg = "sdf glyph cache pixel buffer does not match texture size";
+            io::Error::new(io::ErrorKind::Other, msg)
+        })?;
+        sdf_glyph_cache
+            .save(dir.join("sdf_glyph_cache.png"))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
     }
 
     /// Clear all pending render commands vertex data.
     pub fn clear(&mut self) {
         self.render_commands.clear();
+        self.pass_starts.clear();
         self.mesh.clear();
         self.vertex_mode_buffer.clear();
+        self.instance_buffer.truncate(1);
+        self.pick_id_buffer.clear();
+        self.shader_uniforms.truncate(1);
+        self.retained.clear();
     }
 
     /// Generate a list of `RenderCommand`s from the given **Draw** instance and prepare any
@@ -491,6 +1209,20 @@ impl Renderer {
         scale_factor: f32,
         output_attachment_size: [u32; 2],
     ) {
+        let resources =
+            self.fill_render_commands(device, draw, scale_factor, output_attachment_size);
+        self.create_resources(device, resources, output_attachment_size);
+    }
+
+    // Fill the render commands and vertex data for the given **Draw**, returning the resources
+    // required by the render commands.
+    fn fill_render_commands(
+        &mut self,
+        device: &wgpu::Device,
+        draw: &draw::Draw,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+    ) -> Resources {
         // Pushes a draw command and updates the `curr_start_index`.
         //
         // Returns `true` if the command was added, `false` if there was nothing to
@@ -504,9 +1236,11 @@ impl Renderer {
             if index_range.len() != 0 {
                 let start_vertex = 0;
                 *curr_start_index = index_range.end;
+                let instance_range = 0..1;
                 let cmd = RenderCommand::DrawIndexed {
                     start_vertex,
                     index_range,
+                    instance_range,
                 };
                 render_commands.push(cmd);
                 true
@@ -515,16 +1249,24 @@ impl Renderer {
             }
         }
 
+        #[cfg(feature = "profile")]
+        let fill_start = Instant::now();
+
         let [w_px, h_px] = output_attachment_size;
 
         // Converting between pixels and points.
         let px_to_pt = |s: u32| s as f32 / scale_factor;
         let pt_to_px = |s: f32| (s * scale_factor).round() as u32;
-        let full_rect = Rect::from_w_h(px_to_pt(w_px), px_to_pt(h_px));
+        let projection_offset = self.projection_offset;
+        // The region of the output covered by the output attachment.
+        let viewport = Rect::from_w_h(px_to_pt(w_px), px_to_pt(h_px)).shift(projection_offset);
+        let full_output_rect = self
+            .full_output_rect
+            .unwrap_or_else(|| Rect::from_w_h(px_to_pt(w_px), px_to_pt(h_px)));
 
         let window_to_scissor = |v: Vector2| -> [u32; 2] {
-            let x = map_range(v.x, full_rect.left(), full_rect.right(), 0u32, w_px);
-            let y = map_range(v.y, full_rect.bottom(), full_rect.top(), 0u32, h_px);
+            let x = map_range(v.x, viewport.left(), viewport.right(), 0u32, w_px);
+            let y = map_range(v.y, viewport.bottom(), viewport.top(), 0u32, h_px);
             [x, y]
         };
 
@@ -534,46 +1276,367 @@ impl Renderer {
 
         // Keep track of context changes.
         let mut curr_ctxt = draw::Context::default();
-        let mut new_pipeline_ids = HashMap::new();
+        let mut resources = Resources::default();
         let mut curr_start_index = 0;
-        let mut new_tex_views = HashMap::new();
-        let mut new_tex_sampler_combos = HashMap::new();
         // Track whether new commands are required.
         let mut curr_pipeline_id = None;
         let mut curr_scissor = None;
         let mut curr_tex_sampler_id = None;
+        let mut curr_stencil_reference = None;
+        let mut curr_shader_uniforms = None;
+        let mut curr_shader_uniform_bytes: Option<Vec<u8>> = None;
+        // The blend and sampler IDs of the current context, only re-hashed when they change.
+        let mut curr_ctxt_ids = None;
+
+        // Collect all commands into the re-usable buffer to avoid borrow errors and per-frame
+        // allocations. The background fill (if any) is rendered first using the default context.
+        let mut fill_cmds = std::mem::replace(&mut self.fill_commands, vec![]);
+        let background_fill = draw.state.borrow().background_fill.clone();
+        fill_cmds.extend(
+            background_fill.map(|fill| FillCommand::Primitive(FillPrimitive::Background(fill))),
+        );
+        let drained = fill_cmds.len();
+        let order = draw.drain_sorted_commands_into(&mut fill_cmds);
+
+        // Identify each drawing by the order in which it was submitted, rather than the order in
+        // which it is rendered after sorting by z index or layer.
+        let drawings = fill_cmds[drained..]
+            .iter_mut()
+            .filter_map(|cmd| match *cmd {
+                FillCommand::Primitive(FillPrimitive::Draw(_, ref mut pick_id)) => Some(pick_id),
+                _ => None,
+            });
+        for (i, pick_id) in drawings.enumerate() {
+            let submitted = order.as_ref().map_or(i, |order| order[i]);
+            *pick_id = picking::raw_id(PrimitiveId(submitted));
+        }
+
+        // Bake any retained drawings that are not yet baked for this output.
+        for cmd in &fill_cmds {
+            if let FillCommand::Primitive(FillPrimitive::Draw(
+                draw::Primitive::Retained(ref retained),
+                _,
+            )) = *cmd
+            {
+                self.bake_if_stale(device, retained, scale_factor, output_attachment_size);
+            }
+        }
 
-        // Collect all draw commands to avoid borrow errors.
-        let draw_cmds: Vec<_> = draw.drain_commands().collect();
+        self.tessellation_cache.begin_fill();
         let draw_state = draw.state.borrow_mut();
         let intermediary_state = draw_state.intermediary_state.borrow();
-        for cmd in draw_cmds {
+
+        // Replace each image with a texture, loading and uploading the image if it is not yet
+        // cached. Images that fail to load are left as-is and draw nothing.
+        self.image_cache.begin_fill();
+        for cmd in fill_cmds.iter_mut() {
+            if let FillCommand::Primitive(FillPrimitive::Draw(ref mut prim, _)) = *cmd {
+                // Images drawn via a user fragment shader are replaced in the same manner.
+                let prim = match *prim {
+                    draw::Primitive::Shaded(ref mut shaded) => shaded.primitive_mut(),
+                    ref mut prim => prim,
+                };
+                let texture = match *prim {
+                    draw::Primitive::Image(ref image) => self
+                        .image_cache
+                        .texture_view(device, image.source())
+                        .map(|view| image.to_texture(&view)),
+                    _ => continue,
+                };
+                if let Some(texture) = texture {
+                    *prim = draw::Primitive::Texture(texture);
+                }
+            }
+        }
+
+        // Move the drawings of each opacity group into a render pass of their own.
+        self.arrange_groups(device, &mut fill_cmds, output_attachment_size, viewport);
+
+        // Tessellate each primitive that does not require the glyph cache into its own mesh chunk
+        // across worker threads. The chunks are merged in order below.
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            // Pair each command with the transform of the context in which it is drawn.
+            let mut transform = curr_ctxt.transform;
+            let cmds: Vec<_> = fill_cmds
+                .drain(..)
+                .map(|cmd| {
+                    match cmd {
+                        FillCommand::Context(ref ctxt) => transform = ctxt.transform,
+                        FillCommand::ContextDelta(draw::ContextDelta::Transform(ref t)) => {
+                            transform = *t
+                        }
+                        _ => (),
+                    }
+                    (transform, cmd)
+                })
+                .collect();
+
+            let intermediary_state = &*intermediary_state;
+            let theme = &draw_state.theme;
+            let tessellation_cache = &self.tessellation_cache;
+            let output_attachment_size = Vector2::new(px_to_pt(w_px), px_to_pt(h_px));
+            cmds.into_par_iter()
+                .map_init(
+                    || (FillTessellator::new(), StrokeTessellator::new()),
+                    |tessellators, (transform, cmd)| match cmd {
+                        FillCommand::Primitive(prim)
+                            if !prim.requires_glyph_cache()
+                                && !prim.is_composite()
+                                && !prim.is_retained() =>
+                        {
+                            let key = prim.cache_key(
+                                tessellation_cache,
+                                &transform,
+                                intermediary_state,
+                                theme,
+                            );
+                            let pick_id = prim.pick_id();
+                            if let Some(key) = key {
+                                if tessellation_cache.contains(&key) {
+                                    return FillCommand::Cached { key, pick_id };
+                                }
+                            }
+                            let (ref mut fill_tessellator, ref mut stroke_tessellator) =
+                                *tessellators;
+                            let ctxt = RenderContext {
+                                intermediary_mesh: &intermediary_state.intermediary_mesh,
+                                path_event_buffer: &intermediary_state.path_event_buffer,
+                                path_points_colored_buffer: &intermediary_state
+                                    .path_points_colored_buffer,
+                                path_points_textured_buffer: &intermediary_state
+                                    .path_points_textured_buffer,
+                                path_points_weighted_buffer: &intermediary_state
+                                    .path_points_weighted_buffer,
+                                text_buffer: &intermediary_state.text_buffer,
+                                theme,
+                                transform: &transform,
+                                fill_tessellator,
+                                stroke_tessellator,
+                                glyph_cache: None,
+                                output_attachment_size,
+                                output_attachment_scale_factor: scale_factor,
+                                full_output_rect,
+                                projection_offset,
+                            };
+                            let mut chunk = draw::Mesh::default();
+                            let render = prim.render_primitive(ctxt, &mut chunk);
+                            FillCommand::Tessellated {
+                                chunk,
+                                render,
+                                key,
+                                pick_id,
+                            }
+                        }
+                        cmd => cmd,
+                    },
+                )
+                .collect_into_vec(&mut fill_cmds);
+        }
+
+        // Lay out the glyphs of all text primitives up front and cache them as a single batch,
+        // rather than caching the glyphs of each text primitive as it is rendered.
+        {
+            let output_attachment_size = Vector2::new(px_to_pt(w_px), px_to_pt(h_px));
+            let glyph_cache = &mut self.glyph_cache;
+            glyph_cache.batched_glyphs.clear();
+            glyph_cache.sdf.clear_if_full();
+            for cmd in &fill_cmds {
+                let text = match *cmd {
+                    FillCommand::Primitive(FillPrimitive::Draw(ref prim, _)) => {
+                        match text_primitive(prim) {
+                            Some(text) => text,
+                            None => continue,
+                        }
+                    }
+                    _ => continue,
+                };
+                let runs = text.layout_glyphs(
+                    &intermediary_state.text_buffer,
+                    output_attachment_size,
+                    scale_factor,
+                );
+                // The glyphs of SDF text are cached within the SDF atlas as they are rendered.
+                if !text.is_sdf() {
+                    for run in runs.iter() {
+                        for glyph in run.glyphs.iter() {
+                            glyph_cache.queue_glyph(run.font_index, glyph.clone());
+                        }
+                    }
+                }
+                let cached = false;
+                let batched = BatchedGlyphs { runs, cached };
+                glyph_cache.batched_glyphs.push_back(batched);
+            }
+            // If the whole batch does not fit within the cache at once, the glyphs of each text
+            // primitive are cached separately as they are rendered instead.
+            let cached = match glyph_cache.cache_queued() {
+                Ok(()) => true,
+                Err(_) => {
+                    glyph_cache.clear_queue();
+                    false
+                }
+            };
+            for batched in glyph_cache.batched_glyphs.iter_mut() {
+                batched.cached = cached;
+            }
+        }
+
+        for cmd in fill_cmds.drain(..) {
             match cmd {
-                draw::DrawCommand::Context(ctxt) => curr_ctxt = ctxt,
-                draw::DrawCommand::Primitive(prim) => {
+                FillCommand::Context(ctxt) => {
+                    curr_ctxt = ctxt;
+                    curr_ctxt_ids = None;
+                }
+                FillCommand::ContextDelta(delta) => {
+                    // Transform and scissor changes do not affect the pipeline or bind group.
+                    if let draw::ContextDelta::Blend { .. } = delta {
+                        curr_ctxt_ids = None;
+                    }
+                    delta.apply(&mut curr_ctxt);
+                }
+                FillCommand::BeginPass => {
+                    push_draw_cmd(
+                        &mut curr_start_index,
+                        self.mesh.indices().len() as u32,
+                        &mut self.render_commands,
+                    );
+                    self.pass_starts.push(self.render_commands.len());
+                    curr_ctxt = draw::Context::default();
+                    curr_ctxt_ids = None;
+                    curr_pipeline_id = None;
+                    curr_scissor = None;
+                    curr_tex_sampler_id = None;
+                    curr_stencil_reference = None;
+                    curr_shader_uniforms = None;
+                }
+                FillCommand::Primitive(FillPrimitive::Draw(
+                    draw::Primitive::Retained(retained),
+                    _,
+                )) => {
+                    let baked = match retained.baked() {
+                        Some(baked) => baked,
+                        None => continue,
+                    };
+                    push_draw_cmd(
+                        &mut curr_start_index,
+                        self.mesh.indices().len() as u32,
+                        &mut self.render_commands,
+                    );
+                    resources.extend(&baked.resources);
+                    let cmd = RenderCommand::DrawRetained(self.retained.len());
+                    self.render_commands.push(cmd);
+                    self.retained.push(baked);
+                    // The retained commands leave the render pass in an unknown state.
+                    curr_pipeline_id = None;
+                    curr_scissor = None;
+                    curr_tex_sampler_id = None;
+                    curr_stencil_reference = None;
+                    curr_shader_uniforms = None;
+                }
+                cmd => {
+                    let pick_id = match cmd {
+                        FillCommand::Primitive(ref prim) => prim.pick_id(),
+                        #[cfg(feature = "parallel")]
+                        FillCommand::Tessellated { pick_id, .. }
+                        | FillCommand::Cached { pick_id, .. } => pick_id,
+                        _ => picking::NO_ID,
+                    };
+
                     // Track the prev index and vertex counts.
                     let prev_index_count = self.mesh.indices().len() as u32;
                     let prev_vert_count = self.mesh.vertex_count();
 
-                    // Info required during rendering.
-                    let ctxt = RenderContext {
-                        intermediary_mesh: &intermediary_state.intermediary_mesh,
-                        path_event_buffer: &intermediary_state.path_event_buffer,
-                        path_points_colored_buffer: &intermediary_state.path_points_colored_buffer,
-                        path_points_textured_buffer: &intermediary_state
-                            .path_points_textured_buffer,
-                        text_buffer: &intermediary_state.text_buffer,
-                        theme: &draw_state.theme,
-                        transform: &curr_ctxt.transform,
-                        fill_tessellator: &mut fill_tessellator,
-                        stroke_tessellator: &mut stroke_tessellator,
-                        glyph_cache: &mut self.glyph_cache,
-                        output_attachment_size: Vector2::new(px_to_pt(w_px), px_to_pt(h_px)),
-                        output_attachment_scale_factor: scale_factor,
+                    let render = match cmd {
+                        FillCommand::Primitive(prim) => {
+                            // Reuse the cached tessellation of the primitive if there is one.
+                            let key = prim.cache_key(
+                                &self.tessellation_cache,
+                                &curr_ctxt.transform,
+                                &intermediary_state,
+                                &draw_state.theme,
+                            );
+                            if let Some(render) = key.as_ref().and_then(|k| {
+                                self.tessellation_cache.extend_mesh(k, &mut self.mesh)
+                            }) {
+                                render
+                            } else {
+                                let prev_raw_vert_count = self.mesh.raw_vertex_count();
+
+                                // Info required during rendering.
+                                let ctxt = RenderContext {
+                                    intermediary_mesh: &intermediary_state.intermediary_mesh,
+                                    path_event_buffer: &intermediary_state.path_event_buffer,
+                                    path_points_colored_buffer: &intermediary_state
+                                        .path_points_colored_buffer,
+                                    path_points_textured_buffer: &intermediary_state
+                                        .path_points_textured_buffer,
+                                    path_points_weighted_buffer: &intermediary_state
+                                        .path_points_weighted_buffer,
+                                    text_buffer: &intermediary_state.text_buffer,
+                                    theme: &draw_state.theme,
+                                    transform: &curr_ctxt.transform,
+                                    fill_tessellator: &mut fill_tessellator,
+                                    stroke_tessellator: &mut stroke_tessellator,
+                                    glyph_cache: Some(&mut self.glyph_cache),
+                                    output_attachment_size: Vector2::new(
+                                        px_to_pt(w_px),
+                                        px_to_pt(h_px),
+                                    ),
+                                    output_attachment_scale_factor: scale_factor,
+                                    full_output_rect,
+                                    projection_offset,
+                                };
+
+                                // Render the primitive.
+                                let render = prim.render_primitive(ctxt, &mut self.mesh);
+                                if let Some(key) = key {
+                                    self.tessellation_cache.insert(
+                                        key,
+                                        &self.mesh,
+                                        prev_raw_vert_count,
+                                        prev_index_count as usize,
+                                        &render,
+                                    );
+                                }
+                                render
+                            }
+                        }
+                        #[cfg(feature = "parallel")]
+                        FillCommand::Cached { key, .. } => self
+                            .tessellation_cache
+                            .extend_mesh(&key, &mut self.mesh)
+                            .expect("no tessellation cache entry for key"),
+                        #[cfg(feature = "parallel")]
+                        FillCommand::Tessellated {
+                            chunk, render, key, ..
+                        } => {
+                            if let Some(key) = key {
+                                self.tessellation_cache.insert(key, &chunk, 0, 0, &render);
+                            }
+                            // Offset the chunk's indices to where its vertices land in the mesh.
+                            let offset = self.mesh.raw_vertex_count() as u32;
+                            let indices = chunk.indices().iter().map(|&i| offset + i);
+                            self.mesh.extend_vertices_from_slices(
+                                chunk.points(),
+                                chunk.colors(),
+                                chunk.tex_coords(),
+                            );
+                            self.mesh.extend_indices(indices);
+                            render
+                        }
+                        FillCommand::Context(_)
+                        | FillCommand::ContextDelta(_)
+                        | FillCommand::BeginPass => unreachable!(),
                     };
 
-                    // Render the primitive.
-                    let render = prim.render_primitive(ctxt, &mut self.mesh);
+                    // In wireframe mode, replace the primitive's triangles with their edges.
+                    // Primitives that specify their own topology are left untouched.
+                    if curr_ctxt.wireframe && render.topology.is_none() {
+                        triangles_to_edges(self.mesh.indices_mut(), prev_index_count as usize);
+                    }
 
                     // If the mesh indices are unchanged, there's nothing to be drawn.
                     if prev_index_count == self.mesh.indices().len() as u32 {
@@ -594,25 +1657,72 @@ impl Renderer {
                     };
                     let tex_view_id = tex_view.id();
                     let texture_component_type = tex_view.component_type();
-                    new_tex_views.insert(tex_view_id, tex_view);
+                    let texture_view_dimension = match tex_view.dimension() {
+                        wgpu::TextureViewDimension::D2Array => wgpu::TextureViewDimension::D2Array,
+                        _ => wgpu::TextureViewDimension::D2,
+                    };
+                    resources.tex_views.insert(tex_view_id, tex_view);
 
                     // Determine the new current bind group layout ID, pipeline ID, bind group ID
                     // and scissor required for drawing this primitive.
+                    let (color_id, alpha_id, sampler_id) =
+                        *curr_ctxt_ids.get_or_insert_with(|| {
+                            let color_id = blend_descriptor_hash(&curr_ctxt.color_blend);
+                            let alpha_id = blend_descriptor_hash(&curr_ctxt.alpha_blend);
+                            let sampler_id = sampler_descriptor_hash(&curr_ctxt.sampler);
+                            (color_id, alpha_id, sampler_id)
+                        });
                     let new_pipeline_id = {
-                        let color_id = blend_descriptor_hash(&curr_ctxt.color_blend);
-                        let alpha_id = blend_descriptor_hash(&curr_ctxt.alpha_blend);
-                        let topology = curr_ctxt.topology;
+                        let topology = render.topology.unwrap_or(curr_ctxt.topology);
                         PipelineId {
                             color_id,
                             alpha_id,
                             topology,
                             texture_component_type,
+                            texture_view_dimension,
+                            depth_compare: curr_ctxt.depth_compare,
+                            depth_write: curr_ctxt.depth_write,
+                            mask: match curr_ctxt.mask {
+                                draw::Mask::None => MaskMode::None,
+                                draw::Mask::Write(_) => MaskMode::Write,
+                                draw::Mask::Clip(_) => MaskMode::Clip,
+                            },
+                            fragment_shader: render
+                                .fragment_shader
+                                .as_ref()
+                                .map(|shader| shader_id(&shader.module)),
                         }
                     };
-                    let new_bind_group_id = {
-                        let sampler_id = sampler_descriptor_hash(&curr_ctxt.sampler);
-                        (sampler_id, tex_view_id)
+                    if let Some(ref shader) = render.fragment_shader {
+                        resources
+                            .fragment_shaders
+                            .insert(shader_id(&shader.module), shader.module.clone());
+                    }
+                    let new_stencil_reference = match curr_ctxt.mask {
+                        draw::Mask::None => curr_stencil_reference,
+                        draw::Mask::Write(id) | draw::Mask::Clip(id) => Some(id as u32),
+                    };
+                    // Consecutive drawings with identical uniforms share a buffer.
+                    let new_shader_uniforms = match render.fragment_shader {
+                        None => None,
+                        Some(draw::primitive::FragmentShader {
+                            uniforms: Some(ref bytes),
+                            ..
+                        }) if !bytes.is_empty() => {
+                            if curr_shader_uniform_bytes.as_ref() != Some(bytes) {
+                                let uniforms = create_shader_uniforms(
+                                    device,
+                                    &self.shader_uniform_bind_group_layout,
+                                    bytes,
+                                );
+                                self.shader_uniforms.push(uniforms);
+                                curr_shader_uniform_bytes = Some(bytes.clone());
+                            }
+                            Some(self.shader_uniforms.len() - 1)
+                        }
+                        Some(_) => Some(0),
                     };
+                    let new_bind_group_id = (sampler_id, tex_view_id);
                     let new_scissor = curr_ctxt.scissor;
 
                     // Determine which have changed and in turn which require submitting new
@@ -620,11 +1730,20 @@ impl Renderer {
                     let pipeline_changed = Some(new_pipeline_id) != curr_pipeline_id;
                     let bind_group_changed = Some(new_bind_group_id) != curr_tex_sampler_id;
                     let scissor_changed = Some(new_scissor) != curr_scissor;
+                    let stencil_reference_changed = new_stencil_reference != curr_stencil_reference;
+                    // The uniforms are re-bound on each change to a pipeline with a user shader.
+                    let shader_uniforms_changed = new_shader_uniforms.is_some()
+                        && (pipeline_changed || new_shader_uniforms != curr_shader_uniforms);
 
                     // If we require submitting a scissor, pipeline or bind group command, first
                     // draw whatever pending vertices we have collected so far. If there have been
                     // no graphics yet, this will do nothing.
-                    if scissor_changed || pipeline_changed || bind_group_changed {
+                    if scissor_changed
+                        || pipeline_changed
+                        || bind_group_changed
+                        || stencil_reference_changed
+                        || shader_uniforms_changed
+                    {
                         push_draw_cmd(
                             &mut curr_start_index,
                             prev_index_count,
@@ -637,15 +1756,31 @@ impl Renderer {
                         curr_pipeline_id = Some(new_pipeline_id);
                         let color_blend = curr_ctxt.color_blend.clone();
                         let alpha_blend = curr_ctxt.alpha_blend.clone();
-                        new_pipeline_ids.insert(new_pipeline_id, (color_blend, alpha_blend));
+                        let blends = (color_blend, alpha_blend);
+                        resources.pipelines.insert(new_pipeline_id, blends);
                         let cmd = RenderCommand::SetPipeline(new_pipeline_id);
                         self.render_commands.push(cmd);
                     }
 
+                    // If necessary, push a new shader uniforms command.
+                    if shader_uniforms_changed {
+                        curr_shader_uniforms = new_shader_uniforms;
+                        if let Some(ix) = new_shader_uniforms {
+                            let cmd = RenderCommand::SetShaderUniforms(ix);
+                            self.render_commands.push(cmd);
+                        }
+                    }
+
                     // If necessary, push a new bind group command.
                     if bind_group_changed {
                         curr_tex_sampler_id = Some(new_bind_group_id);
-                        new_tex_sampler_combos.insert(new_bind_group_id, new_pipeline_id);
+                        resources
+                            .tex_sampler_combos
+                            .insert(new_bind_group_id, new_pipeline_id);
+                        resources
+                            .samplers
+                            .entry(sampler_id)
+                            .or_insert_with(|| curr_ctxt.sampler.clone());
                         let cmd = RenderCommand::SetBindGroup(new_bind_group_id);
                         self.render_commands.push(cmd);
                     }
@@ -654,8 +1789,8 @@ impl Renderer {
                     if scissor_changed {
                         curr_scissor = Some(new_scissor);
                         let rect = match curr_ctxt.scissor {
-                            draw::Scissor::Full => full_rect,
-                            draw::Scissor::Rect(rect) => full_rect
+                            draw::Scissor::Full => viewport,
+                            draw::Scissor::Rect(rect) => viewport
                                 .overlap(rect)
                                 .unwrap_or(geom::Rect::from_w_h(0.0, 0.0)),
                             draw::Scissor::NoOverlap => geom::Rect::from_w_h(0.0, 0.0),
@@ -673,14 +1808,54 @@ impl Renderer {
                         self.render_commands.push(cmd);
                     }
 
+                    // If necessary, push a new stencil reference command.
+                    if stencil_reference_changed {
+                        curr_stencil_reference = new_stencil_reference;
+                        if let Some(reference) = new_stencil_reference {
+                            let cmd = RenderCommand::SetStencilReference(reference);
+                            self.render_commands.push(cmd);
+                        }
+                    }
+
+                    // Instanced primitives are drawn via a command of their own spanning their
+                    // instances.
+                    if !render.instances.is_empty() {
+                        push_draw_cmd(
+                            &mut curr_start_index,
+                            prev_index_count,
+                            &mut self.render_commands,
+                        );
+                        let start_instance = self.instance_buffer.len() as u32;
+                        let instances = render
+                            .instances
+                            .iter()
+                            .map(|instance| Instance::new(&instance.transform, instance.color));
+                        self.instance_buffer.extend(instances);
+                        let index_range = prev_index_count..self.mesh.indices().len() as u32;
+                        let instance_range = start_instance..self.instance_buffer.len() as u32;
+                        curr_start_index = index_range.end;
+                        let cmd = RenderCommand::DrawIndexed {
+                            start_vertex: 0,
+                            index_range,
+                            instance_range,
+                        };
+                        self.render_commands.push(cmd);
+                    }
+
                     // Extend the vertex mode channel.
-                    let mode = render.vertex_mode;
+                    let mode = pack_vertex_mode(render.vertex_mode, render.texture_layer);
                     let new_vs = self.mesh.points().len() - self.vertex_mode_buffer.len();
                     self.vertex_mode_buffer.extend((0..new_vs).map(|_| mode));
+                    if self.picking_enabled {
+                        self.pick_id_buffer.extend((0..new_vs).map(|_| pick_id));
+                    }
                 }
             }
         }
 
+        self.tessellation_cache.end_fill();
+        self.fill_commands = fill_cmds;
+
         // Insert the final draw command if there is still some drawing to be done.
         push_draw_cmd(
             &mut curr_start_index,
@@ -688,36 +1863,103 @@ impl Renderer {
             &mut self.render_commands,
         );
 
+        #[cfg(feature = "profile")]
+        {
+            self.timings.fill = fill_start.elapsed();
+        }
+
+        resources
+    }
+
+    // Create the pipelines, samplers and bind groups required by the filled render commands,
+    // dropping any that are no longer required.
+    fn create_resources(
+        &mut self,
+        device: &wgpu::Device,
+        resources: Resources,
+        output_attachment_size: [u32; 2],
+    ) {
+        #[cfg(feature = "profile")]
+        let resources_start = Instant::now();
+
+        let Resources {
+            pipelines: mut new_pipeline_ids,
+            tex_views: new_tex_views,
+            tex_sampler_combos: mut new_tex_sampler_combos,
+            samplers,
+            fragment_shaders: new_fragment_shaders,
+        } = resources;
+
         // Clear out unnecessary pipelines.
         self.pipelines
             .retain(|id, _| new_pipeline_ids.contains_key(id));
+        // Only keep the fragment shaders that are used by the remaining pipelines.
+        self.fragment_shaders = new_fragment_shaders;
         // Clear new combos that we already have.
         new_pipeline_ids.retain(|id, _| !self.pipelines.contains_key(id));
         // Create new render pipelines as necessary.
         for (new_id, (color_blend, alpha_blend)) in new_pipeline_ids {
             let bind_group_layout = self
                 .texture_bind_group_layouts
-                .entry(new_id.texture_component_type)
+                .entry(new_id.texture_layout_id())
                 .or_insert_with(|| {
-                    create_texture_bind_group_layout(device, new_id.texture_component_type)
+                    create_texture_bind_group_layout(
+                        device,
+                        new_id.texture_component_type,
+                        new_id.texture_view_dimension,
+                    )
                 });
+            let (fs_mod, shader_layout) = match new_id.fragment_shader {
+                None => match new_id.texture_view_dimension {
+                    wgpu::TextureViewDimension::D2Array => (&self.fs_array_mod, None),
+                    _ => (&self.fs_mod, None),
+                },
+                Some(id) => (
+                    &*self.fragment_shaders[&id],
+                    Some(&self.shader_uniform_bind_group_layout),
+                ),
+            };
             let new_pipeline = create_render_pipeline(
                 device,
                 &self.uniform_bind_group_layout,
                 &self.text_bind_group_layout,
                 &bind_group_layout,
+                shader_layout,
                 &self.vs_mod,
-                &self.fs_mod,
+                fs_mod,
                 self.output_color_format,
                 self.depth_texture.format(),
                 self.sample_count,
                 color_blend,
                 alpha_blend,
                 new_id.topology,
+                new_id.depth_compare,
+                new_id.depth_write,
+                new_id.mask,
             );
             self.pipelines.insert(new_id, new_pipeline);
         }
 
+        // Ensure the ID texture matches the output and has a pipeline for each color pipeline.
+        if self.picking_enabled {
+            let depth_format = self.depth_texture.format();
+            let picking = match self.picking.take() {
+                Some(picking) if picking.size() == output_attachment_size => picking,
+                _ => picking::Picking::new(device, output_attachment_size, depth_format),
+            };
+            let picking = self.picking.get_or_insert(picking);
+            picking.create_pipelines(
+                device,
+                self.pipelines.keys(),
+                &self.uniform_bind_group_layout,
+                &self.text_bind_group_layout,
+                &self.texture_bind_group_layouts,
+                &self.vs_mod,
+                &self.fs_mod,
+                &self.fs_array_mod,
+            );
+        }
+
         // Clear out unnecessary bind groups.
         self.texture_bind_groups
             .retain(|id, _| new_tex_sampler_combos.contains_key(id));
@@ -733,17 +1975,22 @@ impl Renderer {
             let sampler = self
                 .texture_samplers
                 .entry(new_sampler_id)
-                .or_insert_with(|| device.create_sampler(&curr_ctxt.sampler));
+                .or_insert_with(|| device.create_sampler(&samplers[&new_sampler_id]));
             // Retrieve the texture view.
             let texture_view = &new_tex_views[&new_tex_view_id];
             // Retrieve the associated bind group layout.
             let bind_group_layout =
-                &self.texture_bind_group_layouts[&pipeline_id.texture_component_type];
+                &self.texture_bind_group_layouts[&pipeline_id.texture_layout_id()];
             // Create the bind group.
             let bind_group =
                 create_texture_bind_group(device, bind_group_layout, sampler, texture_view);
             self.texture_bind_groups.insert(new_id, bind_group);
         }
+
+        #[cfg(feature = "profile")]
+        {
+            self.timings.resources = resources_start.elapsed();
+        }
     }
 
     /// Encode a render pass with the given **Draw**ing to the given `output_attachment`.
@@ -753,6 +2000,8 @@ impl Renderer {
     ///
     /// If the `output_attachment` is multisampled and should be resolved to another texture,
     /// include the `resolve_target`.
+    ///
+    /// Returns statistics describing the encoded render pass.
     pub fn encode_render_pass(
         &mut self,
         device: &wgpu::Device,
@@ -762,30 +2011,51 @@ impl Renderer {
         output_attachment_size: [u32; 2],
         output_attachment: &wgpu::TextureView,
         resolve_target: Option<&wgpu::TextureView>,
-    ) {
+    ) -> RenderResult {
+        let start = Instant::now();
         self.clear();
         self.fill(device, draw, scale_factor, output_attachment_size);
+        self.image_cache.encode_uploads(device, encoder);
+
+        #[cfg(feature = "profile")]
+        let encode_start = Instant::now();
 
         let Renderer {
             ref pipelines,
-            ref glyph_cache,
+            ref mut glyph_cache,
             ref glyph_cache_texture,
+            ref sdf_glyph_cache_texture,
             ref mut depth_texture,
             ref mut depth_texture_view,
             ref uniform_bind_group,
             ref text_bind_group,
             ref texture_bind_groups,
+            ref shader_uniforms,
             ref mesh,
             ref vertex_mode_buffer,
-            ref mut render_commands,
+            ref instance_buffer,
+            ref render_commands,
             ref uniform_buffer,
             scale_factor: ref mut old_scale_factor,
+            projection_offset,
+            ref mut uniforms_projection_offset,
+            color_vision_deficiency,
+            ref mut picking,
+            ref pick_id_buffer,
+            ref group_targets,
+            ref pass_starts,
+            ref retained,
             ..
         } = *self;
 
         // Update glyph cache texture if necessary.
         if glyph_cache.requires_upload {
             glyph_cache_texture.upload_data(device, encoder, &glyph_cache.pixel_buffer);
+            glyph_cache.requires_upload = false;
+        }
+        if glyph_cache.sdf.requires_upload {
+            sdf_glyph_cache_texture.upload_data(device, encoder, &glyph_cache.sdf.pixel_buffer);
+            glyph_cache.sdf.requires_upload = false;
         }
 
         // Resize the depth texture if the output attachment size has changed.
@@ -800,6 +2070,10 @@ impl Renderer {
 
         // Retrieve the clear values based on the bg color.
         let bg_color = draw.state.borrow().background_color;
+        let bg_color = match color_vision_deficiency {
+            Some(cvd) => bg_color.map(|c| cvd.simulate(c)),
+            None => bg_color,
+        };
         let (load_op, clear_color) = match bg_color {
             None => (wgpu::LoadOp::Load, wgpu::Color::TRANSPARENT),
             Some(color) => {
@@ -821,30 +2095,62 @@ impl Renderer {
             .depth_stencil_attachment(&*depth_texture_view, |depth| depth);
 
         // Guard for empty mesh.
-        if mesh.points().is_empty() {
+        if mesh.points().is_empty() && retained.is_empty() {
             // Encode the render pass. Only clears the frame.
             render_pass_builder.begin(encoder);
-            return;
+            if let Some(picking) = picking {
+                picking.encode_clear(device, encoder);
+            }
+            let cpu_time = start.elapsed();
+            return RenderResult {
+                cpu_time,
+                ..Default::default()
+            };
         }
 
         // Create the vertex and index buffers.
-        let vertex_usage = wgpu::BufferUsage::VERTEX;
-        let points_bytes = points_as_bytes(mesh.points());
-        let colors_bytes = colors_as_bytes(mesh.colors());
-        let tex_coords_bytes = tex_coords_as_bytes(mesh.tex_coords());
-        let modes_bytes = vertex_modes_as_bytes(vertex_mode_buffer);
-        let indices_bytes = indices_as_bytes(mesh.indices());
-        let point_buffer = device.create_buffer_with_data(points_bytes, vertex_usage);
-        let color_buffer = device.create_buffer_with_data(colors_bytes, vertex_usage);
-        let tex_coords_buffer = device.create_buffer_with_data(tex_coords_bytes, vertex_usage);
-        let mode_buffer = device.create_buffer_with_data(modes_bytes, vertex_usage);
-        let index_buffer = device.create_buffer_with_data(indices_bytes, wgpu::BufferUsage::INDEX);
-
-        // If the scale factor or window size has changed, update the uniforms for vertex scaling.
-        if *old_scale_factor != scale_factor || output_attachment_size != depth_size {
+        let simulated_colors: Vec<Color>;
+        let colors = match color_vision_deficiency {
+            None => mesh.colors(),
+            Some(cvd) => {
+                simulated_colors = mesh.colors().iter().map(|&c| cvd.simulate(c)).collect();
+                &simulated_colors[..]
+            }
+        };
+        let simulated_instances: Vec<Instance>;
+        let instances = match color_vision_deficiency {
+            None => &instance_buffer[..],
+            Some(cvd) => {
+                simulated_instances = instance_buffer
+                    .iter()
+                    .map(|&instance| Instance {
+                        color: cvd.simulate(instance.color),
+                        ..instance
+                    })
+                    .collect();
+                &simulated_instances[..]
+            }
+        };
+        let buffers = MeshBuffers::new(
+            device,
+            mesh.points(),
+            colors,
+            mesh.tex_coords(),
+            vertex_mode_buffer,
+            instances,
+            mesh.indices(),
+        );
+
+        // If the scale factor, window size or projection offset has changed, update the uniforms
+        // for vertex scaling.
+        if *old_scale_factor != scale_factor
+            || output_attachment_size != depth_size
+            || *uniforms_projection_offset != projection_offset
+        {
             *old_scale_factor = scale_factor;
+            *uniforms_projection_offset = projection_offset;
             // Upload uniform data for vertex scaling.
-            let uniforms = create_uniforms(output_attachment_size, scale_factor);
+            let uniforms = create_uniforms(output_attachment_size, scale_factor, projection_offset);
             let uniforms_size = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
             let uniforms_bytes = uniforms_as_bytes(&uniforms);
             let usage = wgpu::BufferUsage::COPY_SRC;
@@ -853,51 +2159,101 @@ impl Renderer {
             encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, uniform_buffer, 0, uniforms_size);
         }
 
+        let mut result = RenderResult {
+            vertex_count: mesh.points().len(),
+            index_count: mesh.indices().len(),
+            ..Default::default()
+        };
+
+        // Render each opacity group to its intermediate target.
+        for (i, target) in group_targets.iter().enumerate() {
+            let commands = &render_commands[pass_starts[i]..pass_starts[i + 1]];
+            let (attachment, resolve_target) = target.attachments();
+            let mut render_pass = wgpu::RenderPassBuilder::new()
+                .color_attachment(attachment, |color| {
+                    color
+                        .resolve_target(resolve_target)
+                        .load_op(wgpu::LoadOp::Clear)
+                        .clear_color(wgpu::Color::TRANSPARENT)
+                })
+                .depth_stencil_attachment(&*depth_texture_view, |depth| depth)
+                .begin(encoder);
+            if let Some(ref buffers) = buffers {
+                buffers.set(&mut render_pass);
+            }
+            render_pass.set_bind_group(0, uniform_bind_group, &[]);
+            render_pass.set_bind_group(1, text_bind_group, &[]);
+            encode_render_commands(
+                &mut render_pass,
+                commands,
+                buffers.as_ref(),
+                pipelines,
+                texture_bind_groups,
+                shader_uniforms,
+                retained,
+                &mut result,
+            );
+        }
+
         // Encode the render pass.
         let mut render_pass = render_pass_builder.begin(encoder);
 
         // Set the buffers.
-        render_pass.set_index_buffer(&index_buffer, 0, 0);
-        render_pass.set_vertex_buffer(0, &point_buffer, 0, 0);
-        render_pass.set_vertex_buffer(1, &color_buffer, 0, 0);
-        render_pass.set_vertex_buffer(2, &tex_coords_buffer, 0, 0);
-        render_pass.set_vertex_buffer(3, &mode_buffer, 0, 0);
+        if let Some(ref buffers) = buffers {
+            buffers.set(&mut render_pass);
+        }
 
         // Set the uniform and text bind groups here.
         render_pass.set_bind_group(0, uniform_bind_group, &[]);
         render_pass.set_bind_group(1, text_bind_group, &[]);
 
-        // Follow the render commands.
-        for cmd in render_commands.drain(..) {
-            match cmd {
-                RenderCommand::SetPipeline(id) => {
-                    let pipeline = &pipelines[&id];
-                    render_pass.set_pipeline(pipeline);
-                }
-
-                RenderCommand::SetBindGroup(tex_view_id) => {
-                    let bind_group = &texture_bind_groups[&tex_view_id];
-                    render_pass.set_bind_group(2, bind_group, &[]);
-                }
-
-                RenderCommand::SetScissor(Scissor {
-                    left,
-                    bottom,
-                    width,
-                    height,
-                }) => {
-                    render_pass.set_scissor_rect(left, bottom, width, height);
-                }
-
-                RenderCommand::DrawIndexed {
-                    start_vertex,
-                    index_range,
-                } => {
-                    let instance_range = 0..1u32;
-                    render_pass.draw_indexed(index_range, start_vertex, instance_range);
+        // Follow the render commands of the final pass.
+        let main_start = pass_starts.last().cloned().unwrap_or(0);
+        encode_render_commands(
+            &mut render_pass,
+            &render_commands[main_start..],
+            buffers.as_ref(),
+            pipelines,
+            texture_bind_groups,
+            shader_uniforms,
+            retained,
+            &mut result,
+        );
+        drop(render_pass);
+
+        // Render the ID of each primitive for picking.
+        // Retained drawings are not pickable.
+        if let Some(picking) = picking {
+            match buffers {
+                None => picking.encode_clear(device, encoder),
+                Some(ref buffers) => {
+                    let buffers = picking::VertexBuffers {
+                        points: &buffers.points,
+                        tex_coords: &buffers.tex_coords,
+                        indices: &buffers.indices,
+                    };
+                    picking.encode_render_pass(
+                        device,
+                        encoder,
+                        pick_id_buffer,
+                        instance_buffer,
+                        buffers,
+                        render_commands,
+                        uniform_bind_group,
+                        text_bind_group,
+                        texture_bind_groups,
+                    );
                 }
             }
         }
+
+        #[cfg(feature = "profile")]
+        {
+            self.timings.encode = encode_start.elapsed();
+        }
+
+        result.cpu_time = start.elapsed();
+        result
     }
 
     /// Encode the necessary commands to render the contents of the given **Draw**ing to the given
@@ -908,9 +2264,10 @@ impl Renderer {
         encoder: &mut wgpu::CommandEncoder,
         draw: &draw::Draw,
         texture: &wgpu::Texture,
-    ) {
+    ) -> RenderResult {
         let size = texture.size();
         let view = texture.view().build();
+        self.set_sample_count(device, texture.sample_count());
         // TODO: Should we expose this for rendering to textures?
         let scale_factor = 1.0;
         let resolve_target = None;
@@ -922,7 +2279,64 @@ impl Renderer {
             size,
             &view,
             resolve_target,
+        )
+    }
+
+    /// Encode the necessary commands to render the contents of the given **Draw**ing to the given
+    /// multisampled **Texture**, resolving the result to `resolve_texture`.
+    ///
+    /// This allows offscreen captures to use the same MSAA sample count and resolve behaviour as
+    /// a window's **Frame** (see `Window::msaa_samples`), so that exported images are no more
+    /// aliased than what is shown on screen. The renderer's sample count is set to that of
+    /// `msaa_texture`, while `resolve_texture` must be a non-multisampled texture of the same size
+    /// and format, ready to be read via a `TextureCapturer`.
+    pub fn render_to_texture_resolved(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        draw: &draw::Draw,
+        msaa_texture: &wgpu::Texture,
+        resolve_texture: &wgpu::Texture,
+    ) -> RenderResult {
+        assert_eq!(
+            msaa_texture.size(),
+            resolve_texture.size(),
+            "the MSAA texture and resolve texture must be the same size",
+        );
+        assert_eq!(
+            resolve_texture.sample_count(),
+            1,
+            "the resolve texture must not be multisampled",
         );
+        let size = msaa_texture.size();
+        let scale_factor = 1.0;
+        self.set_sample_count(device, msaa_texture.sample_count().max(1));
+        // Without multisampling there is nothing to resolve, so render to the target directly.
+        if msaa_texture.sample_count() <= 1 {
+            let view = resolve_texture.view().build();
+            let resolve_target = None;
+            return self.encode_render_pass(
+                device,
+                encoder,
+                draw,
+                scale_factor,
+                size,
+                &view,
+                resolve_target,
+            );
+        }
+        let view = msaa_texture.view().build();
+        let resolve_view = resolve_texture.view().build();
+        let resolve_target = Some(&resolve_view);
+        self.encode_render_pass(
+            device,
+            encoder,
+            draw,
+            scale_factor,
+            size,
+            &view,
+            resolve_target,
+        )
     }
 
     /// Encode the necessary commands to render the contents of the given **Draw**ing to the given
@@ -933,8 +2347,9 @@ impl Renderer {
         draw: &draw::Draw,
         scale_factor: f32,
         frame: &Frame,
-    ) {
+    ) -> RenderResult {
         let size = frame.texture().size();
+        self.set_sample_count(device, frame.texture_msaa_samples());
         let attachment = frame.texture_view();
         let resolve_target = None;
         let mut command_encoder = frame.command_encoder();
@@ -946,7 +2361,7 @@ impl Renderer {
             size,
             attachment,
             resolve_target,
-        );
+        )
     }
 }
 
@@ -983,11 +2398,13 @@ fn create_depth_texture(
         .build(device)
 }
 
-fn create_uniforms([img_w, img_h]: [u32; 2], scale_factor: f32) -> Uniforms {
-    let right = img_w as f32 * 0.5 / scale_factor;
-    let left = -right;
-    let top = img_h as f32 * 0.5 / scale_factor;
-    let bottom = -top;
+fn create_uniforms([img_w, img_h]: [u32; 2], scale_factor: f32, offset: Vector2) -> Uniforms {
+    let half_w = img_w as f32 * 0.5 / scale_factor;
+    let half_h = img_h as f32 * 0.5 / scale_factor;
+    let left = offset.x - half_w;
+    let right = offset.x + half_w;
+    let bottom = offset.y - half_h;
+    let top = offset.y + half_h;
     let far = std::cmp::max(img_w, img_h) as f32 / scale_factor;
     let near = -far;
     let proj = cgmath::ortho(left, right, bottom, top, near, far);
@@ -1006,6 +2423,12 @@ fn create_uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLay
         .build(device)
 }
 
+fn create_shader_uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    wgpu::BindGroupLayoutBuilder::new()
+        .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+        .build(device)
+}
+
 fn create_text_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
     wgpu::BindGroupLayoutBuilder::new()
         .sampler(wgpu::ShaderStage::FRAGMENT)
@@ -1015,19 +2438,26 @@ fn create_text_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout
             wgpu::TextureViewDimension::D2,
             wgpu::texture_format_to_component_type(Renderer::GLYPH_CACHE_TEXTURE_FORMAT),
         )
+        .sampled_texture(
+            wgpu::ShaderStage::FRAGMENT,
+            false,
+            wgpu::TextureViewDimension::D2,
+            wgpu::texture_format_to_component_type(Renderer::GLYPH_CACHE_TEXTURE_FORMAT),
+        )
         .build(device)
 }
 
 fn create_texture_bind_group_layout(
     device: &wgpu::Device,
     texture_component_type: wgpu::TextureComponentType,
+    texture_view_dimension: wgpu::TextureViewDimension,
 ) -> wgpu::BindGroupLayout {
     wgpu::BindGroupLayoutBuilder::new()
         .sampler(wgpu::ShaderStage::FRAGMENT)
         .sampled_texture(
             wgpu::ShaderStage::FRAGMENT,
             false,
-            wgpu::TextureViewDimension::D2,
+            texture_view_dimension,
             texture_component_type,
         )
         .build(device)
@@ -1043,15 +2473,30 @@ fn create_uniform_bind_group(
         .build(device, layout)
 }
 
+// Create a buffer containing the given uniform bytes along with its bind group.
+fn create_shader_uniforms(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    bytes: &[u8],
+) -> (wgpu::Buffer, wgpu::BindGroup) {
+    let buffer = device.create_buffer_with_data(bytes, wgpu::BufferUsage::UNIFORM);
+    let bind_group = wgpu::BindGroupBuilder::new()
+        .buffer_bytes(&buffer, 0..bytes.len() as wgpu::BufferAddress)
+        .build(device, layout);
+    (buffer, bind_group)
+}
+
 fn create_text_bind_group(
     device: &wgpu::Device,
     layout: &wgpu::BindGroupLayout,
     sampler: &wgpu::Sampler,
     glyph_cache_texture_view: &wgpu::TextureViewHandle,
+    sdf_glyph_cache_texture_view: &wgpu::TextureViewHandle,
 ) -> wgpu::BindGroup {
     wgpu::BindGroupBuilder::new()
         .sampler(sampler)
         .texture_view(glyph_cache_texture_view)
+        .texture_view(sdf_glyph_cache_texture_view)
         .build(device, layout)
 }
 
@@ -1072,6 +2517,7 @@ fn create_render_pipeline(
     uniform_layout: &wgpu::BindGroupLayout,
     text_layout: &wgpu::BindGroupLayout,
     texture_layout: &wgpu::BindGroupLayout,
+    shader_layout: Option<&wgpu::BindGroupLayout>,
     vs_mod: &wgpu::ShaderModule,
     fs_mod: &wgpu::ShaderModule,
     dst_format: wgpu::TextureFormat,
@@ -1080,16 +2526,66 @@ fn create_render_pipeline(
     color_blend: wgpu::BlendDescriptor,
     alpha_blend: wgpu::BlendDescriptor,
     topology: wgpu::PrimitiveTopology,
+    depth_compare: wgpu::CompareFunction,
+    depth_write: bool,
+    mask: MaskMode,
 ) -> wgpu::RenderPipeline {
-    let bind_group_layouts = &[uniform_layout, text_layout, texture_layout];
+    // Primitives forming a mask only write their stencil value, covering the mask's full region.
+    let (depth_compare, depth_write, write_mask) = match mask {
+        MaskMode::Write => (
+            wgpu::CompareFunction::Always,
+            false,
+            wgpu::ColorWrite::empty(),
+        ),
+        _ => (depth_compare, depth_write, wgpu::ColorWrite::ALL),
+    };
+    let (stencil, stencil_read_mask, stencil_write_mask) = match mask {
+        MaskMode::None => (wgpu::StencilStateFaceDescriptor::IGNORE, 0, 0),
+        MaskMode::Write => {
+            let stencil = wgpu::StencilStateFaceDescriptor {
+                compare: wgpu::CompareFunction::Always,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: wgpu::StencilOperation::Replace,
+            };
+            (stencil, !0, !0)
+        }
+        MaskMode::Clip => {
+            let stencil = wgpu::StencilStateFaceDescriptor {
+                compare: wgpu::CompareFunction::Equal,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op: wgpu::StencilOperation::Keep,
+            };
+            (stencil, !0, 0)
+        }
+    };
+    // User fragment shaders are given their uniforms at set 3.
+    let mut bind_group_layouts = vec![uniform_layout, text_layout, texture_layout];
+    bind_group_layouts.extend(shader_layout);
     wgpu::RenderPipelineBuilder::from_layout_descriptor(&bind_group_layouts[..], vs_mod)
         .fragment_shader(fs_mod)
         .color_format(dst_format)
         .add_vertex_buffer::<draw::mesh::vertex::Point>(&wgpu::vertex_attr_array![0 => Float3])
         .add_vertex_buffer::<draw::mesh::vertex::Color>(&wgpu::vertex_attr_array![1 => Float4])
         .add_vertex_buffer::<draw::mesh::vertex::TexCoords>(&wgpu::vertex_attr_array![2 => Float2])
-        .add_vertex_buffer::<VertexMode>(&wgpu::vertex_attr_array![3 => Uint])
+        .add_vertex_buffer::<u32>(&wgpu::vertex_attr_array![3 => Uint])
+        .add_instance_buffer::<Instance>(&wgpu::vertex_attr_array![
+            4 => Float4,
+            5 => Float4,
+            6 => Float4,
+            7 => Float4,
+            8 => Float4,
+            9 => Uint
+        ])
         .depth_format(depth_format)
+        .depth_compare(depth_compare)
+        .depth_write_enabled(depth_write)
+        .stencil_front(stencil.clone())
+        .stencil_back(stencil)
+        .stencil_read_mask(stencil_read_mask)
+        .stencil_write_mask(stencil_write_mask)
+        .write_mask(write_mask)
         .sample_count(sample_count)
         .color_blend(color_blend)
         .alpha_blend(alpha_blend)
@@ -1097,6 +2593,122 @@ fn create_render_pipeline(
         .build(device)
 }
 
+// Encode the given render commands within the render pass.
+fn encode_render_commands<'a>(
+    render_pass: &mut wgpu::RenderPass<'a>,
+    render_commands: &'a [RenderCommand],
+    buffers: Option<&'a MeshBuffers>,
+    pipelines: &'a HashMap<PipelineId, wgpu::RenderPipeline>,
+    texture_bind_groups: &'a HashMap<BindGroupId, wgpu::BindGroup>,
+    shader_uniforms: &'a [(wgpu::Buffer, wgpu::BindGroup)],
+    retained: &'a [Arc<retained::Baked>],
+    result: &mut RenderResult,
+) {
+    for cmd in render_commands {
+        match *cmd {
+            RenderCommand::SetPipeline(ref id) => {
+                let pipeline = &pipelines[id];
+                render_pass.set_pipeline(pipeline);
+                result.pipeline_switches += 1;
+            }
+
+            RenderCommand::SetBindGroup(ref tex_view_id) => {
+                let bind_group = &texture_bind_groups[tex_view_id];
+                render_pass.set_bind_group(2, bind_group, &[]);
+            }
+
+            RenderCommand::SetScissor(Scissor {
+                left,
+                bottom,
+                width,
+                height,
+            }) => {
+                render_pass.set_scissor_rect(left, bottom, width, height);
+            }
+
+            RenderCommand::SetStencilReference(reference) => {
+                render_pass.set_stencil_reference(reference);
+            }
+
+            RenderCommand::SetShaderUniforms(ix) => {
+                let (_, ref bind_group) = shader_uniforms[ix];
+                render_pass.set_bind_group(3, bind_group, &[]);
+            }
+
+            RenderCommand::DrawRetained(ix) => {
+                let baked = &retained[ix];
+                if let Some(ref baked_buffers) = baked.buffers {
+                    baked_buffers.set(render_pass);
+                    encode_render_commands(
+                        render_pass,
+                        &baked.render_commands,
+                        Some(baked_buffers),
+                        pipelines,
+                        texture_bind_groups,
+                        &baked.shader_uniforms,
+                        &baked.retained,
+                        result,
+                    );
+                }
+                // Restore the buffers of the mesh being drawn.
+                if let Some(buffers) = buffers {
+                    buffers.set(render_pass);
+                }
+            }
+
+            RenderCommand::DrawIndexed {
+                start_vertex,
+                ref index_range,
+                ref instance_range,
+            } => {
+                render_pass.draw_indexed(index_range.clone(), start_vertex, instance_range.clone());
+                result.draw_calls += 1;
+            }
+        }
+    }
+}
+
+// The text primitive, whether drawn directly or via a user fragment shader.
+fn text_primitive(prim: &draw::Primitive) -> Option<&draw::primitive::Text> {
+    match *prim {
+        draw::Primitive::Text(ref text) => Some(text),
+        draw::Primitive::Shaded(ref shaded) => match *shaded.primitive() {
+            draw::Primitive::Text(ref text) => Some(text),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// The number of lower bits of each vertex's packed mode that hold the **VertexMode**. The upper
+// bits hold the layer of the array texture to sample. Must match `MODE_BITS` in `shader.frag`.
+const VERTEX_MODE_BITS: u32 = 8;
+
+// Pack the vertex mode along with the array texture layer into the value uploaded per vertex.
+fn pack_vertex_mode(mode: VertexMode, texture_layer: u32) -> u32 {
+    mode as u32 | texture_layer << VERTEX_MODE_BITS
+}
+
+// Uniquely identifies a user fragment shader by its address.
+fn shader_id(module: &Arc<wgpu::ShaderModule>) -> usize {
+    &**module as *const wgpu::ShaderModule as usize
+}
+
+// Replace the triangle list indices following `start` with the unique edges of the triangles as a
+// line list.
+fn triangles_to_edges(indices: &mut Vec<u32>, start: usize) {
+    let triangles: Vec<u32> = indices.drain(start..).collect();
+    let mut edges = HashSet::new();
+    for tri in triangles.chunks_exact(3) {
+        for &(a, b) in &[(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+            let edge = (a.min(b), a.max(b));
+            if edges.insert(edge) {
+                indices.extend_from_slice(&[edge.0, edge.1]);
+            }
+        }
+    }
+}
+
 fn sampler_descriptor_hash(desc: &wgpu::SamplerDescriptor) -> SamplerId {
     let mut s = std::collections::hash_map::DefaultHasher::new();
     desc.address_mode_u.hash(&mut s);
@@ -1137,10 +2749,55 @@ fn tex_coords_as_bytes(data: &[draw::mesh::vertex::TexCoords]) -> &[u8] {
     unsafe { wgpu::bytes::from_slice(data) }
 }
 
-fn vertex_modes_as_bytes(data: &[VertexMode]) -> &[u8] {
+fn vertex_modes_as_bytes(data: &[u32]) -> &[u8] {
+    unsafe { wgpu::bytes::from_slice(data) }
+}
+
+fn instances_as_bytes(data: &[Instance]) -> &[u8] {
     unsafe { wgpu::bytes::from_slice(data) }
 }
 
 fn indices_as_bytes(data: &[u32]) -> &[u8] {
     unsafe { wgpu::bytes::from_slice(data) }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_vertex_mode() {
+        let mode_mask = (1 << VERTEX_MODE_BITS) - 1;
+        let packed = pack_vertex_mode(VertexMode::Texture, 5);
+        assert_eq!(packed & mode_mask, VertexMode::Texture as u32);
+        assert_eq!(packed >> VERTEX_MODE_BITS, 5);
+        let packed = pack_vertex_mode(VertexMode::Text, 0);
+        assert_eq!(packed, VertexMode::Text as u32);
+        let packed = pack_vertex_mode(VertexMode::SdfText, 0);
+        assert_eq!(packed, VertexMode::SdfText as u32);
+    }
+
+    #[test]
+    fn test_vertex_mode_bits_match_shader() {
+        let src = include_str!("shaders/shader.frag");
+        let decl = format!("const uint MODE_BITS = uint({});", VERTEX_MODE_BITS);
+        assert!(src.contains(&decl));
+    }
+
+    #[test]
+    fn test_instance_layout() {
+        // Four `Float4` columns, a `Float4` color and a `Uint` color mode.
+        assert_eq!(std::mem::size_of::<Instance>(), 4 * 16 + 16 + 4);
+        let identity = Instance::identity();
+        assert_eq!(
+            identity.transform,
+            <[[f32; 4]; 4]>::from(Matrix4::identity())
+        );
+        assert_eq!(identity.color_mode, 0);
+        let color = Color::new(0.25, 0.5, 0.75, 1.0);
+        let instance = Instance::new(&Matrix4::from_scale(2.0), Some(color));
+        assert_eq!(instance.transform[0][0], 2.0);
+        assert_eq!(instance.color, color);
+        assert_eq!(instance.color_mode, 1);
+    }
+}
@@ -0,0 +1,390 @@
+//! Rendering of primitive IDs to an offscreen target, allowing for pixel-accurate picking.
+
+use crate::draw::mesh::vertex::Color;
+use crate::draw::renderer::{self, RenderCommand, Scissor, VertexMode};
+use crate::wgpu;
+use std::collections::HashMap;
+
+/// Identifies a primitive by the order in which it was submitted to the **Draw**.
+///
+/// The first primitive drawn after the **Draw** is reset has the ID `PrimitiveId(0)`, the second
+/// `PrimitiveId(1)` and so on. IDs are assigned before drawings are sorted by their `z_index` or
+/// layer, so a primitive's ID does not depend on the order in which it is rendered. The background
+/// and retained drawings are not pickable, though retained drawings still occupy an ID.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PrimitiveId(pub usize);
+
+/// The offscreen target and pipelines used to render primitive IDs.
+///
+/// IDs are encoded into the RGB channels of an `Rgba8Unorm` texture rather than an integer
+/// texture, as the draw shaders only output normalised color. This allows for over 16 million
+/// pickable primitives per frame.
+#[derive(Debug)]
+pub(crate) struct Picking {
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    depth_texture: wgpu::Texture,
+    depth_texture_view: wgpu::TextureView,
+    pipelines: HashMap<PipelineId, wgpu::RenderPipeline>,
+    buffer: Option<wgpu::BufferBytes>,
+    vertex_buffers: Option<IdVertexBuffers>,
+    // Re-usable storage for the color of each vertex.
+    colors: Vec<Color>,
+}
+
+// The vertex buffers specific to the ID pass, reused between renders for as long as they have the
+// capacity for the vertices of each render.
+#[derive(Debug)]
+struct IdVertexBuffers {
+    capacity: usize,
+    colors: wgpu::Buffer,
+    modes: wgpu::Buffer,
+}
+
+type PipelineId = (
+    wgpu::PrimitiveTopology,
+    renderer::TextureLayoutId,
+    wgpu::CompareFunction,
+    bool,
+    renderer::MaskMode,
+);
+
+/// The vertex buffers shared between the color and ID render passes.
+pub(crate) struct VertexBuffers<'a> {
+    pub points: &'a wgpu::Buffer,
+    pub tex_coords: &'a wgpu::Buffer,
+    pub indices: &'a wgpu::Buffer,
+}
+
+/// The format of the texture to which IDs are rendered.
+pub const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+// The raw ID written for vertices that do not belong to a pickable primitive.
+pub(super) const NO_ID: u32 = 0;
+
+// IDs are written with full alpha, replacing those beneath them, while vertices without an ID are
+// fully transparent and leave the IDs beneath them untouched.
+const ID_BLEND: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::SrcAlpha,
+    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+    operation: wgpu::BlendOperation::Add,
+};
+
+impl Picking {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        size: [u32; 2],
+        depth_format: wgpu::TextureFormat,
+    ) -> Self {
+        let texture = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(TEXTURE_FORMAT)
+            .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC)
+            .build(device);
+        let texture_view = texture.view().build();
+        let depth_texture = renderer::create_depth_texture(device, size, depth_format, 1);
+        let depth_texture_view = depth_texture.view().build();
+        Picking {
+            texture,
+            texture_view,
+            depth_texture,
+            depth_texture_view,
+            pipelines: Default::default(),
+            buffer: None,
+            vertex_buffers: None,
+            colors: vec![],
+        }
+    }
+
+    /// The size of the ID texture in pixels.
+    pub(crate) fn size(&self) -> [u32; 2] {
+        self.texture.size()
+    }
+
+    // Ensure there is a pipeline for each of the given pipelines used by the color pass.
+    pub(crate) fn create_pipelines<'a, I>(
+        &mut self,
+        device: &wgpu::Device,
+        renderer_pipeline_ids: I,
+        uniform_layout: &wgpu::BindGroupLayout,
+        text_layout: &wgpu::BindGroupLayout,
+        texture_layouts: &HashMap<renderer::TextureLayoutId, wgpu::BindGroupLayout>,
+        vs_mod: &wgpu::ShaderModule,
+        fs_mod: &wgpu::ShaderModule,
+        fs_array_mod: &wgpu::ShaderModule,
+    ) where
+        I: IntoIterator<Item = &'a renderer::PipelineId>,
+    {
+        let depth_format = self.depth_texture.format();
+        for id in renderer_pipeline_ids {
+            let pipeline_id = pipeline_id(id);
+            let texture_layout = &texture_layouts[&id.texture_layout_id()];
+            // The shader must declare the texture with the same dimension as the layout.
+            let fs_mod = match id.texture_view_dimension {
+                wgpu::TextureViewDimension::D2Array => fs_array_mod,
+                _ => fs_mod,
+            };
+            self.pipelines.entry(pipeline_id).or_insert_with(|| {
+                renderer::create_render_pipeline(
+                    device,
+                    uniform_layout,
+                    text_layout,
+                    texture_layout,
+                    None,
+                    vs_mod,
+                    fs_mod,
+                    TEXTURE_FORMAT,
+                    depth_format,
+                    1,
+                    ID_BLEND,
+                    ID_BLEND,
+                    id.topology,
+                    id.depth_compare,
+                    id.depth_write,
+                    id.mask,
+                )
+            });
+        }
+    }
+
+    // Encode a render pass drawing each vertex in the color of its raw ID, followed by a copy of
+    // the resulting texture to a buffer that may be read by `pick`.
+    pub(crate) fn encode_render_pass(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        raw_ids: &[u32],
+        instances: &[renderer::Instance],
+        buffers: VertexBuffers,
+        render_commands: &[RenderCommand],
+        uniform_bind_group: &wgpu::BindGroup,
+        text_bind_group: &wgpu::BindGroup,
+        texture_bind_groups: &HashMap<renderer::BindGroupId, wgpu::BindGroup>,
+    ) {
+        let stale = self
+            .vertex_buffers
+            .as_ref()
+            .map(|buffers| buffers.capacity < raw_ids.len())
+            .unwrap_or(true);
+        if stale {
+            let capacity = raw_ids.len().next_power_of_two();
+            self.vertex_buffers = Some(IdVertexBuffers::new(device, capacity));
+        }
+        let id_buffers = self.vertex_buffers.as_ref().expect("no ID vertex buffers");
+
+        // Upload the color of each vertex via a staging buffer.
+        self.colors.clear();
+        self.colors.extend(raw_ids.iter().map(|&id| encode_id(id)));
+        let colors_bytes = renderer::colors_as_bytes(&self.colors);
+        let staging = device.create_buffer_with_data(colors_bytes, wgpu::BufferUsage::COPY_SRC);
+        let colors_size = colors_bytes.len() as wgpu::BufferAddress;
+        encoder.copy_buffer_to_buffer(&staging, 0, &id_buffers.colors, 0, colors_size);
+
+        // Instances keep their transforms but must not replace the ID colors.
+        let instances: Vec<_> = instances
+            .iter()
+            .map(|&instance| renderer::Instance {
+                color_mode: 0,
+                ..instance
+            })
+            .collect();
+        let instances_bytes = renderer::instances_as_bytes(&instances);
+        let instances = device.create_buffer_with_data(instances_bytes, wgpu::BufferUsage::VERTEX);
+
+        {
+            let mut render_pass = wgpu::RenderPassBuilder::new()
+                .color_attachment(&self.texture_view, |color| {
+                    color
+                        .load_op(wgpu::LoadOp::Clear)
+                        .clear_color(wgpu::Color::TRANSPARENT)
+                })
+                .depth_stencil_attachment(&self.depth_texture_view, |depth| depth)
+                .begin(encoder);
+
+            render_pass.set_index_buffer(buffers.indices, 0, 0);
+            render_pass.set_vertex_buffer(0, buffers.points, 0, 0);
+            render_pass.set_vertex_buffer(1, &id_buffers.colors, 0, 0);
+            render_pass.set_vertex_buffer(2, buffers.tex_coords, 0, 0);
+            render_pass.set_vertex_buffer(3, &id_buffers.modes, 0, 0);
+            render_pass.set_vertex_buffer(4, &instances, 0, 0);
+            render_pass.set_bind_group(0, uniform_bind_group, &[]);
+            render_pass.set_bind_group(1, text_bind_group, &[]);
+
+            for cmd in render_commands {
+                match *cmd {
+                    RenderCommand::SetPipeline(ref id) => {
+                        let pipeline = &self.pipelines[&pipeline_id(id)];
+                        render_pass.set_pipeline(pipeline);
+                    }
+                    RenderCommand::SetBindGroup(ref id) => {
+                        let bind_group = &texture_bind_groups[id];
+                        render_pass.set_bind_group(2, bind_group, &[]);
+                    }
+                    RenderCommand::SetScissor(Scissor {
+                        left,
+                        bottom,
+                        width,
+                        height,
+                    }) => {
+                        render_pass.set_scissor_rect(left, bottom, width, height);
+                    }
+                    RenderCommand::SetStencilReference(reference) => {
+                        render_pass.set_stencil_reference(reference);
+                    }
+                    // IDs are always rendered via the default fragment shader.
+                    RenderCommand::SetShaderUniforms(_) => (),
+                    // Retained drawings are not pickable.
+                    RenderCommand::DrawRetained(_) => (),
+                    RenderCommand::DrawIndexed {
+                        start_vertex,
+                        ref index_range,
+                        ref instance_range,
+                    } => {
+                        let instance_range = instance_range.clone();
+                        render_pass.draw_indexed(index_range.clone(), start_vertex, instance_range);
+                    }
+                }
+            }
+        }
+
+        self.buffer = Some(self.texture.to_buffer_bytes(device, encoder));
+    }
+
+    // Clear the ID texture, e.g. in the case that nothing was drawn.
+    pub(crate) fn encode_clear(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        wgpu::RenderPassBuilder::new()
+            .color_attachment(&self.texture_view, |color| {
+                color
+                    .load_op(wgpu::LoadOp::Clear)
+                    .clear_color(wgpu::Color::TRANSPARENT)
+            })
+            .begin(encoder);
+        self.buffer = Some(self.texture.to_buffer_bytes(device, encoder));
+    }
+
+    // Read the ID at the given pixel of the most recently rendered ID texture.
+    //
+    // Blocks until the pixel is mapped for reading.
+    pub(crate) fn pick(&self, device: &wgpu::Device, [x, y]: [u32; 2]) -> Option<PrimitiveId> {
+        let [w, h] = self.size();
+        if x >= w || y >= h {
+            return None;
+        }
+        let buffer = self.buffer.as_ref()?;
+        let pixel_size = wgpu::texture_format_size_bytes(TEXTURE_FORMAT) as wgpu::BufferAddress;
+        let offset = (y as wgpu::BufferAddress * w as wgpu::BufferAddress
+            + x as wgpu::BufferAddress)
+            * pixel_size;
+        let future = buffer.inner().map_read(offset, pixel_size);
+        device.poll(wgpu::Maintain::Wait);
+        let mapping = futures::executor::block_on(future).ok()?;
+        let bytes = mapping.as_slice();
+        decode_id([bytes[0], bytes[1], bytes[2]])
+    }
+}
+
+impl IdVertexBuffers {
+    fn new(device: &wgpu::Device, capacity: usize) -> Self {
+        let colors_size = (capacity * std::mem::size_of::<Color>()) as wgpu::BufferAddress;
+        let colors = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("nannou_draw_picking_colors"),
+            size: colors_size,
+            usage: wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST,
+        });
+        // Textures and glyphs are picked by their full quads, so every vertex uses the color mode.
+        // As the modes never change, they are only written once.
+        let modes = vec![VertexMode::Color as u32; capacity];
+        let modes_bytes = renderer::vertex_modes_as_bytes(&modes);
+        let modes = device.create_buffer_with_data(modes_bytes, wgpu::BufferUsage::VERTEX);
+        IdVertexBuffers {
+            capacity,
+            colors,
+            modes,
+        }
+    }
+}
+
+// The ID of the pipeline used to render the IDs of primitives drawn via the given color pipeline.
+fn pipeline_id(id: &renderer::PipelineId) -> PipelineId {
+    (
+        id.topology,
+        id.texture_layout_id(),
+        id.depth_compare,
+        id.depth_write,
+        id.mask,
+    )
+}
+
+// The raw ID written for the vertices of the primitive with the given ID.
+//
+// Raw IDs are offset by one so that `NO_ID` may represent the absence of a primitive.
+pub(super) fn raw_id(id: PrimitiveId) -> u32 {
+    id.0 as u32 + 1
+}
+
+// Encode the raw ID as a color whose RGB channels contain its three least significant bytes.
+//
+// The absence of an ID is encoded as a fully transparent color.
+fn encode_id(raw_id: u32) -> Color {
+    if raw_id == NO_ID {
+        return Color::new(0.0, 0.0, 0.0, 0.0);
+    }
+    let channel = |shift: u32| ((raw_id >> shift) & 0xFF) as f32 / 255.0;
+    Color::new(channel(16), channel(8), channel(0), 1.0)
+}
+
+// Decode a primitive ID from the RGB bytes of a pixel within the ID texture.
+fn decode_id([r, g, b]: [u8; 3]) -> Option<PrimitiveId> {
+    let raw_id = (r as u32) << 16 | (g as u32) << 8 | b as u32;
+    match raw_id {
+        NO_ID => None,
+        raw_id => Some(PrimitiveId(raw_id as usize - 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The bytes written to the ID texture for the given color, as quantized by `Rgba8Unorm`.
+    fn to_bytes(color: Color) -> [u8; 4] {
+        let byte = |c: f32| (c * 255.0).round() as u8;
+        [
+            byte(color.red),
+            byte(color.green),
+            byte(color.blue),
+            byte(color.alpha),
+        ]
+    }
+
+    fn round_trip(id: PrimitiveId) -> Option<PrimitiveId> {
+        let [r, g, b, _] = to_bytes(encode_id(raw_id(id)));
+        decode_id([r, g, b])
+    }
+
+    #[test]
+    fn test_id_round_trip() {
+        let max = PrimitiveId(0xFF_FF_FE);
+        for &id in &[0, 1, 254, 255, 256, 0xFF_FF, 0x01_00_00, 0x12_34_56, max.0] {
+            assert_eq!(round_trip(PrimitiveId(id)), Some(PrimitiveId(id)));
+        }
+    }
+
+    #[test]
+    fn test_id_bytes() {
+        // The raw ID is stored big-endian within the RGB channels at full alpha.
+        assert_eq!(to_bytes(encode_id(0x12_34_56)), [0x12, 0x34, 0x56, 0xFF]);
+        assert_eq!(raw_id(PrimitiveId(0)), 1);
+    }
+
+    #[test]
+    fn test_no_id() {
+        // The absence of an ID is fully transparent, leaving the IDs beneath it untouched.
+        assert_eq!(to_bytes(encode_id(NO_ID)), [0; 4]);
+        assert_eq!(decode_id([0, 0, 0]), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! Baking of the retained drawings created via `Draw::bake`.
+//!
+//! The first time a retained drawing is rendered, its commands are filled into a mesh of their
+//! own in the same manner as those of any other **Draw**. The resulting vertex data is uploaded
+//! once and the render commands are kept alongside it, so that drawing it again only requires
+//! encoding those commands against the retained buffers.
+
+use super::{Instance, MeshBuffers, RenderCommand, Renderer, Resources};
+use crate::draw;
+use crate::geom::{Rect, Vector2};
+use crate::wgpu;
+use std::mem;
+use std::sync::Arc;
+
+/// The GPU buffers and render commands of a baked retained drawing.
+#[derive(Debug)]
+pub(crate) struct Baked {
+    // `None` if the drawing produced no vertices.
+    pub(super) buffers: Option<MeshBuffers>,
+    pub(super) render_commands: Vec<RenderCommand>,
+    // The pipelines and bind groups required by the render commands, created by the renderer
+    // that draws the retained drawing.
+    pub(super) resources: Resources,
+    pub(super) shader_uniforms: Vec<(wgpu::Buffer, wgpu::BindGroup)>,
+    // Any retained drawings drawn within this one.
+    pub(super) retained: Vec<Arc<Baked>>,
+    scale_factor: f32,
+    output_attachment_size: [u32; 2],
+    projection_offset: Vector2,
+    full_output_rect: Option<Rect>,
+}
+
+impl Renderer {
+    // Bake the given retained drawing unless it has already been baked for an output of the
+    // given scale factor and size and the current projection offset and full output rect.
+    pub(super) fn bake_if_stale(
+        &mut self,
+        device: &wgpu::Device,
+        retained: &draw::Retained,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+    ) {
+        let stale = retained
+            .baked()
+            .map(|baked| {
+                baked.scale_factor != scale_factor
+                    || baked.output_attachment_size != output_attachment_size
+                    || baked.projection_offset != self.projection_offset
+                    || baked.full_output_rect != self.full_output_rect
+            })
+            .unwrap_or(true);
+        if stale {
+            let baked = self.bake(device, retained, scale_factor, output_attachment_size);
+            retained.set_baked(Arc::new(baked));
+        }
+    }
+
+    // Fill the commands of the retained drawing into a mesh of their own and upload it.
+    //
+    // The renderer's per-frame state is set aside while doing so and restored afterwards.
+    fn bake(
+        &mut self,
+        device: &wgpu::Device,
+        retained: &draw::Retained,
+        scale_factor: f32,
+        output_attachment_size: [u32; 2],
+    ) -> Baked {
+        let draw = draw::Draw::new();
+        retained.replay(&draw);
+
+        let default_shader_uniforms =
+            super::create_shader_uniforms(device, &self.shader_uniform_bind_group_layout, &[0; 16]);
+        let render_commands = mem::replace(&mut self.render_commands, vec![]);
+        let pass_starts = mem::replace(&mut self.pass_starts, vec![]);
+        let mesh = mem::replace(&mut self.mesh, Default::default());
+        let vertex_mode_buffer = mem::replace(&mut self.vertex_mode_buffer, vec![]);
+        let instance_buffer = mem::replace(&mut self.instance_buffer, vec![Instance::identity()]);
+        let pick_id_buffer = mem::replace(&mut self.pick_id_buffer, vec![]);
+        let shader_uniforms =
+            mem::replace(&mut self.shader_uniforms, vec![default_shader_uniforms]);
+        let retained_draws = mem::replace(&mut self.retained, vec![]);
+        let tessellation_cache = mem::replace(&mut self.tessellation_cache, Default::default());
+
+        let resources =
+            self.fill_render_commands(device, &draw, scale_factor, output_attachment_size);
+        let buffers = MeshBuffers::new(
+            device,
+            self.mesh.points(),
+            self.mesh.colors(),
+            self.mesh.tex_coords(),
+            &self.vertex_mode_buffer,
+            &self.instance_buffer,
+            self.mesh.indices(),
+        );
+        let baked = Baked {
+            buffers,
+            render_commands: mem::replace(&mut self.render_commands, render_commands),
+            resources,
+            shader_uniforms: mem::replace(&mut self.shader_uniforms, shader_uniforms),
+            retained: mem::replace(&mut self.retained, retained_draws),
+            scale_factor,
+            output_attachment_size,
+            projection_offset: self.projection_offset,
+            full_output_rect: self.full_output_rect,
+        };
+
+        self.pass_starts = pass_starts;
+        self.mesh = mesh;
+        self.vertex_mode_buffer = vertex_mode_buffer;
+        self.instance_buffer = instance_buffer;
+        self.pick_id_buffer = pick_id_buffer;
+        self.tessellation_cache = tessellation_cache;
+        baked
+    }
+}
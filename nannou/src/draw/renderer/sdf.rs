@@ -0,0 +1,399 @@
+//! A cache of signed distance fields for the glyphs of text drawn via `draw.text(..).sdf()`.
+//!
+//! Each glyph is rasterized once at `GLYPH_SCALE`, converted to a signed distance field and packed
+//! into an atlas. The text shader reconstructs the glyph's edge from the interpolated distance at
+//! any scale, so that text remains crisp when scaled, rotated or zoomed without re-rasterizing.
+
+use crate::text::{self, rt};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The pixel height at which glyphs are rasterized before being converted to distance fields.
+pub const GLYPH_SCALE: f32 = 48.0;
+
+/// The distance in pixels at `GLYPH_SCALE` either side of a glyph's edge covered by its field.
+///
+/// Each glyph is padded by this many pixels within the atlas.
+pub const SPREAD: u32 = 6;
+
+// Stands in for an infinite squared distance while avoiding `inf - inf` within the transform.
+const INF: f64 = 1e20;
+
+/// The signed distance fields of glyphs packed into a single channel atlas.
+///
+/// A texel value of `0.5` lies on the edge of the glyph, with greater values lying inside.
+pub struct SdfGlyphCache {
+    size: [u32; 2],
+    // Each glyph keyed by font index and glyph ID, or `None` for glyphs without an outline.
+    glyphs: HashMap<(usize, text::GlyphId), Option<Entry>>,
+    // The position of the next glyph within the current row of the atlas.
+    cursor: [u32; 2],
+    // The height of the tallest glyph within the current row of the atlas.
+    row_height: u32,
+    // Whether or not a glyph failed to fit within the atlas since it was last cleared.
+    full: bool,
+    /// The buffer used to store the distance fields of the glyphs.
+    pub pixel_buffer: Vec<u8>,
+    /// Will be set to `true` after the cache has been updated if the texture requires re-uploading.
+    pub requires_upload: bool,
+}
+
+/// Returned when a glyph does not fit within the remaining space of the atlas.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CacheFull;
+
+#[derive(Copy, Clone, Debug)]
+struct Entry {
+    // The normalised texture coordinates of the glyph's field within the atlas.
+    uv_rect: rt::Rect<f32>,
+    // The rect covered by the glyph's field relative to its origin, in units of its scale.
+    bounds: rt::Rect<f32>,
+}
+
+impl SdfGlyphCache {
+    /// Create an empty cache with an atlas of the given size.
+    pub fn new(size: [u32; 2]) -> Self {
+        let [w, h] = size;
+        SdfGlyphCache {
+            size,
+            glyphs: Default::default(),
+            cursor: [0; 2],
+            row_height: 0,
+            full: false,
+            pixel_buffer: vec![0u8; w as usize * h as usize],
+            requires_upload: false,
+        }
+    }
+
+    /// The dimensions of the atlas.
+    pub fn dimensions(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// The number of glyphs within the cache, including those without an outline.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Whether or not the cache contains any glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// Remove all glyphs from the cache.
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+        self.cursor = [0; 2];
+        self.row_height = 0;
+        self.full = false;
+        for byte in self.pixel_buffer.iter_mut() {
+            *byte = 0;
+        }
+        self.requires_upload = true;
+    }
+
+    /// Clear the cache if a glyph has failed to fit since it was last cleared.
+    ///
+    /// This is called before the glyphs of each frame are cached, so that the atlas is only ever
+    /// repacked between frames.
+    pub fn clear_if_full(&mut self) {
+        if self.full {
+            self.clear();
+        }
+    }
+
+    /// The texture coordinates of the given glyph's field within the atlas alongside the rect it
+    /// covers in the pixel space of the glyph's position, caching the glyph if necessary.
+    ///
+    /// Returns `None` for glyphs without an outline, such as spaces.
+    pub fn rect_for(
+        &mut self,
+        font_index: usize,
+        glyph: &text::PositionedGlyph,
+    ) -> Result<Option<(rt::Rect<f32>, rt::Rect<f32>)>, CacheFull> {
+        let key = (font_index, glyph.id());
+        let entry = match self.glyphs.get(&key) {
+            Some(&entry) => entry,
+            None => {
+                let entry = self.insert(glyph)?;
+                self.glyphs.insert(key, entry);
+                entry
+            }
+        };
+        let entry = match entry {
+            None => return Ok(None),
+            Some(entry) => entry,
+        };
+        let scale = glyph.scale();
+        let position = glyph.position();
+        let screen_rect = rt::Rect {
+            min: rt::point(
+                position.x + entry.bounds.min.x * scale.x,
+                position.y + entry.bounds.min.y * scale.y,
+            ),
+            max: rt::point(
+                position.x + entry.bounds.max.x * scale.x,
+                position.y + entry.bounds.max.y * scale.y,
+            ),
+        };
+        Ok(Some((entry.uv_rect, screen_rect)))
+    }
+
+    // Rasterize the given glyph at `GLYPH_SCALE` and write its distance field to the atlas.
+    fn insert(&mut self, glyph: &text::PositionedGlyph) -> Result<Option<Entry>, CacheFull> {
+        let glyph = glyph
+            .unpositioned()
+            .unscaled()
+            .clone()
+            .scaled(text::Scale::uniform(GLYPH_SCALE))
+            .positioned(rt::point(0.0, 0.0));
+        let bb = match glyph.pixel_bounding_box() {
+            None => return Ok(None),
+            Some(bb) => bb,
+        };
+
+        // Rasterize the glyph's coverage with enough padding for its field.
+        let pad = SPREAD as i32;
+        let w = (bb.width() + pad * 2) as u32;
+        let h = (bb.height() + pad * 2) as u32;
+        let mut coverage = vec![0.0; w as usize * h as usize];
+        glyph.draw(|x, y, v| {
+            let ix = (y + SPREAD) as usize * w as usize + (x + SPREAD) as usize;
+            coverage[ix] = v;
+        });
+
+        let [x, y] = self.allocate(w, h)?;
+        let field = signed_distance_field(&coverage, w as usize, h as usize, SPREAD as f32);
+        let atlas_w = self.size[0] as usize;
+        for (row, src) in field.chunks_exact(w as usize).enumerate() {
+            let dst_ix = (y as usize + row) * atlas_w + x as usize;
+            self.pixel_buffer[dst_ix..dst_ix + w as usize].copy_from_slice(src);
+        }
+        self.requires_upload = true;
+
+        let [atlas_w, atlas_h] = [self.size[0] as f32, self.size[1] as f32];
+        let uv_rect = rt::Rect {
+            min: rt::point(x as f32 / atlas_w, y as f32 / atlas_h),
+            max: rt::point((x + w) as f32 / atlas_w, (y + h) as f32 / atlas_h),
+        };
+        let bounds = rt::Rect {
+            min: rt::point(
+                (bb.min.x - pad) as f32 / GLYPH_SCALE,
+                (bb.min.y - pad) as f32 / GLYPH_SCALE,
+            ),
+            max: rt::point(
+                (bb.max.x + pad) as f32 / GLYPH_SCALE,
+                (bb.max.y + pad) as f32 / GLYPH_SCALE,
+            ),
+        };
+        Ok(Some(Entry { uv_rect, bounds }))
+    }
+
+    // Find space for a field of the given size within the atlas, packing glyphs into rows.
+    //
+    // A single texel gap is left between glyphs so that filtering never blends neighbours.
+    fn allocate(&mut self, w: u32, h: u32) -> Result<[u32; 2], CacheFull> {
+        let [atlas_w, atlas_h] = self.size;
+        if self.cursor[0] + w > atlas_w {
+            self.cursor = [0, self.cursor[1] + self.row_height + 1];
+            self.row_height = 0;
+        }
+        if self.cursor[0] + w > atlas_w || self.cursor[1] + h > atlas_h {
+            self.full = true;
+            return Err(CacheFull);
+        }
+        let position = self.cursor;
+        self.cursor[0] += w + 1;
+        self.row_height = self.row_height.max(h);
+        Ok(position)
+    }
+}
+
+/// Convert the given coverage values into a signed distance field of the same size.
+///
+/// Each texel of the result maps the distance to the nearest edge, ranging from `spread` outside
+/// the shape to `spread` inside it, onto `0..=255`. Partially covered texels are used to estimate
+/// the position of the edge to sub-texel precision.
+pub fn signed_distance_field(
+    coverage: &[f32],
+    width: usize,
+    height: usize,
+    spread: f32,
+) -> Vec<u8> {
+    assert_eq!(coverage.len(), width * height);
+
+    // The squared distance to the nearest texel outside and inside the shape respectively.
+    let mut outer = vec![INF; coverage.len()];
+    let mut inner = vec![0.0; coverage.len()];
+    for (ix, &a) in coverage.iter().enumerate() {
+        let a = a.max(0.0).min(1.0) as f64;
+        if a == 0.0 {
+            continue;
+        }
+        if a == 1.0 {
+            outer[ix] = 0.0;
+            inner[ix] = INF;
+        } else {
+            let d = 0.5 - a;
+            outer[ix] = if d > 0.0 { d * d } else { 0.0 };
+            inner[ix] = if d < 0.0 { d * d } else { 0.0 };
+        }
+    }
+    distance_transform(&mut outer, width, height);
+    distance_transform(&mut inner, width, height);
+
+    outer
+        .iter()
+        .zip(&inner)
+        .map(|(&outer, &inner)| {
+            let d = (outer.sqrt() - inner.sqrt()) as f32;
+            let value = 0.5 - d / (2.0 * spread);
+            (value.max(0.0).min(1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+// The squared euclidean distance transform of a 2D grid of squared distances, after Felzenszwalb
+// and Huttenlocher's "Distance Transforms of Sampled Functions".
+fn distance_transform(grid: &mut [f64], width: usize, height: usize) {
+    let len = width.max(height);
+    let mut f = vec![0.0; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.0; len + 1];
+    for x in 0..width {
+        distance_transform_1d(grid, x, width, height, &mut f, &mut v, &mut z);
+    }
+    for y in 0..height {
+        distance_transform_1d(grid, y * width, 1, width, &mut f, &mut v, &mut z);
+    }
+}
+
+// The squared distance transform of a single row or column of the grid, computed via the lower
+// envelope of the parabolas rooted at each of its cells.
+fn distance_transform_1d(
+    grid: &mut [f64],
+    offset: usize,
+    stride: usize,
+    len: usize,
+    f: &mut [f64],
+    v: &mut [usize],
+    z: &mut [f64],
+) {
+    v[0] = 0;
+    z[0] = -INF;
+    z[1] = INF;
+    f[0] = grid[offset];
+    let mut k = 0;
+    for q in 1..len {
+        f[q] = grid[offset + q * stride];
+        let qf = q as f64;
+        let mut s;
+        loop {
+            let r = v[k] as f64;
+            s = (f[q] - f[v[k]] + qf * qf - r * r) / (qf - r) / 2.0;
+            if s <= z[k] && k > 0 {
+                k -= 1;
+                continue;
+            }
+            break;
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = INF;
+    }
+    let mut k = 0;
+    for q in 0..len {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let d = q as f64 - v[k] as f64;
+        grid[offset + q * stride] = f[v[k]] + d * d;
+    }
+}
+
+impl fmt::Debug for SdfGlyphCache {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SdfGlyphCache")
+            .field("size", &self.size)
+            .field("glyphs", &self.glyphs.len())
+            .field("full", &self.full)
+            .field("requires_upload", &self.requires_upload)
+            .finish()
+    }
+}
+
+impl fmt::Display for CacheFull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the glyph does not fit within the signed distance field atlas"
+        )
+    }
+}
+
+impl std::error::Error for CacheFull {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The coverage of a disk of the given radius centred within a square grid of the given size.
+    fn disk(size: usize, radius: f32) -> Vec<f32> {
+        let c = size as f32 / 2.0;
+        let mut coverage = vec![];
+        for y in 0..size {
+            for x in 0..size {
+                let (dx, dy) = (x as f32 + 0.5 - c, y as f32 + 0.5 - c);
+                let d = (dx * dx + dy * dy).sqrt();
+                coverage.push((radius - d + 0.5).max(0.0).min(1.0));
+            }
+        }
+        coverage
+    }
+
+    #[test]
+    fn test_distance_transform() {
+        // A single seed at the centre of a row and column.
+        let (w, h) = (5, 3);
+        let mut grid = vec![INF; w * h];
+        grid[w + 2] = 0.0;
+        distance_transform(&mut grid, w, h);
+        for y in 0..h {
+            for x in 0..w {
+                let (dx, dy) = (x as f64 - 2.0, y as f64 - 1.0);
+                assert_eq!(grid[y * w + x], dx * dx + dy * dy);
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_distance_field_of_disk() {
+        let (size, radius, spread) = (32, 8.0, 4.0);
+        let field = signed_distance_field(&disk(size, radius), size, size, spread);
+        let c = size as f32 / 2.0;
+        for y in 0..size {
+            for x in 0..size {
+                let (dx, dy) = (x as f32 + 0.5 - c, y as f32 + 0.5 - c);
+                let d = (dx * dx + dy * dy).sqrt() - radius;
+                let expected = (0.5 - d / (2.0 * spread)).max(0.0).min(1.0);
+                let value = field[y * size + x] as f32 / 255.0;
+                // Within a texel of the exact distance.
+                assert!((value - expected).abs() <= 1.0 / (2.0 * spread) + 1.0 / 255.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_allocate_rows() {
+        let mut cache = SdfGlyphCache::new([16, 8]);
+        assert_eq!(cache.allocate(6, 3), Ok([0, 0]));
+        assert_eq!(cache.allocate(6, 2), Ok([7, 0]));
+        // Too wide for the remainder of the row, so a new row begins below the tallest glyph.
+        assert_eq!(cache.allocate(6, 4), Ok([0, 4]));
+        assert_eq!(cache.allocate(16, 4), Err(CacheFull));
+        assert!(cache.full);
+        cache.clear_if_full();
+        assert_eq!(cache.allocate(16, 8), Ok([0, 0]));
+    }
+}
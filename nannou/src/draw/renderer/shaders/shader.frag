@@ -1,15 +1,26 @@
 // NOTE: This shader requires being manually compiled to SPIR-V in order to
 // avoid having downstream users require building shaderc and compiling the
 // shader themselves. If you update this shader, be sure to also re-compile it
-// and update `frag.spv`. You can do so using `glslangValidator` with the
-// following command: `glslangValidator -V -o frag.spv shader.frag`
+// and update `frag.spv` and `frag_array.spv`. You can do so using
+// `glslangValidator` with the following commands:
+//
+// glslangValidator -V -o frag.spv shader.frag
+// glslangValidator -V -DTEXTURE_ARRAY -o frag_array.spv shader.frag
+//
+// When `TEXTURE_ARRAY` is defined, the texture is a `texture2DArray` and the
+// layer to sample is given by the upper bits of the vertex mode.
 
 #version 450
 
 layout(set = 1, binding = 0) uniform sampler text_sampler;
 layout(set = 1, binding = 1) uniform texture2D text;
+layout(set = 1, binding = 2) uniform texture2D sdf_text;
 layout(set = 2, binding = 0) uniform sampler tex_sampler;
+#ifdef TEXTURE_ARRAY
+layout(set = 2, binding = 1) uniform texture2DArray tex;
+#else
 layout(set = 2, binding = 1) uniform texture2D tex;
+#endif
 
 layout(location = 0) in vec4 v_color;
 layout(location = 1) in vec2 v_tex_coords;
@@ -17,20 +28,40 @@ layout(location = 2) flat in uint v_mode;
 
 layout(location = 0) out vec4 f_color;
 
+// The number of lower bits of the vertex mode that describe the mode itself.
+const uint MODE_BITS = uint(8);
+
 void main() {
+    uint mode = v_mode & ((uint(1) << MODE_BITS) - uint(1));
+
+    // The signed distance to the edge of the glyph, where 0.5 lies on the edge. Sampled outside of
+    // the mode branches so that its screen space derivatives are well defined.
+    float sdf_dist = texture(sampler2D(sdf_text, text_sampler), v_tex_coords).r;
+    float sdf_width = max(fwidth(sdf_dist) * 0.5, 0.0001);
+
     // Color
-    if (v_mode == uint(0)) {
+    if (mode == uint(0)) {
         f_color = v_color;
 
     // Texture
-    } else if (v_mode == uint(1)) {
+    } else if (mode == uint(1)) {
+#ifdef TEXTURE_ARRAY
+        float layer = float(v_mode >> MODE_BITS);
+        f_color = texture(sampler2DArray(tex, tex_sampler), vec3(v_tex_coords, layer));
+#else
         f_color = texture(sampler2D(tex, tex_sampler), v_tex_coords);
+#endif
 
     // Text
-    } else if (v_mode == uint(2)) {
+    } else if (mode == uint(2)) {
         float tex_a = texture(sampler2D(text, text_sampler), v_tex_coords).r;
         f_color = vec4(v_color.rgb, v_color.a * tex_a);
 
+    // Signed distance field text
+    } else if (mode == uint(3)) {
+        float sdf_a = smoothstep(0.5 - sdf_width, 0.5 + sdf_width, sdf_dist);
+        f_color = vec4(v_color.rgb, v_color.a * sdf_a);
+
     // Unhandled mode - Indicate error with red.
     } else {
         f_color = vec4(1.0, 0.0, 0.0, 1.0);
@@ -15,13 +15,29 @@ layout(location = 1) in vec4 color;
 layout(location = 2) in vec2 tex_coords;
 layout(location = 3) in uint mode;
 
+// The attributes of each instance. Primitives that are not instanced are drawn with a single
+// instance whose transform is the identity and whose color is unused.
+layout(location = 4) in vec4 instance_transform_x;
+layout(location = 5) in vec4 instance_transform_y;
+layout(location = 6) in vec4 instance_transform_z;
+layout(location = 7) in vec4 instance_transform_w;
+layout(location = 8) in vec4 instance_color;
+// Whether or not `instance_color` replaces the vertex color.
+layout(location = 9) in uint instance_color_mode;
+
 layout(location = 0) out vec4 v_color;
 layout(location = 1) out vec2 v_tex_coords;
 layout(location = 2) flat out uint v_mode;
 
 void main() {
-    gl_Position = uniforms.proj * vec4(position, 1.0);
-    v_color = color;
+    mat4 instance_transform = mat4(
+        instance_transform_x,
+        instance_transform_y,
+        instance_transform_z,
+        instance_transform_w
+    );
+    gl_Position = uniforms.proj * instance_transform * vec4(position, 1.0);
+    v_color = instance_color_mode == uint(0) ? color : instance_color;
     v_tex_coords = tex_coords;
     v_mode = mode;
 }
@@ -0,0 +1,115 @@
+//! Items related to retaining static drawings on the GPU across frames.
+
+use crate::draw::primitive::Primitive;
+use crate::draw::renderer::retained::Baked;
+use crate::draw::{Draw, Drawing, Recording, Theme};
+use crate::serde_derive::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Drawings that are tessellated and uploaded to the GPU once, after which they may be drawn each
+/// frame at the cost of a few draw calls.
+///
+/// A **Retained** drawing is produced via `Draw::bake` and drawn via `Draw::retained`. This is
+/// useful for static content such as backgrounds, which would otherwise be re-tessellated and
+/// re-uploaded every frame.
+///
+/// ```no_run
+/// # use nannou::prelude::*;
+/// # struct Model { background: nannou::draw::Retained }
+/// fn model(_app: &App) -> Model {
+///     let draw = Draw::new();
+///     for i in 0..1000 {
+///         draw.ellipse().x(i as f32).w_h(2.0, 2.0);
+///     }
+///     Model { background: draw.bake() }
+/// }
+///
+/// fn view(app: &App, model: &Model, frame: Frame) {
+///     let draw = app.draw();
+///     draw.retained(&model.background);
+///     draw.to_frame(app, &frame).unwrap();
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The drawing is baked by the renderer the first time that it is drawn, and is re-baked if it is
+/// later drawn to an output with a different size or scale factor. Retained drawings are drawn
+/// exactly as they were baked, ignoring the transform and other context of the **Draw** they are
+/// drawn with. They cannot be picked, and opacity groups within them are not supported. Text is
+/// supported, but may be corrupted if the glyph cache later evicts its glyphs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Retained {
+    #[serde(with = "crate::draw::serialize::resource")]
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    recording: Recording,
+    theme: Theme,
+    baked: Mutex<Option<Arc<Baked>>>,
+}
+
+impl Retained {
+    // The drawing as it was last baked, if at all.
+    pub(crate) fn baked(&self) -> Option<Arc<Baked>> {
+        self.inner
+            .baked
+            .lock()
+            .expect("failed to acquire `Retained` lock")
+            .clone()
+    }
+
+    pub(crate) fn set_baked(&self, baked: Arc<Baked>) {
+        let mut guard = self
+            .inner
+            .baked
+            .lock()
+            .expect("failed to acquire `Retained` lock");
+        *guard = Some(baked);
+    }
+
+    // Submit the retained commands to the given **Draw** so that they may be baked.
+    pub(crate) fn replay(&self, draw: &Draw) {
+        draw.set_theme(self.inner.theme.clone());
+        draw.append(self.inner.recording.clone());
+    }
+}
+
+impl Draw {
+    /// Finish any drawings-in-progress and take all commands drawn so far into a **Retained**
+    /// drawing that may be drawn each frame without being re-tessellated.
+    ///
+    /// The theme of the **Draw** is retained along with the commands.
+    pub fn bake(&self) -> Retained {
+        let theme = self.state.borrow().theme.clone();
+        let recording = self.take_recording();
+        let baked = Mutex::new(None);
+        let inner = Arc::new(Inner {
+            recording,
+            theme,
+            baked,
+        });
+        Retained { inner }
+    }
+
+    /// Draw the given **Retained** drawing.
+    pub fn retained(&self, retained: &Retained) -> Drawing<Retained> {
+        self.a(retained.clone())
+    }
+}
+
+impl<S> From<Retained> for Primitive<S> {
+    fn from(prim: Retained) -> Self {
+        Primitive::Retained(prim)
+    }
+}
+
+impl<S> Into<Option<Retained>> for Primitive<S> {
+    fn into(self) -> Option<Retained> {
+        match self {
+            Primitive::Retained(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Helpers for serializing **Draw** commands and their primitives with serde.
+//!
+//! Most of the draw API's types derive `Serialize` and `Deserialize` directly. This module
+//! provides definitions for the wgpu types referenced by the draw **Context**, along with
+//! functions for fields that refer to GPU resources or fonts. The latter have no serializable
+//! representation, so serialization fails if such a resource is present.
+
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::wgpu;
+use serde::de::Error as DeError;
+use serde::ser::Error as SerError;
+use serde::{Deserializer, Serializer};
+
+const RESOURCE_ERROR: &str =
+    "GPU resources and fonts referenced by draw commands cannot be serialized";
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendDescriptor")]
+pub(crate) struct BlendDescriptor {
+    #[serde(with = "BlendFactor")]
+    pub src_factor: wgpu::BlendFactor,
+    #[serde(with = "BlendFactor")]
+    pub dst_factor: wgpu::BlendFactor,
+    #[serde(with = "BlendOperation")]
+    pub operation: wgpu::BlendOperation,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendFactor")]
+pub(crate) enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstColor,
+    OneMinusDstColor,
+    DstAlpha,
+    OneMinusDstAlpha,
+    SrcAlphaSaturated,
+    BlendColor,
+    OneMinusBlendColor,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendOperation")]
+pub(crate) enum BlendOperation {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::PrimitiveTopology")]
+pub(crate) enum PrimitiveTopology {
+    PointList,
+    LineList,
+    LineStrip,
+    TriangleList,
+    TriangleStrip,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::SamplerDescriptor")]
+pub(crate) struct SamplerDescriptor {
+    #[serde(with = "AddressMode")]
+    pub address_mode_u: wgpu::AddressMode,
+    #[serde(with = "AddressMode")]
+    pub address_mode_v: wgpu::AddressMode,
+    #[serde(with = "AddressMode")]
+    pub address_mode_w: wgpu::AddressMode,
+    #[serde(with = "FilterMode")]
+    pub mag_filter: wgpu::FilterMode,
+    #[serde(with = "FilterMode")]
+    pub min_filter: wgpu::FilterMode,
+    #[serde(with = "FilterMode")]
+    pub mipmap_filter: wgpu::FilterMode,
+    pub lod_min_clamp: f32,
+    pub lod_max_clamp: f32,
+    #[serde(with = "CompareFunction")]
+    pub compare: wgpu::CompareFunction,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::AddressMode")]
+pub(crate) enum AddressMode {
+    ClampToEdge,
+    Repeat,
+    MirrorRepeat,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::FilterMode")]
+pub(crate) enum FilterMode {
+    Nearest,
+    Linear,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::CompareFunction")]
+pub(crate) enum CompareFunction {
+    Undefined,
+    Never,
+    Less,
+    Equal,
+    LessEqual,
+    Greater,
+    NotEqual,
+    GreaterEqual,
+    Always,
+}
+
+/// For fields holding a resource that must always be present, e.g. the view of a **Texture**.
+///
+/// Both serialization and deserialization fail.
+pub(crate) mod resource {
+    use super::*;
+
+    pub fn serialize<T, S>(_: &T, _: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Err(S::Error::custom(RESOURCE_ERROR))
+    }
+
+    pub fn deserialize<'de, T, D>(_: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Err(D::Error::custom(RESOURCE_ERROR))
+    }
+}
+
+/// For fields holding an optional resource, e.g. the texture of a path.
+///
+/// Serialization fails if the resource is present.
+pub(crate) mod optional_resource {
+    use super::*;
+
+    pub fn serialize<T, S>(resource: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *resource {
+            None => serializer.serialize_none(),
+            Some(_) => Err(S::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match <Option<()> as serde::Deserialize>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(()) => Err(D::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+}
+
+/// For builder fields that may override a default resource with either another resource or none,
+/// e.g. the font of a text layout.
+///
+/// Serialization fails if an overriding resource is present.
+pub(crate) mod optional_resource_override {
+    use super::*;
+
+    pub fn serialize<T, S>(resource: &Option<Option<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *resource {
+            None => serializer.serialize_none(),
+            Some(None) => serializer.serialize_some(&None::<()>),
+            Some(Some(_)) => Err(S::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match <Option<Option<()>> as serde::Deserialize>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(None) => Ok(Some(None)),
+            Some(Some(())) => Err(D::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+}
+
+/// For builder fields holding an optional list of resources, e.g. the fallback fonts of a text
+/// layout.
+///
+/// Serialization fails if the list contains any resources.
+pub(crate) mod optional_resources {
+    use super::*;
+
+    pub fn serialize<T, S>(resources: &Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *resources {
+            None => serializer.serialize_none(),
+            Some(ref rs) if rs.is_empty() => serializer.serialize_some(&[(); 0]),
+            Some(_) => Err(S::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match <Option<Vec<()>> as serde::Deserialize>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(ref rs) if rs.is_empty() => Ok(Some(vec![])),
+            Some(_) => Err(D::Error::custom(RESOURCE_ERROR)),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::color::{LinSrgba, Srgba};
+use crate::color::{LinSrgba, Palette, Srgba};
 use std::collections::HashMap;
 
 /// A set of styling defaults used for coloring texturing geometric primitives that have no entry
@@ -23,21 +23,85 @@ pub struct Color {
 /// These are used as keys into the **Theme**'s geometry primitive default values.
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Primitive {
+    Arc,
     Arrow,
+    Bezier,
+    Cone,
     Cuboid,
+    Cylinder,
     Ellipse,
     Line,
     Mesh,
     Path,
+    PointCloud,
     Polygon,
     Quad,
     Rect,
+    RegularPolygon,
+    Sphere,
+    Star,
     Text,
     Texture,
+    Torus,
     Tri,
 }
 
+impl Primitive {
+    /// All primitive kinds.
+    pub const ALL: &'static [Primitive] = &[
+        Primitive::Arc,
+        Primitive::Arrow,
+        Primitive::Bezier,
+        Primitive::Cone,
+        Primitive::Cuboid,
+        Primitive::Cylinder,
+        Primitive::Ellipse,
+        Primitive::Line,
+        Primitive::Mesh,
+        Primitive::Path,
+        Primitive::PointCloud,
+        Primitive::Polygon,
+        Primitive::Quad,
+        Primitive::Rect,
+        Primitive::RegularPolygon,
+        Primitive::Sphere,
+        Primitive::Star,
+        Primitive::Text,
+        Primitive::Texture,
+        Primitive::Torus,
+        Primitive::Tri,
+    ];
+}
+
 impl Theme {
+    /// Create a theme from the given palette.
+    ///
+    /// The first color of the palette becomes the default fill color and the second becomes the
+    /// default stroke color. Any remaining colors are assigned as fill colors to each primitive
+    /// kind in turn, wrapping around to the start of the palette if necessary.
+    ///
+    /// If the palette contains fewer than two colors, the default theme colors are used in their
+    /// place.
+    pub fn from_palette(palette: &Palette) -> Self {
+        let mut theme = Self::default();
+        let colors = palette.colors();
+        if let Some(&fill) = colors.get(0) {
+            theme.fill_color.default = fill;
+        }
+        if let Some(&stroke) = colors.get(1) {
+            theme.stroke_color.default = stroke;
+            theme.stroke_color.primitive.clear();
+        }
+        if colors.len() > 2 {
+            let rest = &colors[2..];
+            for (i, &prim) in Primitive::ALL.iter().enumerate() {
+                let color = rest[i % rest.len()];
+                theme.fill_color.primitive.insert(prim, color);
+            }
+        }
+        theme
+    }
+
     /// Retrieve the non-linear sRGBA fill color representation for the given primitive.
     pub fn fill_srgba(&self, prim: &Primitive) -> Srgba {
         self.fill_color
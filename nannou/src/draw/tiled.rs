@@ -0,0 +1,324 @@
+//! Rendering a **Draw** to images larger than the GPU can render in a single pass.
+//!
+//! The image is split into tiles, each of which is rendered by offsetting the renderer's
+//! projection to the region of the image covered by the tile. The tiles are read back to the CPU
+//! and stitched together into the resulting image.
+//!
+//! Supersampled exports are produced by rendering the tiled image at a multiple of the requested
+//! size and averaging each block of pixels of each tile in linear, premultiplied space as the tile
+//! is read back.
+
+use crate::color::{self, LinSrgb};
+use crate::draw::{self, Draw};
+use crate::frame::video;
+use crate::geom::{Rect, Vector2};
+use crate::wgpu;
+use crate::window::Window;
+
+/// The maximum width and height of each tile in pixels.
+///
+/// This is well within the maximum texture size supported by all backends.
+const TILE_SIZE: u32 = 2048;
+
+impl Draw {
+    /// Render the **Draw**'s inner list of commands to an image of the given size in pixels,
+    /// rendering the image in tiles of at most 2048x2048 pixels via the GPU device and queue of
+    /// the given **Window**.
+    ///
+    /// This allows for exporting drawings at a much higher resolution than the window, e.g. for
+    /// printing, without concern for the maximum texture size supported by the GPU. The window's
+    /// `rect` is scaled uniformly to fit the image, so that the image contains exactly what would
+    /// be drawn to the window if their aspect ratios are equal. Text is rasterized at the scale of
+    /// the image rather than being upscaled. Each tile is rendered with the window's MSAA sample
+    /// count.
+    ///
+    /// As with `to_frame`, the **Draw**'s commands are consumed by the render.
+    ///
+    /// Blocks until all tiles have been rendered and read back from the GPU. If either dimension
+    /// is `0`, an empty image is returned without rendering.
+    pub fn to_image_tiled(
+        &self,
+        window: &Window,
+        [w_px, h_px]: [u32; 2],
+    ) -> Result<image::RgbaImage, wgpu::BufferAsyncErr> {
+        let mut image = image::RgbaImage::new(w_px, h_px);
+        if w_px == 0 || h_px == 0 {
+            return Ok(image);
+        }
+        // Tiles along the right and bottom edges are cropped by `replace`.
+        self.render_tiles(window, [w_px, h_px], TILE_SIZE, |[x, y], tile| {
+            image::imageops::replace(&mut image, tile, x, y);
+        })?;
+        Ok(image)
+    }
+
+    /// Render the **Draw**'s inner list of commands to an image of the given size in pixels,
+    /// supersampled by the given factor.
+    ///
+    /// The drawing is rendered via tiles as for `to_image_tiled` at `samples` times the given size
+    /// along each axis. As each tile is read back, each `samples` x `samples` block of its pixels
+    /// is averaged into a single pixel of the resulting image, so that the full supersampled image
+    /// is never held in memory. Pixels are converted to linear color and premultiplied by their
+    /// alpha before being averaged, so that edges are neither darkened by the sRGB encoding nor by
+    /// transparent pixels. This allows for capturing a frame at an arbitrary resolution and with a
+    /// higher quality of anti-aliasing than that of the window, regardless of the window's size or
+    /// scale factor.
+    ///
+    /// A `samples` factor of `1` is equivalent to `to_image_tiled`.
+    ///
+    /// **Panics** if `samples` is `0`.
+    pub fn to_image_supersampled(
+        &self,
+        window: &Window,
+        [w_px, h_px]: [u32; 2],
+        samples: u32,
+    ) -> Result<image::RgbaImage, wgpu::BufferAsyncErr> {
+        assert!(samples > 0, "the supersampling factor must be at least 1");
+        if samples == 1 {
+            return self.to_image_tiled(window, [w_px, h_px]);
+        }
+        let mut image = image::RgbaImage::new(w_px, h_px);
+        if w_px == 0 || h_px == 0 {
+            return Ok(image);
+        }
+        let max_tile_size = supersampled_tile_size(samples);
+        let size = [w_px * samples, h_px * samples];
+        self.render_tiles(window, size, max_tile_size, |[x, y], tile| {
+            let block_size = [tile.width() / samples, tile.height() / samples];
+            let block = downsample(tile, block_size, samples);
+            image::imageops::replace(&mut image, &block, x / samples, y / samples);
+        })?;
+        Ok(image)
+    }
+
+    // Render the **Draw**'s inner list of commands to an image of the given size in pixels via
+    // tiles of at most `max_tile_size` pixels along each axis, passing the position of each tile
+    // within the image to `read_tile` along with the tile as it is read back.
+    //
+    // Both dimensions must be non-zero.
+    fn render_tiles<F>(
+        &self,
+        window: &Window,
+        [w_px, h_px]: [u32; 2],
+        max_tile_size: u32,
+        mut read_tile: F,
+    ) -> Result<(), wgpu::BufferAsyncErr>
+    where
+        F: FnMut([u32; 2], &image::ImageBuffer<image::Rgba<u8>, wgpu::Rgba8ReadMapping>),
+    {
+        let device = window.swap_chain_device();
+        let queue = window.swap_chain_queue();
+        let rect = window.rect();
+        let scale_factor = (w_px as f32 / rect.w()).min(h_px as f32 / rect.h());
+        let msaa_samples = window.msaa_samples();
+        let tile_size = [max_tile_size.min(w_px), max_tile_size.min(h_px)];
+        let [tile_w, tile_h] = tile_size;
+
+        // Render directly to the capturer's format to avoid a conversion pass per tile.
+        let format = wgpu::TextureCapturer::DST_FORMAT;
+        let resolve_texture = wgpu::TextureBuilder::new()
+            .size(tile_size)
+            .format(format)
+            .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC)
+            .build(device);
+        let resolve_view = resolve_texture.view().build();
+        // Without multisampling there is nothing to resolve, so tiles are rendered directly.
+        let msaa_texture = match msaa_samples {
+            0 | 1 => None,
+            _ => Some(
+                wgpu::TextureBuilder::new()
+                    .size(tile_size)
+                    .format(format)
+                    .sample_count(msaa_samples)
+                    .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+                    .build(device),
+            ),
+        };
+        let msaa_view = msaa_texture.as_ref().map(|texture| texture.view().build());
+        let (attachment, resolve_target) = match msaa_view {
+            None => (&resolve_view, None),
+            Some(ref msaa_view) => (msaa_view, Some(&resolve_view)),
+        };
+        let mut renderer = draw::RendererBuilder::new().build(
+            device,
+            tile_size,
+            scale_factor,
+            msaa_samples.max(1),
+            format,
+        );
+        // Backgrounds are laid out across the whole image rather than across each tile.
+        let full_output_rect =
+            Rect::from_w_h(w_px as f32 / scale_factor, h_px as f32 / scale_factor);
+        renderer.set_full_output_rect(Some(full_output_rect));
+        let capturer = wgpu::TextureCapturer::default();
+
+        // Each tile is rendered from its own copy of the drawing, as rendering drains a **Draw**.
+        let recording = self.take_recording();
+        for y in (0..h_px).step_by(tile_h as usize) {
+            for x in (0..w_px).step_by(tile_w as usize) {
+                let offset = tile_offset([x, y], tile_size, [w_px, h_px], scale_factor);
+                renderer.set_projection_offset(offset);
+
+                let tile_draw = Draw::new();
+                {
+                    let state = self.state.borrow();
+                    let mut tile_state = tile_draw.state.borrow_mut();
+                    tile_state.background_color = state.background_color;
+                    tile_state.background_fill = state.background_fill.clone();
+                }
+                tile_draw.append(recording.clone());
+
+                let cmd_encoder_desc = wgpu::CommandEncoderDescriptor {
+                    label: Some("nannou_draw_to_image_tiled"),
+                };
+                let mut encoder = device.create_command_encoder(&cmd_encoder_desc);
+                renderer.encode_render_pass(
+                    device,
+                    &mut encoder,
+                    &tile_draw,
+                    scale_factor,
+                    tile_size,
+                    attachment,
+                    resolve_target,
+                );
+                let snapshot = capturer.capture(device, &mut encoder, &resolve_texture);
+                queue.submit(&[encoder.finish()]);
+
+                let tile = video::read_snapshot(device, snapshot)?;
+                read_tile([x, y], &*tile);
+            }
+        }
+        Ok(())
+    }
+}
+
+// The center of the tile whose top left corner lies at the given pixel position within the image,
+// in the coordinates of the **Draw**.
+//
+// Tiles along the right and bottom edges may extend beyond the image. Their offset is still that
+// of a full tile, as the whole tile texture is rendered before being cropped.
+fn tile_offset(
+    [x, y]: [u32; 2],
+    [tile_w, tile_h]: [u32; 2],
+    [w_px, h_px]: [u32; 2],
+    scale_factor: f32,
+) -> Vector2 {
+    let offset_x = (x as f32 + tile_w as f32 * 0.5 - w_px as f32 * 0.5) / scale_factor;
+    let offset_y = (h_px as f32 * 0.5 - y as f32 - tile_h as f32 * 0.5) / scale_factor;
+    Vector2::new(offset_x, offset_y)
+}
+
+// The maximum size of each tile of a supersampled image.
+//
+// Tiles must cover whole blocks of samples so that no block spans two tiles.
+fn supersampled_tile_size(samples: u32) -> u32 {
+    (TILE_SIZE - TILE_SIZE % samples).max(samples)
+}
+
+// Average each `samples` x `samples` block of the given sRGBA image into a single pixel.
+//
+// Averaging is performed on linear, alpha-premultiplied colors, after which the result is
+// converted back to non-linear, straight sRGBA.
+fn downsample<I>(full: &I, [w_px, h_px]: [u32; 2], samples: u32) -> image::RgbaImage
+where
+    I: image::GenericImageView<Pixel = image::Rgba<u8>>,
+{
+    let n_samples = (samples * samples) as f32;
+    image::RgbaImage::from_fn(w_px, h_px, |x, y| {
+        let mut sum = [0.0f32; 4];
+        for sy in 0..samples {
+            for sx in 0..samples {
+                let image::Rgba([r, g, b, a]) = full.get_pixel(x * samples + sx, y * samples + sy);
+                let lin = color::Srgb::new(r, g, b).into_format::<f32>().into_linear();
+                let a = a as f32 / 255.0;
+                sum[0] += lin.red * a;
+                sum[1] += lin.green * a;
+                sum[2] += lin.blue * a;
+                sum[3] += a;
+            }
+        }
+        if sum[3] <= 0.0 {
+            return image::Rgba([0; 4]);
+        }
+        // Un-premultiply the average color.
+        let lin = LinSrgb::new(sum[0] / sum[3], sum[1] / sum[3], sum[2] / sum[3]);
+        let (r, g, b) = color::Srgb::from_linear(lin)
+            .into_format::<u8>()
+            .into_components();
+        let a = (sum[3] / n_samples * 255.0).round() as u8;
+        image::Rgba([r, g, b, a])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_offset_single_tile() {
+        // A single tile covering the whole image is centered on the origin.
+        let offset = tile_offset([0, 0], [100, 50], [100, 50], 1.0);
+        assert_eq!(offset, Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_tile_offset_grid() {
+        // A 4x4 image split into 2x2 tiles, with the y axis of the **Draw** pointing upwards.
+        let offset = |pos| tile_offset(pos, [2, 2], [4, 4], 1.0);
+        assert_eq!(offset([0, 0]), Vector2::new(-1.0, 1.0));
+        assert_eq!(offset([2, 0]), Vector2::new(1.0, 1.0));
+        assert_eq!(offset([0, 2]), Vector2::new(-1.0, -1.0));
+        assert_eq!(offset([2, 2]), Vector2::new(1.0, -1.0));
+    }
+
+    #[test]
+    fn test_tile_offset_scale_factor() {
+        // Pixels are mapped to the **Draw**'s coordinates by the scale factor.
+        let offset = tile_offset([0, 0], [2, 2], [4, 4], 2.0);
+        assert_eq!(offset, Vector2::new(-0.5, 0.5));
+    }
+
+    #[test]
+    fn test_tile_offset_partial_edge_tile() {
+        // A 3x1 image split into 2x1 tiles. The second tile extends one pixel beyond the image.
+        let offset = tile_offset([2, 0], [2, 1], [3, 1], 1.0);
+        assert_eq!(offset, Vector2::new(1.5, 0.0));
+    }
+
+    #[test]
+    fn test_supersampled_tile_size() {
+        assert_eq!(supersampled_tile_size(1), TILE_SIZE);
+        assert_eq!(supersampled_tile_size(2), TILE_SIZE);
+        assert_eq!(supersampled_tile_size(3), 2046);
+        assert_eq!(supersampled_tile_size(5000), 5000);
+    }
+
+    #[test]
+    fn test_downsample_linear() {
+        // Black and white average to the sRGB encoding of linear 50% grey, rather than 128.
+        let full = image::RgbaImage::from_fn(2, 2, |x, _| {
+            let c = if x == 0 { 0 } else { 255 };
+            image::Rgba([c, c, c, 255])
+        });
+        let image = downsample(&full, [1, 1], 2);
+        assert_eq!(image.get_pixel(0, 0).0, [188, 188, 188, 255]);
+    }
+
+    #[test]
+    fn test_downsample_premultiplied() {
+        // Fully transparent pixels do not darken the color of the opaque pixels they border.
+        let full = image::RgbaImage::from_fn(2, 2, |x, _| match x {
+            0 => image::Rgba([255, 0, 0, 255]),
+            _ => image::Rgba([0, 0, 0, 0]),
+        });
+        let image = downsample(&full, [1, 1], 2);
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn test_downsample_transparent() {
+        let full = image::RgbaImage::new(2, 2);
+        let image = downsample(&full, [1, 1], 2);
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+    }
+}
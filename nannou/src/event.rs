@@ -120,6 +120,18 @@ pub enum WindowEvent {
     /// The given keyboard key was released.
     KeyReleased(Key),
 
+    /// A character of text was input.
+    ///
+    /// Unlike `KeyPressed`, this describes the text produced by the keyboard layout and any input
+    /// method editor (IME), e.g. accented, Chinese, Japanese or Korean characters composed over
+    /// several key presses. Text committed by an IME arrives as one event per character. Control
+    /// characters such as backspace or return are not emitted, as they are better handled via
+    /// `KeyPressed`. See `Window::set_ime_position_points` for positioning the IME candidate box.
+    ///
+    /// **Note:** The IME's in-progress composition (preedit) text is not yet reported by winit
+    /// and is instead displayed by the IME itself.
+    ReceivedCharacter(char),
+
     /// The mouse moved to the given x, y position.
     MouseMoved(Point2<geom::scalar::Default>),
 
@@ -290,12 +302,18 @@ impl WindowEvent {
                 None => return None,
             },
 
+            winit::event::WindowEvent::ReceivedCharacter(c) => {
+                if c.is_control() {
+                    return None;
+                }
+                ReceivedCharacter(*c)
+            }
+
             winit::event::WindowEvent::ModifiersChanged(_) => {
                 return None;
             }
 
             winit::event::WindowEvent::AxisMotion { .. }
-            | winit::event::WindowEvent::ReceivedCharacter(_)
             | winit::event::WindowEvent::ThemeChanged(_)
             | winit::event::WindowEvent::ScaleFactorChanged { .. } => {
                 return None;
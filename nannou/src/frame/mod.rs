@@ -8,8 +8,10 @@ use std::sync::Mutex;
 use std::time::Duration;
 
 pub mod raw;
+pub mod video;
 
 pub use self::raw::RawFrame;
+pub use self::video::VideoSettings;
 
 /// A **Frame** to which the user can draw graphics before it is presented to the display.
 ///
@@ -40,8 +42,16 @@ pub struct RenderData {
 pub(crate) struct CaptureData {
     // If `Some`, indicates a path to which the current frame should be written.
     pub(crate) next_frame_path: Mutex<Option<PathBuf>>,
+    // If `Some`, indicates a path to which the current frame should be written via the staging
+    // buffers of the `TextureCapturer`.
+    pub(crate) next_staged_frame_path: Mutex<Option<PathBuf>>,
+    // If `Some`, indicates a directory to which debug output for the current frame should be
+    // written.
+    pub(crate) next_debug_dump_dir: Mutex<Option<PathBuf>>,
     // The `TextureCapturer` used to capture the frame.
     pub(crate) texture_capturer: wgpu::TextureCapturer,
+    // If `Some`, each frame is written to the video that is being recorded.
+    pub(crate) video_recorder: Mutex<Option<video::Recorder>>,
 }
 
 /// Intermediary textures used as a target before resolving multisampling and writing to the
@@ -106,6 +116,8 @@ impl<'swap_chain> Frame<'swap_chain> {
 
         // Check to see if the user specified capturing the frame.
         let mut snapshot_capture = None;
+        let mut staged_capture = None;
+        let mut debug_captures = vec![];
         if let Ok(mut guard) = capture_data.next_frame_path.lock() {
             if let Some(path) = guard.take() {
                 let device = raw_frame.device_queue_pair().device();
@@ -118,6 +130,69 @@ impl<'swap_chain> Frame<'swap_chain> {
                 snapshot_capture = Some((path, snapshot));
             }
         }
+        if let Ok(mut guard) = capture_data.next_staged_frame_path.lock() {
+            if let Some(path) = guard.take() {
+                let device = raw_frame.device_queue_pair().device();
+                let mut encoder = raw_frame.command_encoder();
+                let snapshot = capture_data.texture_capturer.capture_staged(
+                    device,
+                    &mut *encoder,
+                    &render_data.intermediary_lin_srgba.texture,
+                );
+                match snapshot {
+                    Some(snapshot) => staged_capture = Some((path, snapshot)),
+                    // TODO: Log errors, don't print to stderr.
+                    None => eprintln!(
+                        "skipped capture of frame to \"{}\" as all staging buffers are in use",
+                        path.display()
+                    ),
+                }
+            }
+        }
+
+        // Check to see if the frame should be written to a video that is being recorded.
+        let mut video_capture = None;
+        if let Ok(mut guard) = capture_data.video_recorder.lock() {
+            if let Some(recorder) = guard.as_mut() {
+                let texture = &render_data.intermediary_lin_srgba.texture;
+                // Frames of a different size than the video (e.g. after a resize) are skipped.
+                if texture.size() == recorder.size() {
+                    let repeats = recorder.frames_due();
+                    if repeats > 0 {
+                        let device = raw_frame.device_queue_pair().device();
+                        let mut encoder = raw_frame.command_encoder();
+                        let snapshot =
+                            capture_data
+                                .texture_capturer
+                                .capture(device, &mut *encoder, texture);
+                        video_capture = Some((snapshot, repeats));
+                    }
+                }
+            }
+        }
+
+        // Check to see if the user requested a debug dump of the frame's textures.
+        if let Ok(mut guard) = capture_data.next_debug_dump_dir.lock() {
+            if let Some(dir) = guard.take() {
+                let device = raw_frame.device_queue_pair().device();
+                let mut encoder = raw_frame.command_encoder();
+                let snapshot = capture_data.texture_capturer.capture(
+                    device,
+                    &mut *encoder,
+                    &render_data.intermediary_lin_srgba.texture,
+                );
+                debug_captures.push((dir.join("intermediary_lin_srgba.png"), snapshot));
+                if let Some((ref msaa_texture, _)) = render_data.intermediary_lin_srgba.msaa_texture
+                {
+                    // The capturer resolves the samples of the MSAA target independently.
+                    let snapshot =
+                        capture_data
+                            .texture_capturer
+                            .capture(device, &mut *encoder, msaa_texture);
+                    debug_captures.push((dir.join("msaa_target.png"), snapshot));
+                }
+            }
+        }
 
         // Convert the linear sRGBA image to the swapchain image.
         //
@@ -133,8 +208,22 @@ impl<'swap_chain> Frame<'swap_chain> {
         // Submit all commands on the device queue.
         raw_frame.submit_inner();
 
-        // If the user did specify capturing the frame, submit the asynchronous read.
-        if let Some((path, snapshot)) = snapshot_capture {
+        // Queue the frame to be written to the video.
+        if let Some((snapshot, repeats)) = video_capture {
+            if let Ok(guard) = capture_data.video_recorder.lock() {
+                if let Some(recorder) = guard.as_ref() {
+                    recorder.submit(snapshot, repeats);
+                }
+            }
+        }
+
+        // If the user did specify capturing the frame or a debug dump, submit the asynchronous
+        // reads.
+        let captures = snapshot_capture
+            .into_iter()
+            .chain(staged_capture)
+            .chain(debug_captures);
+        for (path, snapshot) in captures {
             let result = snapshot.read(move |result| match result {
                 // TODO: Log errors, don't print to stderr.
                 Err(e) => eprintln!("failed to async read captured frame: {:?}", e),
@@ -257,6 +346,15 @@ impl<'swap_chain> Frame<'swap_chain> {
         wgpu::clear_texture(self.texture_view(), color, &mut *self.command_encoder())
     }
 
+    // The directory to which a debug dump of this frame was requested, if any.
+    pub(crate) fn debug_dump_dir(&self) -> Option<PathBuf> {
+        self.capture_data
+            .next_debug_dump_dir
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone())
+    }
+
     /// Submit the frame to the GPU!
     ///
     /// Note that you do not need to call this manually as submission will occur automatically when
@@ -265,7 +363,8 @@ impl<'swap_chain> Frame<'swap_chain> {
     /// Before submission, the frame does the following:
     ///
     /// - If the frame's intermediary linear sRGBA texture is multisampled, resolve it.
-    /// - Write the intermediary linear sRGBA image to the swap chain texture.
+    /// - Write the intermediary linear sRGBA image to the swap chain texture, applying the window's
+    ///   tonemapping curve if any.
     ///
     /// It can sometimes be useful to submit the **Frame** before `view` completes in order to read
     /// the frame's texture back to the CPU (e.g. for screen shots, recordings, etc).
@@ -278,6 +377,9 @@ impl CaptureData {
     pub(crate) fn new(max_jobs: u32, timeout: Option<Duration>) -> Self {
         CaptureData {
             next_frame_path: Default::default(),
+            next_staged_frame_path: Default::default(),
+            video_recorder: Default::default(),
+            next_debug_dump_dir: Default::default(),
             texture_capturer: wgpu::TextureCapturer::new(Some(max_jobs), timeout),
         }
     }
@@ -295,18 +397,20 @@ impl RenderData {
         swap_chain_dims: [u32; 2],
         swap_chain_format: wgpu::TextureFormat,
         msaa_samples: u32,
+        tonemap: wgpu::Tonemap,
     ) -> Self {
         let intermediary_lin_srgba =
             create_intermediary_lin_srgba(device, swap_chain_dims, msaa_samples);
         let src_sample_count = 1;
         let swap_chain_sample_count = 1;
-        let texture_reshaper = wgpu::TextureReshaper::new(
+        let texture_reshaper = wgpu::TextureReshaper::with_tonemap(
             device,
             &intermediary_lin_srgba.texture_view,
             src_sample_count,
             intermediary_lin_srgba.texture_view.component_type(),
             swap_chain_sample_count,
             swap_chain_format,
+            tonemap,
         );
         RenderData {
             intermediary_lin_srgba,
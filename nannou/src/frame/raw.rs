@@ -90,6 +90,21 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         }
     }
 
+    /// Encode a compute pass that dispatches the given pipeline with the given bind groups and
+    /// number of work groups.
+    ///
+    /// The compute pass is submitted along with the rest of the frame's commands, so its results
+    /// are available to any rendering encoded afterwards. See `wgpu::dispatch_compute`.
+    pub fn dispatch_compute(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        bind_groups: &[&wgpu::BindGroup],
+        work_groups: [u32; 3],
+    ) {
+        let mut encoder = self.command_encoder();
+        wgpu::dispatch_compute(&mut encoder, pipeline, bind_groups, work_groups);
+    }
+
     /// The `Id` of the window whose wgpu surface is associated with this frame.
     pub fn window_id(&self) -> window::Id {
         self.window_id
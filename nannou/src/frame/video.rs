@@ -0,0 +1,233 @@
+//! Items related to recording the frames of a window to a video file.
+//!
+//! Frames are captured in the same manner as `Window::capture_frame`, after which their raw RGBA
+//! pixels are piped to an `ffmpeg` subprocess on a dedicated thread.
+
+use crate::wgpu;
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::task::Poll;
+use std::thread;
+use std::time::Instant;
+
+/// Parameters for recording a video via `Window::record_video`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoSettings {
+    /// The number of frames per second of the video.
+    pub fps: f64,
+    /// Whether or not frames are paced by the time at which they are rendered.
+    ///
+    /// If `true`, frames are repeated or dropped as necessary so that the video plays back at the
+    /// same speed as the sketch ran while recording. If `false`, every frame rendered is written
+    /// exactly once, which is useful for sketches that advance by a fixed step each frame.
+    pub real_time: bool,
+    /// The `ffmpeg` executable. By default, `ffmpeg` is expected to be found on the `PATH`.
+    pub ffmpeg: PathBuf,
+    /// Arguments describing the encoding of the output, passed to `ffmpeg` before the output path.
+    ///
+    /// By default, the video is encoded via `libx264` with the `yuv420p` pixel format for broad
+    /// player support. As `yuv420p` requires even dimensions, frames with an odd width or height
+    /// are padded by a single pixel.
+    pub output_args: Vec<String>,
+}
+
+/// Records the frames submitted to a window to a video via an `ffmpeg` subprocess.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    sender: Option<mpsc::SyncSender<Job>>,
+    thread: Option<thread::JoinHandle<io::Result<()>>>,
+    size: [u32; 2],
+    fps: f64,
+    real_time: bool,
+    start: Option<Instant>,
+    frames: u64,
+}
+
+// A captured frame, along with the number of times that it should be written to the video.
+struct Job {
+    snapshot: wgpu::TextureSnapshot,
+    repeats: u64,
+}
+
+// The maximum number of captured frames that may be waiting to be written at once before the
+// render loop blocks.
+const MAX_PENDING_FRAMES: usize = 4;
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        VideoSettings {
+            fps: 60.0,
+            real_time: true,
+            ffmpeg: "ffmpeg".into(),
+            output_args: vec![
+                "-c:v".into(),
+                "libx264".into(),
+                "-pix_fmt".into(),
+                "yuv420p".into(),
+                "-vf".into(),
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2".into(),
+            ],
+        }
+    }
+}
+
+impl Recorder {
+    // Spawn the `ffmpeg` process and the thread that writes frames to it.
+    pub(crate) fn new(
+        device_queue_pair: Arc<wgpu::DeviceQueuePair>,
+        path: &Path,
+        size: [u32; 2],
+        settings: VideoSettings,
+    ) -> io::Result<Self> {
+        let [w, h] = size;
+        let mut child = Command::new(&settings.ffmpeg)
+            .args(&["-hide_banner", "-loglevel", "error"])
+            .args(&["-y", "-f", "rawvideo", "-pix_fmt", "rgba"])
+            .args(&["-s", &format!("{}x{}", w, h)])
+            .args(&["-r", &settings.fps.to_string()])
+            .args(&["-i", "-"])
+            .args(&settings.output_args)
+            .arg(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut stdin = child.stdin.take().expect("failed to open `ffmpeg` stdin");
+
+        // Collect `ffmpeg`'s error output on a thread of its own so that it can never fill the
+        // pipe and block the encoder.
+        let mut stderr = child.stderr.take().expect("failed to open `ffmpeg` stderr");
+        let stderr_thread = thread::Builder::new()
+            .name("nannou_video_recorder_stderr".into())
+            .spawn(move || {
+                let mut output = String::new();
+                stderr.read_to_string(&mut output).ok();
+                output
+            })?;
+
+        let (sender, receiver) = mpsc::sync_channel::<Job>(MAX_PENDING_FRAMES);
+        let thread = thread::Builder::new()
+            .name("nannou_video_recorder".into())
+            .spawn(move || {
+                let device = device_queue_pair.device();
+                let written = (|| -> io::Result<()> {
+                    for Job { snapshot, repeats } in receiver {
+                        let image = read_snapshot(device, snapshot).map_err(|e| {
+                            io::Error::new(io::ErrorKind::Other, format!("{:?}", e))
+                        })?;
+                        let bytes: &[u8] = image.as_raw();
+                        for _ in 0..repeats {
+                            stdin.write_all(bytes)?;
+                        }
+                    }
+                    Ok(())
+                })();
+                std::mem::drop(stdin);
+                let status = child.wait()?;
+                let stderr = stderr_thread.join().unwrap_or_default();
+                if let Err(err) = written {
+                    return Err(ffmpeg_error(err.to_string(), &stderr));
+                }
+                if !status.success() {
+                    let msg = format!("`ffmpeg` exited with {}", status);
+                    return Err(ffmpeg_error(msg, &stderr));
+                }
+                Ok(())
+            })?;
+
+        Ok(Recorder {
+            sender: Some(sender),
+            thread: Some(thread),
+            size,
+            fps: settings.fps,
+            real_time: settings.real_time,
+            start: None,
+            frames: 0,
+        })
+    }
+
+    /// The size of the video's frames in pixels.
+    pub(crate) fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    // The number of times that the frame being submitted should be written to the video.
+    //
+    // Returns `0` if the frame should be dropped.
+    pub(crate) fn frames_due(&mut self) -> u64 {
+        if !self.real_time {
+            self.frames += 1;
+            return 1;
+        }
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let due = (start.elapsed().as_secs_f64() * self.fps) as u64 + 1;
+        let repeats = due.saturating_sub(self.frames);
+        self.frames += repeats;
+        repeats
+    }
+
+    // Queue the captured frame to be written to the video once the frame has been submitted.
+    //
+    // Blocks if the writer thread has fallen too far behind.
+    pub(crate) fn submit(&self, snapshot: wgpu::TextureSnapshot, repeats: u64) {
+        if let Some(sender) = self.sender.as_ref() {
+            // If the writer thread has stopped, its error is reported by `finish`.
+            let _ = sender.send(Job { snapshot, repeats });
+        }
+    }
+
+    /// Wait for all pending frames to be written and for `ffmpeg` to finish encoding the video.
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        self.finish_inner()
+    }
+
+    fn finish_inner(&mut self) -> io::Result<()> {
+        std::mem::drop(self.sender.take());
+        match self.thread.take() {
+            None => Ok(()),
+            Some(thread) => thread
+                .join()
+                .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::Other, "writer panicked"))),
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish_inner() {
+            // TODO: Log errors, don't print to stderr.
+            eprintln!("failed to finish recording video: {}", e);
+        }
+    }
+}
+
+// Map the snapshot for reading, polling the device from this thread so that the mapping does not
+// depend on the render loop.
+pub(crate) fn read_snapshot(
+    device: &wgpu::Device,
+    snapshot: wgpu::TextureSnapshot,
+) -> Result<wgpu::Rgba8AsyncMappedImageBuffer, wgpu::BufferAsyncErr> {
+    let mut read = Box::pin(snapshot.read_async());
+    futures::executor::block_on(futures::future::poll_fn(|cx| {
+        let poll = read.as_mut().poll(cx);
+        if let Poll::Pending = poll {
+            device.poll(wgpu::Maintain::Wait);
+            cx.waker().wake_by_ref();
+        }
+        poll
+    }))
+}
+
+// An error describing the failure of the `ffmpeg` process, including its error output, if any.
+fn ffmpeg_error(msg: String, stderr: &str) -> io::Error {
+    let stderr = stderr.trim();
+    let msg = if stderr.is_empty() {
+        msg
+    } else {
+        format!("{}:\n{}", msg, stderr)
+    };
+    io::Error::new(io::ErrorKind::Other, msg)
+}
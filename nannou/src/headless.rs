@@ -0,0 +1,169 @@
+//! Items related to running an **App** headlessly, without any windows or display.
+//!
+//! See `app::Builder::headless` for details.
+
+use crate::frame::{self, Frame};
+use crate::geom;
+use crate::wgpu;
+use crate::window;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The offscreen render target that takes the place of a window when an **App** is run via
+/// `app::Builder::headless`.
+///
+/// Each **Frame** is drawn to an offscreen texture rather than a swap chain image. As there is no
+/// display, the scale factor is always `1.0`, so points and pixels are equivalent.
+#[derive(Debug)]
+pub struct Headless {
+    id: window::Id,
+    device_queue_pair: Arc<wgpu::DeviceQueuePair>,
+    msaa_samples: u32,
+    // Takes the place of the swap chain image to which each frame is written.
+    texture: wgpu::Texture,
+    pub(crate) texture_view: wgpu::TextureView,
+    pub(crate) frame_data: window::FrameData,
+    pub(crate) frame_count: u64,
+}
+
+impl Headless {
+    /// The format of the texture that takes the place of the swap chain image.
+    pub const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    // Request a device without a compatible surface and create the offscreen render target.
+    //
+    // Returns `None` if no adapter is available.
+    pub(crate) fn new(
+        adapters: &wgpu::AdapterMap,
+        size: [u32; 2],
+        max_capture_frame_jobs: u32,
+        capture_frame_timeout: Option<Duration>,
+    ) -> Option<Self> {
+        let request_adapter_opts = wgpu::RequestAdapterOptions {
+            power_preference: wgpu::DEFAULT_POWER_PREFERENCE,
+            compatible_surface: None,
+        };
+        let adapter = adapters.get_or_request(request_adapter_opts, wgpu::DEFAULT_BACKENDS)?;
+        let device_queue_pair = adapter.get_or_request_device(wgpu::default_device_descriptor());
+
+        let device = device_queue_pair.device();
+        let texture = wgpu::TextureBuilder::new()
+            .size(size)
+            .format(Self::TEXTURE_FORMAT)
+            .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC)
+            .build(device);
+        let texture_view = texture.view().build();
+        let msaa_samples = Frame::DEFAULT_MSAA_SAMPLES;
+        let render = frame::RenderData::new(
+            device,
+            size,
+            Self::TEXTURE_FORMAT,
+            msaa_samples,
+            Default::default(),
+        );
+        let capture = frame::CaptureData::new(max_capture_frame_jobs, capture_frame_timeout);
+        let frame_data = window::FrameData { render, capture };
+
+        // There is no window with which the ID could collide, so winit's dummy ID is used to
+        // identify headless frames.
+        let id = unsafe { winit::window::WindowId::dummy() };
+
+        Some(Headless {
+            id,
+            device_queue_pair,
+            msaa_samples,
+            texture,
+            texture_view,
+            frame_data,
+            frame_count: 0,
+        })
+    }
+
+    /// The ID by which the **Frame**s of the headless render target are identified.
+    ///
+    /// This is also the ID returned by `app.window_id()` while running headlessly, allowing for
+    /// APIs like `app.draw_for` to be used as they would be with a window.
+    pub fn id(&self) -> window::Id {
+        self.id
+    }
+
+    /// The size of the render target in pixels.
+    pub fn size_pixels(&self) -> [u32; 2] {
+        self.texture.size()
+    }
+
+    /// A **Rect** describing the bounds of the render target.
+    ///
+    /// As the scale factor is always `1.0`, this is equal to the size in pixels.
+    pub fn rect(&self) -> geom::Rect {
+        let [w, h] = self.size_pixels();
+        geom::Rect::from_w_h(w as f32, h as f32)
+    }
+
+    /// The number of samples used in the MSAA of each **Frame**.
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    /// The device and queue with which frames are rendered.
+    pub fn device_queue_pair(&self) -> &Arc<wgpu::DeviceQueuePair> {
+        &self.device_queue_pair
+    }
+
+    /// The wgpu logical device with which frames are rendered.
+    pub fn device(&self) -> &wgpu::Device {
+        self.device_queue_pair.device()
+    }
+
+    /// The queue to which frames are submitted.
+    pub fn queue(&self) -> &wgpu::Queue {
+        self.device_queue_pair.queue()
+    }
+
+    /// The number of times `view` has been called with a `Frame`.
+    pub fn elapsed_frames(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Capture the next frame and write it to an image file at the given path, in the same manner
+    /// as `Window::capture_frame`.
+    pub fn capture_frame<P>(&self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let dir = path.parent().expect("capture_frame path has no directory");
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir).expect("failed to create `capture_frame` directory");
+        }
+        let mut capture_next_frame_path = self
+            .frame_data
+            .capture
+            .next_frame_path
+            .lock()
+            .expect("failed to lock `capture_next_frame_path`");
+        *capture_next_frame_path = Some(path.to_path_buf());
+    }
+
+    /// Block and wait for all active capture frame jobs to complete.
+    ///
+    /// This is called implicitly when the **App** exits to ensure any pending captures complete.
+    pub fn await_capture_frame_jobs(
+        &self,
+    ) -> Result<(), wgpu::TextureCapturerAwaitWorkerTimeout<()>> {
+        self.frame_data
+            .capture
+            .texture_capturer
+            .await_active_snapshots(self.device())
+    }
+}
+
+impl Drop for Headless {
+    fn drop(&mut self) {
+        if self.await_capture_frame_jobs().is_err() {
+            // TODO: Replace eprintlns with proper logging.
+            eprintln!("timed out while waiting for capture jobs to complete");
+        }
+    }
+}
@@ -35,6 +35,7 @@ pub mod ease;
 pub mod event;
 pub mod frame;
 pub mod geom;
+pub mod headless;
 pub mod image;
 pub mod io;
 pub mod math;
@@ -43,6 +44,8 @@ pub mod noise;
 pub mod prelude;
 pub mod rand;
 pub mod state;
+#[cfg(feature = "svg")]
+pub mod svg;
 pub mod text;
 pub mod time;
 pub mod ui;
@@ -120,6 +120,18 @@ pub trait Clear: ClearIndices + ClearVertices {
     }
 }
 
+/// Meshes whose **Indices** channel can reserve capacity for additional indices.
+pub trait ReserveIndices {
+    /// Reserve capacity for at least `additional` more indices.
+    fn reserve_indices(&mut self, additional: usize);
+}
+
+/// Meshes whose vertices channels can reserve capacity for additional vertices.
+pub trait ReserveVertices {
+    /// Reserve capacity for at least `additional` more vertices.
+    fn reserve_vertices(&mut self, additional: usize);
+}
+
 /// Meshes that may be extended from a slice of data.
 pub trait ExtendFromSlice<'a> {
     /// The slice type expected via the mesh.
@@ -169,6 +181,13 @@ pub struct WithNormals<M, N> {
     normals: N,
 }
 
+impl<M, I> WithIndices<M, I> {
+    // Mutable access to the indices channel.
+    pub(crate) fn indices_mut(&mut self) -> &mut I {
+        &mut self.indices
+    }
+}
+
 // **GetVertex** implementations.
 
 impl<'a, M, I> GetVertex<I> for &'a M
@@ -1051,6 +1070,106 @@ where
     }
 }
 
+// **ReserveIndices** implementations
+
+impl<'a, M> ReserveIndices for &'a mut M
+where
+    M: ReserveIndices,
+{
+    fn reserve_indices(&mut self, additional: usize) {
+        (**self).reserve_indices(additional);
+    }
+}
+
+impl<M, I> ReserveIndices for WithIndices<M, Vec<I>> {
+    fn reserve_indices(&mut self, additional: usize) {
+        self.indices.reserve(additional);
+    }
+}
+
+impl<M, C> ReserveIndices for WithColors<M, C>
+where
+    M: ReserveIndices,
+{
+    fn reserve_indices(&mut self, additional: usize) {
+        self.mesh.reserve_indices(additional);
+    }
+}
+
+impl<M, T> ReserveIndices for WithTexCoords<M, T>
+where
+    M: ReserveIndices,
+{
+    fn reserve_indices(&mut self, additional: usize) {
+        self.mesh.reserve_indices(additional);
+    }
+}
+
+impl<M, N> ReserveIndices for WithNormals<M, N>
+where
+    M: ReserveIndices,
+{
+    fn reserve_indices(&mut self, additional: usize) {
+        self.mesh.reserve_indices(additional);
+    }
+}
+
+// **ReserveVertices** implementations
+
+impl<'a, M> ReserveVertices for &'a mut M
+where
+    M: ReserveVertices,
+{
+    fn reserve_vertices(&mut self, additional: usize) {
+        (**self).reserve_vertices(additional);
+    }
+}
+
+impl<V> ReserveVertices for MeshPoints<Vec<V>> {
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.points.reserve(additional);
+    }
+}
+
+impl<M, I> ReserveVertices for WithIndices<M, I>
+where
+    M: ReserveVertices,
+{
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.mesh.reserve_vertices(additional);
+    }
+}
+
+impl<M, C> ReserveVertices for WithColors<M, Vec<C>>
+where
+    M: ReserveVertices,
+{
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.mesh.reserve_vertices(additional);
+        self.colors.reserve(additional);
+    }
+}
+
+impl<M, T> ReserveVertices for WithTexCoords<M, Vec<T>>
+where
+    M: ReserveVertices,
+{
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.mesh.reserve_vertices(additional);
+        self.tex_coords.reserve(additional);
+    }
+}
+
+impl<M, N> ReserveVertices for WithNormals<M, Vec<N>>
+where
+    M: ReserveVertices,
+{
+    fn reserve_vertices(&mut self, additional: usize) {
+        self.mesh.reserve_vertices(additional);
+        self.normals.reserve(additional);
+    }
+}
+
 // **ExtendFromSlice** implementations
 
 impl<'a, P> ExtendFromSlice<'a> for MeshPoints<Vec<P>>
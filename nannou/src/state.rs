@@ -1,6 +1,7 @@
-//! Small tracked parts of the application state. Includes **window**, **keys**, **mouse**, and
-//! **time** - each of which are stored in the **App**.
+//! Small tracked parts of the application state. Includes **window**, **keys**, **mouse**,
+//! **files** and **time** - each of which are stored in the **App**.
 
+pub use self::files::Files;
 pub use self::keys::Keys;
 pub use self::mouse::Mouse;
 pub use self::time::Time;
@@ -279,6 +280,55 @@ pub mod mouse {
     }
 }
 
+/// Tracked state related to files dragged and dropped onto the app's windows.
+pub mod files {
+    use crate::window;
+    use std::path::{Path, PathBuf};
+
+    /// The state of files dragged over and dropped onto the app's windows.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Files {
+        /// The files currently being dragged over a window.
+        ///
+        /// Cleared when the files are dropped or are dragged out of the window.
+        pub hovered: Vec<PathBuf>,
+        /// The files dropped since the previous update.
+        ///
+        /// The OS emits a separate `DroppedFile` event for each file when dropping several at once.
+        /// They are collected here so that they may be handled together within `update`, after
+        /// which the list is cleared.
+        pub dropped: Vec<PathBuf>,
+        /// The window over which files were most recently hovered or dropped.
+        pub window: Option<window::Id>,
+    }
+
+    impl Files {
+        /// Whether or not files are currently being dragged over a window.
+        ///
+        /// This is useful for providing visual feedback before the files are dropped.
+        pub fn is_hovering(&self) -> bool {
+            !self.hovered.is_empty()
+        }
+
+        /// The files dropped since the previous update whose extension matches one of the given
+        /// extensions, ignoring case, e.g. `&["png", "jpg", "svg"]`.
+        pub fn dropped_with_extensions<'a>(
+            &'a self,
+            extensions: &'a [&'a str],
+        ) -> impl Iterator<Item = &'a Path> {
+            self.dropped
+                .iter()
+                .map(|path| path.as_path())
+                .filter(move |path| {
+                    path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+                        .unwrap_or(false)
+                })
+        }
+    }
+}
+
 /// Tracked durations related to the App.
 pub mod time {
     /// The state of time tracked by the App.
@@ -288,6 +338,15 @@ pub mod time {
         pub since_start: std::time::Duration,
         /// The duration since the previous update.
         pub since_prev_update: std::time::Duration,
+        /// The time taken by the user's `event` and `update` functions during the most recent
+        /// update.
+        pub update_duration: std::time::Duration,
+        /// The time taken by the most recent call to a `view` function, including the rendering
+        /// of any **Draw**s to the frame and the submission of the frame to the GPU.
+        ///
+        /// This measures the CPU time spent producing the frame, not the time taken by the GPU to
+        /// execute it.
+        pub view_duration: std::time::Duration,
     }
 
     impl Time {
@@ -0,0 +1,282 @@
+//! Loading SVG files as vector geometry that may be drawn via `draw.svg(&svg)`.
+//!
+//! Files are parsed and simplified by [usvg](https://docs.rs/usvg), which resolves styles, shapes
+//! and transforms into plain paths. Each path is converted into lyon path events in nannou's
+//! coordinate space, in which the centre of the SVG's view box lies at the origin and the y axis
+//! points upwards.
+//!
+//! Only solid fills and strokes are supported. Gradient and pattern paints, text and raster images
+//! are skipped.
+
+use crate::color::{self, IntoLinSrgba, LinSrgba};
+use crate::geom;
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::path::PathEvent;
+use lyon::tessellation::{FillOptions, LineCap, LineJoin, StrokeOptions};
+use std::path::Path as FilePath;
+use std::sync::Arc;
+use usvg::NodeExt;
+
+pub use usvg;
+
+/// Vector geometry loaded from an SVG file.
+///
+/// Cloning an **Svg** is cheap, as the underlying paths are shared.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Svg {
+    size: geom::Vector2,
+    paths: Arc<Vec<Path>>,
+}
+
+/// A single path of an **Svg** along with its fill and stroke.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Path {
+    /// The path events describing the path's geometry.
+    pub events: Vec<PathEvent>,
+    /// The color and options with which the path is filled, if at all.
+    pub fill: Option<(LinSrgba, FillOptions)>,
+    /// The color and options with which the path is stroked, if at all.
+    pub stroke: Option<(LinSrgba, StrokeOptions)>,
+}
+
+/// Returned when loading an **Svg** fails.
+#[derive(Debug)]
+pub enum Error {
+    /// Some error occurred while reading the SVG file.
+    Io(std::io::Error),
+    /// Some error occurred while parsing the SVG.
+    Parse(usvg::Error),
+}
+
+impl Svg {
+    /// Load an **Svg** from the file at the given path.
+    pub fn from_path<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<FilePath>,
+    {
+        let data = std::fs::read(path)?;
+        Self::from_data(&data)
+    }
+
+    /// Load an **Svg** from the given SVG data, which may be gzip compressed.
+    pub fn from_data(data: &[u8]) -> Result<Self, Error> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())?;
+        Ok(Self::from_tree(&tree))
+    }
+
+    /// Convert the paths of the given usvg tree into an **Svg**.
+    pub fn from_tree(tree: &usvg::Tree) -> Self {
+        let svg = tree.svg_node();
+        let view_box = svg.view_box.rect;
+        let (vb_x, vb_y) = (view_box.x(), view_box.y());
+        let (vb_w, vb_h) = (view_box.width(), view_box.height());
+
+        // Map view box coordinates into nannou's centred, y-up coordinates, scaled to `size`.
+        let size = geom::vec2(svg.size.width() as f32, svg.size.height() as f32);
+        let scale_x = if vb_w > 0.0 {
+            size.x as f64 / vb_w
+        } else {
+            1.0
+        };
+        let scale_y = if vb_h > 0.0 {
+            size.y as f64 / vb_h
+        } else {
+            1.0
+        };
+        let to_point = |x: f64, y: f64| {
+            let x = (x - vb_x - vb_w / 2.0) * scale_x;
+            let y = (vb_y + vb_h / 2.0 - y) * scale_y;
+            lyon::math::point(x as f32, y as f32)
+        };
+
+        let mut paths = vec![];
+        for node in tree.root().descendants() {
+            let path = match *node.borrow() {
+                usvg::NodeKind::Path(ref path) => path.clone(),
+                _ => continue,
+            };
+            if path.visibility != usvg::Visibility::Visible {
+                continue;
+            }
+            let ts = node.abs_transform();
+            let events = path_events(&path.data, |x, y| {
+                let (x, y) = ts.apply(x, y);
+                to_point(x, y)
+            });
+            if events.is_empty() {
+                continue;
+            }
+
+            // Scale stroke widths by the average scale of the transform.
+            let ts_scale = (ts.a * ts.d - ts.b * ts.c).abs().sqrt();
+            let stroke_scale = ts_scale * (scale_x * scale_y).sqrt();
+            let fill = path.fill.as_ref().and_then(|fill| {
+                let color = paint_color(&fill.paint, fill.opacity.value())?;
+                let rule = match fill.rule {
+                    usvg::FillRule::NonZero => lyon::tessellation::FillRule::NonZero,
+                    usvg::FillRule::EvenOdd => lyon::tessellation::FillRule::EvenOdd,
+                };
+                let opts = FillOptions::default().with_fill_rule(rule);
+                Some((color, opts))
+            });
+            let stroke = path.stroke.as_ref().and_then(|stroke| {
+                let color = paint_color(&stroke.paint, stroke.opacity.value())?;
+                let cap = match stroke.linecap {
+                    usvg::LineCap::Butt => LineCap::Butt,
+                    usvg::LineCap::Round => LineCap::Round,
+                    usvg::LineCap::Square => LineCap::Square,
+                };
+                let join = match stroke.linejoin {
+                    usvg::LineJoin::Miter => LineJoin::Miter,
+                    usvg::LineJoin::Round => LineJoin::Round,
+                    usvg::LineJoin::Bevel => LineJoin::Bevel,
+                };
+                let width = stroke.width.value() * stroke_scale;
+                let opts = StrokeOptions::default()
+                    .with_line_width(width as f32)
+                    .with_line_cap(cap)
+                    .with_line_join(join)
+                    .with_miter_limit(stroke.miterlimit.value() as f32);
+                Some((color, opts))
+            });
+            if fill.is_none() && stroke.is_none() {
+                continue;
+            }
+            paths.push(Path {
+                events,
+                fill,
+                stroke,
+            });
+        }
+
+        let paths = Arc::new(paths);
+        Svg { size, paths }
+    }
+
+    /// The size of the SVG as specified by its `width` and `height` attributes.
+    ///
+    /// This is the size at which the SVG is drawn unless other dimensions are specified.
+    pub fn size(&self) -> geom::Vector2 {
+        self.size
+    }
+
+    /// The paths of the SVG in the order in which they are drawn.
+    pub fn paths(&self) -> &[Path] {
+        &self.paths
+    }
+}
+
+// Convert the given usvg path data into lyon path events, mapping each point via `to_point`.
+fn path_events<F>(data: &usvg::PathData, mut to_point: F) -> Vec<PathEvent>
+where
+    F: FnMut(f64, f64) -> lyon::math::Point,
+{
+    let mut events = vec![];
+    let mut first = None;
+    let mut last = lyon::math::point(0.0, 0.0);
+    for seg in data.iter() {
+        match *seg {
+            usvg::PathSegment::MoveTo { x, y } => {
+                if let Some(first) = first.take() {
+                    events.push(PathEvent::End {
+                        last,
+                        first,
+                        close: false,
+                    });
+                }
+                let at = to_point(x, y);
+                events.push(PathEvent::Begin { at });
+                first = Some(at);
+                last = at;
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                let to = to_point(x, y);
+                events.push(PathEvent::Line { from: last, to });
+                last = to;
+            }
+            usvg::PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                let to = to_point(x, y);
+                events.push(PathEvent::Cubic {
+                    from: last,
+                    ctrl1: to_point(x1, y1),
+                    ctrl2: to_point(x2, y2),
+                    to,
+                });
+                last = to;
+            }
+            usvg::PathSegment::ClosePath => {
+                if let Some(first) = first.take() {
+                    events.push(PathEvent::End {
+                        last,
+                        first,
+                        close: true,
+                    });
+                    last = first;
+                }
+            }
+        }
+    }
+    if let Some(first) = first {
+        events.push(PathEvent::End {
+            last,
+            first,
+            close: false,
+        });
+    }
+    events
+}
+
+// The color of the given paint, if it is a solid color.
+fn paint_color(paint: &usvg::Paint, opacity: f64) -> Option<LinSrgba> {
+    match *paint {
+        usvg::Paint::Color(c) => {
+            let srgba = color::rgba8(c.red, c.green, c.blue, (opacity * 255.0).round() as u8);
+            Some(srgba.into_lin_srgba())
+        }
+        _ => None,
+    }
+}
+
+impl std::str::FromStr for Svg {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::from_data(text.as_bytes())
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<usvg::Error> for Error {
+    fn from(e: usvg::Error) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            Error::Parse(ref e) => Some(e),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            Error::Io(ref e) => std::fmt::Display::fmt(e, f),
+            Error::Parse(ref e) => std::fmt::Display::fmt(e, f),
+        }
+    }
+}
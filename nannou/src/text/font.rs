@@ -1,6 +1,6 @@
 //! The `font::Id` and `font::Map` types.
 
-use crate::text::{Font, FontCollection};
+use crate::text::{Font, FontCollection, GlyphId};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
@@ -40,6 +40,8 @@ pub enum Error {
     Io(std::io::Error),
     /// No `Font`s could be yielded from the `FontCollection`.
     NoFont,
+    /// No font installed on the system matched the requested family name.
+    NotFound(String),
 }
 
 /// The name of the default directory that is searched for fonts.
@@ -152,6 +154,68 @@ pub fn default_notosans() -> Font {
         .expect("the `notosans::REGULAR_TTF` font collection contained no fonts")
 }
 
+/// Load the font installed on the system that best matches the given family name, e.g.
+/// `"Helvetica"`, `"DejaVu Sans"` or one of the generic families `"serif"`, `"sans-serif"` and
+/// `"monospace"`.
+///
+/// This function is only available if the `system_fonts` feature is enabled.
+#[cfg(feature = "system_fonts")]
+pub fn from_family(family: &str) -> Result<Font, Error> {
+    use font_kit::family_name::FamilyName;
+    use font_kit::handle::Handle;
+    use font_kit::properties::Properties;
+    use font_kit::source::SystemSource;
+
+    let family_name = match family {
+        "serif" => FamilyName::Serif,
+        "sans-serif" => FamilyName::SansSerif,
+        "monospace" => FamilyName::Monospace,
+        "cursive" => FamilyName::Cursive,
+        "fantasy" => FamilyName::Fantasy,
+        name => FamilyName::Title(name.to_string()),
+    };
+    let handle = SystemSource::new()
+        .select_best_match(&[family_name], &Properties::new())
+        .map_err(|_| Error::NotFound(family.to_string()))?;
+    let (collection, font_index) = match handle {
+        Handle::Path { path, font_index } => (collection_from_file(path)?, font_index),
+        Handle::Memory { bytes, font_index } => {
+            let collection =
+                FontCollection::from_bytes(bytes.to_vec()).map_err(std::io::Error::from)?;
+            (collection, font_index)
+        }
+    };
+    collection
+        .font_at(font_index as usize)
+        .or(Err(Error::NoFont))
+}
+
+/// The family names of all fonts installed on the system.
+///
+/// This function is only available if the `system_fonts` feature is enabled.
+#[cfg(feature = "system_fonts")]
+pub fn system_families() -> Vec<String> {
+    font_kit::source::SystemSource::new()
+        .all_families()
+        .unwrap_or_default()
+}
+
+/// Whether or not the given font contains a glyph for the given character.
+pub fn has_glyph(font: &Font, ch: char) -> bool {
+    font.glyph(ch).id() != GlyphId(0)
+}
+
+/// Select the first font from the given fallback chain that contains a glyph for the given
+/// character.
+///
+/// Returns `None` if none of the fonts contain a glyph for the character.
+pub fn select<'a, I>(fonts: I, ch: char) -> Option<&'a Font>
+where
+    I: IntoIterator<Item = &'a Font>,
+{
+    fonts.into_iter().find(|font| has_glyph(font, ch))
+}
+
 /// The directory that is searched for default fonts.
 pub fn default_directory(assets: &Path) -> PathBuf {
     assets.join(DEFAULT_DIRECTORY_NAME)
@@ -213,7 +277,7 @@ impl std::error::Error for Error {
     fn cause(&self) -> Option<&dyn std::error::Error> {
         match *self {
             Error::Io(ref e) => Some(e),
-            Error::NoFont => None,
+            Error::NoFont | Error::NotFound(_) => None,
         }
     }
 }
@@ -223,6 +287,9 @@ impl std::fmt::Display for Error {
         match *self {
             Error::Io(ref e) => std::fmt::Display::fmt(e, f),
             Error::NoFont => write!(f, "No `Font` found in the loaded `FontCollection`."),
+            Error::NotFound(ref family) => {
+                write!(f, "No system font found matching the family `{}`.", family)
+            }
         }
     }
 }
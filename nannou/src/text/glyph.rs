@@ -17,6 +17,12 @@ pub struct Rects<'a, 'b> {
     y: Range,
     /// `PositionedGlyphs` yielded by the RustType `LayoutIter`.
     layout: text::LayoutIter<'a, 'b>,
+    /// The characters of the line, used to determine where word spacing applies.
+    chars: std::str::Chars<'b>,
+    /// The spacing inserted between glyphs in addition to that of the `LayoutIter`.
+    spacing: text::line::Spacing,
+    /// The offset along the *x* axis accumulated via `spacing` for the next glyph.
+    offset: Scalar,
 }
 
 /// An iterator that, for every `(line, line_rect)` pair yielded by the given iterator,
@@ -25,6 +31,8 @@ pub struct RectsPerLine<'a, I> {
     lines_with_rects: I,
     font: &'a text::Font,
     font_size: FontSize,
+    spacing: text::line::Spacing,
+    justify: text::Justify,
 }
 
 /// Yields a `Rect` for each selected character in a single line of text.
@@ -59,8 +67,19 @@ struct ContourPathEvents {
 impl<'a, 'b> Iterator for Rects<'a, 'b> {
     type Item = (ScaledGlyph<'a>, Rect);
     fn next(&mut self) -> Option<Self::Item> {
-        let Rects { ref mut layout, y } = *self;
+        let Rects {
+            ref mut layout,
+            ref mut chars,
+            spacing,
+            ref mut offset,
+            y,
+        } = *self;
         layout.next().map(|g| {
+            let x_offset = *offset;
+            *offset += spacing.letter;
+            if chars.next().map(char::is_whitespace).unwrap_or(false) {
+                *offset += spacing.word;
+            }
             let left = g.position().x;
             let (right, height) = g
                 .pixel_bounding_box()
@@ -71,7 +90,7 @@ impl<'a, 'b> Iterator for Rects<'a, 'b> {
                     let h = 0.0;
                     (r, h)
                 });
-            let x = Range::new(left, right);
+            let x = Range::new(left + x_offset, right + x_offset);
             let y = Range::new(y.start, y.start + height);
             let r = Rect { x: x, y: y };
             let g = g.into_unpositioned();
@@ -90,13 +109,26 @@ where
             ref mut lines_with_rects,
             font,
             font_size,
+            spacing,
+            justify,
         } = *self;
         let scale = text::pt_to_scale(font_size);
         lines_with_rects.next().map(|(line, line_rect)| {
             let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
             let point = text::rt::Point { x: x, y: y };
+            let spacing = text::line::justified_spacing(
+                line,
+                font,
+                font_size,
+                spacing,
+                justify,
+                line_rect.w(),
+            );
             Rects {
                 layout: font.layout(line, scale, point),
+                chars: line.chars(),
+                spacing,
+                offset: 0.0,
                 y: line_rect.y,
             }
         })
@@ -192,10 +224,14 @@ impl Iterator for ContourPathEvents {
 ///
 /// This is useful when information about character positioning is needed when reasoning about
 /// text layout.
+///
+/// The given `spacing` and `justify` should match those used to produce the line rects.
 pub fn rects_per_line<'a, I>(
     lines_with_rects: I,
     font: &'a text::Font,
     font_size: FontSize,
+    spacing: text::line::Spacing,
+    justify: text::Justify,
 ) -> RectsPerLine<'a, I>
 where
     I: Iterator<Item = (&'a str, Rect)>,
@@ -204,6 +240,8 @@ where
         lines_with_rects: lines_with_rects,
         font: font,
         font_size: font_size,
+        spacing: spacing,
+        justify: justify,
     }
 }
 
@@ -238,6 +276,8 @@ pub fn selected_rects_per_line<'a, I>(
     lines_with_rects: I,
     font: &'a text::Font,
     font_size: FontSize,
+    spacing: text::line::Spacing,
+    justify: text::Justify,
     start: text::cursor::Index,
     end: text::cursor::Index,
 ) -> SelectedRectsPerLine<'a, I>
@@ -245,7 +285,14 @@ where
     I: Iterator<Item = (&'a str, Rect)>,
 {
     SelectedRectsPerLine {
-        enumerated_rects_per_line: rects_per_line(lines_with_rects, font, font_size).enumerate(),
+        enumerated_rects_per_line: rects_per_line(
+            lines_with_rects,
+            font,
+            font_size,
+            spacing,
+            justify,
+        )
+        .enumerate(),
         start_cursor_idx: start,
         end_cursor_idx: end,
     }
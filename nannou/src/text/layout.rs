@@ -1,16 +1,23 @@
 //! Items related to the styling of text.
 
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::text::{Align, Font, FontSize, Justify, Scalar, Wrap};
 
 /// A context for building a text layout.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Builder {
     pub line_spacing: Option<Scalar>,
     pub line_wrap: Option<Option<Wrap>>,
     pub font_size: Option<FontSize>,
     pub justify: Option<Justify>,
+    #[serde(with = "crate::draw::serialize::optional_resource_override")]
     pub font: Option<Option<Font>>,
     pub y_align: Option<Align>,
+    pub letter_spacing: Option<Scalar>,
+    pub word_spacing: Option<Scalar>,
+    pub max_width: Option<Option<Scalar>>,
+    #[serde(with = "crate::draw::serialize::optional_resources")]
+    pub font_fallback: Option<Vec<Font>>,
 }
 
 /// Properties related to the layout of multi-line text for a single font and font size.
@@ -22,6 +29,10 @@ pub struct Layout {
     pub font_size: FontSize,
     pub font: Option<Font>,
     pub y_align: Align,
+    pub letter_spacing: Scalar,
+    pub word_spacing: Scalar,
+    pub max_width: Option<Scalar>,
+    pub font_fallback: Vec<Font>,
 }
 
 pub const DEFAULT_LINE_WRAP: Option<Wrap> = Some(Wrap::Whitespace);
@@ -29,6 +40,9 @@ pub const DEFAULT_FONT_SIZE: u32 = 12;
 pub const DEFAULT_LINE_SPACING: f32 = 0.0;
 pub const DEFAULT_JUSTIFY: Justify = Justify::Center;
 pub const DEFAULT_Y_ALIGN: Align = Align::Middle;
+pub const DEFAULT_LETTER_SPACING: f32 = 0.0;
+pub const DEFAULT_WORD_SPACING: f32 = 0.0;
+pub const DEFAULT_MAX_WIDTH: Option<f32> = None;
 
 impl Builder {
     /// The font size to use for the text.
@@ -72,6 +86,16 @@ impl Builder {
         self
     }
 
+    /// Specify the fonts to fall back to, in order, for characters that the `Font` has no glyph
+    /// for.
+    pub fn font_fallback<I>(mut self, fonts: I) -> Self
+    where
+        I: IntoIterator<Item = Font>,
+    {
+        self.font_fallback = Some(fonts.into_iter().collect());
+        self
+    }
+
     /// Describe the end along the *x* axis to which the text should be aligned.
     pub fn justify(mut self, justify: Justify) -> Self {
         self.justify = Some(justify);
@@ -93,12 +117,40 @@ impl Builder {
         self.justify(Justify::Right)
     }
 
+    /// Align wrapped lines to both ends of the bounding **Rect**'s *x* axis range.
+    pub fn full_justify(self) -> Self {
+        self.justify(Justify::Full)
+    }
+
     /// Specify how much vertical space should separate each line of text.
     pub fn line_spacing(mut self, spacing: Scalar) -> Self {
         self.line_spacing = Some(spacing);
         self
     }
 
+    /// Specify how much additional horizontal space should separate each character.
+    ///
+    /// Negative values bring characters closer together.
+    pub fn letter_spacing(mut self, spacing: Scalar) -> Self {
+        self.letter_spacing = Some(spacing);
+        self
+    }
+
+    /// Specify how much additional horizontal space should follow each whitespace character.
+    pub fn word_spacing(mut self, spacing: Scalar) -> Self {
+        self.word_spacing = Some(spacing);
+        self
+    }
+
+    /// Specify the maximum width of each line of text.
+    ///
+    /// Lines are wrapped and justified within the smaller of this width and the width of the
+    /// bounding **Rect**. By default, the full width of the bounding **Rect** is used.
+    pub fn max_width(mut self, max_width: Option<Scalar>) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
     /// Specify how the whole text should be aligned along the y axis of its bounding rectangle
     pub fn y_align(mut self, align: Align) -> Self {
         self.y_align = Some(align);
@@ -130,6 +182,10 @@ impl Builder {
             .justify(layout.justify)
             .font_size(layout.font_size)
             .y_align(layout.y_align)
+            .letter_spacing(layout.letter_spacing)
+            .word_spacing(layout.word_spacing)
+            .max_width(layout.max_width)
+            .font_fallback(layout.font_fallback.iter().cloned())
     }
 
     /// Build the text layout.
@@ -141,6 +197,10 @@ impl Builder {
             font_size: self.font_size.unwrap_or(DEFAULT_FONT_SIZE),
             font: self.font.unwrap_or(None),
             y_align: self.y_align.unwrap_or(DEFAULT_Y_ALIGN),
+            letter_spacing: self.letter_spacing.unwrap_or(DEFAULT_LETTER_SPACING),
+            word_spacing: self.word_spacing.unwrap_or(DEFAULT_WORD_SPACING),
+            max_width: self.max_width.unwrap_or(DEFAULT_MAX_WIDTH),
+            font_fallback: self.font_fallback.unwrap_or_default(),
         }
     }
 }
@@ -154,6 +214,10 @@ impl Default for Layout {
             font_size: DEFAULT_FONT_SIZE,
             font: None,
             y_align: DEFAULT_Y_ALIGN,
+            letter_spacing: DEFAULT_LETTER_SPACING,
+            word_spacing: DEFAULT_WORD_SPACING,
+            max_width: DEFAULT_MAX_WIDTH,
+            font_fallback: vec![],
         }
     }
 }
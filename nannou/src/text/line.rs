@@ -47,6 +47,15 @@ pub struct NextBreak {
     pub height: Scalar,
 }
 
+/// Additional horizontal space inserted between the glyphs of a line of text.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Spacing {
+    /// Space added between each pair of adjacent characters.
+    pub letter: Scalar,
+    /// Space added after each whitespace character.
+    pub word: Scalar,
+}
+
 /// Information about a single line of text within a `&str`.
 ///
 /// `Info` is a minimal amount of information that can be stored for efficient reasoning about
@@ -81,6 +90,7 @@ pub struct Infos<'a, F> {
     font: &'a text::Font,
     font_size: FontSize,
     max_width: Scalar,
+    spacing: Spacing,
     next_break_fn: F,
     /// The index that indicates the start of the next line to be yielded.
     start_byte: usize,
@@ -95,6 +105,7 @@ pub struct Infos<'a, F> {
 pub struct Rects<I> {
     infos: I,
     x_align: text::Justify,
+    max_width: Scalar,
     line_spacing: Scalar,
     last_line_top: Scalar,
     font_size: FontSize,
@@ -112,7 +123,7 @@ pub struct SelectedRects<'a, I> {
 
 /// An alias for function pointers that are compatible with the `Block`'s required text
 /// wrapping function.
-pub type NextBreakFnPtr = fn(&str, &text::Font, FontSize, Scalar) -> NextBreak;
+pub type NextBreakFnPtr = fn(&str, &text::Font, FontSize, Scalar, Spacing) -> NextBreak;
 
 impl Break {
     /// Return the index at which the break occurs.
@@ -146,6 +157,7 @@ where
             font: self.font,
             font_size: self.font_size,
             max_width: self.max_width,
+            spacing: self.spacing,
             next_break_fn: self.next_break_fn.clone(),
             start_byte: self.start_byte,
             start_char: self.start_char,
@@ -194,8 +206,17 @@ impl<'a> Infos<'a, NextBreakFnPtr> {
     }
 }
 
+impl<'a, F> Infos<'a, F> {
+    /// Specify the additional spacing to insert between characters and words when measuring the
+    /// width of each line.
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
+
 /// A function for finding the advance width between the given character that also considers
-/// the kerning for some previous glyph.
+/// the kerning and letter spacing for some previous glyph, along with any word spacing.
 ///
 /// This also updates the `last_glyph` with the glyph produced for the given `char`.
 ///
@@ -206,25 +227,31 @@ fn advance_width_and_height(
     ch: char,
     font: &text::Font,
     scale: text::Scale,
+    spacing: Spacing,
     last_glyph: &mut Option<text::GlyphId>,
 ) -> (Scalar, Scalar) {
     let g = font.glyph(ch).scaled(scale);
     let kern = last_glyph
-        .map(|last| font.pair_kerning(scale, last, g.id()))
+        .map(|last| font.pair_kerning(scale, last, g.id()) + spacing.letter)
         .unwrap_or(0.0);
+    let word = if ch.is_whitespace() {
+        spacing.word
+    } else {
+        0.0
+    };
     let advance_width = g.h_metrics().advance_width;
     let height = g
         .exact_bounding_box()
         .map(|bb| bb.min.y.abs() as Scalar)
         .unwrap_or(0.0);
     *last_glyph = Some(g.id());
-    let adv_w = (kern + advance_width) as Scalar;
+    let adv_w = (kern + advance_width + word) as Scalar;
     (adv_w, height)
 }
 
 /// Returns the next index at which the text naturally breaks via a newline character,
 /// along with the width of the line.
-fn next_break(text: &str, font: &text::Font, font_size: FontSize) -> NextBreak {
+fn next_break(text: &str, font: &text::Font, font_size: FontSize, spacing: Spacing) -> NextBreak {
     let scale = text::pt_to_scale(font_size);
     let mut width = 0.0;
     let mut height = 0.0;
@@ -260,7 +287,7 @@ fn next_break(text: &str, font: &text::Font, font_size: FontSize) -> NextBreak {
         }
 
         // Update the width.
-        let (adv_w, h) = advance_width_and_height(ch, font, scale, &mut last_glyph);
+        let (adv_w, h) = advance_width_and_height(ch, font, scale, spacing, &mut last_glyph);
         width += adv_w;
         height = height.max(h);
         char_i += 1;
@@ -286,6 +313,7 @@ fn next_break_by_character(
     font: &text::Font,
     font_size: FontSize,
     max_width: Scalar,
+    spacing: Spacing,
 ) -> NextBreak {
     let scale = text::pt_to_scale(font_size);
     let mut width = 0.0;
@@ -322,7 +350,7 @@ fn next_break_by_character(
         }
 
         // Add the character's width to the width so far.
-        let (adv_w, h) = advance_width_and_height(ch, font, scale, &mut last_glyph);
+        let (adv_w, h) = advance_width_and_height(ch, font, scale, spacing, &mut last_glyph);
         let new_width = width + adv_w;
 
         // Check for a line wrap.
@@ -368,6 +396,7 @@ fn next_break_by_whitespace(
     font: &text::Font,
     font_size: FontSize,
     max_width: Scalar,
+    spacing: Spacing,
 ) -> NextBreak {
     struct Last {
         byte: usize,
@@ -410,7 +439,7 @@ fn next_break_by_whitespace(
         }
 
         // Add the character's width to the width so far.
-        let (adv_w, h) = advance_width_and_height(ch, font, scale, &mut last_glyph);
+        let (adv_w, h) = advance_width_and_height(ch, font, scale, spacing, &mut last_glyph);
         let new_width = width + adv_w;
 
         // Check for a line wrap.
@@ -489,6 +518,61 @@ pub fn width(text: &str, font: &text::Font, font_size: FontSize) -> Scalar {
     total_w as Scalar
 }
 
+/// Produce the width of the given line of text including the given spacing.
+///
+/// This matches the `width` of the line's `Info` when the same `spacing` is used.
+pub fn width_with_spacing(
+    text: &str,
+    font: &text::Font,
+    font_size: FontSize,
+    spacing: Spacing,
+) -> Scalar {
+    next_break(text, font, font_size, spacing).width
+}
+
+/// Stretch the given `spacing` so that the line of text occupies `extra_width` more space.
+///
+/// The extra width is distributed between words, or between characters if the line contains no
+/// whitespace.
+pub fn stretched_spacing(text: &str, spacing: Spacing, extra_width: Scalar) -> Spacing {
+    if extra_width <= 0.0 {
+        return spacing;
+    }
+    let words = text.chars().filter(|ch| ch.is_whitespace()).count();
+    if words > 0 {
+        let word = spacing.word + extra_width / words as Scalar;
+        return Spacing { word, ..spacing };
+    }
+    let gaps = text.chars().count().saturating_sub(1);
+    if gaps > 0 {
+        let letter = spacing.letter + extra_width / gaps as Scalar;
+        return Spacing { letter, ..spacing };
+    }
+    spacing
+}
+
+/// The spacing with which the glyphs of the given line should be laid out within its `line_rect`
+/// width.
+///
+/// This is the given `spacing` unless the line is fully justified, in which case it is stretched
+/// to fill the width of the line's rect.
+pub fn justified_spacing(
+    text: &str,
+    font: &text::Font,
+    font_size: FontSize,
+    spacing: Spacing,
+    justify: text::Justify,
+    line_width: Scalar,
+) -> Spacing {
+    match justify {
+        text::Justify::Full => {
+            let natural_width = width_with_spacing(text, font, font_size, spacing);
+            stretched_spacing(text, spacing, line_width - natural_width)
+        }
+        _ => spacing,
+    }
+}
+
 /// Produce an `Infos` iterator wrapped by the given `next_break_fn`.
 pub fn infos_wrapped_by<'a, F>(
     text: &'a str,
@@ -498,13 +582,14 @@ pub fn infos_wrapped_by<'a, F>(
     next_break_fn: F,
 ) -> Infos<'a, F>
 where
-    F: for<'b> FnMut(&'b str, &'b text::Font, FontSize, Scalar) -> NextBreak,
+    F: for<'b> FnMut(&'b str, &'b text::Font, FontSize, Scalar, Spacing) -> NextBreak,
 {
     Infos {
         text: text,
         font: font,
         font_size: font_size,
         max_width: max_width,
+        spacing: Spacing::default(),
         next_break_fn: next_break_fn,
         start_byte: 0,
         start_char: 0,
@@ -526,8 +611,9 @@ pub fn infos<'a>(
         font: &text::Font,
         font_size: FontSize,
         _max_width: Scalar,
+        spacing: Spacing,
     ) -> NextBreak {
-        next_break(text, font, font_size)
+        next_break(text, font, font_size, spacing)
     }
 
     infos_wrapped_by(text, font, font_size, std::f32::MAX, no_wrap)
@@ -568,11 +654,7 @@ where
         // Calculate the `x` `Range` of the first line `Rect`.
         let x_bounds = Range::new(0.0, max_width);
         let range = Range::new(0.0, first_info.width);
-        let x = match x_align {
-            text::Justify::Left => range.align_start_of(x_bounds),
-            text::Justify::Center => range.align_middle_of(x_bounds),
-            text::Justify::Right => range.align_end_of(x_bounds),
-        };
+        let x = align_line(first_info, range, x_bounds, x_align);
         let y_start = -(font_size as Scalar);
         //let y_end = y_start + first_info.height;
         let y_end = y_start + font_size as Scalar;
@@ -583,12 +665,29 @@ where
         infos: infos,
         next: first_rect,
         x_align: x_align,
+        max_width: max_width,
         last_line_top: 0.0,
         font_size: font_size,
         line_spacing: line_spacing,
     }
 }
 
+// Align the `x` `Range` of a line within the given bounds.
+//
+// Fully justified lines that were wrapped are stretched to fill the bounds, while all other fully
+// justified lines are aligned to the start.
+fn align_line(info: Info, range: Range, x_bounds: Range, x_align: text::Justify) -> Range {
+    match x_align {
+        text::Justify::Left => range.align_start_of(x_bounds),
+        text::Justify::Center => range.align_middle_of(x_bounds),
+        text::Justify::Right => range.align_end_of(x_bounds),
+        text::Justify::Full => match info.end_break {
+            Break::Wrap { .. } => x_bounds,
+            _ => range.align_start_of(x_bounds),
+        },
+    }
+}
+
 /// Produces an iterator yielding a `Rect` for the selected range in each selected line in a block
 /// of text.
 ///
@@ -599,6 +698,8 @@ pub fn selected_rects<'a, I>(
     lines_with_rects: I,
     font: &'a text::Font,
     font_size: FontSize,
+    spacing: Spacing,
+    justify: text::Justify,
     start: text::cursor::Index,
     end: text::cursor::Index,
 ) -> SelectedRects<'a, I>
@@ -610,6 +711,8 @@ where
             lines_with_rects,
             font,
             font_size,
+            spacing,
+            justify,
             start,
             end,
         ),
@@ -618,7 +721,7 @@ where
 
 impl<'a, F> Iterator for Infos<'a, F>
 where
-    F: for<'b> FnMut(&'b str, &'b text::Font, FontSize, Scalar) -> NextBreak,
+    F: for<'b> FnMut(&'b str, &'b text::Font, FontSize, Scalar, Spacing) -> NextBreak,
 {
     type Item = Info;
     fn next(&mut self) -> Option<Self::Item> {
@@ -627,13 +730,14 @@ where
             font,
             font_size,
             max_width,
+            spacing,
             ref mut next_break_fn,
             ref mut start_byte,
             ref mut start_char,
             ref mut last_break,
         } = *self;
 
-        let next = next_break_fn(&text[*start_byte..], font, font_size, max_width);
+        let next = next_break_fn(&text[*start_byte..], font, font_size, max_width, spacing);
         match next.break_ {
             Break::Newline { .. } | Break::Wrap { .. } => {
                 let next_break = match next.break_ {
@@ -734,6 +838,7 @@ where
             ref mut next,
             ref mut infos,
             x_align,
+            max_width,
             ref mut last_line_top,
             font_size,
             line_spacing,
@@ -751,11 +856,11 @@ where
 
                 let x = {
                     let range = Range::new(0.0, info.width);
-                    match x_align {
-                        text::Justify::Left => range.align_start_of(line_rect.x),
-                        text::Justify::Center => range.align_middle_of(line_rect.x),
-                        text::Justify::Right => range.align_end_of(line_rect.x),
-                    }
+                    let x_bounds = match x_align {
+                        text::Justify::Full => Range::new(0.0, max_width),
+                        _ => line_rect.x,
+                    };
+                    align_line(info, range, x_bounds, x_align)
                 };
 
                 Rect { x: x, y: y }
@@ -8,6 +8,7 @@ pub mod font;
 pub mod glyph;
 pub mod layout;
 pub mod line;
+pub mod rich;
 pub mod rt {
     //! Re-exported RustType geometric types.
     pub use rusttype::{gpu_cache, point, vector, Point, Rect, Vector};
@@ -19,6 +20,8 @@ pub use rusttype::gpu_cache::Cache as GlyphCache;
 pub use rusttype::{Glyph, GlyphId, GlyphIter, LayoutIter, Scale, ScaledGlyph};
 
 use crate::geom;
+use crate::serde_derive::{Deserialize, Serialize};
+use lyon::path::PathEvent;
 use std::borrow::Cow;
 
 /// The RustType `FontCollection` type used by nannou.
@@ -94,7 +97,7 @@ pub type TextGlyphs<'a> = std::iter::FlatMap<
 >;
 
 /// Alignment along an axis.
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Align {
     Start,
     Middle,
@@ -102,7 +105,7 @@ pub enum Align {
 }
 
 /// A type used for referring to typographic alignment of `Text`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Justify {
     /// Align text to the start of the bounding `Rect`'s *x* axis.
     Left,
@@ -110,14 +113,15 @@ pub enum Justify {
     Center,
     /// Align text to the end of the bounding `Rect`'s *x* axis.
     Right,
-    // /// Align wrapped text to both the start and end of the bounding `Rect`s *x* axis.
-    // ///
-    // /// Extra space is added between words in order to achieve this alignment.
-    // TODO: Full,
+    /// Align wrapped text to both the start and end of the bounding `Rect`s *x* axis.
+    ///
+    /// Extra space is added between words in order to achieve this alignment. Lines that end via
+    /// a newline or the end of the text are aligned to the start.
+    Full,
 }
 
 /// The way in which text should wrap around the width.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Wrap {
     /// Wrap at the first character that exceeds the width.
     Character,
@@ -197,6 +201,18 @@ impl<'a> Builder<'a> {
         self.map_layout(|l| l.font(font))
     }
 
+    /// Specify the fonts to fall back to, in order, for characters that the `Font` has no glyph
+    /// for.
+    ///
+    /// Note that **Text** is always laid out with a single font. Fallback fonts are applied by
+    /// `rich::Text` and the `draw.text()` API.
+    pub fn font_fallback<I>(self, fonts: I) -> Self
+    where
+        I: IntoIterator<Item = Font>,
+    {
+        self.map_layout(|l| l.font_fallback(fonts))
+    }
+
     /// Describe the end along the *x* axis to which the text should be aligned.
     pub fn justify(self, justify: Justify) -> Self {
         self.map_layout(|l| l.justify(justify))
@@ -217,11 +233,31 @@ impl<'a> Builder<'a> {
         self.map_layout(|l| l.right_justify())
     }
 
+    /// Align wrapped lines to both ends of the bounding **Rect**'s *x* axis range.
+    pub fn full_justify(self) -> Self {
+        self.map_layout(|l| l.full_justify())
+    }
+
     /// Specify how much vertical space should separate each line of text.
     pub fn line_spacing(self, spacing: Scalar) -> Self {
         self.map_layout(|l| l.line_spacing(spacing))
     }
 
+    /// Specify how much additional horizontal space should separate each character.
+    pub fn letter_spacing(self, spacing: Scalar) -> Self {
+        self.map_layout(|l| l.letter_spacing(spacing))
+    }
+
+    /// Specify how much additional horizontal space should follow each whitespace character.
+    pub fn word_spacing(self, spacing: Scalar) -> Self {
+        self.map_layout(|l| l.word_spacing(spacing))
+    }
+
+    /// Specify the maximum width of each line of text.
+    pub fn max_width(self, max_width: Option<Scalar>) -> Self {
+        self.map_layout(|l| l.max_width(max_width))
+    }
+
     /// Specify how the whole text should be aligned along the y axis of its bounding rectangle
     pub fn y_align(self, align: Align) -> Self {
         self.map_layout(|l| l.y_align(align))
@@ -259,19 +295,12 @@ impl<'a> Builder<'a> {
     pub fn build(self, rect: geom::Rect) -> Text<'a> {
         let text = self.text;
         let layout = self.layout_builder.build();
-        #[allow(unreachable_code)]
-        let font = layout.font.clone().unwrap_or_else(|| {
-            #[cfg(feature = "notosans")]
-            {
-                return font::default_notosans();
-            }
-            let assets = crate::app::find_assets_path()
-                .expect("failed to detect the assets directory when searching for a default font");
-            font::default(&assets).expect("failed to detect a default font")
-        });
-        let max_width = rect.w();
+        let font = layout.font.clone().unwrap_or_else(default_font);
+        let max_width = line_width(&layout, rect);
+        let spacing = layout_spacing(&layout);
         let line_infos =
             line::infos_maybe_wrapped(&text, &font, layout.font_size, layout.line_wrap, max_width)
+                .spacing(spacing)
                 .collect();
         Text {
             text,
@@ -346,10 +375,22 @@ impl<'a> Text<'a> {
     }
 
     /// The width of the widest line of text.
+    ///
+    /// This includes the space added between the words of fully justified lines.
     pub fn width(&self) -> Scalar {
-        self.line_infos
-            .iter()
-            .fold(0.0, |max, info| max.max(info.width))
+        self.line_rects().fold(0.0, |max, rect| max.max(rect.w()))
+    }
+
+    /// The width within which each line is wrapped and justified.
+    ///
+    /// This is the smaller of the layout's `max_width` and the width of the layout rect.
+    pub fn line_width(&self) -> Scalar {
+        line_width(&self.layout, self.rect)
+    }
+
+    /// The spacing inserted between the characters and words of each line.
+    pub fn spacing(&self) -> line::Spacing {
+        layout_spacing(&self.layout)
     }
 
     /// The exact height of the full text accounting for font size and line spacing..
@@ -393,7 +434,7 @@ impl<'a> Text<'a> {
         let line_rects = line::rects(
             self.line_infos.iter().cloned(),
             self.layout.font_size,
-            self.rect.w(),
+            self.line_width(),
             self.layout.justify,
             self.layout.line_spacing,
         );
@@ -408,7 +449,13 @@ impl<'a> Text<'a> {
     /// Produce an iterator yielding iterators yielding every glyph alongside its bounding rect for
     /// each line.
     pub fn glyphs_per_line(&self) -> TextGlyphsPerLine {
-        glyph::rects_per_line(self.lines_with_rects(), &self.font, self.layout.font_size)
+        glyph::rects_per_line(
+            self.lines_with_rects(),
+            &self.font,
+            self.layout.font_size,
+            self.spacing(),
+            self.layout.justify,
+        )
     }
 
     /// Produce an iterator yielding every glyph alongside its bounding rect.
@@ -419,56 +466,7 @@ impl<'a> Text<'a> {
     }
 
     /// Produce an iterator yielding the path events for every glyph in every line.
-    pub fn path_events<'b>(&'b self) -> impl 'b + Iterator<Item = lyon::path::PathEvent> {
-        use lyon::path::PathEvent;
-
-        // Translate the given lyon point by the given vector.
-        fn trans_lyon_point(p: &lyon::math::Point, v: geom::Vector2) -> lyon::math::Point {
-            lyon::math::point(p.x + v.x, p.y + v.y)
-        }
-
-        // Translate the given path event in 2D space.
-        fn trans_path_event(e: &PathEvent, v: geom::Vector2) -> PathEvent {
-            match *e {
-                PathEvent::Begin { ref at } => PathEvent::Begin {
-                    at: trans_lyon_point(at, v),
-                },
-                PathEvent::Line { ref from, ref to } => PathEvent::Line {
-                    from: trans_lyon_point(from, v),
-                    to: trans_lyon_point(to, v),
-                },
-                PathEvent::Quadratic {
-                    ref from,
-                    ref ctrl,
-                    ref to,
-                } => PathEvent::Quadratic {
-                    from: trans_lyon_point(from, v),
-                    ctrl: trans_lyon_point(ctrl, v),
-                    to: trans_lyon_point(to, v),
-                },
-                PathEvent::Cubic {
-                    ref from,
-                    ref ctrl1,
-                    ref ctrl2,
-                    ref to,
-                } => PathEvent::Cubic {
-                    from: trans_lyon_point(from, v),
-                    ctrl1: trans_lyon_point(ctrl1, v),
-                    ctrl2: trans_lyon_point(ctrl2, v),
-                    to: trans_lyon_point(to, v),
-                },
-                PathEvent::End {
-                    ref last,
-                    ref first,
-                    ref close,
-                } => PathEvent::End {
-                    last: trans_lyon_point(last, v),
-                    first: trans_lyon_point(first, v),
-                    close: *close,
-                },
-            }
-        }
-
+    pub fn path_events<'b>(&'b self) -> impl 'b + Iterator<Item = PathEvent> {
         self.glyphs().flat_map(|(g, r)| {
             glyph::path_events(g)
                 .into_iter()
@@ -477,6 +475,21 @@ impl<'a> Text<'a> {
         })
     }
 
+    /// Produce a path describing the outline of each glyph in every line.
+    ///
+    /// Glyphs without an outline, such as whitespace, are skipped.
+    pub fn glyph_outlines(&self) -> Vec<geom::Path> {
+        self.glyphs()
+            .filter_map(|(g, r)| {
+                let offset = r.bottom_left();
+                glyph::path_events(g).map(|events| {
+                    let events = events.map(|e| trans_path_event(&e, offset));
+                    events_to_path(events)
+                })
+            })
+            .collect()
+    }
+
     /// Produce an iterator yielding positioned rusttype glyphs ready for caching.
     ///
     /// The window dimensions (in logical space) and scale_factor are required to transform glyph
@@ -491,6 +504,8 @@ impl<'a> Text<'a> {
             self.lines_with_rects(),
             &self.font,
             self.layout.font_size,
+            self.spacing(),
+            self.layout.justify,
             window_size,
             scale_factor,
         )
@@ -516,13 +531,21 @@ impl<'a> Text<'a> {
     }
 
     fn position_offset(&self) -> geom::Vector2 {
-        position_offset(
+        let mut offset = position_offset(
             self.num_lines(),
             self.layout.font_size,
             self.layout.line_spacing,
             self.rect,
             self.layout.y_align,
-        )
+        );
+        // Align the block of lines within the rect when narrower due to the `max_width`.
+        let spare_width = self.rect.w() - self.line_width();
+        offset.x += match self.layout.justify {
+            Justify::Left | Justify::Full => 0.0,
+            Justify::Center => spare_width / 2.0,
+            Justify::Right => spare_width,
+        };
+        offset
     }
 }
 
@@ -620,11 +643,14 @@ pub fn position_offset(
 
 /// Produce the position of each glyph ready for the rusttype glyph cache.
 ///
-/// Window dimensions are expected in logical coordinates.
+/// Window dimensions are expected in logical coordinates. The given `spacing` and `justify`
+/// should match those used to produce the line rects.
 pub fn rt_positioned_glyphs<'a, I>(
     lines_with_rects: I,
     font: &'a Font,
     font_size: FontSize,
+    spacing: line::Spacing,
+    justify: Justify,
     window_size: geom::Vector2,
     scale_factor: Scalar,
 ) -> impl 'a + Iterator<Item = PositionedGlyph>
@@ -646,10 +672,54 @@ where
                 trans_y(line_rect.bottom()) as f32,
             );
             let point = rt::Point { x: x, y: y };
-            font.layout(line, scale, point).map(|g| g.standalone())
+            let spacing =
+                line::justified_spacing(line, font, font_size, spacing, justify, line_rect.w());
+            let letter_spacing = spacing.letter * scale_factor;
+            let word_spacing = spacing.word * scale_factor;
+            let mut offset = 0.0;
+            font.layout(line, scale, point)
+                .zip(line.chars())
+                .map(move |(g, ch)| {
+                    let position = g.position();
+                    let x_offset = offset;
+                    offset += letter_spacing;
+                    if ch.is_whitespace() {
+                        offset += word_spacing;
+                    }
+                    let point = rt::point(position.x + x_offset, position.y);
+                    g.into_unpositioned().positioned(point).standalone()
+                })
         })
 }
 
+// The font used by text whose layout does not specify one.
+#[allow(unreachable_code)]
+fn default_font() -> Font {
+    #[cfg(feature = "notosans")]
+    {
+        return font::default_notosans();
+    }
+    let assets = crate::app::find_assets_path()
+        .expect("failed to detect the assets directory when searching for a default font");
+    font::default(&assets).expect("failed to detect a default font")
+}
+
+// The width within which lines of text are wrapped and justified for the given layout.
+fn line_width(layout: &Layout, rect: geom::Rect) -> Scalar {
+    match layout.max_width {
+        Some(max_width) => max_width.max(0.0).min(rect.w()),
+        None => rect.w(),
+    }
+}
+
+// The spacing between characters and words described by the given layout.
+fn layout_spacing(layout: &Layout) -> line::Spacing {
+    line::Spacing {
+        letter: layout.letter_spacing,
+        word: layout.word_spacing,
+    }
+}
+
 /// Converts the given font size in "points" to its font size in pixels.
 /// This is useful for when the font size is not an integer.
 pub fn f32_pt_to_px(font_size_in_points: f32) -> f32 {
@@ -676,3 +746,89 @@ pub fn pt_to_scale(font_size_in_points: FontSize) -> Scale {
 pub fn text(s: &str) -> Builder {
     Builder::from(s)
 }
+
+/// Produce a path describing the outline of each glyph in the given text, so that letterforms may
+/// be stroked, tessellated or plotted as geometry.
+///
+/// Lines are broken at newline characters only, left-aligned and stacked downwards from the
+/// origin, which marks the top-left of the first line. Glyphs without an outline, such as
+/// whitespace, are skipped.
+pub fn glyph_outlines(text: &str, font: &Font, font_size: FontSize) -> Vec<geom::Path> {
+    self::text(text)
+        .font(font.clone())
+        .font_size(font_size)
+        .no_line_wrap()
+        .left_justify()
+        .align_top()
+        .build(geom::Rect::from_w_h(0.0, 0.0))
+        .glyph_outlines()
+}
+
+// Translate the given lyon point by the given vector.
+fn trans_lyon_point(p: &lyon::math::Point, v: geom::Vector2) -> lyon::math::Point {
+    lyon::math::point(p.x + v.x, p.y + v.y)
+}
+
+// Translate the given path event in 2D space.
+fn trans_path_event(e: &PathEvent, v: geom::Vector2) -> PathEvent {
+    match *e {
+        PathEvent::Begin { ref at } => PathEvent::Begin {
+            at: trans_lyon_point(at, v),
+        },
+        PathEvent::Line { ref from, ref to } => PathEvent::Line {
+            from: trans_lyon_point(from, v),
+            to: trans_lyon_point(to, v),
+        },
+        PathEvent::Quadratic {
+            ref from,
+            ref ctrl,
+            ref to,
+        } => PathEvent::Quadratic {
+            from: trans_lyon_point(from, v),
+            ctrl: trans_lyon_point(ctrl, v),
+            to: trans_lyon_point(to, v),
+        },
+        PathEvent::Cubic {
+            ref from,
+            ref ctrl1,
+            ref ctrl2,
+            ref to,
+        } => PathEvent::Cubic {
+            from: trans_lyon_point(from, v),
+            ctrl1: trans_lyon_point(ctrl1, v),
+            ctrl2: trans_lyon_point(ctrl2, v),
+            to: trans_lyon_point(to, v),
+        },
+        PathEvent::End {
+            ref last,
+            ref first,
+            ref close,
+        } => PathEvent::End {
+            last: trans_lyon_point(last, v),
+            first: trans_lyon_point(first, v),
+            close: *close,
+        },
+    }
+}
+
+// Collect the given path events into a path.
+fn events_to_path<I>(events: I) -> geom::Path
+where
+    I: IntoIterator<Item = PathEvent>,
+{
+    let pt = |p: lyon::math::Point| geom::pt2(p.x, p.y);
+    let mut builder = geom::path();
+    for event in events {
+        builder = match event {
+            PathEvent::Begin { at } => builder.move_to(pt(at)),
+            PathEvent::Line { to, .. } => builder.line_to(pt(to)),
+            PathEvent::Quadratic { ctrl, to, .. } => builder.quadratic_bezier_to(pt(ctrl), pt(to)),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.cubic_bezier_to(pt(ctrl1), pt(ctrl2), pt(to)),
+            PathEvent::End { close: true, .. } => builder.close(),
+            PathEvent::End { .. } => builder,
+        };
+    }
+    builder.build()
+}
@@ -0,0 +1,362 @@
+//! Layout of rich text, made up of spans that may each use a different font and font size.
+//!
+//! Rich text is laid out according to the same **Layout** parameters as plain text. The height of
+//! each line is determined by the largest font size within that line, so that spans of different
+//! sizes share a common baseline without overlapping the lines above or below.
+//!
+//! Characters for which a span's font has no glyph are drawn with the first font of the layout's
+//! `font_fallback` list that does.
+
+use crate::geom::{self, Range, Rect};
+use crate::text::{self, Align, Font, FontSize, Justify, Layout, Scalar, Wrap};
+
+/// A run of text that shares a single font and font size.
+///
+/// Spans without a font or font size fall back to those of the **Layout**.
+#[derive(Clone, Debug)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub font: Option<&'a Font>,
+    pub font_size: Option<FontSize>,
+}
+
+/// A single glyph laid out as a part of some rich **Text**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Glyph {
+    /// The index of the span to which the glyph belongs.
+    pub span: usize,
+    /// The index of the glyph's font within `Text::fonts`.
+    ///
+    /// This is the font of the glyph's span unless it was substituted by a fallback font.
+    pub font: usize,
+    /// The character represented by the glyph.
+    pub ch: char,
+    /// The font size of the span to which the glyph belongs.
+    pub font_size: FontSize,
+    /// The position of the glyph's origin, which lies on the baseline of its line.
+    pub position: geom::Point2,
+}
+
+/// Some rich text laid out within a rect.
+#[derive(Clone)]
+pub struct Text {
+    fonts: Vec<Font>,
+    glyphs: Vec<Glyph>,
+    line_rects: Vec<Rect>,
+}
+
+// A single character alongside the metrics used to lay it out.
+struct Item {
+    span: usize,
+    font: usize,
+    ch: char,
+    font_size: FontSize,
+    newline: bool,
+    // The space between this character and the previous one, including kerning and letter spacing.
+    kern: Scalar,
+    // The advance width of the character, including word spacing.
+    advance: Scalar,
+}
+
+// A single line of items.
+struct Line {
+    items: std::ops::Range<usize>,
+    width: Scalar,
+    height: Scalar,
+    wrapped: bool,
+}
+
+impl Text {
+    /// Lay out the given spans within the given `rect`.
+    ///
+    /// Spans are laid out one after the other as though they were a single string, wrapping and
+    /// justifying lines as described by the `layout`.
+    pub fn new(spans: &[Span], layout: &Layout, rect: Rect) -> Self {
+        let fonts: Vec<Font> = spans
+            .iter()
+            .map(|span| match span.font.or(layout.font.as_ref()) {
+                Some(font) => font.clone(),
+                None => text::default_font(),
+            })
+            .chain(layout.font_fallback.iter().cloned())
+            .collect();
+        let items = items(spans, &fonts, layout);
+        let max_width = text::line_width(layout, rect);
+        let lines = lines(&items, layout.line_wrap, max_width, layout.font_size);
+
+        // Align the block of lines within the rect along the y axis.
+        let total_height = lines.iter().map(|line| line.height).sum::<Scalar>()
+            + lines.len().saturating_sub(1) as Scalar * layout.line_spacing;
+        let total_y = Range::new(0.0, total_height);
+        let total_y = match layout.y_align {
+            Align::Start => total_y.align_start_of(rect.y),
+            Align::Middle => total_y.align_middle_of(rect.y),
+            Align::End => total_y.align_end_of(rect.y),
+        };
+
+        // Align the block of lines within the rect when narrower due to the `max_width`.
+        let spare_width = rect.w() - max_width;
+        let left = rect.left()
+            + match layout.justify {
+                Justify::Left | Justify::Full => 0.0,
+                Justify::Center => spare_width / 2.0,
+                Justify::Right => spare_width,
+            };
+
+        let mut glyphs = vec![];
+        let mut line_rects = vec![];
+        let mut top = total_y.end;
+        let x_bounds = Range::new(0.0, max_width);
+        for line in lines {
+            let bottom = top - line.height;
+            let range = Range::new(0.0, line.width);
+            let x = match layout.justify {
+                Justify::Left => range.align_start_of(x_bounds),
+                Justify::Center => range.align_middle_of(x_bounds),
+                Justify::Right => range.align_end_of(x_bounds),
+                Justify::Full if line.wrapped => x_bounds,
+                Justify::Full => range.align_start_of(x_bounds),
+            };
+
+            // Distribute the space added by full justification between words or characters.
+            let line_items = &items[line.items.clone()];
+            let extra_width = x.len() - line.width;
+            let (extra_letter, extra_word) = if extra_width > 0.0 {
+                let words = line_items.iter().filter(|it| it.ch.is_whitespace()).count();
+                let gaps = line_items.len().saturating_sub(1);
+                if words > 0 {
+                    (0.0, extra_width / words as Scalar)
+                } else if gaps > 0 {
+                    (extra_width / gaps as Scalar, 0.0)
+                } else {
+                    (0.0, 0.0)
+                }
+            } else {
+                (0.0, 0.0)
+            };
+
+            let mut pos_x = left + x.start;
+            for (i, item) in line_items.iter().enumerate() {
+                if i > 0 {
+                    pos_x += item.kern + extra_letter;
+                }
+                glyphs.push(Glyph {
+                    span: item.span,
+                    font: item.font,
+                    ch: item.ch,
+                    font_size: item.font_size,
+                    position: geom::pt2(pos_x, bottom),
+                });
+                pos_x += item.advance;
+                if item.ch.is_whitespace() {
+                    pos_x += extra_word;
+                }
+            }
+
+            let x = Range::new(left + x.start, left + x.end);
+            let y = Range::new(bottom, top);
+            line_rects.push(Rect { x, y });
+            top = bottom - layout.line_spacing;
+        }
+
+        Text {
+            fonts,
+            glyphs,
+            line_rects,
+        }
+    }
+
+    /// The font used by each span in the order in which the spans were given, followed by the
+    /// layout's fallback fonts.
+    pub fn fonts(&self) -> &[Font] {
+        &self.fonts
+    }
+
+    /// Every glyph of every span in order, excluding newline characters.
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    /// The bounding rectangle for each line.
+    pub fn line_rects(&self) -> &[Rect] {
+        &self.line_rects
+    }
+
+    /// The rectangle that describes the min and max bounds along each axis reached by the lines.
+    pub fn bounding_rect(&self) -> Rect {
+        let mut lrs = self.line_rects.iter().cloned();
+        let lr = match lrs.next() {
+            None => return Rect::from_w_h(0.0, 0.0),
+            Some(lr) => lr,
+        };
+        lrs.fold(lr, |acc, lr| {
+            let x = Range::new(acc.x.start.min(lr.x.start), acc.x.end.max(lr.x.end));
+            let y = Range::new(acc.y.start.min(lr.y.start), acc.y.end.max(lr.y.end));
+            Rect { x, y }
+        })
+    }
+}
+
+// Measure every character of every span.
+fn items(spans: &[Span], fonts: &[Font], layout: &Layout) -> Vec<Item> {
+    let mut items = vec![];
+    let mut last_glyph: Option<(usize, text::GlyphId)> = None;
+    let fallback = &fonts[spans.len()..];
+    for (span_ix, span) in spans.iter().enumerate() {
+        let font_size = span.font_size.unwrap_or(layout.font_size);
+        let scale = text::pt_to_scale(font_size);
+        let mut chars = span.text.chars().peekable();
+        while let Some(ch) = chars.next() {
+            // Treat `\r\n` as a single newline.
+            let newline = match ch {
+                '\n' => true,
+                '\r' => chars.peek() == Some(&'\n'),
+                _ => false,
+            };
+            if newline {
+                if ch == '\r' {
+                    chars.next();
+                }
+                last_glyph = None;
+                items.push(Item {
+                    span: span_ix,
+                    font: span_ix,
+                    ch: '\n',
+                    font_size,
+                    newline,
+                    kern: 0.0,
+                    advance: 0.0,
+                });
+                continue;
+            }
+
+            // Fall back to the first font that has a glyph for the character, if any.
+            let font_ix = if text::font::has_glyph(&fonts[span_ix], ch) {
+                span_ix
+            } else {
+                fallback
+                    .iter()
+                    .position(|font| text::font::has_glyph(font, ch))
+                    .map(|i| spans.len() + i)
+                    .unwrap_or(span_ix)
+            };
+            let font = &fonts[font_ix];
+
+            // Kerning only applies between glyphs of the same font.
+            let g = font.glyph(ch).scaled(scale);
+            let kern = match last_glyph {
+                None => 0.0,
+                Some((last_font, last_id)) if last_font == font_ix => {
+                    font.pair_kerning(scale, last_id, g.id()) + layout.letter_spacing
+                }
+                Some(_) => layout.letter_spacing,
+            };
+            let word = if ch.is_whitespace() {
+                layout.word_spacing
+            } else {
+                0.0
+            };
+            let advance = g.h_metrics().advance_width + word;
+            last_glyph = Some((font_ix, g.id()));
+            items.push(Item {
+                span: span_ix,
+                font: font_ix,
+                ch,
+                font_size,
+                newline,
+                kern,
+                advance,
+            });
+        }
+    }
+    items
+}
+
+// The width of the given items laid out along a single line.
+fn width(items: &[Item]) -> Scalar {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, it)| {
+            if i > 0 {
+                it.kern + it.advance
+            } else {
+                it.advance
+            }
+        })
+        .sum()
+}
+
+// Break the given items into lines.
+fn lines(
+    items: &[Item],
+    wrap: Option<Wrap>,
+    max_width: Scalar,
+    default_font_size: FontSize,
+) -> Vec<Line> {
+    let line = |range: std::ops::Range<usize>, wrapped: bool| {
+        let line_items = &items[range.clone()];
+        let width = width(line_items);
+        // Empty lines take the size of the newline that ended them.
+        let font_size = line_items
+            .iter()
+            .map(|it| it.font_size)
+            .max()
+            .or_else(|| items.get(range.start).map(|it| it.font_size))
+            .unwrap_or(default_font_size);
+        let height = font_size as Scalar;
+        Line {
+            items: range,
+            width,
+            height,
+            wrapped,
+        }
+    };
+
+    let mut lines = vec![];
+    let mut start = 0;
+    let mut line_width = 0.0;
+    let mut last_whitespace = None;
+    let mut i = 0;
+    while i < items.len() {
+        let item = &items[i];
+        if item.newline {
+            lines.push(line(start..i, false));
+            start = i + 1;
+            line_width = 0.0;
+            last_whitespace = None;
+            i += 1;
+            continue;
+        }
+
+        let kern = if i > start { item.kern } else { 0.0 };
+        let new_width = line_width + kern + item.advance;
+
+        // Wrap the line if it exceeds the max width, keeping at least one character per line.
+        if let Some(wrap) = wrap {
+            if new_width > max_width && i > start {
+                match (wrap, last_whitespace) {
+                    (Wrap::Whitespace, Some(ws)) => {
+                        lines.push(line(start..ws, true));
+                        start = ws + 1;
+                    }
+                    _ => {
+                        lines.push(line(start..i, true));
+                        start = i;
+                    }
+                }
+                line_width = width(&items[start..i]);
+                last_whitespace = (start..i).rev().find(|&j| items[j].ch.is_whitespace());
+                continue;
+            }
+        }
+
+        if item.ch.is_whitespace() {
+            last_whitespace = Some(i);
+        }
+        line_width = new_width;
+        i += 1;
+    }
+    lines.push(line(start..items.len(), false));
+    lines
+}
@@ -0,0 +1,123 @@
+//! Items aimed at easing the contruction of a compute pipeline and the dispatch of its work.
+//!
+//! Mirrors the `RenderPipelineBuilder`, allowing for the construction of a `ComputePipeline` from
+//! either an existing pipeline layout or the bind group layouts from which one may be created.
+
+use super::render_pipeline_builder::{IntoPipelineLayoutDescriptor, Layout};
+use crate::wgpu;
+
+/// A builder type to help simplify the construction of a **ComputePipeline**.
+#[derive(Debug)]
+pub struct ComputePipelineBuilder<'a> {
+    layout: Layout<'a>,
+    cs_mod: &'a wgpu::ShaderModule,
+    cs_entry_point: &'a str,
+}
+
+impl<'a> ComputePipelineBuilder<'a> {
+    // The default entry point used for the compute shader when unspecified.
+    pub const DEFAULT_SHADER_ENTRY_POINT: &'static str = "main";
+
+    // Constructors
+
+    /// Begin building the compute pipeline for the given pipeline layout and compute shader
+    /// module.
+    pub fn from_layout(layout: &'a wgpu::PipelineLayout, cs_mod: &'a wgpu::ShaderModule) -> Self {
+        let layout = Layout::Created(layout);
+        Self::new_inner(layout, cs_mod)
+    }
+
+    /// Begin building the compute pipeline for a pipeline with the given layout descriptor and
+    /// compute shader module.
+    ///
+    /// A slice of bind group layouts may be given in place of a descriptor.
+    pub fn from_layout_descriptor<T>(layout_desc: T, cs_mod: &'a wgpu::ShaderModule) -> Self
+    where
+        T: IntoPipelineLayoutDescriptor<'a>,
+    {
+        let desc = layout_desc.into_pipeline_layout_descriptor();
+        let layout = Layout::Descriptor(desc);
+        Self::new_inner(layout, cs_mod)
+    }
+
+    // Shared between constructors.
+    fn new_inner(layout: Layout<'a>, cs_mod: &'a wgpu::ShaderModule) -> Self {
+        ComputePipelineBuilder {
+            layout,
+            cs_mod,
+            cs_entry_point: Self::DEFAULT_SHADER_ENTRY_POINT,
+        }
+    }
+
+    // Builders
+
+    /// The name of the entry point function within the compute shader module.
+    pub fn entry_point(mut self, entry_point: &'a str) -> Self {
+        self.cs_entry_point = entry_point;
+        self
+    }
+
+    // Finalising methods.
+
+    /// Build the pipeline layout if necessary and ultimately the compute pipeline itself.
+    pub fn build(self, device: &wgpu::Device) -> wgpu::ComputePipeline {
+        match self.layout {
+            Layout::Descriptor(ref desc) => {
+                let layout = device.create_pipeline_layout(desc);
+                build(&self, &layout, device)
+            }
+            Layout::Created(layout) => build(&self, layout, device),
+        }
+    }
+}
+
+/// Encode a compute pass to the given encoder that dispatches the given pipeline.
+///
+/// Each of the given `bind_groups` is set at the index at which it appears in the slice. The
+/// `work_groups` describe the number of work groups dispatched along the `x`, `y` and `z` axes.
+/// See `work_group_count` for determining the number of work groups required for some number of
+/// invocations.
+pub fn dispatch_compute(
+    encoder: &mut wgpu::CommandEncoder,
+    pipeline: &wgpu::ComputePipeline,
+    bind_groups: &[&wgpu::BindGroup],
+    [x, y, z]: [u32; 3],
+) {
+    let mut cpass = encoder.begin_compute_pass();
+    cpass.set_pipeline(pipeline);
+    for (i, bind_group) in bind_groups.iter().enumerate() {
+        cpass.set_bind_group(i as u32, bind_group, &[]);
+    }
+    cpass.dispatch(x, y, z);
+}
+
+/// The number of work groups of the given size required to cover the given number of
+/// invocations, i.e. the invocation count divided by the work group size, rounded up.
+///
+/// E.g. a 1920x1080 image processed by 16x16 work groups requires
+/// `[work_group_count(1920, 16), work_group_count(1080, 16), 1]` work groups.
+///
+/// **Panics** if `work_group_size` is `0`.
+pub fn work_group_count(invocations: u32, work_group_size: u32) -> u32 {
+    assert!(
+        work_group_size > 0,
+        "the work group size must be greater than 0"
+    );
+    invocations / work_group_size + (invocations % work_group_size != 0) as u32
+}
+
+fn build(
+    builder: &ComputePipelineBuilder,
+    layout: &wgpu::PipelineLayout,
+    device: &wgpu::Device,
+) -> wgpu::ComputePipeline {
+    let compute_stage = wgpu::ProgrammableStageDescriptor {
+        module: builder.cs_mod,
+        entry_point: builder.cs_entry_point,
+    };
+    let desc = wgpu::ComputePipelineDescriptor {
+        layout,
+        compute_stage,
+    };
+    device.create_compute_pipeline(&desc)
+}
@@ -17,10 +17,12 @@
 
 mod bind_group_builder;
 pub mod blend;
+mod compute_pipeline_builder;
 mod device_map;
 mod render_pass;
 mod render_pipeline_builder;
 mod sampler_builder;
+mod shader_watcher;
 mod texture;
 
 // Re-export all of `wgpu` along with its documentation.
@@ -30,6 +32,9 @@ mod texture;
 pub use self::bind_group_builder::{
     Builder as BindGroupBuilder, LayoutBuilder as BindGroupLayoutBuilder,
 };
+pub use self::compute_pipeline_builder::{
+    dispatch_compute, work_group_count, ComputePipelineBuilder,
+};
 pub use self::device_map::{
     ActiveAdapter, AdapterMap, AdapterMapKey, DeviceMap, DeviceMapKey, DeviceQueuePair,
 };
@@ -39,15 +44,19 @@ pub use self::render_pass::{
 };
 pub use self::render_pipeline_builder::RenderPipelineBuilder;
 pub use self::sampler_builder::SamplerBuilder;
+pub use self::shader_watcher::{Error as ShaderWatcherError, Watcher as ShaderWatcher};
+pub use self::texture::atlas::{
+    Atlas as TextureAtlas, Builder as TextureAtlasBuilder, Error as TextureAtlasError,
+};
 pub use self::texture::capturer::{
     AwaitWorkerTimeout as TextureCapturerAwaitWorkerTimeout, Capturer as TextureCapturer,
-    Rgba8ReadMapping, Snapshot as TextureSnapshot,
+    Rgba8AsyncMappedImageBuffer, Rgba8ReadMapping, Snapshot as TextureSnapshot,
 };
 pub use self::texture::image::{
     format_from_image_color_type as texture_format_from_image_color_type, BufferImage,
     ImageReadMapping,
 };
-pub use self::texture::reshaper::Reshaper as TextureReshaper;
+pub use self::texture::reshaper::{Reshaper as TextureReshaper, Tonemap};
 pub use self::texture::{
     descriptor_eq as texture_descriptor_eq, extent_3d_eq,
     format_size_bytes as texture_format_size_bytes,
@@ -5,7 +5,7 @@
 //! simplify the process and fall back to a set of reasonable defaults.
 
 #[derive(Debug)]
-enum Layout<'a> {
+pub(crate) enum Layout<'a> {
     Descriptor(wgpu::PipelineLayoutDescriptor<'a>),
     Created(&'a wgpu::PipelineLayout),
 }
@@ -0,0 +1,406 @@
+//! Items related to hot reloading shaders from files while a sketch is running.
+//!
+//! The **Watcher** loads a set of shader files and checks them for changes each time `update` is
+//! called, reloading any shaders that were modified. Shaders that fail to load or compile are
+//! reported rather than causing a panic, and the previously loaded shader module remains in use
+//! until the error is fixed.
+//!
+//! The structure of all SPIR-V is validated before a shader module is created, so that a file that
+//! is truncated or only partially written at the time of reloading is rejected rather than passed
+//! to the driver. If a `spirv-val` executable is found on the `PATH`, it is also used to validate
+//! the module's semantics.
+//!
+//! SPIR-V files (with the `spv` extension) are loaded directly. All other files are assumed to be
+//! GLSL source and are first compiled to SPIR-V via `glslangValidator` or the compiler specified
+//! via `Watcher::glsl_compiler`. Note that WGSL is not supported by the version of wgpu in use.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use std::{fmt, io};
+
+/// Watches a set of shader files, reloading their shader modules when they change.
+///
+/// A typical usage is to rebuild the render pipeline within `update` each time
+/// `Watcher::update` returns `true`, so that the new pipeline is used for the next frame:
+///
+/// ```ignore
+/// fn update(app: &App, model: &mut Model, _update: Update) {
+///     let window = app.main_window();
+///     let device = window.swap_chain_device();
+///     if model.shaders.update(device) {
+///         if let Some(modules) = model.shaders.modules() {
+///             model.pipeline = create_pipeline(device, modules[0], modules[1]);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Watcher {
+    shaders: Vec<Shader>,
+    glsl_compiler: PathBuf,
+}
+
+// A single watched shader file.
+#[derive(Debug)]
+struct Shader {
+    path: PathBuf,
+    // The modification time of the file at the last load attempt.
+    modified: Option<SystemTime>,
+    module: Option<wgpu::ShaderModule>,
+    error: Option<Error>,
+}
+
+/// Errors that might occur while loading a watched shader.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occurred while reading the shader file or running the GLSL compiler.
+    Io(io::Error),
+    /// The GLSL compiler failed to compile the shader. Contains the compiler's output.
+    Compile(String),
+    /// The SPIR-V failed validation. Contains a description of the problem.
+    InvalidSpirv(String),
+}
+
+impl Watcher {
+    /// The GLSL compiler used by default for shaders that are not SPIR-V.
+    pub const DEFAULT_GLSL_COMPILER: &'static str = "glslangValidator";
+
+    /// Create a new **Watcher** without any shaders.
+    pub fn new() -> Self {
+        Watcher {
+            shaders: vec![],
+            glsl_compiler: Self::DEFAULT_GLSL_COMPILER.into(),
+        }
+    }
+
+    /// Specify the program used to compile GLSL shaders to SPIR-V.
+    ///
+    /// The program is invoked as `<program> -V <source> -o <output>`, matching the interface of
+    /// `glslangValidator`. By default, `glslangValidator` is expected to be on the `PATH`.
+    pub fn glsl_compiler<P>(mut self, program: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.glsl_compiler = program.into();
+        self
+    }
+
+    /// Load the shader at the given path and watch it for changes.
+    ///
+    /// Returns the index of the shader, by which its module may be accessed via `module`.
+    ///
+    /// If the shader fails to load, the error is reported and may be retrieved via `error`. The
+    /// shader continues to be watched and will be loaded once the error is fixed.
+    pub fn watch<P>(&mut self, device: &wgpu::Device, path: P) -> usize
+    where
+        P: Into<PathBuf>,
+    {
+        let path = path.into();
+        let modified = modified(&path);
+        let (module, error) = match load(device, &path, &self.glsl_compiler) {
+            Ok(module) => (Some(module), None),
+            Err(err) => {
+                report(&path, &err);
+                (None, Some(err))
+            }
+        };
+        let shader = Shader {
+            path,
+            modified,
+            module,
+            error,
+        };
+        self.shaders.push(shader);
+        self.shaders.len() - 1
+    }
+
+    /// Check each shader for changes, reloading those that were modified.
+    ///
+    /// Returns `true` if any shader module was successfully replaced, indicating that any
+    /// pipelines created with the previous modules should be recreated.
+    ///
+    /// If a shader fails to reload, the error is reported and the previous shader module remains
+    /// in use.
+    pub fn update(&mut self, device: &wgpu::Device) -> bool {
+        let mut reloaded = false;
+        for shader in &mut self.shaders {
+            let modified = modified(&shader.path);
+            if modified == shader.modified {
+                continue;
+            }
+            shader.modified = modified;
+            match load(device, &shader.path, &self.glsl_compiler) {
+                Ok(module) => {
+                    shader.module = Some(module);
+                    shader.error = None;
+                    reloaded = true;
+                }
+                Err(err) => {
+                    report(&shader.path, &err);
+                    shader.error = Some(err);
+                }
+            }
+        }
+        reloaded
+    }
+
+    /// The path of the shader at the given index.
+    pub fn path(&self, index: usize) -> Option<&Path> {
+        self.shaders.get(index).map(|shader| shader.path.as_path())
+    }
+
+    /// The most recently loaded module of the shader at the given index.
+    ///
+    /// Returns `None` if the shader has never successfully loaded.
+    pub fn module(&self, index: usize) -> Option<&wgpu::ShaderModule> {
+        self.shaders
+            .get(index)
+            .and_then(|shader| shader.module.as_ref())
+    }
+
+    /// The modules of all shaders in the order in which they were added.
+    ///
+    /// Returns `None` if any of the shaders have never successfully loaded.
+    pub fn modules(&self) -> Option<Vec<&wgpu::ShaderModule>> {
+        self.shaders
+            .iter()
+            .map(|shader| shader.module.as_ref())
+            .collect()
+    }
+
+    /// The error produced by the most recent attempt to load the shader at the given index.
+    ///
+    /// Returns `None` if the most recent attempt succeeded.
+    pub fn error(&self, index: usize) -> Option<&Error> {
+        self.shaders
+            .get(index)
+            .and_then(|shader| shader.error.as_ref())
+    }
+
+    /// The path and error of each shader whose most recent load attempt failed.
+    ///
+    /// This is useful for displaying shader errors within the sketch itself.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &Error)> {
+        self.shaders.iter().filter_map(|shader| {
+            let error = shader.error.as_ref()?;
+            Some((shader.path.as_path(), error))
+        })
+    }
+}
+
+impl Default for Watcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The time at which the file at the given path was last modified, if it can be determined.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Whether or not the file at the given path is SPIR-V rather than GLSL source.
+fn is_spirv(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("spv")
+}
+
+// Load the shader at the given path, compiling it with the given GLSL compiler if necessary.
+fn load(
+    device: &wgpu::Device,
+    path: &Path,
+    glsl_compiler: &Path,
+) -> Result<wgpu::ShaderModule, Error> {
+    let bytes = if is_spirv(path) {
+        std::fs::read(path)?
+    } else {
+        compile_glsl(path, glsl_compiler)?
+    };
+    validate_spirv(&bytes).map_err(Error::InvalidSpirv)?;
+    validate_spirv_with_spirv_val(&bytes)?;
+    let spirv = wgpu::read_spirv(io::Cursor::new(&bytes[..]))?;
+    Ok(device.create_shader_module(&spirv))
+}
+
+// Check the structure of the given SPIR-V module.
+//
+// This checks the header, that each instruction lies within the module and that the instructions
+// required of every shader module are present. It does not check the semantics of the module.
+fn validate_spirv(bytes: &[u8]) -> Result<(), String> {
+    const MAGIC: u32 = 0x0723_0203;
+    const HEADER_WORDS: usize = 5;
+    const OP_MEMORY_MODEL: u32 = 14;
+    const OP_ENTRY_POINT: u32 = 15;
+
+    if bytes.len() % 4 != 0 {
+        return Err(format!(
+            "length of {} bytes is not a multiple of 4",
+            bytes.len()
+        ));
+    }
+    if bytes.len() < HEADER_WORDS * 4 {
+        return Err("the module is too short to contain a header".to_string());
+    }
+    let word_le = |i: usize| {
+        let b = &bytes[i * 4..i * 4 + 4];
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]])
+    };
+    let little_endian = match word_le(0) {
+        MAGIC => true,
+        m if m.swap_bytes() == MAGIC => false,
+        m => return Err(format!("invalid magic number {:#010x}", m)),
+    };
+    let word = |i: usize| match little_endian {
+        true => word_le(i),
+        false => word_le(i).swap_bytes(),
+    };
+    let bound = word(3);
+    if bound == 0 {
+        return Err("the id bound is 0".to_string());
+    }
+
+    let n_words = bytes.len() / 4;
+    let mut i = HEADER_WORDS;
+    let (mut memory_model, mut entry_point) = (false, false);
+    while i < n_words {
+        let instruction = word(i);
+        let word_count = (instruction >> 16) as usize;
+        let opcode = instruction & 0xFFFF;
+        if word_count == 0 {
+            return Err(format!("instruction at word {} has a word count of 0", i));
+        }
+        if i + word_count > n_words {
+            return Err(format!("instruction at word {} extends past the end", i));
+        }
+        match opcode {
+            OP_MEMORY_MODEL => memory_model = true,
+            OP_ENTRY_POINT => entry_point = true,
+            _ => (),
+        }
+        i += word_count;
+    }
+    if !memory_model {
+        return Err("the module has no memory model".to_string());
+    }
+    if !entry_point {
+        return Err("the module has no entry point".to_string());
+    }
+    Ok(())
+}
+
+// Validate the module via `spirv-val`, if it is available on the `PATH`.
+fn validate_spirv_with_spirv_val(bytes: &[u8]) -> Result<(), Error> {
+    let input_path = std::env::temp_dir().join(format!(
+        "nannou_shader_watcher_{}_validate.spv",
+        std::process::id(),
+    ));
+    std::fs::write(&input_path, bytes)?;
+    let output = Command::new("spirv-val").arg(&input_path).output();
+    std::fs::remove_file(&input_path).ok();
+    let output = match output {
+        Ok(output) => output,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    if !output.status.success() {
+        let mut msg = String::from_utf8_lossy(&output.stdout).into_owned();
+        msg.push_str(&String::from_utf8_lossy(&output.stderr));
+        return Err(Error::InvalidSpirv(msg.trim().to_string()));
+    }
+    Ok(())
+}
+
+// Compile the GLSL shader at the given path to SPIR-V bytes.
+fn compile_glsl(path: &Path, glsl_compiler: &Path) -> Result<Vec<u8>, Error> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("shader");
+    let output_path = std::env::temp_dir().join(format!(
+        "nannou_shader_watcher_{}_{}.spv",
+        std::process::id(),
+        file_name,
+    ));
+    let output = Command::new(glsl_compiler)
+        .arg("-V")
+        .arg(path)
+        .arg("-o")
+        .arg(&output_path)
+        .output()?;
+    if !output.status.success() {
+        let mut msg = String::from_utf8_lossy(&output.stdout).into_owned();
+        msg.push_str(&String::from_utf8_lossy(&output.stderr));
+        return Err(Error::Compile(msg.trim().to_string()));
+    }
+    let bytes = std::fs::read(&output_path)?;
+    std::fs::remove_file(&output_path).ok();
+    Ok(bytes)
+}
+
+// Report the failure to load the shader at the given path.
+fn report(path: &Path, err: &Error) {
+    // TODO: Log errors, don't print to stderr.
+    eprintln!("failed to load shader `{}`: {}", path.display(), err);
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Compile(_) | Error::InvalidSpirv(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => fmt::Display::fmt(err, f),
+            Error::Compile(ref output) => write!(f, "failed to compile GLSL: {}", output),
+            Error::InvalidSpirv(ref msg) => write!(f, "invalid SPIR-V: {}", msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERT_SPV: &[u8] = include_bytes!("../draw/renderer/shaders/vert.spv");
+
+    #[test]
+    fn test_validate_spirv() {
+        assert_eq!(validate_spirv(VERT_SPV), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_spirv_big_endian() {
+        let swapped: Vec<u8> = VERT_SPV
+            .chunks(4)
+            .flat_map(|w| vec![w[3], w[2], w[1], w[0]])
+            .collect();
+        assert_eq!(validate_spirv(&swapped), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_spirv_truncated() {
+        assert!(validate_spirv(&[]).is_err());
+        assert!(validate_spirv(&VERT_SPV[..VERT_SPV.len() - 2]).is_err());
+        // Cut within the `OpExtInstImport` following the `OpCapability` after the header.
+        assert!(validate_spirv(&VERT_SPV[..32]).is_err());
+        assert!(validate_spirv(&VERT_SPV[..20]).is_err());
+    }
+
+    #[test]
+    fn test_validate_spirv_invalid_magic() {
+        let mut bytes = VERT_SPV.to_vec();
+        bytes[0] ^= 0xFF;
+        assert!(validate_spirv(&bytes).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Items related to packing many images into a single texture.
+//!
+//! Drawing many sprites that each have their own texture requires switching bind groups between
+//! each sprite. By packing the images into a single **Atlas** texture, each sprite may instead be
+//! drawn from the same texture via `draw.texture(atlas.texture()).src_rect(atlas.src_rect(i))`,
+//! allowing the draw renderer to batch them.
+
+use crate::geom;
+use crate::wgpu;
+use crate::wgpu::texture::image::WithDeviceQueuePair;
+use std::path::Path;
+
+/// Collects images to be packed into a single texture.
+#[derive(Debug)]
+pub struct Builder {
+    images: Vec<image::RgbaImage>,
+    padding: u32,
+    max_size: u32,
+    usage: wgpu::TextureUsage,
+}
+
+/// A texture containing many packed images, along with the location of each image.
+#[derive(Debug)]
+pub struct Atlas {
+    texture: wgpu::Texture,
+    // The position and size of each image in pixels, with the origin in the top left.
+    rects: Vec<[u32; 4]>,
+}
+
+/// Errors that might occur while building an **Atlas**.
+#[derive(Debug)]
+pub enum Error {
+    /// The images could not be packed into a texture within the maximum size.
+    TooLarge {
+        /// The maximum width and height of the atlas texture.
+        max_size: u32,
+    },
+}
+
+impl Builder {
+    /// The default padding in pixels between each image.
+    ///
+    /// Padding avoids colors bleeding between neighbouring images when sampling with linear
+    /// filtering.
+    pub const DEFAULT_PADDING: u32 = 1;
+
+    /// The default maximum width and height of the atlas texture.
+    pub const DEFAULT_MAX_SIZE: u32 = 8192;
+
+    /// Begin building a new texture atlas.
+    pub fn new() -> Self {
+        Builder {
+            images: vec![],
+            padding: Self::DEFAULT_PADDING,
+            max_size: Self::DEFAULT_MAX_SIZE,
+            usage: wgpu::TextureBuilder::default_image_texture_usage(),
+        }
+    }
+
+    /// Specify the padding in pixels between each image.
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Specify the maximum width and height of the atlas texture.
+    ///
+    /// Building the atlas fails if the images cannot be packed within this size.
+    pub fn max_size(mut self, max_size: u32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Specify the usage of the atlas texture.
+    ///
+    /// By default, this is `wgpu::TextureBuilder::default_image_texture_usage()`.
+    pub fn usage(mut self, usage: wgpu::TextureUsage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Add an image to the atlas.
+    ///
+    /// Returns the index of the image, by which its location may be retrieved from the **Atlas**.
+    pub fn add(&mut self, image: &image::DynamicImage) -> usize {
+        self.images.push(image.to_rgba());
+        self.images.len() - 1
+    }
+
+    /// Read the image at the given path and add it to the atlas.
+    ///
+    /// Returns the index of the image, by which its location may be retrieved from the **Atlas**.
+    pub fn add_path<P>(&mut self, path: P) -> image::ImageResult<usize>
+    where
+        P: AsRef<Path>,
+    {
+        let image = image::open(path)?;
+        Ok(self.add(&image))
+    }
+
+    /// Pack the images and upload the resulting atlas to a texture.
+    ///
+    /// The device and queue `src` can be either the `App`, a `Window`, a `wgpu::DeviceQueuePair`
+    /// or a tuple `(&wgpu::Device, &mut wgpu::Queue)`.
+    ///
+    /// The texture format is `Rgba8UnormSrgb`.
+    pub fn build<T>(self, src: T) -> Result<Atlas, Error>
+    where
+        T: WithDeviceQueuePair,
+    {
+        let ([w, h], rects) = pack(&self.images, self.padding, self.max_size)?;
+        let mut atlas_image = image::RgbaImage::new(w, h);
+        for (image, &[x, y, _, _]) in self.images.iter().zip(&rects) {
+            image::imageops::replace(&mut atlas_image, image, x, y);
+        }
+        let usage = self.usage;
+        let texture = src.with_device_queue_pair(|device, queue| {
+            wgpu::Texture::load_from_image_buffer(device, queue, usage, &atlas_image)
+        });
+        Ok(Atlas { texture, rects })
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Atlas {
+    /// The texture containing all packed images.
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// The number of images within the atlas.
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    /// Whether or not the atlas contains no images.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The area occupied by the image at the given index in texels.
+    ///
+    /// The rect uses the same coordinates as `draw.texture(..).src_rect(..)`, with the origin in
+    /// the bottom left of the texture.
+    ///
+    /// **Panics** if there is no image at the given index.
+    pub fn src_rect(&self, index: usize) -> geom::Rect {
+        let [x, y, w, h] = self.rects[index];
+        let [_, atlas_h] = self.texture.size();
+        let left = x as f32;
+        let bottom = (atlas_h - y - h) as f32;
+        geom::Rect::from_corners(
+            geom::pt2(left, bottom),
+            geom::pt2(left + w as f32, bottom + h as f32),
+        )
+    }
+
+    /// The area occupied by the image at the given index as texture coordinates in the range
+    /// `0.0..=1.0`.
+    ///
+    /// The rect uses the same coordinates as `draw.texture(..).area(..)`, with the origin in the
+    /// bottom left of the texture. This is useful for custom pipelines that sample the atlas.
+    ///
+    /// **Panics** if there is no image at the given index.
+    pub fn uv_rect(&self, index: usize) -> geom::Rect {
+        let rect = self.src_rect(index);
+        let [w, h] = self.texture.size();
+        let (w, h) = (w as f32, h as f32);
+        geom::Rect::from_corners(
+            geom::pt2(rect.left() / w, rect.bottom() / h),
+            geom::pt2(rect.right() / w, rect.top() / h),
+        )
+    }
+
+    /// The size of the image at the given index in pixels.
+    ///
+    /// **Panics** if there is no image at the given index.
+    pub fn image_size(&self, index: usize) -> [u32; 2] {
+        let [_, _, w, h] = self.rects[index];
+        [w, h]
+    }
+}
+
+// Pack the given images into rows ("shelves"), tallest first.
+//
+// Starting from the smallest power of two that could fit the total area, the atlas width is
+// doubled until the packed height fits within the width, or until the maximum size is reached.
+//
+// Returns the size of the atlas along with the `[x, y, w, h]` of each image.
+fn pack(
+    images: &[image::RgbaImage],
+    padding: u32,
+    max_size: u32,
+) -> Result<([u32; 2], Vec<[u32; 4]>), Error> {
+    let too_large = Error::TooLarge { max_size };
+    let mut order: Vec<usize> = (0..images.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(images[i].height()));
+
+    let padded = |image: &image::RgbaImage| (image.width() + padding, image.height() + padding);
+    let area: u64 = images
+        .iter()
+        .map(|image| {
+            let (w, h) = padded(image);
+            w as u64 * h as u64
+        })
+        .sum();
+    let widest = images
+        .iter()
+        .map(|image| padded(image).0)
+        .max()
+        .unwrap_or(1);
+    if widest > max_size {
+        return Err(too_large);
+    }
+    let mut width = ((area as f64).sqrt().ceil() as u32)
+        .max(widest)
+        .max(1)
+        .next_power_of_two()
+        .min(max_size);
+
+    loop {
+        let mut rects = vec![[0; 4]; images.len()];
+        let (mut x, mut y, mut shelf_h) = (0, 0, 0);
+        for &i in &order {
+            let image = &images[i];
+            let (w, h) = padded(image);
+            if x + w > width {
+                x = 0;
+                y += shelf_h;
+                shelf_h = 0;
+            }
+            rects[i] = [x, y, image.width(), image.height()];
+            x += w;
+            shelf_h = shelf_h.max(h);
+        }
+        let height = (y + shelf_h).max(1);
+        if height <= width || (width >= max_size && height <= max_size) {
+            return Ok(([width, height], rects));
+        }
+        if width >= max_size {
+            return Err(too_large);
+        }
+        width = (width * 2).min(max_size);
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Error::TooLarge { max_size } => write!(
+                f,
+                "the images could not be packed within a {}x{} texture",
+                max_size, max_size
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn images(sizes: &[(u32, u32)]) -> Vec<image::RgbaImage> {
+        sizes
+            .iter()
+            .map(|&(w, h)| image::RgbaImage::new(w, h))
+            .collect()
+    }
+
+    // Whether or not the two `[x, y, w, h]` rects overlap once padded.
+    fn overlap(a: [u32; 4], b: [u32; 4], padding: u32) -> bool {
+        let [ax, ay, aw, ah] = a;
+        let [bx, by, bw, bh] = b;
+        ax < bx + bw + padding
+            && bx < ax + aw + padding
+            && ay < by + bh + padding
+            && by < ay + ah + padding
+    }
+
+    #[test]
+    fn test_pack_within_bounds_without_overlap() {
+        let images = images(&[(30, 10), (10, 40), (25, 25), (5, 5), (60, 8), (12, 30)]);
+        let padding = 2;
+        let ([w, h], rects) = pack(&images, padding, 1024).unwrap();
+        assert!(w.is_power_of_two());
+        for (i, (image, &rect)) in images.iter().zip(&rects).enumerate() {
+            let [x, y, rw, rh] = rect;
+            assert_eq!([rw, rh], [image.width(), image.height()]);
+            assert!(x + rw <= w && y + rh <= h, "image {} exceeds the atlas", i);
+            for &other in &rects[i + 1..] {
+                assert!(
+                    !overlap(rect, other, padding),
+                    "{:?} overlaps {:?}",
+                    rect,
+                    other
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_tallest_first() {
+        // The tallest image begins the first shelf.
+        let images = images(&[(4, 4), (4, 16), (4, 8)]);
+        let (_, rects) = pack(&images, 0, 1024).unwrap();
+        assert_eq!(rects[1], [0, 0, 4, 16]);
+        assert_eq!(rects[2], [4, 0, 4, 8]);
+        assert_eq!(rects[0], [8, 0, 4, 4]);
+    }
+
+    #[test]
+    fn test_pack_empty() {
+        let ([w, h], rects) = pack(&[], 1, 1024).unwrap();
+        assert_eq!([w, h], [1, 1]);
+        assert!(rects.is_empty());
+    }
+
+    #[test]
+    fn test_pack_too_large() {
+        // An image wider than the maximum size.
+        let result = pack(&images(&[(65, 1)]), 0, 64);
+        assert!(matches!(result, Err(Error::TooLarge { max_size: 64 })));
+        // Images that each fit, but not all at once.
+        let result = pack(&images(&[(64, 64), (64, 64)]), 0, 64);
+        assert!(matches!(result, Err(Error::TooLarge { max_size: 64 })));
+    }
+
+    #[test]
+    fn test_pack_exact_fit() {
+        let ([w, h], rects) = pack(&images(&[(32, 32); 4]), 0, 64).unwrap();
+        assert_eq!([w, h], [64, 64]);
+        assert_eq!(rects[3], [32, 32, 32, 32]);
+    }
+}
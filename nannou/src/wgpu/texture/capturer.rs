@@ -13,11 +13,16 @@ use std::time::Duration;
 /// has been submitted. **Snapshot**s can be read on the current thread via **read** or on a thread
 /// pool via **read_threaded**.
 ///
+/// Alternatively, **capture_staged** copies the texture into one of a pair of staging buffers that
+/// are reused between captures, skipping the capture rather than blocking in the case that both
+/// are still being read.
+///
 /// If the **Capturer** is dropped while threaded callbacks are still being processed, the drop
 /// implementation will block the current thread.
 #[derive(Debug, Default)]
 pub struct Capturer {
     converter_data_pair: Mutex<Option<ConverterDataPair>>,
+    staging: Mutex<Staging>,
     thread_pool: Arc<Mutex<Option<Arc<ThreadPool>>>>,
     workers: Option<u32>,
     timeout: Option<Duration>,
@@ -38,6 +43,8 @@ struct ThreadPool {
 /// specifically non-linear sRGBA8.
 pub struct Snapshot {
     buffer: wgpu::BufferImage,
+    // Whether or not the snapshot was copied into a staging buffer via `capture_staged`.
+    staged: bool,
     thread_pool: Arc<Mutex<Option<Arc<ThreadPool>>>>,
     workers: Option<u32>,
     timeout: Option<Duration>,
@@ -59,6 +66,15 @@ pub struct Rgba8ReadMapping {
 /// available.
 pub struct AwaitWorkerTimeout<F>(pub F);
 
+// The pair of staging buffers used by `capture_staged`, alternating between captures.
+//
+// A buffer is in use for as long as a `Snapshot` of it exists.
+#[derive(Debug, Default)]
+struct Staging {
+    buffers: [Option<Arc<wgpu::BufferBytes>>; 2],
+    next: usize,
+}
+
 #[derive(Debug)]
 struct ConverterDataPair {
     src_descriptor: wgpu::TextureDescriptor<'static>,
@@ -93,6 +109,15 @@ impl ThreadPool {
             interval_us *= 2;
         }
 
+        self.spawn(future);
+        Ok(())
+    }
+
+    /// Spawns the given future regardless of whether or not a worker is available.
+    fn spawn<F>(&self, future: F)
+    where
+        F: 'static + Future<Output = ()> + Send,
+    {
         // Wrap the future with the counter.
         let active_futures = self.active_futures.clone();
         let future = async move {
@@ -100,9 +125,7 @@ impl ThreadPool {
             future.await;
             active_futures.fetch_sub(1, atomic::Ordering::SeqCst);
         };
-
         self.thread_pool.spawn_ok(future);
-        Ok(())
     }
 
     fn active_futures(&self) -> u32 {
@@ -157,6 +180,7 @@ impl Capturer {
     pub fn new(workers: Option<u32>, timeout: Option<Duration>) -> Self {
         Capturer {
             converter_data_pair: Default::default(),
+            staging: Default::default(),
             thread_pool: Default::default(),
             workers,
             timeout,
@@ -234,12 +258,101 @@ impl Capturer {
 
         Snapshot {
             buffer: buffer_image,
+            staged: false,
             thread_pool: self.thread_pool.clone(),
             workers: self.workers,
             timeout: self.timeout,
         }
     }
 
+    /// Capture the given texture at the state of the given command encoder, copying it into one of
+    /// the capturer's two staging buffers.
+    ///
+    /// Unlike `capture`, no buffer is allocated for each capture and reading the returned
+    /// **Snapshot** via `Snapshot::read` never blocks while waiting for a worker. Instead, the
+    /// number of snapshots in flight is bounded by the staging buffers themselves.
+    ///
+    /// Returns `None` without encoding any commands if the next staging buffer is still in use by
+    /// a previous **Snapshot**, e.g. if writing the previous captures is slower than rendering.
+    pub fn capture_staged(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        src_texture: &wgpu::Texture,
+    ) -> Option<Snapshot> {
+        let mut staging = self.staging.lock().expect("failed to lock staging buffers");
+        let next = staging.next;
+        let in_use = staging.buffers[next]
+            .as_ref()
+            .map(|buffer| Arc::strong_count(buffer) > 1)
+            .unwrap_or(false);
+        if in_use {
+            return None;
+        }
+
+        // Convert the texture to the destination format and resolve it if necessary.
+        let mut converter_data_pair = self
+            .converter_data_pair
+            .lock()
+            .expect("failed to lock converter");
+        let texture = if src_texture.format() != Self::DST_FORMAT || src_texture.sample_count() > 1
+        {
+            let converter_data_pair = converter_data_pair
+                .get_or_insert_with(|| create_converter_data_pair(device, src_texture));
+            if !wgpu::texture_descriptor_eq(
+                src_texture.descriptor(),
+                &converter_data_pair.src_descriptor,
+            ) {
+                *converter_data_pair = create_converter_data_pair(device, src_texture);
+            }
+            let dst_view = converter_data_pair.dst_texture.create_default_view();
+            converter_data_pair
+                .reshaper
+                .encode_render_pass(&dst_view, encoder);
+            &converter_data_pair.dst_texture
+        } else {
+            src_texture
+        };
+
+        // Recreate the staging buffer if the size of the texture has changed.
+        let len_bytes = texture.size_bytes() as wgpu::BufferAddress;
+        let reusable = staging.buffers[next]
+            .as_ref()
+            .filter(|buffer| buffer.len_bytes() == len_bytes)
+            .cloned();
+        let buffer = match reusable {
+            Some(buffer) => buffer,
+            None => {
+                let buffer_descriptor = wgpu::BufferDescriptor {
+                    label: Some("nannou_texture_capturer_staging"),
+                    size: len_bytes,
+                    usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+                };
+                let buffer = device.create_buffer(&buffer_descriptor);
+                let buffer = Arc::new(super::BufferBytes { buffer, len_bytes });
+                staging.buffers[next] = Some(buffer.clone());
+                buffer
+            }
+        };
+        staging.next = (next + 1) % staging.buffers.len();
+
+        // Copy the full contents of the texture to the staging buffer.
+        let texture_copy_view = texture.default_copy_view();
+        let buffer_copy_view = texture.default_buffer_copy_view(buffer.inner());
+        encoder.copy_texture_to_buffer(texture_copy_view, buffer_copy_view, texture.extent());
+
+        let color_type = image::ColorType::Rgba8;
+        let buffer_image =
+            wgpu::BufferImage::from_shared_buffer(color_type, texture.size(), buffer);
+        Some(Snapshot {
+            buffer: buffer_image,
+            staged: true,
+            thread_pool: self.thread_pool.clone(),
+            workers: self.workers,
+            timeout: self.timeout,
+        })
+    }
+
     /// Await for the completion of all `Snapshot::read` active futures, polling the device as
     /// necessary until all futures have reached completion or until a timeout is reached.
     pub fn await_active_snapshots(
@@ -280,21 +393,27 @@ impl Snapshot {
     /// Note: The given callback will be called on the inner thread pool and will not be called on
     /// the current thread.
     ///
-    /// Note: **This method may block** if the associated `wgpu::TextureCapturer` has an
-    /// `active_futures` count that is greater than the number of worker threads with which it was
-    /// created. This is necessary in order to avoid "out of memory" errors resulting from an
-    /// accumulating queue of pending texture buffers waiting to be mapped. To avoid blocking, you
-    /// can try using a higher thread count, capturing a smaller texture, or using `read_async`
-    /// instead and running the resulting future on a custom runtime or threadpool.
+    /// Note: Unless the snapshot was produced by `capture_staged`, **this method may block** if the
+    /// associated `wgpu::TextureCapturer` has an `active_futures` count that is greater than the
+    /// number of worker threads with which it was created. This is necessary in order to avoid "out
+    /// of memory" errors resulting from an accumulating queue of pending texture buffers waiting to
+    /// be mapped. To avoid blocking, you can try using a higher thread count, capturing a smaller
+    /// texture, or using `read_async` instead and running the resulting future on a custom runtime
+    /// or threadpool.
     pub fn read<F>(self, callback: F) -> Result<(), AwaitWorkerTimeout<impl Future<Output = ()>>>
     where
         F: 'static + Send + FnOnce(Result<Rgba8AsyncMappedImageBuffer, wgpu::BufferAsyncErr>),
     {
         let thread_pool = self.thread_pool();
+        let staged = self.staged;
         let read_future = async {
             let res = self.read_async().await;
             callback(res);
         };
+        if staged {
+            thread_pool.spawn(read_future);
+            return Ok(());
+        }
         thread_pool.spawn_when_worker_available(read_future)
     }
 
@@ -3,6 +3,7 @@
 
 use crate::wgpu;
 use std::path::Path;
+use std::sync::Arc;
 
 /// The set of pixel types from the image crate that can be loaded directly into a texture.
 ///
@@ -20,7 +21,7 @@ pub trait Pixel: image::Pixel {
 pub struct BufferImage {
     color_type: image::ColorType,
     size: [u32; 2],
-    buffer: wgpu::BufferBytes,
+    buffer: Arc<wgpu::BufferBytes>,
 }
 
 /// A wrapper around a slice of bytes representing an image.
@@ -294,7 +295,7 @@ impl wgpu::Texture {
     ) -> Option<BufferImage> {
         let color_type = image_color_type_from_format(self.format())?;
         let size = self.size();
-        let buffer = self.to_buffer_bytes(device, encoder);
+        let buffer = Arc::new(self.to_buffer_bytes(device, encoder));
         Some(BufferImage {
             color_type,
             size,
@@ -304,6 +305,19 @@ impl wgpu::Texture {
 }
 
 impl BufferImage {
+    // Wrap a buffer that may be shared with, and later reused by, the creator of the image.
+    pub(crate) fn from_shared_buffer(
+        color_type: image::ColorType,
+        size: [u32; 2],
+        buffer: Arc<wgpu::BufferBytes>,
+    ) -> Self {
+        BufferImage {
+            color_type,
+            size,
+            buffer,
+        }
+    }
+
     /// The dimensions of the image stored within the buffer.
     pub fn size(&self) -> [u32; 2] {
         self.size
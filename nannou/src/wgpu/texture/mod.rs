@@ -2,6 +2,7 @@ use crate::wgpu::{self, TextureHandle, TextureViewHandle};
 use std::ops::Deref;
 use std::sync::Arc;
 
+pub mod atlas;
 pub mod capturer;
 pub mod image;
 pub mod reshaper;
@@ -693,6 +694,15 @@ impl Clone for TextureView {
     }
 }
 
+// Two views are equal if they view the same texture with the same descriptor.
+impl PartialEq for TextureView {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl Eq for TextureView {}
+
 impl Clone for Texture {
     fn clone(&self) -> Self {
         let handle = self.handle.clone();
@@ -18,6 +18,21 @@ pub struct Reshaper {
     vertex_buffer: wgpu::Buffer,
 }
 
+/// The tonemapping curve applied by a `Reshaper` when writing a high dynamic range source texture
+/// to the destination texture.
+///
+/// Without tonemapping, color channels outside of the `0.0..=1.0` range are clipped by the
+/// destination format, e.g. sketches with additive blending quickly saturate to white.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Tonemap {
+    /// Colors are written as they are, clipping those outside the range of the destination.
+    None,
+    /// The simple Reinhard curve `x / (1 + x)`, applied to each color channel.
+    Reinhard,
+    /// An approximation of the ACES filmic curve, which retains more contrast than `Reinhard`.
+    Aces,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 struct Vertex {
@@ -30,6 +45,12 @@ struct Uniforms {
     sample_count: u32,
 }
 
+impl Default for Tonemap {
+    fn default() -> Self {
+        Tonemap::None
+    }
+}
+
 impl Reshaper {
     /// Construct a new `Reshaper`.
     pub fn new(
@@ -40,10 +61,50 @@ impl Reshaper {
         dst_sample_count: u32,
         dst_format: wgpu::TextureFormat,
     ) -> Self {
+        Self::with_tonemap(
+            device,
+            src_texture,
+            src_sample_count,
+            src_component_type,
+            dst_sample_count,
+            dst_format,
+            Tonemap::None,
+        )
+    }
+
+    /// Construct a new `Reshaper` that applies the given tonemapping curve to the source texture.
+    ///
+    /// **Panics** if a tonemapping curve other than `Tonemap::None` is specified for a
+    /// multisampled source texture.
+    pub fn with_tonemap(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureViewHandle,
+        src_sample_count: u32,
+        src_component_type: wgpu::TextureComponentType,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        tonemap: Tonemap,
+    ) -> Self {
+        assert!(
+            tonemap == Tonemap::None || src_sample_count == 1,
+            "tonemapping is only supported for non-multisampled source textures",
+        );
+
         // Load shader modules.
         let vs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert.spv"));
         let fs_mod = match src_sample_count {
-            1 => wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag.spv")),
+            1 => match tonemap {
+                Tonemap::None => {
+                    wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag.spv"))
+                }
+                Tonemap::Reinhard => wgpu::shader_from_spirv_bytes(
+                    device,
+                    include_bytes!("shaders/frag_reinhard.spv"),
+                ),
+                Tonemap::Aces => {
+                    wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_aces.spv"))
+                }
+            },
             2 => wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_msaa2.spv")),
             4 => wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_msaa4.spv")),
             8 => wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_msaa8.spv")),
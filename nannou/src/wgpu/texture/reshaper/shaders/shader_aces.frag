@@ -0,0 +1,21 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_aces.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag_aces.spv shader_aces.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+
+// Krzysztof Narkowicz's fit of the ACES filmic tonemapping curve.
+void main() {
+    vec4 color = texture(sampler2D(tex, tex_sampler), tex_coords);
+    vec3 x = max(color.rgb, vec3(0.0));
+    vec3 mapped = (x * (2.51 * x + vec3(0.03))) / (x * (2.43 * x + vec3(0.59)) + vec3(0.14));
+    f_color = vec4(clamp(mapped, vec3(0.0), vec3(1.0)), color.a);
+}
@@ -0,0 +1,20 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_reinhard.spv`. You can do so using `glslangValidator` with
+// the following command:
+// `glslangValidator -V -o frag_reinhard.spv shader_reinhard.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+
+void main() {
+    vec4 color = texture(sampler2D(tex, tex_sampler), tex_coords);
+    vec3 x = max(color.rgb, vec3(0.0));
+    f_color = vec4(x / (x + vec3(1.0)), color.a);
+}
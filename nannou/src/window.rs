@@ -3,6 +3,7 @@
 //! Create a new window via `app.new_window()`. This produces a [**Builder**](./struct.Builder.html)
 //! which can be used to build a [**Window**](./struct.Window.html).
 
+use crate::draw;
 use crate::event::{
     Key, MouseButton, MouseScrollDelta, TouchEvent, TouchPhase, TouchpadPressure, WindowEvent,
 };
@@ -12,6 +13,8 @@ use crate::geom::{Point2, Vector2};
 use crate::wgpu;
 use crate::App;
 use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
@@ -38,6 +41,8 @@ pub struct Builder<'app> {
     device_desc: Option<wgpu::DeviceDescriptor>,
     user_functions: UserFunctions,
     msaa_samples: Option<u32>,
+    tonemap: wgpu::Tonemap,
+    click_through: bool,
     max_capture_frame_jobs: u32,
     capture_frame_timeout: Option<Duration>,
 }
@@ -50,6 +55,7 @@ pub(crate) struct UserFunctions {
     pub(crate) raw_event: Option<RawEventFnAny>,
     pub(crate) key_pressed: Option<KeyPressedFnAny>,
     pub(crate) key_released: Option<KeyReleasedFnAny>,
+    pub(crate) received_character: Option<ReceivedCharacterFnAny>,
     pub(crate) mouse_moved: Option<MouseMovedFnAny>,
     pub(crate) mouse_pressed: Option<MousePressedFnAny>,
     pub(crate) mouse_released: Option<MouseReleasedFnAny>,
@@ -102,6 +108,9 @@ pub type KeyPressedFn<Model> = fn(&App, &mut Model, Key);
 /// A function for processing key release events.
 pub type KeyReleasedFn<Model> = fn(&App, &mut Model, Key);
 
+/// A function for processing text input events.
+pub type ReceivedCharacterFn<Model> = fn(&App, &mut Model, char);
+
 /// A function for processing mouse moved events.
 pub type MouseMovedFn<Model> = fn(&App, &mut Model, Point2);
 
@@ -200,6 +209,7 @@ fn_any!(EventFn<M>, EventFnAny);
 fn_any!(RawEventFn<M>, RawEventFnAny);
 fn_any!(KeyPressedFn<M>, KeyPressedFnAny);
 fn_any!(KeyReleasedFn<M>, KeyReleasedFnAny);
+fn_any!(ReceivedCharacterFn<M>, ReceivedCharacterFnAny);
 fn_any!(MouseMovedFn<M>, MouseMovedFnAny);
 fn_any!(MousePressedFn<M>, MousePressedFnAny);
 fn_any!(MouseReleasedFn<M>, MouseReleasedFnAny);
@@ -227,11 +237,18 @@ pub struct Window {
     pub(crate) surface: wgpu::Surface,
     pub(crate) device_queue_pair: Arc<wgpu::DeviceQueuePair>,
     msaa_samples: u32,
+    tonemap: wgpu::Tonemap,
+    transparent: bool,
+    click_through: Cell<bool>,
+    // A present mode requested via `set_present_mode`, applied before the next redraw.
+    pending_present_mode: Cell<Option<wgpu::PresentMode>>,
     pub(crate) swap_chain: WindowSwapChain,
     pub(crate) frame_data: Option<FrameData>,
     pub(crate) frame_count: u64,
     pub(crate) user_functions: UserFunctions,
     pub(crate) tracked_state: TrackedState,
+    // The renderers used by `Draw::to_texture`, one for each target texture format.
+    pub(crate) texture_renderers: RefCell<HashMap<wgpu::TextureFormat, draw::Renderer>>,
 }
 
 // Data related to `Frame`s produced for this window's swapchain textures.
@@ -355,6 +372,8 @@ impl<'app> Builder<'app> {
             device_desc: None,
             user_functions: Default::default(),
             msaa_samples: None,
+            tonemap: Default::default(),
+            click_through: false,
             max_capture_frame_jobs: Default::default(),
             capture_frame_timeout: Default::default(),
         }
@@ -420,6 +439,32 @@ impl<'app> Builder<'app> {
         self
     }
 
+    /// Specify the tonemapping curve applied when writing the `Frame`'s intermediary texture to
+    /// the swap chain image.
+    ///
+    /// The `Frame`'s texture uses the high dynamic range `Frame::TEXTURE_FORMAT`, so colors may
+    /// exceed `1.0`, e.g. when many primitives are drawn with additive blending. By default, no
+    /// tonemapping is applied and such colors are clipped to white by the swap chain format.
+    ///
+    /// **Note:** Like `msaa_samples`, this parameter has no meaning if the window uses a
+    /// **raw_view** function.
+    pub fn tonemap(mut self, tonemap: wgpu::Tonemap) -> Self {
+        self.tonemap = tonemap;
+        self
+    }
+
+    /// Whether or not mouse input should pass through the window to whatever lies beneath it.
+    ///
+    /// Combined with `transparent`, `decorations(false)` and `always_on_top`, this allows for
+    /// overlay-style windows that draw over the desktop or other applications without capturing
+    /// their input. See `Window::set_click_through` for platform support.
+    ///
+    /// By default, this is `false`.
+    pub fn click_through(mut self, click_through: bool) -> Self {
+        self.click_through = click_through;
+        self
+    }
+
     /// Provide a simple function for drawing to the window.
     ///
     /// This is similar to `view` but does not provide access to user data via a Model type. This
@@ -513,6 +558,18 @@ impl<'app> Builder<'app> {
         self
     }
 
+    /// A function for processing text input associated with this window, including characters
+    /// composed via an input method editor (IME).
+    ///
+    /// See `WindowEvent::ReceivedCharacter` for details.
+    pub fn received_character<M>(mut self, f: ReceivedCharacterFn<M>) -> Self
+    where
+        M: 'static,
+    {
+        self.user_functions.received_character = Some(ReceivedCharacterFnAny::from_fn_ptr(f));
+        self
+    }
+
     /// A function for processing mouse moved events associated with this window.
     pub fn mouse_moved<M>(mut self, f: MouseMovedFn<M>) -> Self
     where
@@ -705,6 +762,8 @@ impl<'app> Builder<'app> {
             device_desc,
             user_functions,
             msaa_samples,
+            tonemap,
+            click_through,
             max_capture_frame_jobs,
             capture_frame_timeout,
         } = self;
@@ -782,6 +841,7 @@ impl<'app> Builder<'app> {
         }
 
         // Build the window.
+        let transparent = window.window.transparent;
         let window = {
             let window_target = app
                 .event_loop_window_target
@@ -827,6 +887,7 @@ impl<'app> Builder<'app> {
                     swap_chain_dims,
                     swap_chain_desc.format,
                     msaa_samples,
+                    tonemap,
                 );
                 let capture =
                     frame::CaptureData::new(max_capture_frame_jobs, capture_frame_timeout);
@@ -848,17 +909,27 @@ impl<'app> Builder<'app> {
             physical_size: win_physical_size,
         };
 
+        let texture_renderers = Default::default();
+
         let window = Window {
             window,
             surface,
             device_queue_pair,
             msaa_samples,
+            tonemap,
+            transparent,
+            click_through: Cell::new(false),
+            pending_present_mode: Cell::new(None),
             swap_chain,
             frame_data,
             frame_count,
             user_functions,
             tracked_state,
+            texture_renderers,
         };
+        if click_through {
+            window.set_click_through(true);
+        }
         app.windows.borrow_mut().insert(window_id, window);
 
         // If this is the first window, set it as the app's "focused" window.
@@ -883,6 +954,8 @@ impl<'app> Builder<'app> {
             swap_chain_builder,
             user_functions,
             msaa_samples,
+            tonemap,
+            click_through,
             max_capture_frame_jobs,
             capture_frame_timeout,
         } = self;
@@ -897,6 +970,8 @@ impl<'app> Builder<'app> {
             swap_chain_builder,
             user_functions,
             msaa_samples,
+            tonemap,
+            click_through,
             max_capture_frame_jobs,
             capture_frame_timeout,
         }
@@ -953,6 +1028,14 @@ impl<'app> Builder<'app> {
         self.fullscreen_with(Some(fullscreen))
     }
 
+    /// Create the window borderless fullscreen on the given monitor.
+    ///
+    /// This is useful for multi-projector setups where each window should cover a specific
+    /// output. See `App::available_monitors` and `App::monitor` for enumerating monitors.
+    pub fn fullscreen_on(self, monitor: winit::monitor::MonitorHandle) -> Self {
+        self.fullscreen_with(Some(Fullscreen::Borderless(monitor)))
+    }
+
     /// Set the window fullscreen state with the given settings.
     ///
     /// - `None` indicates a normal window. This is the default case.
@@ -972,6 +1055,12 @@ impl<'app> Builder<'app> {
     }
 
     /// Sets whether the background of the window should be transparent.
+    ///
+    /// The alpha of each `Frame` is preserved when it is written to the swap chain image, so any
+    /// region left transparent (e.g. via `frame.clear(TRANSPARENT)` or a background color with an
+    /// alpha of `0.0`) will show whatever lies behind the window. Note that colors are expected to
+    /// be premultiplied by their alpha, as is the case for drawings blended over a transparent
+    /// background via the default alpha blending.
     pub fn transparent(self, transparent: bool) -> Self {
         self.map_window(|w| w.with_transparent(transparent))
     }
@@ -1181,6 +1270,20 @@ impl Window {
         }
     }
 
+    /// Set the window to borderless fullscreen on the monitor at the given index within the list
+    /// of monitors produced by `App::available_monitors`.
+    ///
+    /// Returns `false` and leaves the window untouched if there is no monitor at the given index.
+    pub fn set_fullscreen_borderless(&self, monitor_index: usize) -> bool {
+        match self.window.available_monitors().nth(monitor_index) {
+            None => false,
+            Some(monitor) => {
+                self.set_fullscreen_with(Some(Fullscreen::Borderless(monitor)));
+                true
+            }
+        }
+    }
+
     /// Set the window to fullscreen.
     ///
     /// Call this method again with `None` to revert back from fullscreen.
@@ -1360,8 +1463,88 @@ impl Window {
         self.msaa_samples
     }
 
+    /// The tonemapping curve applied when writing the `view` function's `Frame` to the swap chain
+    /// image.
+    pub fn tonemap(&self) -> wgpu::Tonemap {
+        self.tonemap
+    }
+
+    /// Whether or not the window was built with a transparent background.
+    ///
+    /// See `Builder::transparent`.
+    pub fn is_transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Set whether or not mouse input should pass through the window to whatever lies beneath it.
+    ///
+    /// While enabled, the window receives no mouse events.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** the window is made a layered window that is transparent to hit testing.
+    /// - **macOS:** the window is set to ignore mouse events.
+    /// - **Linux and others:** unsupported, this has no effect.
+    ///
+    /// Returns `false` if click-through is unsupported on the current platform, in which case
+    /// `is_click_through` remains `false`.
+    pub fn set_click_through(&self, click_through: bool) -> bool {
+        let supported = set_click_through(&self.window, click_through);
+        if supported {
+            self.click_through.set(click_through);
+        }
+        supported
+    }
+
+    /// Whether or not mouse input currently passes through the window.
+    ///
+    /// This is always `false` on platforms where click-through is unsupported. See
+    /// `set_click_through`.
+    pub fn is_click_through(&self) -> bool {
+        self.click_through.get()
+    }
+
+    /// The present mode of the window's swap chain.
+    ///
+    /// If a new present mode was requested via `set_present_mode`, that mode is returned even if
+    /// it has not yet been applied.
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        self.pending_present_mode
+            .get()
+            .unwrap_or(self.swap_chain.descriptor.present_mode)
+    }
+
+    /// Change the present mode of the window's swap chain.
+    ///
+    /// - `Fifo` synchronises presentation with the display's refresh rate (vsync).
+    /// - `Mailbox` presents the most recent frame at each refresh, without blocking rendering.
+    /// - `Immediate` presents frames as soon as they are ready, possibly with tearing. This is
+    ///   useful for measuring latency or unlocking the frame rate.
+    ///
+    /// The swap chain is rebuilt with the new present mode before the window's next redraw.
+    /// Combine with `App::set_fps_limit` to cap the frame rate independently of the display.
+    pub fn set_present_mode(&self, present_mode: wgpu::PresentMode) {
+        if present_mode != self.swap_chain.descriptor.present_mode {
+            self.pending_present_mode.set(Some(present_mode));
+        } else {
+            self.pending_present_mode.set(None);
+        }
+    }
+
     // Custom methods.
 
+    // Rebuild the swap chain if a new present mode was requested via `set_present_mode`.
+    pub(crate) fn apply_pending_present_mode(&mut self) {
+        if let Some(present_mode) = self.pending_present_mode.take() {
+            self.swap_chain.descriptor.present_mode = present_mode;
+            let size_px = [
+                self.swap_chain.descriptor.width,
+                self.swap_chain.descriptor.height,
+            ];
+            self.rebuild_swap_chain(size_px);
+        }
+    }
+
     // A utility function to simplify the recreation of a swap_chain.
     pub(crate) fn rebuild_swap_chain(&mut self, size_px: [u32; 2]) {
         std::mem::drop(self.swap_chain.swap_chain.take());
@@ -1378,6 +1561,7 @@ impl Window {
                 size_px,
                 self.swap_chain.descriptor.format,
                 self.msaa_samples,
+                self.tonemap,
             );
             self.frame_data.as_mut().unwrap().render = render_data;
         }
@@ -1418,6 +1602,36 @@ impl Window {
         self.capture_frame_inner(path.as_ref());
     }
 
+    /// The same as `capture_frame`, but never stalls the render loop.
+    ///
+    /// The frame is copied into one of a pair of staging buffers that are reused between captures,
+    /// after which the image is encoded and written to the file on a worker thread. If both
+    /// staging buffers are still in use by previous captures that are yet to be written, the
+    /// capture of the frame is skipped rather than waiting for a worker to become available.
+    ///
+    /// This is useful for recording every frame of a sketch without affecting its frame rate,
+    /// providing that writing a frame usually takes no longer than rendering one.
+    pub fn capture_frame_non_blocking<P>(&self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let dir = path.parent().expect("capture_frame path has no directory");
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir).expect("failed to create `capture_frame` directory");
+        }
+
+        let mut capture_next_frame_path = self
+            .frame_data
+            .as_ref()
+            .expect("window capture requires that `view` draws to a `Frame` (not a `RawFrame`)")
+            .capture
+            .next_staged_frame_path
+            .lock()
+            .expect("failed to lock `capture_next_staged_frame_path`");
+        *capture_next_frame_path = Some(path.to_path_buf());
+    }
+
     fn capture_frame_inner(&self, path: &Path) {
         // If the parent directory does not exist, create it.
         let dir = path.parent().expect("capture_frame path has no directory");
@@ -1436,6 +1650,114 @@ impl Window {
         *capture_next_frame_path = Some(path.to_path_buf());
     }
 
+    /// Dump debug output for the next frame of this window to the given directory.
+    ///
+    /// Once the frame is submitted, the following are written to the directory:
+    ///
+    /// - `intermediary_lin_srgba.png`: the frame's resolved intermediary texture.
+    /// - `msaa_target.png`: the frame's multisampled texture, if MSAA is enabled.
+    /// - `render_commands.txt` and `glyph_cache.png`: the state of the window's `draw::Renderer`,
+    ///   if the frame was drawn via `Draw::to_frame`. See `draw::Renderer::dump_debug`.
+    ///
+    /// The directory will be created if it does not already exist.
+    pub fn dump_render_debug<P>(&self, dir: P)
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir).expect("failed to create `dump_render_debug` directory");
+        }
+
+        let mut next_debug_dump_dir = self
+            .frame_data
+            .as_ref()
+            .expect("render debug dumps require that `view` draws to a `Frame` (not a `RawFrame`)")
+            .capture
+            .next_debug_dump_dir
+            .lock()
+            .expect("failed to lock `next_debug_dump_dir`");
+        *next_debug_dump_dir = Some(dir.to_path_buf());
+    }
+
+    /// Begin recording each frame of this window to a video file at the given path.
+    ///
+    /// Frames are captured in the same manner as `capture_frame` and piped to an `ffmpeg`
+    /// subprocess, which must be installed. The video has the size of the window at the time
+    /// recording begins, and frames of any other size are skipped. See `VideoSettings` for the
+    /// frame rate, pacing and encoding of the video.
+    ///
+    /// If a video is already being recorded, it is finished first as if via `stop_video`.
+    ///
+    /// Returns an error if the `ffmpeg` process could not be spawned.
+    ///
+    /// ```no_run
+    /// # use nannou::prelude::*;
+    /// # fn model(app: &App) {
+    /// app.main_window()
+    ///     .record_video("out.mp4", Default::default())
+    ///     .expect("failed to start recording");
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn record_video<P>(&self, path: P, settings: frame::VideoSettings) -> std::io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() && !dir.exists() {
+                std::fs::create_dir_all(&dir)?;
+            }
+        }
+        self.stop_video()?;
+        let size = [
+            self.swap_chain.descriptor.width,
+            self.swap_chain.descriptor.height,
+        ];
+        let device_queue_pair = self.device_queue_pair.clone();
+        let recorder = frame::video::Recorder::new(device_queue_pair, path, size, settings)?;
+        *self
+            .video_recorder()
+            .lock()
+            .expect("failed to lock `video_recorder`") = Some(recorder);
+        Ok(())
+    }
+
+    /// Stop recording the video started via `record_video`, if any.
+    ///
+    /// Blocks until all frames have been written and `ffmpeg` has finished encoding the video.
+    /// Returns any error that occurred while writing the video.
+    pub fn stop_video(&self) -> std::io::Result<()> {
+        let recorder = self
+            .video_recorder()
+            .lock()
+            .expect("failed to lock `video_recorder`")
+            .take();
+        match recorder {
+            Some(recorder) => recorder.finish(),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether or not a video of this window is currently being recorded.
+    pub fn is_recording_video(&self) -> bool {
+        self.frame_data
+            .as_ref()
+            .and_then(|data| data.capture.video_recorder.lock().ok())
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+
+    fn video_recorder(&self) -> &std::sync::Mutex<Option<frame::video::Recorder>> {
+        &self
+            .frame_data
+            .as_ref()
+            .expect("video recording requires that `view` draws to a `Frame` (not a `RawFrame`)")
+            .capture
+            .video_recorder
+    }
+
     /// Block and wait for all active capture frame jobs to complete.
     ///
     /// This is called implicitly when the window is dropped to ensure any pending captures
@@ -1463,6 +1785,53 @@ impl Drop for Window {
     }
 }
 
+// Make the given window transparent to mouse input, or restore it.
+//
+// Returns `false` if this is unsupported on the current platform.
+
+#[cfg(target_os = "windows")]
+fn set_click_through(window: &winit::window::Window, click_through: bool) -> bool {
+    use winapi::um::winuser::{
+        GetWindowLongW, SetLayeredWindowAttributes, SetWindowLongW, GWL_EXSTYLE, LWA_ALPHA,
+        WS_EX_LAYERED, WS_EX_TRANSPARENT,
+    };
+    use winit::platform::windows::WindowExtWindows;
+    let hwnd = window.hwnd() as winapi::shared::windef::HWND;
+    unsafe {
+        let style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        let style = if click_through {
+            style | WS_EX_LAYERED | WS_EX_TRANSPARENT
+        } else {
+            style & !WS_EX_TRANSPARENT
+        };
+        SetWindowLongW(hwnd, GWL_EXSTYLE, style as i32);
+        // A layered window is invisible until its attributes are set, so keep it fully opaque and
+        // leave transparency to the swap chain.
+        if click_through {
+            SetLayeredWindowAttributes(hwnd, 0, 255, LWA_ALPHA);
+        }
+    }
+    true
+}
+
+#[cfg(target_os = "macos")]
+fn set_click_through(window: &winit::window::Window, click_through: bool) -> bool {
+    use objc::runtime::{Object, NO, YES};
+    use objc::{msg_send, sel, sel_impl};
+    use winit::platform::macos::WindowExtMacOS;
+    let ns_window = window.ns_window() as *mut Object;
+    let ignores = if click_through { YES } else { NO };
+    unsafe {
+        let _: () = msg_send![ns_window, setIgnoresMouseEvents: ignores];
+    }
+    true
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn set_click_through(_window: &winit::window::Window, _click_through: bool) -> bool {
+    false
+}
+
 // Debug implementations for function wrappers.
 
 impl fmt::Debug for View {
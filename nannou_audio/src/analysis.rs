@@ -0,0 +1,419 @@
+//! Items related to the analysis of audio signals, e.g. for driving audio-reactive sketches.
+//!
+//! The **Analyser** is fed with the samples of an input stream within the stream's `capture`
+//! function. Samples are collected within a ring buffer, and each time enough new samples have
+//! arrived, the most recent window of samples is analysed via a windowed FFT. The results of the
+//! most recent analysis may then be queried at any time, e.g. within `view`.
+//!
+//! As the **Analyser** is typically written to on the audio thread and read from on the main
+//! thread, it is usually shared between them via an `Arc<Mutex<Analyser>>`.
+
+use crate::Buffer;
+use dasp_sample::{Sample, ToSample};
+use std::f32::consts::PI;
+
+/// Analyses a stream of audio samples via a windowed FFT.
+///
+/// Provides the magnitude spectrum, the energy within frequency bands, the RMS and onset
+/// detection of the most recently analysed window of samples.
+#[derive(Clone, Debug)]
+pub struct Analyser {
+    sample_rate: u32,
+    fft_size: usize,
+    hop_size: usize,
+    // The most recent `fft_size` mono samples, written to from `write_pos`.
+    ring: Vec<f32>,
+    write_pos: usize,
+    // The number of samples received since the last analysis.
+    samples_since_analysis: usize,
+    // Hann window coefficients along with their sum, used for normalising magnitudes.
+    window: Vec<f32>,
+    window_sum: f32,
+    fft: Fft,
+    // Scratch buffers for the real and imaginary parts of the FFT.
+    re: Vec<f32>,
+    im: Vec<f32>,
+    spectrum: Vec<f32>,
+    prev_spectrum: Vec<f32>,
+    rms: f32,
+    onset: Onset,
+    analyses: u64,
+}
+
+// Detects onsets by comparing the spectral flux of each analysis against the recent average.
+#[derive(Clone, Debug)]
+struct Onset {
+    threshold: f32,
+    flux: f32,
+    history: Vec<f32>,
+    history_pos: usize,
+    detected: bool,
+    count: u64,
+}
+
+// Precomputed tables for an iterative radix-2 FFT of a fixed size.
+#[derive(Clone, Debug)]
+struct Fft {
+    bit_reversed: Vec<usize>,
+    cos: Vec<f32>,
+    sin: Vec<f32>,
+}
+
+impl Analyser {
+    /// The FFT size used by `Analyser::default`.
+    pub const DEFAULT_FFT_SIZE: usize = 1024;
+
+    /// The default onset threshold. See `set_onset_threshold`.
+    pub const DEFAULT_ONSET_THRESHOLD: f32 = 1.5;
+
+    // The number of recent spectral flux values against which onsets are detected.
+    const ONSET_HISTORY_LEN: usize = 32;
+
+    /// Create a new **Analyser** for a signal of the given sample rate.
+    ///
+    /// `fft_size` is the number of samples analysed at once, and must be a power of two. Larger
+    /// sizes provide a finer frequency resolution at the cost of time resolution. A new analysis
+    /// is performed each time `fft_size / 2` new samples have been pushed.
+    ///
+    /// **Panics** if `fft_size` is not a power of two greater than `1`.
+    pub fn new(fft_size: usize, sample_rate: u32) -> Self {
+        assert!(
+            fft_size > 1 && fft_size.is_power_of_two(),
+            "`fft_size` must be a power of two greater than 1",
+        );
+        let window: Vec<f32> = (0..fft_size)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / fft_size as f32).cos())
+            .collect();
+        let window_sum = window.iter().sum();
+        let n_bins = fft_size / 2;
+        let onset = Onset {
+            threshold: Self::DEFAULT_ONSET_THRESHOLD,
+            flux: 0.0,
+            history: vec![0.0; Self::ONSET_HISTORY_LEN],
+            history_pos: 0,
+            detected: false,
+            count: 0,
+        };
+        Analyser {
+            sample_rate,
+            fft_size,
+            hop_size: fft_size / 2,
+            ring: vec![0.0; fft_size],
+            write_pos: 0,
+            samples_since_analysis: 0,
+            window,
+            window_sum,
+            fft: Fft::new(fft_size),
+            re: vec![0.0; fft_size],
+            im: vec![0.0; fft_size],
+            spectrum: vec![0.0; n_bins],
+            prev_spectrum: vec![0.0; n_bins],
+            rms: 0.0,
+            onset,
+            analyses: 0,
+        }
+    }
+
+    /// Specify the number of new samples that must be pushed before each analysis.
+    ///
+    /// By default this is half of the FFT size. Smaller values provide more frequent updates and
+    /// more responsive onset detection at the cost of CPU time.
+    ///
+    /// **Panics** if `hop_size` is `0`.
+    pub fn set_hop_size(&mut self, hop_size: usize) {
+        assert!(hop_size > 0, "`hop_size` must be greater than 0");
+        self.hop_size = hop_size;
+    }
+
+    /// Specify how far the spectral flux of an analysis must exceed the recent average in order to
+    /// be considered an onset, e.g. `1.5` requires the flux to be 50% greater than average.
+    ///
+    /// Lower values are more sensitive. By default, this is `DEFAULT_ONSET_THRESHOLD`.
+    pub fn set_onset_threshold(&mut self, threshold: f32) {
+        self.onset.threshold = threshold;
+    }
+
+    /// The sample rate of the analysed signal.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The number of samples analysed at once.
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Push the interleaved samples of the given buffer, mixing all channels down to mono.
+    ///
+    /// This is intended to be called from an input stream's `capture` function.
+    pub fn push_buffer<S>(&mut self, buffer: &Buffer<S>)
+    where
+        S: Sample + ToSample<f32>,
+    {
+        self.push_interleaved(buffer, buffer.channels());
+    }
+
+    /// Push the given interleaved samples with the given number of channels, mixing all channels
+    /// down to mono.
+    ///
+    /// **Panics** if `channels` is `0`.
+    pub fn push_interleaved<S>(&mut self, samples: &[S], channels: usize)
+    where
+        S: Sample + ToSample<f32>,
+    {
+        assert!(channels > 0, "`channels` must be greater than 0");
+        let scale = 1.0 / channels as f32;
+        for frame in samples.chunks(channels) {
+            let sum: f32 = frame.iter().map(|&s| s.to_sample::<f32>()).sum();
+            self.push_sample(sum * scale);
+        }
+    }
+
+    /// Push a single mono sample.
+    ///
+    /// Analyses the most recent `fft_size` samples if `hop_size` samples have been pushed since
+    /// the previous analysis.
+    pub fn push_sample(&mut self, sample: f32) {
+        self.ring[self.write_pos] = sample;
+        self.write_pos = (self.write_pos + 1) % self.fft_size;
+        self.samples_since_analysis += 1;
+        if self.samples_since_analysis >= self.hop_size {
+            self.samples_since_analysis = 0;
+            self.analyse();
+        }
+    }
+
+    /// The magnitude of each frequency bin of the most recent analysis.
+    ///
+    /// There are `fft_size / 2` bins evenly spaced between 0hz and the Nyquist frequency. See
+    /// `bin_frequency` for the frequency of each bin. A full-scale sine wave centred within a bin
+    /// has a magnitude of roughly `1.0`.
+    pub fn spectrum(&self) -> &[f32] {
+        &self.spectrum
+    }
+
+    /// The centre frequency of the bin at the given index in hz.
+    pub fn bin_frequency(&self, index: usize) -> f32 {
+        index as f32 * self.sample_rate as f32 / self.fft_size as f32
+    }
+
+    /// The summed magnitude of all bins between the given frequencies in hz.
+    ///
+    /// Negative frequencies are clamped to 0hz. Returns `0.0` if `max_hz` is less than `min_hz`.
+    pub fn band(&self, min_hz: f32, max_hz: f32) -> f32 {
+        let min_hz = min_hz.max(0.0);
+        if max_hz.is_nan() || max_hz < min_hz {
+            return 0.0;
+        }
+        let bin_hz = self.sample_rate as f32 / self.fft_size as f32;
+        let start = (min_hz / bin_hz).ceil() as usize;
+        let end = ((max_hz / bin_hz).floor() as usize + 1).min(self.spectrum.len());
+        self.spectrum
+            .get(start..end)
+            .map_or(0.0, |bins| bins.iter().sum())
+    }
+
+    /// The summed magnitude of `n` logarithmically spaced bands between 20hz and the Nyquist
+    /// frequency, ordered from lowest to highest.
+    ///
+    /// Logarithmic spacing roughly matches the perception of pitch, making this a good fit for
+    /// driving visualisations such as spectrum bars.
+    pub fn bands(&self, n: usize) -> Vec<f32> {
+        let min_hz: f32 = 20.0;
+        let max_hz = self.sample_rate as f32 / 2.0;
+        let ratio = (max_hz / min_hz).powf(1.0 / n as f32);
+        (0..n)
+            .map(|i| {
+                let lo = min_hz * ratio.powi(i as i32);
+                let hi = lo * ratio;
+                self.band(lo, hi)
+            })
+            .collect()
+    }
+
+    /// The root mean square amplitude of the most recently analysed window of samples.
+    pub fn rms(&self) -> f32 {
+        self.rms
+    }
+
+    /// The spectral flux of the most recent analysis, i.e. the total increase in magnitude across
+    /// all bins since the previous analysis.
+    pub fn spectral_flux(&self) -> f32 {
+        self.onset.flux
+    }
+
+    /// Whether or not an onset was detected by the most recent analysis.
+    pub fn is_onset(&self) -> bool {
+        self.onset.detected
+    }
+
+    /// Returns `true` if an onset has been detected since the last call to `take_onset`.
+    ///
+    /// As analyses occur at the rate at which samples arrive rather than once per frame, this
+    /// ensures that onsets are not missed when queried from `view` or `update`.
+    pub fn take_onset(&mut self) -> bool {
+        let detected = self.onset.count > 0;
+        self.onset.count = 0;
+        detected
+    }
+
+    /// The total number of analyses performed since the **Analyser** was created.
+    pub fn analyses(&self) -> u64 {
+        self.analyses
+    }
+
+    // Analyse the most recent `fft_size` samples.
+    fn analyse(&mut self) {
+        // Unroll the ring buffer from oldest to newest while applying the window.
+        let mut sum_sq = 0.0;
+        for i in 0..self.fft_size {
+            let sample = self.ring[(self.write_pos + i) % self.fft_size];
+            sum_sq += sample * sample;
+            self.re[i] = sample * self.window[i];
+            self.im[i] = 0.0;
+        }
+        self.rms = (sum_sq / self.fft_size as f32).sqrt();
+
+        self.fft.process(&mut self.re, &mut self.im);
+
+        std::mem::swap(&mut self.spectrum, &mut self.prev_spectrum);
+        let norm = 2.0 / self.window_sum;
+        let mut flux = 0.0;
+        for (i, (mag, prev)) in self
+            .spectrum
+            .iter_mut()
+            .zip(&self.prev_spectrum)
+            .enumerate()
+        {
+            *mag = (self.re[i] * self.re[i] + self.im[i] * self.im[i]).sqrt() * norm;
+            flux += (*mag - prev).max(0.0);
+        }
+
+        self.onset.detect(flux);
+        self.analyses += 1;
+    }
+}
+
+impl Default for Analyser {
+    fn default() -> Self {
+        Analyser::new(Self::DEFAULT_FFT_SIZE, 44_100)
+    }
+}
+
+impl Onset {
+    // Compare the given flux against the average of the recent history.
+    fn detect(&mut self, flux: f32) {
+        // Ignore near-silent changes in order to avoid detecting onsets in noise.
+        const MIN_FLUX: f32 = 1e-3;
+        let mean = self.history.iter().sum::<f32>() / self.history.len() as f32;
+        self.detected = flux > MIN_FLUX && flux > mean * self.threshold && flux > self.flux;
+        if self.detected {
+            self.count += 1;
+        }
+        self.flux = flux;
+        self.history[self.history_pos] = flux;
+        self.history_pos = (self.history_pos + 1) % self.history.len();
+    }
+}
+
+impl Fft {
+    fn new(size: usize) -> Self {
+        let bits = size.trailing_zeros();
+        let bit_reversed = (0..size)
+            .map(|i| i.reverse_bits() >> (std::mem::size_of::<usize>() as u32 * 8 - bits))
+            .collect();
+        let (cos, sin) = (0..size / 2)
+            .map(|i| {
+                let angle = -2.0 * PI * i as f32 / size as f32;
+                (angle.cos(), angle.sin())
+            })
+            .unzip();
+        Fft {
+            bit_reversed,
+            cos,
+            sin,
+        }
+    }
+
+    // Perform an in-place forward FFT of the given real and imaginary parts.
+    fn process(&self, re: &mut [f32], im: &mut [f32]) {
+        let n = re.len();
+        for (i, &j) in self.bit_reversed.iter().enumerate() {
+            if i < j {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let step = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..half {
+                    let (wr, wi) = (self.cos[k * step], self.sin[k * step]);
+                    let (a, b) = (start + k, start + k + half);
+                    let tr = re[b] * wr - im[b] * wi;
+                    let ti = re[b] * wi + im[b] * wr;
+                    re[b] = re[a] - tr;
+                    im[b] = im[a] - ti;
+                    re[a] += tr;
+                    im[a] += ti;
+                }
+            }
+            len *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Push a full window of a full-scale sine wave at the centre frequency of the given bin.
+    fn push_sine(analyser: &mut Analyser, bin: usize) {
+        let hz = analyser.bin_frequency(bin);
+        let sample_rate = analyser.sample_rate() as f32;
+        for i in 0..analyser.fft_size() {
+            let phase = 2.0 * PI * hz * i as f32 / sample_rate;
+            analyser.push_sample(phase.sin());
+        }
+    }
+
+    #[test]
+    fn test_sine_peak_bin() {
+        let mut analyser = Analyser::new(1024, 44_100);
+        push_sine(&mut analyser, 40);
+        let spectrum = analyser.spectrum();
+        let (peak, &mag) = spectrum
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        assert_eq!(peak, 40);
+        assert!((mag - 1.0).abs() < 0.01, "peak magnitude {}", mag);
+        // A Hann window leaks into the neighbouring bins, but no further.
+        assert!(spectrum[37] < 0.01 && spectrum[43] < 0.01);
+    }
+
+    #[test]
+    fn test_sine_band() {
+        let mut analyser = Analyser::new(1024, 44_100);
+        push_sine(&mut analyser, 40);
+        let hz = analyser.bin_frequency(40);
+        assert!(analyser.band(hz - 100.0, hz + 100.0) > 1.0);
+        assert!(analyser.band(0.0, hz / 2.0) < 0.01);
+    }
+
+    #[test]
+    fn test_band_invalid_range() {
+        let mut analyser = Analyser::new(64, 44_100);
+        // A DC offset places all energy within bin 0.
+        for _ in 0..analyser.fft_size() {
+            analyser.push_sample(1.0);
+        }
+        assert!(analyser.band(0.0, 0.0) > 0.0);
+        assert_eq!(analyser.band(1_000.0, 500.0), 0.0);
+        assert_eq!(analyser.band(-500.0, -100.0), 0.0);
+        assert_eq!(analyser.band(f32::NAN, 100.0), analyser.band(0.0, 100.0));
+    }
+}
@@ -11,11 +11,14 @@
 //! - [**Receiver**](./receiver/struct.Receiver.html) and
 //!   [**Requester**](./requester/struct.Requester.html) for buffering input and output streams that
 //!   may deliver buffers of inconsistent sizes into a stream of consistently sized buffers.
+//! - [**Analyser**](./analysis/struct.Analyser.html) - for analysing the spectrum, RMS and onsets of
+//!   an input stream, e.g. for audio-reactive sketches.
 
 use cpal::traits::HostTrait;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+pub use self::analysis::Analyser;
 pub use self::buffer::Buffer;
 pub use self::device::{Device, Devices};
 pub use self::receiver::Receiver;
@@ -32,6 +35,7 @@ pub use cpal::{
 };
 pub use dasp_sample;
 
+pub mod analysis;
 pub mod buffer;
 pub mod device;
 pub mod receiver;